@@ -43,6 +43,7 @@ export class ActivationNetwork {
   private activationDecay: number = 0.7; // Decay per hop (0.7 recommended by research)
   private activationThreshold: number = 0.1; // Stop spreading below this threshold
   private maxHops: number = 4; // Maximum hops to prevent infinite expansion
+  private strengthInitFromConfidence: boolean = false; // Seed unlearned edge strength from static confidence
   private cycleDetection: Set<string> = new Set(); // Prevent cycles
   
   constructor(storage: MindMapStorage) {
@@ -120,7 +121,7 @@ export class ActivationNetwork {
           const targetNode = this.storage.getNode(targetNodeId);
           if (!targetNode) continue;
           
-          const edgeWeight = edge.weight || 1.0;
+          const edgeWeight = this.edgeStrength(edge);
           const edgeConfidence = edge.confidence;
           const contextRelevance = this.calculateContextRelevance(targetNode, context);
           
@@ -284,6 +285,21 @@ export class ActivationNetwork {
     return results.sort((a, b) => b.totalScore - a.totalScore);
   }
 
+  /**
+   * Strength of an edge: its learned weight, or before any learning either a uniform 1.0 or, with
+   * `strengthInitFromConfidence`, its static confidence so derive-backed facts start above heuristic guesses
+   */
+  edgeStrength(edge: MindMapEdge): number {
+    return edge.weight || (this.strengthInitFromConfidence ? edge.confidence : 1.0);
+  }
+
+  /**
+   * Weight a first traversal builds on: the edge's confidence when strength is seeded from it, else 0
+   */
+  initialWeight(edge: MindMapEdge): number {
+    return this.strengthInitFromConfidence ? edge.confidence : 0;
+  }
+
   /**
    * Get configuration for fine-tuning activation parameters
    */
@@ -291,7 +307,8 @@ export class ActivationNetwork {
     return {
       activationDecay: this.activationDecay,
       activationThreshold: this.activationThreshold,
-      maxHops: this.maxHops
+      maxHops: this.maxHops,
+      strengthInitFromConfidence: this.strengthInitFromConfidence
     };
   }
 
@@ -302,6 +319,7 @@ export class ActivationNetwork {
     activationDecay?: number;
     activationThreshold?: number;
     maxHops?: number;
+    strengthInitFromConfidence?: boolean;
   }) {
     if (config.activationDecay !== undefined) {
       this.activationDecay = Math.max(0.1, Math.min(1.0, config.activationDecay));
@@ -312,5 +330,8 @@ export class ActivationNetwork {
     if (config.maxHops !== undefined) {
       this.maxHops = Math.max(1, Math.min(10, config.maxHops));
     }
+    if (config.strengthInitFromConfidence !== undefined) {
+      this.strengthInitFromConfidence = config.strengthInitFromConfidence;
+    }
   }
 }
\ No newline at end of file
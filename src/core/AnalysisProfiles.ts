@@ -0,0 +1,76 @@
+import type { RustAnalysis, RustParseOptions } from './RustAnalyzer.js';
+import { DEFAULT_MAX_TYPE_DEPTH } from './RustTypes.js';
+
+/**
+ * Presets bundling Rust parse options and lint selections, so one setting picks a sensible trade-off
+ * between speed and depth. Any option given alongside a profile overrides the profile's value.
+ */
+
+export type AnalysisProfile = 'fast' | 'balanced' | 'deep' | 'security_audit';
+
+// Lint queries a profile selects; each names the RustAnalysisService query reporting it
+export type RustLint =
+  | 'panic_sites' | 'unsafe_surface' | 'ffi_surface' | 'boxed_errors' | 'ignored_results' | 'shadowing'
+  | 'redundant_clones' | 'naming_violations' | 'async_mismatches' | 'unused_async' | 'visibility_smells'
+  | 'long_parameter_lists' | 'unused_fields' | 'duplicates' | 'const_fn_candidates' | 'thread_safety_report'
+  | 'error_style_report' | 'unnecessary_ownership' | 'non_exhaustive_matches'
+  | 'lock_held_across_await' | 'redundant_lifetimes';
+
+export const ALL_RUST_LINTS: readonly RustLint[] = [
+  'panic_sites', 'unsafe_surface', 'ffi_surface', 'boxed_errors', 'ignored_results', 'shadowing',
+  'redundant_clones', 'naming_violations', 'async_mismatches', 'unused_async', 'visibility_smells',
+  'long_parameter_lists', 'unused_fields', 'duplicates', 'const_fn_candidates', 'thread_safety_report',
+  'error_style_report', 'unnecessary_ownership', 'non_exhaustive_matches', 'lock_held_across_await',
+  'redundant_lifetimes'
+];
+
+export const DEFAULT_RUST_LINTS: readonly RustLint[] = [
+  'panic_sites', 'ignored_results', 'naming_violations', 'async_mismatches', 'unused_async', 'redundant_clones'
+];
+
+const ALL_ANALYSES: RustAnalysis[] = ['call_graph', 'await_flow', 'duplicates', 'discarded_results', 'shadowing', 'literals', 'panics', 'clones', 'const_fn', 'matches'];
+
+type ProfileOptions = Required<Pick<RustParseOptions, 'enabledAnalyses' | 'lints' | 'docTests' | 'maxTypeDepth' | 'macroBodyMode'>> &
+  Pick<RustParseOptions, 'cfgTest'>;
+
+export const ANALYSIS_PROFILES: Record<AnalysisProfile, ProfileOptions> = {
+  // Signatures and little else: no call graph, no body hashing, macro bodies left alone
+  fast: {
+    enabledAnalyses: ['await_flow', 'discarded_results'],
+    lints: ['ignored_results', 'naming_violations'],
+    docTests: false,
+    maxTypeDepth: 8,
+    macroBodyMode: 'opaque'
+  },
+  balanced: {
+    enabledAnalyses: ALL_ANALYSES,
+    lints: DEFAULT_RUST_LINTS,
+    docTests: true,
+    maxTypeDepth: DEFAULT_MAX_TYPE_DEPTH,
+    macroBodyMode: 'best_effort'
+  },
+  deep: {
+    enabledAnalyses: ALL_ANALYSES,
+    lints: ALL_RUST_LINTS,
+    docTests: true,
+    maxTypeDepth: DEFAULT_MAX_TYPE_DEPTH * 2,
+    macroBodyMode: 'best_effort'
+  },
+  // Production code only, with the passes that find panics, dropped errors and the unsafe boundary
+  security_audit: {
+    enabledAnalyses: ['call_graph', 'await_flow', 'discarded_results', 'panics'],
+    lints: ['panic_sites', 'unsafe_surface', 'ffi_surface', 'boxed_errors', 'ignored_results'],
+    docTests: false,
+    maxTypeDepth: DEFAULT_MAX_TYPE_DEPTH,
+    macroBodyMode: 'best_effort',
+    cfgTest: false
+  }
+};
+
+/**
+ * The parse options of a profile, with every option set in `overrides` taking precedence
+ */
+export function withProfile(profile: AnalysisProfile, overrides: RustParseOptions = {}): RustParseOptions {
+  const explicit = Object.fromEntries(Object.entries(overrides).filter(([, value]) => value !== undefined));
+  return { ...ANALYSIS_PROFILES[profile], ...explicit, profile };
+}
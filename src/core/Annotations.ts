@@ -0,0 +1,43 @@
+/**
+ * External data, such as coverage reports or profiler output, overlaid on the nodes of the map. Each
+ * record names a file and a line range and is attached to the innermost node covering the whole range,
+ * so a line in a method lands on the method rather than on its impl block or module.
+ */
+
+export interface AnnotationRecord {
+  filePath: string; // Relative to the project root, or absolute
+  startLine: number; // 1-based
+  endLine?: number; // Inclusive; defaults to startLine
+  annotations: Record<string, unknown>; // Stored under the node's `metadata.annotations`
+}
+
+export interface ImportAnnotationsOptions {
+  replace?: boolean; // Overwrite the keys imported instead of combining with values from earlier imports
+}
+
+export interface AnnotationImportResult {
+  annotated: string[]; // Node IDs, sorted
+  unmatched: AnnotationRecord[]; // Records no node covers, such as lines between items
+}
+
+/**
+ * Combine annotations landing on the same node: numbers add up, so per-line hit counts become per-function
+ * totals; objects combine key by key; anything else takes the later value
+ */
+export function combineAnnotations(previous: Record<string, unknown>, next: Record<string, unknown>): Record<string, unknown> {
+  const combined = { ...previous };
+  for (const [key, value] of Object.entries(next)) {
+    combined[key] = combineValue(combined[key], value);
+  }
+  return combined;
+}
+
+function combineValue(previous: unknown, next: unknown): unknown {
+  if (typeof previous === 'number' && typeof next === 'number') return previous + next;
+  if (isPlainObject(previous) && isPlainObject(next)) return combineAnnotations(previous, next);
+  return next;
+}
+
+function isPlainObject(value: unknown): value is Record<string, unknown> {
+  return typeof value === 'object' && value !== null && !Array.isArray(value) && !(value instanceof Date);
+}
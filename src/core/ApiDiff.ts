@@ -0,0 +1,162 @@
+import * as crypto from 'crypto';
+import { MindMapGraph, MindMapNode } from '../types/index.js';
+
+/**
+ * Classification of API differences between two versions of the same item, and alignment of two
+ * separately scanned graphs (e.g. a template and a service generated from it) by relative path
+ */
+
+export type ApiChangeKind = 'signature' | 'visibility' | 'fields' | 'variants' | 'trait_impls';
+
+export interface ApiChange {
+  kind: ApiChangeKind;
+  before: string;
+  after: string;
+  breaking: boolean; // Could break code using the item as it was before
+}
+
+export interface ComparedNode {
+  key: string; // Node ID, relative to the scanned directory, e.g. `src/main.rs#function:main`
+  name: string;
+  kind: string; // Rust item kind (`struct`, `enum`, `trait`) or node type
+}
+
+export interface NodeDifference extends ComparedNode {
+  changes: ApiChange[];
+}
+
+export interface DirectoryComparison {
+  a: string;
+  b: string;
+  onlyInA: ComparedNode[];
+  onlyInB: ComparedNode[];
+  differing: NodeDifference[]; // Aligned nodes whose API differs
+}
+
+const COMPARED_NODE_TYPES = new Set<MindMapNode['type']>(['function', 'class', 'variable']);
+
+/**
+ * API-level changes from `before` to `after`; an empty list means the item is unchanged
+ */
+export function classifyApiChanges(before: MindMapNode, after: MindMapNode): ApiChange[] {
+  const changes: ApiChange[] = [];
+  const wasPublic = before.metadata?.isExported === true;
+  const meta = (node: MindMapNode, key: string) => node.metadata?.[key];
+
+  const signature = (node: MindMapNode) => String(meta(node, 'signature') || '').replace(/^pub(?:\([^)]*\))?\s+/, '').replace(/\s+/g, ' ');
+  if (signature(before) !== signature(after)) {
+    changes.push({ kind: 'signature', before: signature(before), after: signature(after), breaking: wasPublic });
+  }
+
+  const visibility = (node: MindMapNode) => String(meta(node, 'visibility') || 'private');
+  if (visibility(before) !== visibility(after)) {
+    const narrowed = wasPublic && after.metadata?.isExported !== true;
+    changes.push({ kind: 'visibility', before: visibility(before), after: visibility(after), breaking: narrowed });
+  }
+
+  // Lists compare as sets: removing an entry breaks users, adding one does not
+  const listChange = (kind: ApiChangeKind, entries: (node: MindMapNode) => string[], additionBreaks = false) => {
+    const [old, current] = [entries(before), entries(after)];
+    if (old.join(',') === current.join(',')) return;
+    const removed = old.some(entry => !current.includes(entry));
+    const added = current.some(entry => !old.includes(entry));
+    changes.push({ kind, before: old.join(', '), after: current.join(', '), breaking: wasPublic && (removed || (additionBreaks && added)) });
+  };
+  const fields = (node: MindMapNode) => (meta(node, 'properties') || []).map((field: string, index: number) =>
+    `${field}: ${(meta(node, 'propertyTypes') || [])[index] || '?'}`).sort();
+  listChange('fields', fields);
+  // A new variant breaks exhaustive matches unless the enum is `#[non_exhaustive]`
+  const exhaustive = !(meta(before, 'attributes') || []).some((attribute: { name: string }) => attribute.name === 'non_exhaustive');
+  listChange('variants', node => [...(meta(node, 'variants') || [])].sort(), exhaustive);
+  listChange('trait_impls', node => Array.from(new Set<string>([...(meta(node, 'implementedTraits') || []), ...(meta(node, 'derives') || [])])).sort());
+
+  return changes;
+}
+
+/**
+ * Align the items of two graphs scanned from different roots by node ID, which scanning makes relative
+ * to the root, and report the unmatched ones and the matched ones whose API differs
+ */
+export function compareDirectoryGraphs(a: string, graphA: MindMapGraph, b: string, graphB: MindMapGraph): DirectoryComparison {
+  const nodesA = keyedNodes(graphA);
+  const nodesB = keyedNodes(graphB);
+  const compared = (key: string, node: MindMapNode): ComparedNode => ({
+    key,
+    name: node.name,
+    kind: node.metadata?.rustKind || node.type
+  });
+
+  const differing: NodeDifference[] = [];
+  for (const [key, nodeA] of nodesA) {
+    const nodeB = nodesB.get(key);
+    const changes = nodeB ? classifyApiChanges(nodeA, nodeB) : [];
+    if (changes.length > 0) {
+      differing.push({ ...compared(key, nodeA), changes });
+    }
+  }
+
+  return {
+    a,
+    b,
+    onlyInA: Array.from(nodesA).filter(([key]) => !nodesB.has(key)).map(([key, node]) => compared(key, node)),
+    onlyInB: Array.from(nodesB).filter(([key]) => !nodesA.has(key)).map(([key, node]) => compared(key, node)),
+    differing
+  };
+}
+
+function keyedNodes(graph: MindMapGraph): Map<string, MindMapNode> {
+  const keyed: Array<[string, MindMapNode]> = Array.from(graph.nodes.values())
+    .filter(node => COMPARED_NODE_TYPES.has(node.type) && node.path)
+    .map(node => [node.id.replace(/\\/g, '/'), node]);
+  return new Map(keyed.sort(([x], [y]) => x.localeCompare(y)));
+}
+
+/**
+ * One line per public Rust item describing its API, sorted: items declared `pub` (and the methods of
+ * `pub` traits) with their signatures, public fields, variants and implemented traits. Private items
+ * and function bodies do not appear, so only API changes change the surface.
+ */
+export function publicSurface(graph: MindMapGraph): string[] {
+  const nodes = Array.from(graph.nodes.values()).filter(node => node.metadata?.language === 'rust' && !node.metadata.external && !node.metadata.inactive);
+  const publicTraits = new Set(nodes.filter(node => node.metadata.rustKind === 'trait' && node.metadata.visibility === 'pub').map(node => node.name));
+  const list = (values: string[] | undefined) => [...(values || [])].sort().join(', ');
+  const signature = (node: MindMapNode) => String(node.metadata.signature || '').replace(/^pub(?:\([^)]*\))?\s+/, '').replace(/\s+/g, ' ');
+
+  const lines: string[] = [];
+  for (const node of nodes) {
+    const meta = node.metadata;
+    const path = `${meta.module || 'crate'}::${meta.qualifiedName || node.name}`;
+    const traits = list(Array.from(new Set<string>([...(meta.implementedTraits || []), ...(meta.derives || [])])));
+
+    if (node.type === 'function') {
+      const owner = meta.traitName || meta.implTarget;
+      if (meta.visibility !== 'pub' && !(meta.traitName && publicTraits.has(meta.traitName))) continue;
+      lines.push(`fn ${meta.module || 'crate'}::${owner ? `${owner}::` : ''}${node.name}: ${signature(node)}`);
+    } else if (meta.visibility !== 'pub') {
+      continue;
+    } else if (meta.rustKind === 'struct') {
+      const fields = (meta.properties || []).map((field: string, index: number) => ({ field, index }))
+        .filter(({ index }: { index: number }) => meta.propertyVisibility?.[index] === 'pub')
+        .map(({ field, index }: { field: string; index: number }) => `${field}: ${meta.propertyTypes?.[index] || '?'}`);
+      lines.push(`struct ${path}<${(meta.typeParameters || []).join(', ')}> { ${list(fields)} } impl ${traits}`);
+    } else if (meta.rustKind === 'enum') {
+      const exhaustive = !(meta.attributes || []).some((attribute: { name: string }) => attribute.name === 'non_exhaustive');
+      lines.push(`enum ${path}${exhaustive ? '' : ' #[non_exhaustive]'} { ${list(meta.variants)} } impl ${traits}`);
+    } else if (meta.rustKind === 'trait') {
+      lines.push(`trait ${path}: ${list(meta.supertraits)} { ${list(meta.methods)}; type ${list(meta.associatedTypes)} }`);
+    } else if (meta.rustKind === 'trait_alias') {
+      lines.push(`trait ${path} = ${meta.bounds}`);
+    } else if (node.type === 'variable' && meta.variableType !== 'enum_variant') {
+      lines.push(`${meta.variableType} ${path}: ${meta.dataType || '?'}`);
+    }
+  }
+  return lines.sort();
+}
+
+/**
+ * Stable hash of the public surface, for cheaply telling whether two versions differ in API before
+ * running a full comparison. The same sources give the same hash whatever order files were parsed in.
+ */
+export function publicSurfaceHash(graph: MindMapGraph): string {
+  return crypto.createHash('sha256').update(publicSurface(graph).join('\n')).digest('hex');
+}
@@ -0,0 +1,297 @@
+import { MindMapGraph, MindMapNode, MindMapEdge } from '../types/index.js';
+
+// File layout: magic, u16 format version, string table, then the graph body
+const MAGIC = Buffer.from('MMBG', 'ascii');
+export const BINARY_GRAPH_VERSION = 1;
+
+const TAG = {
+  Null: 0,
+  False: 1,
+  True: 2,
+  Int: 3, // Zigzag varint
+  Float: 4, // Float64, little endian
+  String: 5, // Varint index into the string table
+  Array: 6,
+  Object: 7, // Varint entry count, then key string index and value per entry
+  Date: 8 // Float64 epoch milliseconds
+} as const;
+
+export type BinaryGraphErrorCode = 'invalid_magic' | 'unsupported_version' | 'corrupt';
+
+/**
+ * Raised when a binary graph file is not one this version can read. Nothing is loaded.
+ */
+export class BinaryGraphError extends Error {
+  constructor(
+    public readonly code: BinaryGraphErrorCode,
+    message: string,
+    public readonly version?: number
+  ) {
+    super(message);
+    this.name = 'BinaryGraphError';
+  }
+}
+
+export interface DecodedGraph {
+  nodes: MindMapNode[];
+  edges: MindMapEdge[];
+  lastScan: Date;
+  version: string;
+}
+
+class ByteWriter {
+  private buffer = Buffer.alloc(64 * 1024);
+  private offset = 0;
+
+  byte(value: number): void {
+    this.reserve(1);
+    this.buffer[this.offset++] = value;
+  }
+
+  varint(value: number): void {
+    // Unsigned LEB128; division keeps values above 2^32 exact
+    do {
+      let byte = value % 128;
+      value = Math.floor(value / 128);
+      if (value > 0) byte |= 0x80;
+      this.byte(byte);
+    } while (value > 0);
+  }
+
+  float(value: number): void {
+    this.reserve(8);
+    this.buffer.writeDoubleLE(value, this.offset);
+    this.offset += 8;
+  }
+
+  bytes(data: Buffer): void {
+    this.reserve(data.length);
+    data.copy(this.buffer, this.offset);
+    this.offset += data.length;
+  }
+
+  toBuffer(): Buffer {
+    return this.buffer.subarray(0, this.offset);
+  }
+
+  private reserve(size: number): void {
+    if (this.offset + size <= this.buffer.length) return;
+    const grown = Buffer.alloc(Math.max(this.buffer.length * 2, this.offset + size));
+    this.buffer.copy(grown, 0, 0, this.offset);
+    this.buffer = grown;
+  }
+}
+
+class ByteReader {
+  private offset: number;
+
+  constructor(private buffer: Buffer, offset: number) {
+    this.offset = offset;
+  }
+
+  byte(): number {
+    if (this.offset >= this.buffer.length) {
+      throw new BinaryGraphError('corrupt', 'Unexpected end of binary graph data');
+    }
+    return this.buffer[this.offset++];
+  }
+
+  varint(): number {
+    let value = 0;
+    let scale = 1;
+    for (;;) {
+      const byte = this.byte();
+      value += (byte & 0x7f) * scale;
+      if ((byte & 0x80) === 0) return value;
+      scale *= 128;
+      if (scale > 2 ** 56) {
+        throw new BinaryGraphError('corrupt', 'Varint is too long');
+      }
+    }
+  }
+
+  float(): number {
+    this.ensure(8);
+    const value = this.buffer.readDoubleLE(this.offset);
+    this.offset += 8;
+    return value;
+  }
+
+  bytes(length: number): Buffer {
+    this.ensure(length);
+    const data = this.buffer.subarray(this.offset, this.offset + length);
+    this.offset += length;
+    return data;
+  }
+
+  get done(): boolean {
+    return this.offset === this.buffer.length;
+  }
+
+  private ensure(size: number): void {
+    if (this.offset + size > this.buffer.length) {
+      throw new BinaryGraphError('corrupt', 'Unexpected end of binary graph data');
+    }
+  }
+}
+
+/**
+ * Serialize the whole graph. Strings (IDs, names, metadata keys, paths) are stored once in a
+ * table and referenced by index; dates keep millisecond precision and `undefined` fields are dropped,
+ * so the result loads back exactly like the JSON file does.
+ */
+export function encodeGraph(graph: MindMapGraph): Buffer {
+  const strings = new Map<string, number>();
+  const body = new ByteWriter();
+
+  const stringRef = (value: string) => {
+    let index = strings.get(value);
+    if (index === undefined) {
+      index = strings.size;
+      strings.set(value, index);
+    }
+    body.varint(index);
+  };
+
+  const value = (item: unknown): void => {
+    if (item === null || item === undefined) {
+      body.byte(TAG.Null);
+    } else if (typeof item === 'boolean') {
+      body.byte(item ? TAG.True : TAG.False);
+    } else if (typeof item === 'number') {
+      if (Number.isSafeInteger(item) && !Object.is(item, -0)) {
+        body.byte(TAG.Int);
+        body.varint(item >= 0 ? item * 2 : -item * 2 - 1);
+      } else {
+        body.byte(TAG.Float);
+        body.float(item);
+      }
+    } else if (typeof item === 'string') {
+      body.byte(TAG.String);
+      stringRef(item);
+    } else if (item instanceof Date) {
+      body.byte(TAG.Date);
+      body.float(item.getTime());
+    } else if (Array.isArray(item)) {
+      body.byte(TAG.Array);
+      body.varint(item.length);
+      item.forEach(value);
+    } else if (typeof item === 'object') {
+      const entries = Object.entries(item as Record<string, unknown>).filter(([, field]) => field !== undefined);
+      body.byte(TAG.Object);
+      body.varint(entries.length);
+      for (const [key, field] of entries) {
+        stringRef(key);
+        value(field);
+      }
+    } else {
+      // Functions and symbols have no JSON form either
+      body.byte(TAG.Null);
+    }
+  };
+
+  value(graph.lastScan);
+  value(graph.version);
+  body.varint(graph.nodes.size);
+  for (const node of graph.nodes.values()) value(node);
+  body.varint(graph.edges.size);
+  for (const edge of graph.edges.values()) value(edge);
+
+  const file = new ByteWriter();
+  file.bytes(MAGIC);
+  file.byte(BINARY_GRAPH_VERSION & 0xff);
+  file.byte(BINARY_GRAPH_VERSION >> 8);
+  file.varint(strings.size);
+  for (const text of strings.keys()) {
+    const encoded = Buffer.from(text, 'utf-8');
+    file.varint(encoded.length);
+    file.bytes(encoded);
+  }
+  file.bytes(body.toBuffer());
+
+  return file.toBuffer();
+}
+
+/**
+ * Parse a buffer written by `encodeGraph`, rejecting foreign files and other format versions
+ */
+export function decodeGraph(buffer: Buffer): DecodedGraph {
+  if (buffer.length < MAGIC.length + 2 || !buffer.subarray(0, MAGIC.length).equals(MAGIC)) {
+    throw new BinaryGraphError('invalid_magic', 'Not a binary mind map file');
+  }
+  const version = buffer[MAGIC.length] | (buffer[MAGIC.length + 1] << 8);
+  if (version !== BINARY_GRAPH_VERSION) {
+    throw new BinaryGraphError(
+      'unsupported_version',
+      `Binary mind map format version ${version} is not supported (expected ${BINARY_GRAPH_VERSION})`,
+      version
+    );
+  }
+
+  const reader = new ByteReader(buffer, MAGIC.length + 2);
+  const strings: string[] = [];
+  const stringCount = reader.varint();
+  for (let i = 0; i < stringCount; i++) {
+    strings.push(reader.bytes(reader.varint()).toString('utf-8'));
+  }
+
+  const string = (): string => {
+    const index = reader.varint();
+    if (index >= strings.length) {
+      throw new BinaryGraphError('corrupt', `String index ${index} is out of range`);
+    }
+    return strings[index];
+  };
+
+  const value = (): any => {
+    const tag = reader.byte();
+    switch (tag) {
+      case TAG.Null:
+        return null;
+      case TAG.False:
+        return false;
+      case TAG.True:
+        return true;
+      case TAG.Int: {
+        const zigzag = reader.varint();
+        return zigzag % 2 === 0 ? zigzag / 2 : -(zigzag + 1) / 2;
+      }
+      case TAG.Float:
+        return reader.float();
+      case TAG.String:
+        return string();
+      case TAG.Date:
+        return new Date(reader.float());
+      case TAG.Array: {
+        const length = reader.varint();
+        const items: any[] = [];
+        for (let i = 0; i < length; i++) items.push(value());
+        return items;
+      }
+      case TAG.Object: {
+        const count = reader.varint();
+        const object: Record<string, any> = {};
+        for (let i = 0; i < count; i++) {
+          const key = string();
+          object[key] = value();
+        }
+        return object;
+      }
+      default:
+        throw new BinaryGraphError('corrupt', `Unknown value tag ${tag}`);
+    }
+  };
+
+  const lastScan = value();
+  const graphVersion = value();
+  const nodes: MindMapNode[] = [];
+  for (let count = reader.varint(); count > 0; count--) nodes.push(value());
+  const edges: MindMapEdge[] = [];
+  for (let count = reader.varint(); count > 0; count--) edges.push(value());
+
+  if (!(lastScan instanceof Date) || typeof graphVersion !== 'string' || !reader.done) {
+    throw new BinaryGraphError('corrupt', 'Binary mind map data is malformed');
+  }
+
+  return { nodes, edges, lastScan, version: graphVersion };
+}
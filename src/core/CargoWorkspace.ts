@@ -0,0 +1,166 @@
+import { readFile, readdir } from 'fs/promises';
+import { join, posix } from 'path';
+import { MindMapStorage } from './MindMapStorage.js';
+
+/**
+ * Discovery of the crates in a Cargo workspace, so items parsed from several member crates can be told
+ * apart and searched across
+ */
+
+export interface CargoCrate {
+  name: string; // Package name with dashes as underscores, as Rust paths write it
+  path: string; // Directory relative to the project root, `''` for a package at the root
+}
+
+export interface CargoWorkspace {
+  crates: CargoCrate[]; // Longest path first, so the first match for a file is its crate
+}
+
+/**
+ * The crates under a project root: the members listed by a root `[workspace]`, including globs such as
+ * `crates/*`, and a root `[package]`. Undefined when the root has no Cargo.toml.
+ */
+export async function discoverCargoWorkspace(projectRoot: string): Promise<CargoWorkspace | undefined> {
+  const manifest = await readManifest(projectRoot, '');
+  if (!manifest) return undefined;
+
+  const crates: CargoCrate[] = [];
+  const rootName = tomlString(manifest, 'package', 'name');
+  if (rootName) crates.push({ name: crateIdentifier(rootName), path: '' });
+
+  const excluded = new Set((tomlArray(manifest, 'workspace', 'exclude') || []).map(normalizeMemberPath));
+  for (const pattern of tomlArray(manifest, 'workspace', 'members') || []) {
+    for (const memberPath of await expandMemberPattern(projectRoot, normalizeMemberPath(pattern))) {
+      if (excluded.has(memberPath) || crates.some(existing => existing.path === memberPath)) continue;
+      const member = await readManifest(projectRoot, memberPath);
+      const name = member && tomlString(member, 'package', 'name');
+      if (name) crates.push({ name: crateIdentifier(name), path: memberPath });
+    }
+  }
+
+  return { crates: crates.sort((a, b) => b.path.length - a.path.length || a.path.localeCompare(b.path)) };
+}
+
+/**
+ * The crate a project-relative file belongs to
+ */
+export function crateOfPath(workspace: CargoWorkspace, filePath: string): CargoCrate | undefined {
+  const normalized = filePath.replace(/\\/g, '/');
+  return workspace.crates.find(candidate => candidate.path === '' || normalized.startsWith(`${candidate.path}/`));
+}
+
+/**
+ * Record on every node parsed from a member crate the crate it belongs to, as `metadata.crate`
+ */
+export function tagCrates(storage: MindMapStorage, workspace: CargoWorkspace): void {
+  for (const node of storage.findNodes(node => typeof node.path === 'string' && node.metadata?.external !== true)) {
+    const owner = crateOfPath(workspace, node.path!);
+    if (owner && node.metadata.crate !== owner.name) {
+      node.metadata = { ...node.metadata, crate: owner.name };
+    }
+  }
+}
+
+function crateIdentifier(packageName: string): string {
+  return packageName.replace(/-/g, '_');
+}
+
+function normalizeMemberPath(path: string): string {
+  return posix.normalize(path.replace(/\\/g, '/')).replace(/^\.\/?/, '').replace(/\/$/, '');
+}
+
+async function expandMemberPattern(projectRoot: string, pattern: string): Promise<string[]> {
+  // `*` matches one directory level, as in Cargo's member globs
+  let paths = [''];
+  for (const segment of pattern.split('/')) {
+    if (!segment.includes('*')) {
+      paths = paths.map(path => path ? `${path}/${segment}` : segment);
+      continue;
+    }
+    const regex = new RegExp(`^${segment.replace(/[.+?^${}()|[\]\\]/g, '\\$&').replace(/\*/g, '.*')}$`);
+    const expanded: string[] = [];
+    for (const path of paths) {
+      const entries = await readdir(join(projectRoot, path), { withFileTypes: true }).catch(() => []);
+      for (const entry of entries) {
+        if (entry.isDirectory() && regex.test(entry.name)) expanded.push(path ? `${path}/${entry.name}` : entry.name);
+      }
+    }
+    paths = expanded.sort();
+  }
+  return paths;
+}
+
+async function readManifest(projectRoot: string, cratePath: string): Promise<Map<string, Map<string, string>> | undefined> {
+  const text = await readFile(join(projectRoot, cratePath, 'Cargo.toml'), 'utf-8').catch(() => undefined);
+  return text === undefined ? undefined : parseTomlTables(text);
+}
+
+function parseTomlTables(text: string): Map<string, Map<string, string>> {
+  // Enough TOML for manifests: `[table]` headers and `key = value` pairs, arrays possibly spanning lines.
+  // Values are kept as written and read by the accessors below.
+  const tables = new Map<string, Map<string, string>>([['', new Map()]]);
+  let table = tables.get('')!;
+  let pending: { key: string; value: string } | undefined;
+
+  for (const rawLine of text.split('\n')) {
+    const line = stripTomlComment(rawLine).trim();
+    if (pending) {
+      pending.value += ` ${line}`;
+      if (bracketBalance(pending.value) <= 0) {
+        table.set(pending.key, pending.value);
+        pending = undefined;
+      }
+      continue;
+    }
+    const header = line.match(/^\[([^\[\]]+)\]$/);
+    if (header) {
+      const name = header[1].trim();
+      if (!tables.has(name)) tables.set(name, new Map());
+      table = tables.get(name)!;
+      continue;
+    }
+    const pair = line.match(/^([A-Za-z0-9_.-]+|"[^"]*")\s*=\s*(.*)$/);
+    if (!pair) continue;
+    const key = pair[1].replace(/^"|"$/g, '');
+    if (bracketBalance(pair[2]) > 0) {
+      pending = { key, value: pair[2] };
+    } else {
+      table.set(key, pair[2]);
+    }
+  }
+  return tables;
+}
+
+function tomlString(tables: Map<string, Map<string, string>>, table: string, key: string): string | undefined {
+  const value = tables.get(table)?.get(key);
+  const match = value?.match(/^"((?:\\.|[^"\\])*)"$|^'([^']*)'$/);
+  return match ? match[1] ?? match[2] : undefined;
+}
+
+function tomlArray(tables: Map<string, Map<string, string>>, table: string, key: string): string[] | undefined {
+  const value = tables.get(table)?.get(key);
+  if (!value?.startsWith('[')) return undefined;
+  return Array.from(value.matchAll(/"((?:\\.|[^"\\])*)"|'([^']*)'/g), match => match[1] ?? match[2]);
+}
+
+function stripTomlComment(line: string): string {
+  let quote: string | undefined;
+  for (let i = 0; i < line.length; i++) {
+    const char = line[i];
+    if (quote) {
+      if (char === '\\' && quote === '"') i++;
+      else if (char === quote) quote = undefined;
+    } else if (char === '"' || char === '\'') {
+      quote = char;
+    } else if (char === '#') {
+      return line.substring(0, i);
+    }
+  }
+  return line;
+}
+
+function bracketBalance(value: string): number {
+  // Brackets inside quoted strings are already part of the value and do not count
+  const unquoted = value.replace(/"(?:\\.|[^"\\])*"|'[^']*'/g, '');
+  return (unquoted.match(/\[/g) || []).length - (unquoted.match(/\]/g) || []).length;
+}
@@ -4,7 +4,8 @@ import { CodeStructure } from '../types/index.js';
 import { PythonAnalyzer, PythonCodeStructure } from './PythonAnalyzer.js';
 import { JavaAnalyzer, JavaCodeStructure } from './JavaAnalyzer.js';
 import { GoAnalyzer, GoCodeStructure } from './GoAnalyzer.js';
-import { RustAnalyzer, RustCodeStructure } from './RustAnalyzer.js';
+import { RustAnalyzer, RustCodeStructure, RustParseOptions } from './RustAnalyzer.js';
+import { RustLint } from './AnalysisProfiles.js';
 import { CppAnalyzer, CppCodeStructure } from './CppAnalyzer.js';
 
 export class CodeAnalyzer {
@@ -24,12 +25,23 @@ export class CodeAnalyzer {
     this.cppAnalyzer = new CppAnalyzer();
   }
 
+  setRustParseOptions(options: RustParseOptions): void {
+    this.rustAnalyzer.setParseOptions(options);
+  }
+
+  getRustEnabledLints(): RustLint[] {
+    return this.rustAnalyzer.getEnabledLints();
+  }
+
   canAnalyze(filePath: string): boolean {
     const extension = filePath.split('.').pop()?.toLowerCase();
     return extension ? this.supportedExtensions.has(extension) : false;
   }
 
-  async analyzeFile(filePath: string): Promise<CodeStructure | null> {
+  /**
+   * Analyze a file from disk, or the given content in its place (honoured for Rust and TypeScript/JavaScript)
+   */
+  async analyzeFile(filePath: string, content?: string): Promise<CodeStructure | null> {
     if (!this.canAnalyze(filePath)) {
       return null;
     }
@@ -45,13 +57,13 @@ export class CodeAnalyzer {
       } else if (extension === 'go') {
         return await this.goAnalyzer.analyzeFile(filePath);
       } else if (extension === 'rs') {
-        return await this.rustAnalyzer.analyzeFile(filePath);
+        return await this.rustAnalyzer.analyzeFile(filePath, content);
       } else if (extension && ['c', 'cpp', 'cc', 'cxx', 'c++', 'h', 'hpp', 'hxx', 'h++'].includes(extension)) {
         return await this.cppAnalyzer.analyzeFile(filePath);
       } else {
         // Handle TypeScript/JavaScript files
-        const content = await readFile(filePath, 'utf-8');
-        return this.parseCode(content, filePath);
+        const source = content ?? await readFile(filePath, 'utf-8');
+        return this.parseCode(source, filePath);
       }
     } catch (error) {
       console.warn(`Failed to analyze ${filePath}:`, error);
@@ -104,6 +116,10 @@ export class CodeAnalyzer {
       this.processFunctionDeclaration(node, structure);
     } else if (ts.isClassDeclaration(node)) {
       this.processClassDeclaration(node, structure);
+    } else if (ts.isInterfaceDeclaration(node)) {
+      this.processInterfaceDeclaration(node.name.text, node, node.members, structure);
+    } else if (ts.isTypeAliasDeclaration(node) && ts.isTypeLiteralNode(node.type)) {
+      this.processInterfaceDeclaration(node.name.text, node, node.type.members, structure);
     } else if (ts.isImportDeclaration(node)) {
       this.processImportDeclaration(node, structure);
     } else if (ts.isExportDeclaration(node) || ts.isExportAssignment(node)) {
@@ -175,6 +191,33 @@ export class CodeAnalyzer {
     }
   }
 
+  private processInterfaceDeclaration(
+    name: string,
+    node: ts.Node,
+    members: ts.NodeArray<ts.TypeElement>,
+    structure: CodeStructure
+  ): void {
+    const properties: NonNullable<CodeStructure['interfaces']>[number]['properties'] = [];
+
+    members.forEach(member => {
+      if (ts.isPropertySignature(member) && member.name && (ts.isIdentifier(member.name) || ts.isStringLiteral(member.name))) {
+        properties.push({
+          name: member.name.text,
+          type: member.type?.getText(),
+          optional: member.questionToken !== undefined
+        });
+      }
+    });
+
+    const sourceFile = node.getSourceFile();
+    (structure.interfaces ??= []).push({
+      name,
+      startLine: this.getLineNumber(node),
+      endLine: sourceFile.getLineAndCharacterOfPosition(node.getEnd()).line + 1,
+      properties
+    });
+  }
+
   private processImportDeclaration(
     node: ts.ImportDeclaration, 
     structure: CodeStructure
@@ -0,0 +1,206 @@
+import { execFile } from 'child_process';
+import { promisify } from 'util';
+import { access, mkdir, rm } from 'fs/promises';
+import { join } from 'path';
+import { MindMapStorage } from './MindMapStorage.js';
+import { MindMapNode, MindMapEdge } from '../types/index.js';
+
+const execFileAsync = promisify(execFile);
+
+/**
+ * The mind map as it was at a git commit, for historical questions such as whether a trait existed at
+ * a release tag. The committed tree is checked out under `.mindmap-cache/commits/<hash>` without touching
+ * the working tree or the index, scanned once, and its graph kept there keyed by the commit hash.
+ */
+export class CommitGraph {
+  constructor(readonly commit: string, private storage: MindMapStorage, readonly fromCache: boolean) {}
+
+  getNode(id: string): MindMapNode | undefined {
+    return this.storage.getNode(id);
+  }
+
+  getEdge(id: string): MindMapEdge | undefined {
+    return this.storage.getEdge(id);
+  }
+
+  findNodes(predicate: (node: MindMapNode) => boolean): MindMapNode[] {
+    return this.storage.findNodes(predicate);
+  }
+
+  findEdges(predicate: (edge: MindMapEdge) => boolean): MindMapEdge[] {
+    return this.storage.findEdges(predicate);
+  }
+
+  get nodeCount(): number {
+    return this.storage.getGraph().nodes.size;
+  }
+}
+
+export type SymbolChangeType = 'created' | 'signature_changed' | 'body_changed' | 'moved' | 'deleted';
+
+export interface SymbolTimelineEntry {
+  commit: string;
+  date: string; // Committer date, ISO 8601
+  subject: string;
+  nodeId?: string; // The symbol's node at this commit; unset once it is deleted
+  name?: string;
+  changes: SymbolChangeType[]; // `moved` covers renames as well as moves to another file or module
+}
+
+export interface SymbolTimelineOptions {
+  revision?: string; // Newest commit considered; defaults to HEAD
+  limit?: number; // Most recent commits scanned; defaults to 50
+}
+
+interface CommitInfo {
+  commit: string;
+  date: string;
+  subject: string;
+}
+
+export class CommitGraphs {
+  private graphs = new Map<string, Promise<CommitGraph>>(); // Commit hash -> graph
+
+  constructor(
+    private projectRoot: string,
+    private scan: (directory: string) => Promise<MindMapStorage>
+  ) {}
+
+  /**
+   * The graph at a commit, branch or tag; fails when the revision does not name a commit
+   */
+  async at(revision: string): Promise<CommitGraph> {
+    const commit = await this.resolveCommit(revision);
+    if (!this.graphs.has(commit)) {
+      const pending = this.load(commit);
+      this.graphs.set(commit, pending);
+      pending.catch(() => this.graphs.delete(commit));
+    }
+    return this.graphs.get(commit)!;
+  }
+
+  /**
+   * The commits at which a symbol changed, oldest first. The symbol is followed by node ID; when the ID
+   * disappears, by a node of the same kind and name elsewhere, then by one whose signature matches with
+   * the name left out, so moves and renames keep the same timeline. Commits that leave it untouched are
+   * omitted.
+   */
+  async symbolTimeline(nodeId: string, options: SymbolTimelineOptions = {}): Promise<SymbolTimelineEntry[]> {
+    const commits = await this.history(options.revision ?? 'HEAD', options.limit ?? 50);
+    const timeline: SymbolTimelineEntry[] = [];
+    let previous: MindMapNode | undefined;
+
+    // Walking newest to oldest first finds the symbol's ID at each commit as it was renamed or moved
+    const tracked = new Map<string, MindMapNode>();
+    let current: MindMapNode | undefined;
+    for (const info of [...commits].reverse()) {
+      const graph = await this.at(info.commit);
+      const found = current ? followSymbol(graph, current) : graph.getNode(nodeId);
+      if (!found) {
+        if (current) break;
+        continue;
+      }
+      tracked.set(info.commit, found);
+      current = found;
+    }
+
+    for (const info of commits) {
+      const node = tracked.get(info.commit);
+      const changes: SymbolChangeType[] = [];
+      if (node && !previous) {
+        changes.push('created');
+      } else if (node && previous) {
+        if (node.id !== previous.id) changes.push('moved');
+        if (normalizedSignature(node) !== normalizedSignature(previous)) changes.push('signature_changed');
+        if (node.metadata?.bodyHash !== previous.metadata?.bodyHash) changes.push('body_changed');
+      } else if (previous) {
+        changes.push('deleted');
+      }
+      if (changes.length > 0) {
+        timeline.push({ ...info, nodeId: node?.id, name: node?.name, changes });
+      }
+      previous = node;
+    }
+    return timeline;
+  }
+
+  private async history(revision: string, limit: number): Promise<CommitInfo[]> {
+    const commit = await this.resolveCommit(revision);
+    const { stdout } = await execFileAsync('git', ['log', `--max-count=${limit}`, '--format=%H%x09%cI%x09%s', commit], {
+      cwd: this.projectRoot,
+      maxBuffer: 16 * 1024 * 1024
+    });
+    return stdout.split('\n').filter(Boolean).map(line => {
+      const [hash, date, ...subject] = line.split('\t');
+      return { commit: hash, date, subject: subject.join('\t') };
+    }).reverse();
+  }
+
+  private async resolveCommit(revision: string): Promise<string> {
+    try {
+      const { stdout } = await execFileAsync('git', ['rev-parse', '--verify', '--quiet', `${revision}^{commit}`], { cwd: this.projectRoot });
+      return stdout.trim();
+    } catch {
+      throw new Error(`Not a commit in ${this.projectRoot}: ${revision}`);
+    }
+  }
+
+  private async load(commit: string): Promise<CommitGraph> {
+    // The whole repository is checked out; a project in a subdirectory is scanned from there
+    const directory = join(this.projectRoot, '.mindmap-cache', 'commits', commit);
+    const { stdout: prefix } = await execFileAsync('git', ['rev-parse', '--show-prefix'], { cwd: this.projectRoot });
+    const root = join(directory, prefix.trim());
+
+    if (await access(join(root, '.mindmap-cache', 'mindmap.json')).then(() => true, () => false)) {
+      const cached = new MindMapStorage(root);
+      await cached.load();
+      return new CommitGraph(commit, cached, true);
+    }
+
+    await this.checkout(commit, directory);
+    await mkdir(root, { recursive: true }); // The project directory may not exist yet at that commit
+    const storage = await this.scan(root);
+    await storage.save();
+    return new CommitGraph(commit, storage, false);
+  }
+
+  private async checkout(commit: string, directory: string): Promise<void> {
+    // A private index file keeps the repository's own index and working tree as they are
+    await rm(directory, { recursive: true, force: true });
+    await mkdir(directory, { recursive: true });
+    const index = `${directory}.index`;
+    const env = { ...process.env, GIT_INDEX_FILE: index };
+    try {
+      await execFileAsync('git', ['read-tree', commit], { cwd: this.projectRoot, env });
+      await execFileAsync('git', ['checkout-index', '--all', '--force', `--prefix=${directory}/`], {
+        cwd: this.projectRoot,
+        env,
+        maxBuffer: 64 * 1024 * 1024
+      });
+    } finally {
+      await rm(index, { force: true });
+    }
+  }
+}
+
+function normalizedSignature(node: MindMapNode, withoutName = false): string {
+  const signature = String(node.metadata?.signature || '').replace(/^pub(?:\([^)]*\))?\s+/, '').replace(/\s+/g, ' ').trim();
+  return withoutName ? signature.split(node.name).join('_') : signature;
+}
+
+/**
+ * The node standing for `later` in an earlier graph: the same ID, else the only node of the same kind
+ * and name, else the only node of the same kind whose signature differs from it in name alone
+ */
+function followSymbol(graph: CommitGraph, later: MindMapNode): MindMapNode | undefined {
+  const same = graph.getNode(later.id);
+  if (same) return same;
+  const kind = (node: MindMapNode) => `${node.type}:${node.metadata?.rustKind ?? ''}`;
+  const candidates = graph.findNodes(node => kind(node) === kind(later) && !node.metadata?.external);
+  const named = candidates.filter(node => node.name === later.name);
+  if (named.length === 1) return named[0];
+  if (!later.metadata?.signature) return undefined;
+  const shape = normalizedSignature(later, true);
+  const similar = candidates.filter(node => node.metadata?.signature && normalizedSignature(node, true) === shape);
+  return similar.length === 1 ? similar[0] : undefined;
+}
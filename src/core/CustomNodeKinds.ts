@@ -0,0 +1,74 @@
+import { MindMapNode } from '../types/index.js';
+
+/**
+ * Domain node kinds defined by users, such as `GraphQLResolver` or `DbMigration`. A node carries its
+ * kind in `customKind`: extractors either add nodes of type 'custom' or tag existing nodes, so a
+ * resolver function stays a function for every query while also being found by its kind. Exports
+ * draw each kind with its registered style.
+ */
+
+export type CustomNodeShape = 'box' | 'round' | 'stadium' | 'hexagon' | 'circle';
+
+export interface CustomNodeStyle {
+  color: string; // CSS color, used as the fill
+  shape: CustomNodeShape;
+}
+
+export const DEFAULT_CUSTOM_NODE_STYLE: CustomNodeStyle = { color: '#f5d76e', shape: 'hexagon' };
+
+export class CustomNodeKindRegistry {
+  private kinds = new Map<string, CustomNodeStyle>();
+
+  /**
+   * Register a kind, or restyle one registered earlier; unset style fields take the defaults
+   */
+  register(name: string, style: Partial<CustomNodeStyle> = {}): CustomNodeStyle {
+    const registered = { ...DEFAULT_CUSTOM_NODE_STYLE, ...style };
+    this.kinds.set(name, registered);
+    return registered;
+  }
+
+  unregister(name: string): boolean {
+    return this.kinds.delete(name);
+  }
+
+  has(name: string): boolean {
+    return this.kinds.has(name);
+  }
+
+  /**
+   * Style of a kind; kinds nodes carry without being registered are drawn with the default style
+   */
+  styleOf(name: string): CustomNodeStyle {
+    return this.kinds.get(name) || DEFAULT_CUSTOM_NODE_STYLE;
+  }
+
+  styles(): Record<string, CustomNodeStyle> {
+    return Object.fromEntries(this.kinds);
+  }
+
+  list(): string[] {
+    return Array.from(this.kinds.keys()).sort();
+  }
+}
+
+/**
+ * Build a node of a user-defined kind
+ */
+export function createCustomNode(
+  id: string,
+  name: string,
+  kind: string,
+  path?: string,
+  metadata: Record<string, any> = {},
+  confidence = 0.7
+): MindMapNode {
+  return { id, name, type: 'custom', customKind: kind, path, metadata, confidence, lastUpdated: new Date() };
+}
+
+/**
+ * A copy of a node tagged with a user-defined kind, keeping its type
+ */
+export function withCustomKind(node: MindMapNode, kind: string): MindMapNode {
+  return { ...node, customKind: kind, lastUpdated: new Date() };
+}
@@ -0,0 +1,291 @@
+import { MindMapStorage } from './MindMapStorage.js';
+import { MindMapNode } from '../types/index.js';
+
+export type ExternalItemKind = 'struct' | 'enum' | 'trait' | 'type' | 'function' | 'macro' | 'const';
+
+export interface ExternalItem {
+  path: string; // Within the crate, e.g. `web::Json` for `actix_web::web::Json`
+  kind: ExternalItemKind;
+  signature?: string;
+  typeParameters?: string[];
+  docs?: string;
+}
+
+// Known public items of a dependency, e.g. loaded from a companion JSON file
+export interface ExternalCrateSummary {
+  version?: string;
+  items: ExternalItem[];
+}
+
+// Path prefixes naming the same items, such as `core::error` re-exported as `std::error`
+export interface ExternalPathEquivalence {
+  alias: string; // e.g. `core`
+  canonical: string; // e.g. `std`
+}
+
+// How much of the external crates the graph keeps: a node per item used (the default), one node per
+// crate standing for all of its items, or no external nodes at all
+export type ExternalPolicy = 'full' | 'crate_level' | 'none';
+
+export const DEFAULT_PATH_EQUIVALENCES: readonly ExternalPathEquivalence[] = [
+  { alias: 'core', canonical: 'std' },
+  { alias: 'alloc', canonical: 'std' }
+];
+
+/**
+ * Summaries of external crates, looked up by the full paths Rust code imports and calls
+ */
+export class ExternalCrateRegistry {
+  private crates = new Map<string, { summary: ExternalCrateSummary; items: Map<string, ExternalItem> }>();
+  private equivalences: ExternalPathEquivalence[] = []; // Off until set
+  private policy: ExternalPolicy = 'full';
+
+  register(crateName: string, summary: ExternalCrateSummary): void {
+    const items = new Map(summary.items.map(item => [item.path.replace(/^::/, ''), item]));
+    this.crates.set(this.normalize(crateName), { summary, items });
+  }
+
+  unregister(crateName: string): boolean {
+    return this.crates.delete(this.normalize(crateName));
+  }
+
+  has(crateName: string): boolean {
+    return this.crates.has(this.normalize(crateName));
+  }
+
+  entries(): Array<[string, ExternalCrateSummary]> {
+    return Array.from(this.crates.entries()).map(([name, { summary }]) => [name, summary]);
+  }
+
+  /**
+   * Treat paths under equivalent prefixes as one item; an empty table turns this off
+   */
+  setEquivalences(equivalences: readonly ExternalPathEquivalence[]): void {
+    this.equivalences = equivalences.map(({ alias, canonical }) => ({ alias: this.trim(alias), canonical: this.trim(canonical) }));
+  }
+
+  getEquivalences(): ExternalPathEquivalence[] {
+    return this.equivalences.map(equivalence => ({ ...equivalence }));
+  }
+
+  /**
+   * The registered item a full path names, such as `serde::Serialize`. With equivalences set, every
+   * spelling of an item resolves to the same one: the canonical spelling when it is registered,
+   * otherwise the first registered alias.
+   */
+  lookup(path: string): { crateName: string; version?: string; item: ExternalItem } | undefined {
+    for (const spelling of this.spellings(this.trim(path))) {
+      const [crateName, ...rest] = spelling.split('::');
+      const entry = this.crates.get(crateName);
+      const item = entry?.items.get(rest.join('::'));
+      if (item) return { crateName, version: entry!.summary.version, item };
+    }
+    return undefined;
+  }
+
+  setPolicy(policy: ExternalPolicy): void {
+    this.policy = policy;
+  }
+
+  getPolicy(): ExternalPolicy {
+    return this.policy;
+  }
+
+  /**
+   * The registered crate a full path starts in, whichever items its summary lists
+   */
+  lookupCrate(path: string): { crateName: string; version?: string } | undefined {
+    for (const spelling of this.spellings(this.trim(path))) {
+      const [crateName, ...rest] = spelling.split('::');
+      const entry = this.crates.get(crateName);
+      if (entry && rest.length > 0) return { crateName, version: entry.summary.version };
+    }
+    return undefined;
+  }
+
+  private spellings(path: string): string[] {
+    const under = (prefix: string) => path === prefix || path.startsWith(`${prefix}::`);
+    const match = this.equivalences.find(({ alias, canonical }) => under(alias) || under(canonical));
+    if (!match) return [path];
+    const rest = path.slice((under(match.alias) ? match.alias : match.canonical).length);
+    const aliases = this.equivalences.filter(({ canonical }) => canonical === match.canonical).map(({ alias }) => alias);
+    return Array.from(new Set([match.canonical, ...aliases].map(prefix => `${prefix}${rest}`)));
+  }
+
+  private trim(path: string): string {
+    return path.replace(/^::/, '').replace(/::$/, '');
+  }
+
+  private normalize(crateName: string): string {
+    // Cargo package names may use dashes; paths always use underscores
+    return crateName.replace(/-/g, '_');
+  }
+}
+
+export function externalNodeId(crateName: string, path: string): string {
+  return `external:${crateName}::${path}`;
+}
+
+export function externalCrateNodeId(crateName: string): string {
+  return `external:${crateName}`;
+}
+
+/**
+ * Link Rust items to the registered external items they import, mention in their signatures or
+ * call, adding a node per external item that is used. Under the `crate_level` policy every path into
+ * a registered crate links to one node for the crate instead, and under `none` nothing is linked.
+ * Paths into unregistered crates are left as they are. Returns the number of edges added; running it
+ * again adds none.
+ */
+export function resolveExternalReferences(storage: MindMapStorage, registry: ExternalCrateRegistry): number {
+  if (registry.getPolicy() === 'none') return 0;
+  let added = 0;
+  const linkCrate = (source: MindMapNode, path: string, type: 'references' | 'calls') => {
+    const found = registry.lookupCrate(path);
+    if (!found) return;
+    const targetId = externalCrateNodeId(found.crateName);
+    if (!storage.getNode(targetId)) storage.addNode(externalCrateNode(targetId, found.crateName, found.version));
+
+    const id = `${source.id}->${type}->${targetId}`;
+    const existing = storage.getEdge(id);
+    const written = path.replace(/^::/, '');
+    if (existing) {
+      // One edge per item and crate, listing every path it uses
+      if (!existing.metadata!.paths.includes(written)) existing.metadata!.paths = [...existing.metadata!.paths, written].sort();
+      return;
+    }
+    storage.addEdge({
+      id,
+      source: source.id,
+      target: targetId,
+      type,
+      metadata: type === 'calls' ? { external: true, call: written, paths: [written] } : { relationship: 'external', path: written, paths: [written] },
+      confidence: 0.6,
+      lastUpdated: new Date()
+    });
+    added++;
+  };
+  const link = (source: MindMapNode, path: string, type: 'references' | 'calls') => {
+    if (registry.getPolicy() === 'crate_level') return linkCrate(source, path, type);
+    const found = registry.lookup(path);
+    if (!found) return;
+    const targetId = externalNodeId(found.crateName, found.item.path);
+    if (!storage.getNode(targetId)) storage.addNode(externalNode(targetId, found.crateName, found.version, found.item));
+    recordAlias(storage.getNode(targetId)!, path);
+
+    const id = `${source.id}->${type}->${targetId}`;
+    if (storage.getEdge(id)) return;
+    storage.addEdge({
+      id,
+      source: source.id,
+      target: targetId,
+      type,
+      metadata: type === 'calls' ? { external: true, call: path } : { relationship: 'external', path },
+      confidence: 0.7,
+      lastUpdated: new Date()
+    });
+    added++;
+  };
+
+  for (const node of storage.findNodes(node => node.metadata?.language === 'rust' && node.metadata.external !== true)) {
+    const imported = new Map<string, string>(
+      (node.metadata.importedReferences || []).map((reference: { name: string; path: string }) => [reference.name, reference.path])
+    );
+    if (imported.size === 0 && !node.metadata.externalCalls?.length) continue;
+
+    // `Serialize` -> `serde::Serialize`; `web::Json<T>` -> `actix_web::web::Json`
+    const paths = new Set<string>(imported.values());
+    const typeTexts: string[] = [...(node.metadata.parameterTypes || []), node.metadata.returnType, ...(node.metadata.propertyTypes || [])];
+    for (const written of typeTexts.flatMap(text => (text || '').match(/[A-Za-z_][A-Za-z0-9_]*(?:::[A-Za-z_][A-Za-z0-9_]*)+/g) || [])) {
+      const [first, ...rest] = written.split('::');
+      if (imported.has(first)) paths.add([imported.get(first), ...rest].join('::'));
+    }
+    for (const path of Array.from(paths).sort()) link(node, path, 'references');
+    for (const call of node.metadata.externalCalls || []) link(node, call, 'calls');
+  }
+  return added;
+}
+
+/**
+ * Merge external nodes the registry's equivalences now resolve to another node into that node, moving
+ * their edges over and keeping their paths as `aliases`. Returns the number of nodes merged away.
+ */
+export function mergeEquivalentExternalNodes(storage: MindMapStorage, registry: ExternalCrateRegistry): number {
+  let merged = 0;
+  for (const node of storage.findNodes(node => node.metadata?.external === true && typeof node.metadata.qualifiedName === 'string')) {
+    const found = registry.lookup(node.metadata.qualifiedName);
+    const targetId = found && externalNodeId(found.crateName, found.item.path);
+    if (!found || targetId === node.id) continue;
+
+    if (!storage.getNode(targetId!)) storage.addNode(externalNode(targetId!, found.crateName, found.version, found.item));
+    const target = storage.getNode(targetId!)!;
+    for (const alias of [node.metadata.qualifiedName, ...(node.metadata.aliases || [])]) recordAlias(target, alias);
+    const edges = storage.findEdges(edge => edge.source === node.id || edge.target === node.id);
+    storage.removeNode(node.id);
+    for (const edge of edges) {
+      const source = edge.source === node.id ? targetId! : edge.source;
+      const destination = edge.target === node.id ? targetId! : edge.target;
+      const id = `${source}->${edge.type}->${destination}`;
+      if (source !== destination && !storage.getEdge(id)) storage.addEdge({ ...edge, id, source, target: destination });
+    }
+    merged++;
+  }
+  return merged;
+}
+
+function recordAlias(node: MindMapNode, path: string): void {
+  // Spellings other than the node's own, e.g. `core::error::Error` on `std::error::Error`
+  const written = path.replace(/^::/, '');
+  if (written === node.metadata.qualifiedName || !written.includes('::')) return;
+  if (written.split('::')[0] === node.metadata.crate) return;
+  node.metadata.aliases = Array.from(new Set([...(node.metadata.aliases || []), written])).sort();
+}
+
+/**
+ * Remove the nodes (and so the edges) added for a crate's items
+ */
+export function removeExternalNodes(storage: MindMapStorage, crateName: string): number {
+  const nodes = storage.findNodes(node => node.metadata?.external === true && node.metadata.crate === crateName.replace(/-/g, '_'));
+  nodes.forEach(node => storage.removeNode(node.id));
+  return nodes.length;
+}
+
+function externalCrateNode(id: string, crateName: string, version: string | undefined): MindMapNode {
+  return {
+    id,
+    type: 'pattern',
+    name: crateName,
+    metadata: {
+      external: true,
+      crate: crateName,
+      crateVersion: version,
+      qualifiedName: crateName,
+      rustKind: 'crate',
+      language: 'rust'
+    },
+    confidence: 0.8,
+    lastUpdated: new Date()
+  };
+}
+
+function externalNode(id: string, crateName: string, version: string | undefined, item: ExternalItem): MindMapNode {
+  const type: MindMapNode['type'] = item.kind === 'function' || item.kind === 'macro' ? 'function' : item.kind === 'const' ? 'variable' : 'class';
+  return {
+    id,
+    type,
+    name: item.path.split('::').pop()!,
+    metadata: {
+      external: true,
+      crate: crateName,
+      crateVersion: version,
+      qualifiedName: `${crateName}::${item.path}`,
+      rustKind: item.kind,
+      signature: item.signature,
+      typeParameters: item.typeParameters || [],
+      docComment: item.docs,
+      language: 'rust'
+    },
+    confidence: 0.8,
+    lastUpdated: new Date()
+  };
+}
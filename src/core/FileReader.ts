@@ -0,0 +1,78 @@
+import { open, readFile } from 'fs/promises';
+
+/**
+ * Source file reading for the analyzers. The buffered backend lets `readFile` grow its buffer chunk by
+ * chunk; the presized backend stats the file first and reads it into one buffer of that size; the mmap
+ * backend maps large files into memory through the optional native `mmap-io` module and falls back to
+ * buffered reads where that module is not installed. The whole file is decoded to one string before
+ * parsing either way, so line and byte spans are computed against the same text.
+ */
+
+export type FileReadBackend = 'buffered' | 'presized' | 'mmap';
+
+// Files smaller than this are read with `readFile` even by the presized and mmap backends
+export const PRESIZED_READ_THRESHOLD = 256 * 1024;
+
+// Optional peer dependency; kept in a variable so the build does not need it installed
+const MMAP_MODULE = 'mmap-io';
+
+interface MmapModule {
+  PROT_READ: number;
+  MAP_SHARED: number;
+  map(size: number, protection: number, flags: number, fd: number, offset?: number): Buffer;
+}
+
+let mmapModule: Promise<MmapModule | null> | undefined;
+
+/**
+ * The native mmap binding, or null when it is not installed or failed to build
+ */
+export function loadMmap(): Promise<MmapModule | null> {
+  mmapModule ??= import(MMAP_MODULE)
+    .then(module => (module.default ?? module) as MmapModule)
+    .then(module => (typeof module.map === 'function' ? module : null))
+    .catch(() => null);
+  return mmapModule;
+}
+
+export interface FileContent {
+  text: string;
+  presized: boolean; // Read into a buffer sized from the file rather than through `readFile`
+  mapped: boolean; // Decoded straight from a memory mapping of the file
+}
+
+export class FileReader {
+  constructor(readonly backend: FileReadBackend = 'buffered', private threshold: number = PRESIZED_READ_THRESHOLD) {}
+
+  async read(filePath: string): Promise<FileContent> {
+    const mmap = this.backend === 'mmap' ? await loadMmap() : null;
+    if (this.backend === 'buffered' || (this.backend === 'mmap' && !mmap)) {
+      return { text: await readFile(filePath, 'utf-8'), presized: false, mapped: false };
+    }
+
+    const handle = await open(filePath, 'r');
+    try {
+      const { size } = await handle.stat();
+      if (size < this.threshold) {
+        return { text: await handle.readFile('utf-8'), presized: false, mapped: false };
+      }
+
+      if (mmap) {
+        // The mapping is released when the buffer is garbage collected; closing the descriptor does not end it
+        const region = mmap.map(size, mmap.PROT_READ, mmap.MAP_SHARED, handle.fd, 0);
+        return { text: region.toString('utf-8'), presized: false, mapped: true };
+      }
+
+      const buffer = Buffer.allocUnsafe(size);
+      let offset = 0;
+      while (offset < size) {
+        const { bytesRead } = await handle.read(buffer, offset, size - offset, offset);
+        if (bytesRead === 0) break; // Truncated while being read
+        offset += bytesRead;
+      }
+      return { text: buffer.toString('utf-8', 0, offset), presized: true, mapped: false };
+    } finally {
+      await handle.close();
+    }
+  }
+}
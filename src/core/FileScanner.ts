@@ -90,6 +90,7 @@ export class FileScanner {
       '**/*.so',
       '.vscode/**',
       '.idea/**',
+      '.mindmap-cache/**',
       '*.swp',
       '*.swo',
       '*~'
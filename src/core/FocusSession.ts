@@ -0,0 +1,78 @@
+import { readFile, writeFile, mkdir } from 'fs/promises';
+import { dirname } from 'path';
+
+export interface FocusEntry {
+  nodeId: string;
+  visitedAt: Date;
+}
+
+export interface FocusSessionOptions {
+  maxLength?: number; // Visits kept before the oldest is dropped (default: 20)
+  focusSize?: number; // Most recent visits that make up the current focus (default: 3)
+}
+
+/**
+ * Recency stack of the nodes visited during a browsing session. Revisiting a node moves it back
+ * to the top instead of adding a second entry, and the stack keeps at most `maxLength` visits.
+ */
+export class FocusSession {
+  private entries: FocusEntry[] = []; // Most recent first
+  private maxLength: number;
+  private focusSize: number;
+
+  constructor(options: FocusSessionOptions = {}) {
+    this.maxLength = Math.max(1, options.maxLength ?? 20);
+    this.focusSize = Math.max(1, options.focusSize ?? 3);
+  }
+
+  push(nodeId: string, at: Date = new Date()): void {
+    this.entries = [{ nodeId, visitedAt: at }, ...this.entries.filter(entry => entry.nodeId !== nodeId)]
+      .slice(0, this.maxLength);
+  }
+
+  /**
+   * The focus set: the most recently visited nodes, newest first
+   */
+  current(): string[] {
+    return this.entries.slice(0, this.focusSize).map(entry => entry.nodeId);
+  }
+
+  history(): FocusEntry[] {
+    return this.entries.map(entry => ({ ...entry }));
+  }
+
+  /**
+   * Drop visits to nodes that no longer exist
+   */
+  retain(exists: (nodeId: string) => boolean): void {
+    this.entries = this.entries.filter(entry => exists(entry.nodeId));
+  }
+
+  clear(): void {
+    this.entries = [];
+  }
+
+  async save(path: string): Promise<void> {
+    await mkdir(dirname(path), { recursive: true });
+    await writeFile(path, JSON.stringify(this.entries.map(entry => ({
+      nodeId: entry.nodeId,
+      visitedAt: entry.visitedAt.toISOString()
+    }))));
+  }
+
+  /**
+   * Replace the stack with one saved by `save`; returns false when there is nothing to load
+   */
+  async load(path: string): Promise<boolean> {
+    let stored: Array<{ nodeId: string; visitedAt: string }>;
+    try {
+      stored = JSON.parse(await readFile(path, 'utf-8'));
+    } catch {
+      return false;
+    }
+    this.entries = stored
+      .map(entry => ({ nodeId: entry.nodeId, visitedAt: new Date(entry.visitedAt) }))
+      .slice(0, this.maxLength);
+    return true;
+  }
+}
@@ -0,0 +1,89 @@
+import { execFile } from 'child_process';
+import { promisify } from 'util';
+
+const execFileAsync = promisify(execFile);
+
+interface BlamedLine {
+  sha: string; // All zeros for uncommitted lines
+  time: Date;
+}
+
+/**
+ * Per-line commits and their times from `git blame`, read once per file. Files outside a repository, or
+ * not tracked by it, have no blame information. Uncommitted lines are attributed to the time
+ * blame was run, as git does.
+ */
+export class GitBlame {
+  private lineCommits = new Map<string, Promise<BlamedLine[] | undefined>>(); // File -> commit per line (index 0 = line 1)
+
+  constructor(private projectRoot: string) {}
+
+  /**
+   * Most recent change to any line in the range, or undefined without blame information
+   */
+  async lastChanged(filePath: string, startLine: number = 1, endLine?: number): Promise<Date | undefined> {
+    const range = await this.lineRange(filePath, startLine, endLine);
+    if (!range || range.length === 0) return undefined;
+    return new Date(Math.max(...range.map(line => line.time.getTime())));
+  }
+
+  /**
+   * Distinct commits that last touched a line in the range, a cheap measure of how often the code has
+   * been changed; undefined without blame information. Uncommitted lines count as one more change.
+   */
+  async commitCount(filePath: string, startLine: number = 1, endLine?: number): Promise<number | undefined> {
+    const range = await this.lineRange(filePath, startLine, endLine);
+    return range ? new Set(range.map(line => line.sha)).size : undefined;
+  }
+
+  /**
+   * Forget cached blame, e.g. after a file changed on disk
+   */
+  invalidate(filePath?: string): void {
+    if (filePath === undefined) this.lineCommits.clear();
+    else this.lineCommits.delete(filePath);
+  }
+
+  private async lineRange(filePath: string, startLine: number, endLine?: number): Promise<BlamedLine[] | undefined> {
+    if (!this.lineCommits.has(filePath)) {
+      this.lineCommits.set(filePath, this.blame(filePath));
+    }
+    const lines = await this.lineCommits.get(filePath)!;
+    return lines?.slice(Math.max(0, startLine - 1), endLine ?? lines.length).filter(line => line !== undefined);
+  }
+
+  private async blame(filePath: string): Promise<BlamedLine[] | undefined> {
+    try {
+      const { stdout } = await execFileAsync('git', ['blame', '--porcelain', '--', filePath], {
+        cwd: this.projectRoot,
+        maxBuffer: 64 * 1024 * 1024
+      });
+      return this.parsePorcelain(stdout);
+    } catch {
+      return undefined;
+    }
+  }
+
+  private parsePorcelain(output: string): BlamedLine[] {
+    // Each line gets a `<sha> <orig> <final> [<count>]` header; commit fields follow its first use
+    const commitTimes = new Map<string, number>();
+    const lineCommits: Array<{ line: number; sha: string }> = [];
+    let currentSha = '';
+    for (const line of output.split('\n')) {
+      const header = line.match(/^([0-9a-f]{40}) \d+ (\d+)(?: \d+)?$/);
+      if (header) {
+        currentSha = header[1];
+        lineCommits.push({ line: parseInt(header[2], 10), sha: currentSha });
+        continue;
+      }
+      const committerTime = line.match(/^committer-time (\d+)$/);
+      if (committerTime) commitTimes.set(currentSha, parseInt(committerTime[1], 10) * 1000);
+    }
+
+    const lines: BlamedLine[] = [];
+    for (const { line, sha } of lineCommits) {
+      lines[line - 1] = { sha, time: new Date(commitTimes.get(sha) ?? Date.now()) };
+    }
+    return lines;
+  }
+}
@@ -0,0 +1,58 @@
+/**
+ * Change notifications for the mind map graph, so watchers and editor or protocol layers can react to
+ * edits, rescans, imported annotations and every other mutation without polling. Listeners run after the mutation has finished and every index
+ * derived from the graph has been invalidated.
+ */
+
+export type GraphChangeCause = 'edit' | 'scan' | 'merge' | 'annotate' | 'restore' | 'external' | 'traverse' | 'decay';
+
+export interface GraphChange {
+  cause: GraphChangeCause;
+  filePaths: string[]; // Files rescanned or annotated; empty for a full scan
+  added: string[]; // Node IDs
+  removed: string[];
+  changed: string[]; // For scans and merges, every node that was replaced whether or not it differs; for external changes, nodes whose links moved
+  changedEdges?: string[]; // Edges whose learned weight moved; set for traversals and decay, which leave nodes alone
+  revision: number; // Storage revision after the change
+}
+
+export interface GraphChangeListener {
+  onChange(change: GraphChange): void;
+}
+
+export interface GraphSubscription {
+  unsubscribe(): void;
+}
+
+export class GraphEventBus {
+  private listeners = new Set<GraphChangeListener>();
+
+  /**
+   * Register a listener, either an object with `onChange` or a plain function
+   */
+  subscribe(listener: GraphChangeListener | ((change: GraphChange) => void)): GraphSubscription {
+    // Wrapping gives every subscription its own entry, so one listener can be registered twice
+    const entry: GraphChangeListener = typeof listener === 'function'
+      ? { onChange: listener }
+      : { onChange: change => listener.onChange(change) };
+    this.listeners.add(entry);
+    return { unsubscribe: () => { this.listeners.delete(entry); } };
+  }
+
+  hasListeners(): boolean {
+    return this.listeners.size > 0;
+  }
+
+  /**
+   * Notify every listener; one that throws does not keep the others from being notified
+   */
+  emit(change: GraphChange): void {
+    for (const listener of Array.from(this.listeners)) {
+      try {
+        listener.onChange(change);
+      } catch (error) {
+        console.warn('Graph change listener failed:', error);
+      }
+    }
+  }
+}
@@ -0,0 +1,191 @@
+import { MindMapEdge, MindMapGraph, MindMapNode } from '../types/index.js';
+import { SymbolNodeKind, symbolNodeKind } from './SymbolKinds.js';
+import { CustomNodeStyle, CustomNodeShape, DEFAULT_CUSTOM_NODE_STYLE } from './CustomNodeKinds.js';
+
+/**
+ * Mermaid, Graphviz DOT, JSON and D3 renderings of the mind map. Weighted exports draw edges by their
+ * learned strength, so paths reinforced by repeated traversals stand out and faded ones can be hidden.
+ */
+
+// Narrows what any exporter renders; edges are kept only when both endpoints are
+export interface ExportFilter {
+  kinds?: SymbolNodeKind[]; // Only nodes of these kinds, e.g. `['struct', 'trait', 'impl']` for a type diagram
+  minConfidence?: number; // Only nodes and edges at least this confident; defaults to 0
+  modulePrefix?: string; // Only nodes in this module or below it, e.g. `config` or `crate::config`
+  includeExternal?: boolean; // Keep nodes standing for items outside the project; defaults to true
+  customKinds?: string[]; // Only nodes of these user-defined kinds
+}
+
+export interface GraphExportOptions extends ExportFilter {
+  weighted?: boolean; // Style edges by strength; defaults to false
+  minStrength?: number; // Hide edges weaker than this (weighted exports only); defaults to 0
+  nodeTypes?: Array<MindMapNode['type']>; // Only these node types; defaults to all
+  edgeTypes?: Array<MindMapEdge['type']>; // Only these edge types; defaults to all
+  nodeStyles?: Record<string, CustomNodeStyle>; // Styles of custom node kinds; unlisted kinds use the default
+}
+
+// Edges that were never traversed count as this strong, as in activation spreading
+export const DEFAULT_EDGE_STRENGTH = 1.0;
+
+type EdgeStyle = 'plain' | 'faded' | 'reinforced';
+
+interface ExportedGraph {
+  nodes: MindMapNode[];
+  edges: Array<{ edge: MindMapEdge; strength: number; style: EdgeStyle }>;
+  ids: Map<string, string>; // Node ID -> short identifier safe in either syntax
+}
+
+export function edgeStrength(edge: MindMapEdge): number {
+  return edge.weight ?? DEFAULT_EDGE_STRENGTH;
+}
+
+export function toMermaid(graph: MindMapGraph, options: GraphExportOptions = {}): string {
+  const { nodes, edges, ids } = selectGraph(graph, options);
+  const lines = ['flowchart LR'];
+  const styles: string[] = [];
+  for (const node of nodes) {
+    const style = customStyle(node, options);
+    const [open, close] = style ? MERMAID_SHAPES[style.shape] : MERMAID_SHAPES.box;
+    lines.push(`  ${ids.get(node.id)}${open}"${node.name.replace(/"/g, '#quot;')}"${close}`);
+    if (style) styles.push(`  style ${ids.get(node.id)} fill:${style.color}`);
+  }
+
+  // `==>` is a thick arrow and `-.->` a dotted one; link styles scale the stroke with the strength
+  const arrows: Record<EdgeStyle, string> = { plain: '-->', faded: '-.->', reinforced: '==>' };
+  const linkStyles: string[] = [];
+  edges.forEach(({ edge, strength, style }, index) => {
+    lines.push(`  ${ids.get(edge.source)} ${arrows[style]}|${edgeLabel(edge)}| ${ids.get(edge.target)}`);
+    if (style === 'reinforced') linkStyles.push(`  linkStyle ${index} stroke-width:${strokeWidth(strength)}px`);
+  });
+
+  return [...lines, ...styles, ...linkStyles].join('\n') + '\n';
+}
+
+export function toDot(graph: MindMapGraph, options: GraphExportOptions = {}): string {
+  const { nodes, edges, ids } = selectGraph(graph, options);
+  const quote = (text: string) => `"${text.replace(/\\/g, '\\\\').replace(/"/g, '\\"')}"`;
+  const lines = ['digraph mindmap {', '  rankdir=LR;'];
+  for (const node of nodes) {
+    const style = customStyle(node, options);
+    const fill = style?.shape === 'stadium' ? '"rounded,filled"' : 'filled';
+    const attributes = style ? `, shape=${DOT_SHAPES[style.shape]}, style=${fill}, fillcolor=${quote(style.color)}` : '';
+    lines.push(`  ${ids.get(node.id)} [label=${quote(node.name)}${attributes}];`);
+  }
+
+  for (const { edge, strength, style } of edges) {
+    const attributes = [`label=${quote(edgeLabel(edge))}`];
+    if (style === 'reinforced') attributes.push(`penwidth=${strokeWidth(strength)}`);
+    if (style === 'faded') attributes.push('style=dashed');
+    lines.push(`  ${ids.get(edge.source)} -> ${ids.get(edge.target)} [${attributes.join(', ')}];`);
+  }
+
+  return [...lines, '}'].join('\n') + '\n';
+}
+
+// Plain nodes and edges, for clients that lay the graph out themselves
+export interface JsonGraphExport {
+  nodes: MindMapNode[];
+  edges: MindMapEdge[];
+}
+
+// The `{ nodes, links }` shape d3-force simulations take, with nodes grouped by module
+export interface D3GraphExport {
+  nodes: Array<{ id: string; name: string; kind: string; group: string }>;
+  links: Array<{ source: string; target: string; type: string; value: number }>;
+}
+
+export function toJson(graph: MindMapGraph, options: GraphExportOptions = {}): JsonGraphExport {
+  const { nodes, edges } = selectGraph(graph, options);
+  return { nodes, edges: edges.map(({ edge }) => edge) };
+}
+
+export function toD3(graph: MindMapGraph, options: GraphExportOptions = {}): D3GraphExport {
+  const { nodes, edges } = selectGraph(graph, options);
+  return {
+    nodes: nodes.map(node => ({
+      id: node.id,
+      name: node.name,
+      kind: node.customKind || symbolNodeKind(node) || node.type,
+      group: node.metadata?.module || node.path || ''
+    })),
+    links: edges.map(({ edge, strength }) => ({ source: edge.source, target: edge.target, type: edgeLabel(edge), value: strength }))
+  };
+}
+
+/**
+ * The nodes and edges an export filter keeps, sorted by ID
+ */
+export function filterGraph(graph: MindMapGraph, filter: ExportFilter = {}): { nodes: MindMapNode[]; edges: MindMapEdge[] } {
+  const kinds = filter.kinds && new Set(filter.kinds);
+  const minConfidence = filter.minConfidence ?? 0;
+  const prefix = filter.modulePrefix?.trim().replace(/^crate(?:::|$)/, '');
+  const nodes = Array.from(graph.nodes.values())
+    .filter(node => {
+      if (kinds && !kinds.has(symbolNodeKind(node)!)) return false;
+      if (node.confidence < minConfidence) return false;
+      if (filter.includeExternal === false && node.metadata?.external) return false;
+      if (filter.customKinds && !filter.customKinds.includes(node.customKind || '')) return false;
+      if (prefix) {
+        const module: string | undefined = node.metadata?.module;
+        if (typeof module !== 'string' || (module !== prefix && !module.startsWith(`${prefix}::`))) return false;
+      }
+      return true;
+    })
+    .sort((a, b) => a.id.localeCompare(b.id));
+  const kept = new Set(nodes.map(node => node.id));
+
+  const edges = Array.from(graph.edges.values())
+    .filter(edge => kept.has(edge.source) && kept.has(edge.target) && edge.confidence >= minConfidence)
+    .sort((a, b) => a.id.localeCompare(b.id));
+  return { nodes, edges };
+}
+
+function selectGraph(graph: MindMapGraph, options: GraphExportOptions): ExportedGraph {
+  const filtered = filterGraph(graph, options);
+  const nodes = filtered.nodes.filter(node => !options.nodeTypes || options.nodeTypes.includes(node.type));
+  const ids = new Map(nodes.map((node, index) => [node.id, `n${index}`]));
+
+  const edges = filtered.edges
+    .filter(edge => ids.has(edge.source) && ids.has(edge.target))
+    .filter(edge => !options.edgeTypes || options.edgeTypes.includes(edge.type))
+    .map(edge => ({ edge, strength: edgeStrength(edge), style: 'plain' as EdgeStyle }))
+    .filter(({ strength }) => !options.weighted || strength >= (options.minStrength ?? 0));
+
+  if (options.weighted) {
+    for (const entry of edges) {
+      if (entry.strength > DEFAULT_EDGE_STRENGTH) entry.style = 'reinforced';
+      else if (entry.strength < DEFAULT_EDGE_STRENGTH) entry.style = 'faded';
+    }
+  }
+  return { nodes, edges, ids };
+}
+
+const MERMAID_SHAPES: Record<CustomNodeShape, [string, string]> = {
+  box: ['[', ']'],
+  round: ['(', ')'],
+  stadium: ['([', '])'],
+  hexagon: ['{{', '}}'],
+  circle: ['((', '))']
+};
+
+// Graphviz has no stadium; a box with rounded corners stands in for it
+const DOT_SHAPES: Record<CustomNodeShape, string> = {
+  box: 'box',
+  round: 'ellipse',
+  stadium: 'box',
+  hexagon: 'hexagon',
+  circle: 'circle'
+};
+
+function customStyle(node: MindMapNode, options: GraphExportOptions): CustomNodeStyle | undefined {
+  return node.customKind ? options.nodeStyles?.[node.customKind] || DEFAULT_CUSTOM_NODE_STYLE : undefined;
+}
+
+function edgeLabel(edge: MindMapEdge): string {
+  return edge.type === 'custom' && edge.customKind ? edge.customKind : edge.type;
+}
+
+function strokeWidth(strength: number): number {
+  // 2px at the default strength, growing with reinforcement up to 8px
+  return Math.min(8, Math.round((1 + strength) * 10) / 10);
+}
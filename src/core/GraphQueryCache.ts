@@ -0,0 +1,85 @@
+import { MindMapStorage } from './MindMapStorage.js';
+import { LRUCache } from './LRUCache.js';
+
+export interface GraphQueryCacheStats {
+  hits: number;
+  misses: number;
+  entries: number;
+  maxEntries: number;
+  revision: number; // Graph revision the cached results were computed at
+}
+
+/**
+ * Results of graph queries keyed by query name and parameters. Every entry belongs to the
+ * storage revision it was computed at: once the graph changes, the whole cache is dropped on
+ * the next lookup, so a result is never served for a graph it was not computed from.
+ */
+export class GraphQueryCache {
+  private entries: LRUCache<string, unknown>;
+  private revision: number;
+  private hits = 0;
+  private misses = 0;
+
+  constructor(private storage: MindMapStorage, private maxEntries: number = 256) {
+    this.entries = new LRUCache(Math.max(1, maxEntries));
+    this.revision = storage.getRevision();
+  }
+
+  getOrCompute<T>(query: string, params: unknown, compute: () => T): T {
+    this.dropIfStale();
+    const key = `${query}:${stableStringify(params)}`;
+    if (this.entries.has(key)) {
+      this.hits++;
+      return this.entries.get(key) as T;
+    }
+
+    this.misses++;
+    const result = compute();
+    // A query that modified the graph while running is not reused
+    if (this.storage.getRevision() === this.revision) this.entries.set(key, result);
+    return result;
+  }
+
+  /**
+   * Bound the number of cached results; shrinking keeps the most recently used ones
+   */
+  setMaxEntries(maxEntries: number): void {
+    const resized = new LRUCache<string, unknown>(Math.max(1, maxEntries));
+    for (const key of Array.from(this.entries.keys())) resized.set(key, this.entries.get(key));
+    this.entries = resized;
+    this.maxEntries = maxEntries;
+  }
+
+  clear(): void {
+    this.entries.clear();
+  }
+
+  getStats(): GraphQueryCacheStats {
+    return {
+      hits: this.hits,
+      misses: this.misses,
+      entries: this.entries.size(),
+      maxEntries: this.maxEntries,
+      revision: this.revision
+    };
+  }
+
+  private dropIfStale(): void {
+    const revision = this.storage.getRevision();
+    if (revision === this.revision) return;
+    this.entries.clear();
+    this.revision = revision;
+  }
+}
+
+function stableStringify(value: unknown): string {
+  // Object keys in sorted order, so `{a, b}` and `{b, a}` share an entry
+  if (value === undefined) return 'undefined';
+  if (value === null || typeof value !== 'object') return JSON.stringify(value);
+  if (value instanceof Date) return JSON.stringify(value.toISOString());
+  if (Array.isArray(value)) return `[${value.map(stableStringify).join(',')}]`;
+  const entries = Object.entries(value as Record<string, unknown>)
+    .filter(([, field]) => field !== undefined)
+    .sort(([a], [b]) => a.localeCompare(b));
+  return `{${entries.map(([key, field]) => `${JSON.stringify(key)}:${stableStringify(field)}`).join(',')}}`;
+}
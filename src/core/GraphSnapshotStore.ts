@@ -0,0 +1,303 @@
+import { readFile, writeFile, mkdir } from 'fs/promises';
+import { dirname } from 'path';
+import { MindMapStorage } from './MindMapStorage.js';
+import { MindMapNode, MindMapEdge } from '../types/index.js';
+
+export interface SnapshotInfo {
+  label: string;
+  createdAt: Date;
+  nodeCount: number;
+  edgeCount: number;
+  deltaSize: number; // Entries stored for this snapshot (upserts + removals)
+}
+
+export interface SnapshotDiff {
+  from: string;
+  to: string;
+  addedNodes: string[];
+  removedNodes: string[];
+  changedNodes: string[]; // Present in both with different content (timestamps ignored)
+  addedEdges: string[];
+  removedEdges: string[];
+  changedEdges: string[];
+}
+
+export interface GraphDelta {
+  since: string; // Snapshot label
+  nodes: MindMapNode[]; // Added or changed since the snapshot, in ID order
+  edges: MindMapEdge[];
+  removedNodes: string[];
+  removedEdges: string[];
+}
+
+export interface ChangedSinceOptions {
+  includeEdges?: boolean; // Defaults to true; false leaves `edges` and `removedEdges` empty
+}
+
+interface ElementDelta {
+  upsert: Record<string, string>; // ID -> serialized element
+  remove: string[];
+}
+
+interface StoredSnapshot {
+  label: string;
+  createdAt: string;
+  nodeCount: number;
+  edgeCount: number;
+  nodes: ElementDelta;
+  edges: ElementDelta;
+}
+
+interface SnapshotState {
+  nodes: Map<string, string>;
+  edges: Map<string, string>;
+}
+
+const TIMESTAMP_FIELDS = ['lastUpdated', 'created', 'createdAt'];
+
+/**
+ * Labelled snapshots of the mind map for time travel. Each snapshot stores only the nodes and
+ * edges that changed since the previous one; states are rebuilt by replaying deltas in order.
+ */
+export class GraphSnapshotStore {
+  private snapshots: StoredSnapshot[] | null = null;
+  private latestState: SnapshotState | null = null;
+  private stateCache = new Map<string, SnapshotState>(); // Snapshots never change once taken
+
+  constructor(
+    private storage: MindMapStorage,
+    private snapshotPath: string
+  ) {}
+
+  async snapshot(label: string): Promise<SnapshotInfo> {
+    const snapshots = await this.load();
+    if (snapshots.some(snapshot => snapshot.label === label)) {
+      throw new Error(`Snapshot already exists: ${label}`);
+    }
+
+    const previous = this.latestState || { nodes: new Map<string, string>(), edges: new Map<string, string>() };
+    const graph = this.storage.getGraph();
+    const current: SnapshotState = {
+      nodes: this.serializeAll(graph.nodes),
+      edges: this.serializeAll(graph.edges)
+    };
+
+    const stored: StoredSnapshot = {
+      label,
+      createdAt: new Date().toISOString(),
+      nodeCount: current.nodes.size,
+      edgeCount: current.edges.size,
+      nodes: this.delta(previous.nodes, current.nodes),
+      edges: this.delta(previous.edges, current.edges)
+    };
+
+    snapshots.push(stored);
+    this.latestState = current;
+    this.stateCache.set(label, current);
+    await this.persist();
+
+    return this.toInfo(stored);
+  }
+
+  /**
+   * Replace the mind map contents with a snapshot, preserving node identity, edges and learned weights
+   */
+  async restore(label: string): Promise<SnapshotInfo> {
+    const snapshots = await this.load();
+    const stored = snapshots.find(snapshot => snapshot.label === label);
+    if (!stored) {
+      throw new Error(`Snapshot not found: ${label}`);
+    }
+
+    const state = this.stateAt(label);
+    this.storage.replaceContents(
+      Array.from(state.nodes.values()).map(value => this.deserialize(value) as MindMapNode),
+      Array.from(state.edges.values()).map(value => this.deserialize(value) as MindMapEdge)
+    );
+
+    return this.toInfo(stored);
+  }
+
+  /**
+   * Compare two snapshots, or a snapshot against the current mind map when `to` is omitted
+   */
+  async diff(from: string, to?: string): Promise<SnapshotDiff> {
+    const snapshots = await this.load();
+    for (const label of [from, to]) {
+      if (label !== undefined && !snapshots.some(snapshot => snapshot.label === label)) {
+        throw new Error(`Snapshot not found: ${label}`);
+      }
+    }
+
+    const graph = this.storage.getGraph();
+    const before = this.stateAt(from);
+    const after = to !== undefined
+      ? this.stateAt(to)
+      : { nodes: this.serializeAll(graph.nodes), edges: this.serializeAll(graph.edges) };
+
+    const nodes = this.compare(before.nodes, after.nodes);
+    const edges = this.compare(before.edges, after.edges);
+
+    return {
+      from,
+      to: to ?? 'current',
+      addedNodes: nodes.added,
+      removedNodes: nodes.removed,
+      changedNodes: nodes.changed,
+      addedEdges: edges.added,
+      removedEdges: edges.removed,
+      changedEdges: edges.changed
+    };
+  }
+
+  /**
+   * Only what differs between a snapshot and the current mind map, as the current nodes and edges
+   * plus the IDs removed since, so a client holding the snapshot state can catch up cheaply
+   */
+  async changedSince(label: string, options: ChangedSinceOptions = {}): Promise<GraphDelta> {
+    const diff = await this.diff(label);
+    const graph = this.storage.getGraph();
+    const includeEdges = options.includeEdges ?? true;
+
+    return {
+      since: label,
+      nodes: [...diff.addedNodes, ...diff.changedNodes].sort().map(id => graph.nodes.get(id)!),
+      edges: includeEdges ? [...diff.addedEdges, ...diff.changedEdges].sort().map(id => graph.edges.get(id)!) : [],
+      removedNodes: diff.removedNodes,
+      removedEdges: includeEdges ? diff.removedEdges : []
+    };
+  }
+
+  async list(): Promise<SnapshotInfo[]> {
+    return (await this.load()).map(snapshot => this.toInfo(snapshot));
+  }
+
+  private stateAt(label: string): SnapshotState {
+    const cached = this.stateCache.get(label);
+    if (cached) return cached;
+    const state: SnapshotState = { nodes: new Map(), edges: new Map() };
+
+    for (const snapshot of this.snapshots || []) {
+      this.applyDelta(state.nodes, snapshot.nodes);
+      this.applyDelta(state.edges, snapshot.edges);
+      if (snapshot.label === label) break;
+    }
+
+    this.stateCache.set(label, state);
+    return state;
+  }
+
+  private delta(previous: Map<string, string>, current: Map<string, string>): ElementDelta {
+    const delta: ElementDelta = { upsert: {}, remove: [] };
+
+    for (const [id, value] of current) {
+      if (previous.get(id) !== value) {
+        delta.upsert[id] = value;
+      }
+    }
+    for (const id of previous.keys()) {
+      if (!current.has(id)) {
+        delta.remove.push(id);
+      }
+    }
+
+    return delta;
+  }
+
+  private applyDelta(state: Map<string, string>, delta: ElementDelta): void {
+    for (const id of delta.remove) {
+      state.delete(id);
+    }
+    for (const [id, value] of Object.entries(delta.upsert)) {
+      state.set(id, value);
+    }
+  }
+
+  private compare(before: Map<string, string>, after: Map<string, string>): { added: string[]; removed: string[]; changed: string[] } {
+    const added: string[] = [];
+    const removed: string[] = [];
+    const changed: string[] = [];
+
+    for (const [id, value] of after) {
+      const previous = before.get(id);
+      if (previous === undefined) {
+        added.push(id);
+      } else if (previous !== value && this.withoutTimestamps(previous) !== this.withoutTimestamps(value)) {
+        changed.push(id);
+      }
+    }
+    for (const id of before.keys()) {
+      if (!after.has(id)) {
+        removed.push(id);
+      }
+    }
+
+    return { added: added.sort(), removed: removed.sort(), changed: changed.sort() };
+  }
+
+  private withoutTimestamps(value: string): string {
+    const element = JSON.parse(value);
+    for (const field of TIMESTAMP_FIELDS) {
+      delete element[field];
+    }
+    return JSON.stringify(element);
+  }
+
+  private serializeAll(elements: Map<string, MindMapNode | MindMapEdge>): Map<string, string> {
+    const serialized = new Map<string, string>();
+    for (const [id, element] of elements) {
+      serialized.set(id, this.serialize(element));
+    }
+    return serialized;
+  }
+
+  private serialize(element: MindMapNode | MindMapEdge): string {
+    // Tag dates so they come back as Date objects rather than strings
+    return JSON.stringify(element, function (this: any, key: string, value: any) {
+      const original = this[key];
+      return original instanceof Date ? { $date: original.toISOString() } : value;
+    });
+  }
+
+  private deserialize(value: string): MindMapNode | MindMapEdge {
+    return JSON.parse(value, (_key, item) =>
+      item && typeof item === 'object' && typeof item.$date === 'string' && Object.keys(item).length === 1
+        ? new Date(item.$date)
+        : item
+    );
+  }
+
+  private toInfo(snapshot: StoredSnapshot): SnapshotInfo {
+    return {
+      label: snapshot.label,
+      createdAt: new Date(snapshot.createdAt),
+      nodeCount: snapshot.nodeCount,
+      edgeCount: snapshot.edgeCount,
+      deltaSize: Object.keys(snapshot.nodes.upsert).length + snapshot.nodes.remove.length +
+        Object.keys(snapshot.edges.upsert).length + snapshot.edges.remove.length
+    };
+  }
+
+  private async load(): Promise<StoredSnapshot[]> {
+    if (this.snapshots) return this.snapshots;
+
+    try {
+      this.snapshots = JSON.parse(await readFile(this.snapshotPath, 'utf-8')) as StoredSnapshot[];
+    } catch {
+      this.snapshots = [];
+    }
+
+    const latest = this.snapshots[this.snapshots.length - 1];
+    this.latestState = latest ? this.stateAt(latest.label) : null;
+    return this.snapshots;
+  }
+
+  private async persist(): Promise<void> {
+    try {
+      await mkdir(dirname(this.snapshotPath), { recursive: true });
+      await writeFile(this.snapshotPath, JSON.stringify(this.snapshots));
+    } catch (error) {
+      console.error('Failed to save mind map snapshots:', error);
+    }
+  }
+}
@@ -0,0 +1,96 @@
+import { readFile } from 'fs/promises';
+import { isAbsolute, join } from 'path';
+import { MindMapGraph, MindMapNode } from '../types/index.js';
+
+/**
+ * Structural invariants of the graph, for catching bugs in the analyzers and builders rather than in
+ * the code being analyzed. A healthy scan reports no violations.
+ */
+
+export type GraphViolationKind = 'dangling_edge' | 'duplicate_key' | 'module_mismatch' | 'span_out_of_bounds';
+
+export interface GraphViolation {
+  kind: GraphViolationKind;
+  nodeId?: string;
+  edgeId?: string;
+  message: string;
+}
+
+/**
+ * Check that edges join existing nodes, that node and edge IDs are unique and match the keys they are
+ * stored under, that items contained in another item share its module, and that spans lie within their
+ * file. Files are read from disk to count their lines; spans in files that cannot be read are not checked.
+ */
+export async function validateGraph(graph: MindMapGraph): Promise<GraphViolation[]> {
+  const violations: GraphViolation[] = [];
+
+  // IDs differing only in path separators name the same item twice
+  const normalized = new Map<string, string>();
+  for (const [key, node] of graph.nodes) {
+    if (node.id !== key) {
+      violations.push({ kind: 'duplicate_key', nodeId: node.id, message: `Node ${node.id} is stored under ${key}` });
+    }
+    const canonical = key.replace(/\\/g, '/');
+    const previous = normalized.get(canonical);
+    if (previous !== undefined) {
+      violations.push({ kind: 'duplicate_key', nodeId: key, message: `Nodes ${previous} and ${key} have the same ID` });
+    } else {
+      normalized.set(canonical, key);
+    }
+  }
+
+  for (const [key, edge] of graph.edges) {
+    if (edge.id !== key) {
+      violations.push({ kind: 'duplicate_key', edgeId: edge.id, message: `Edge ${edge.id} is stored under ${key}` });
+    }
+    const missing = [edge.source, edge.target].filter(id => !graph.nodes.has(id));
+    if (missing.length > 0) {
+      violations.push({ kind: 'dangling_edge', edgeId: key, message: `Edge ${key} references missing node${missing.length > 1 ? 's' : ''} ${missing.join(', ')}` });
+      continue;
+    }
+
+    // File nodes carry no module; items nested in another item live in the same module
+    const parent = graph.nodes.get(edge.source)!;
+    const child = graph.nodes.get(edge.target)!;
+    if (edge.type === 'contains' && typeof parent.metadata?.module === 'string' && typeof child.metadata?.module === 'string' &&
+        parent.metadata.module !== child.metadata.module && parent.path === child.path) {
+      violations.push({
+        kind: 'module_mismatch',
+        nodeId: child.id,
+        edgeId: key,
+        message: `${child.id} is in module ${child.metadata.module} but its parent ${parent.id} is in ${parent.metadata.module}`
+      });
+    }
+  }
+
+  const lineCounts = new Map<string, number | undefined>();
+  const lineCount = async (filePath: string) => {
+    if (!lineCounts.has(filePath)) {
+      const fullPath = isAbsolute(filePath) ? filePath : join(graph.projectRoot, filePath);
+      const content = await readFile(fullPath, 'utf-8').catch(() => undefined);
+      lineCounts.set(filePath, content === undefined ? undefined : content.split('\n').length);
+    }
+    return lineCounts.get(filePath);
+  };
+  for (const node of graph.nodes.values()) {
+    const span = spanOf(node);
+    if (!span || node.metadata?.external) continue;
+    const lines = await lineCount(node.path!);
+    if (span.startLine < 1 || span.endLine < span.startLine || (lines !== undefined && span.endLine > lines)) {
+      violations.push({
+        kind: 'span_out_of_bounds',
+        nodeId: node.id,
+        message: `${node.id} spans lines ${span.startLine}-${span.endLine}${lines !== undefined ? ` of a ${lines}-line file` : ''}`
+      });
+    }
+  }
+
+  return violations;
+}
+
+function spanOf(node: MindMapNode): { startLine: number; endLine: number } | undefined {
+  const startLine = node.metadata?.startLine;
+  if (node.type === 'file' || typeof node.path !== 'string' || typeof startLine !== 'number') return undefined;
+  const endLine = node.metadata?.endLine;
+  return { startLine, endLine: typeof endLine === 'number' ? endLine : startLine };
+}
@@ -0,0 +1,80 @@
+/**
+ * Canonical keys for identifiers written under different naming conventions, so the same logical symbol
+ * can be recognized across languages: Rust `create_user`, TypeScript `createUser` and Python
+ * `create_user` all normalize to `createuser`
+ */
+
+export interface IdentifierNormalizationRule {
+  caseSensitive?: boolean; // Keep case; defaults to false
+  separators?: string; // Characters dropped from the name; defaults to `_`, `-` and `$`
+  prefixes?: string[]; // Stripped from the start when present, e.g. `r#` for Rust raw identifiers
+  suffixes?: string[]; // Stripped from the end when present
+}
+
+export const DEFAULT_NORMALIZATION_RULE: IdentifierNormalizationRule = { separators: '_-$' };
+
+export const DEFAULT_NORMALIZATION_RULES: Record<string, IdentifierNormalizationRule> = {
+  rust: { separators: '_', prefixes: ['r#'] },
+  typescript: { separators: '_$' },
+  javascript: { separators: '_$' },
+  // Leading underscores mark private members, not a different name
+  python: { separators: '_', prefixes: ['__', '_'] },
+  go: { separators: '_' },
+  java: { separators: '_$' },
+  kotlin: { separators: '_' },
+  csharp: { separators: '_' }
+};
+
+const LANGUAGE_EXTENSIONS: Record<string, string> = {
+  rs: 'rust',
+  ts: 'typescript',
+  tsx: 'typescript',
+  js: 'javascript',
+  jsx: 'javascript',
+  py: 'python',
+  go: 'go',
+  java: 'java',
+  kt: 'kotlin',
+  cs: 'csharp'
+};
+
+export function languageOfPath(path: string): string | undefined {
+  return LANGUAGE_EXTENSIONS[path.split('.').pop()!.toLowerCase()];
+}
+
+export class IdentifierNormalizer {
+  private rules: Record<string, IdentifierNormalizationRule>;
+
+  constructor(rules: Record<string, IdentifierNormalizationRule> = {}) {
+    this.rules = { ...DEFAULT_NORMALIZATION_RULES, ...rules };
+  }
+
+  setRule(language: string, rule: IdentifierNormalizationRule): void {
+    this.rules[language] = rule;
+  }
+
+  getRule(language: string | undefined): IdentifierNormalizationRule {
+    return (language && this.rules[language]) || DEFAULT_NORMALIZATION_RULE;
+  }
+
+  normalize(name: string, language?: string): string {
+    const rule = this.getRule(language);
+    let key = name;
+    // At most one prefix and one suffix, longest listed first wins
+    const prefix = (rule.prefixes || []).find(candidate => key.startsWith(candidate) && key.length > candidate.length);
+    if (prefix) key = key.substring(prefix.length);
+    const suffix = (rule.suffixes || []).find(candidate => key.endsWith(candidate) && key.length > candidate.length);
+    if (suffix) key = key.substring(0, key.length - suffix.length);
+
+    const separators = new Set(rule.separators ?? DEFAULT_NORMALIZATION_RULE.separators);
+    key = Array.from(key).filter(char => !separators.has(char)).join('');
+    return rule.caseSensitive ? key : key.toLowerCase();
+  }
+
+  /**
+   * Whether two identifiers, each in its own language, name the same logical symbol
+   */
+  same(a: string, languageA: string | undefined, b: string, languageB: string | undefined): boolean {
+    return this.normalize(a, languageA) === this.normalize(b, languageB);
+  }
+}
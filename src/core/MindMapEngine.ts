@@ -10,6 +10,9 @@ import { LanguageToolingDetector } from './LanguageToolingDetector.js';
 import { EnhancedFrameworkDetector } from './EnhancedFrameworkDetector.js';
 import { ActivationNetwork, QueryContext } from './ActivationNetwork.js';
 import { QueryCache } from './QueryCache.js';
+import { GraphQueryCache, GraphQueryCacheStats } from './GraphQueryCache.js';
+import { FocusSession, FocusEntry, FocusSessionOptions } from './FocusSession.js';
+import { TextSearchIndex, TextSearchHit, TextSearchIndexStats } from './TextSearchIndex.js';
 import { ParallelFileProcessor } from './ParallelFileProcessor.js';
 import { InhibitoryLearningSystem } from './InhibitoryLearningSystem.js';
 import { HebbianLearningSystem } from './HebbianLearningSystem.js';
@@ -23,17 +26,44 @@ import { CustomPatternEngine } from './CustomPatternEngine.js';
 import { MultiModalConfidenceFusion, MultiModalConfidence, ConfidenceEvidence, FusedConfidence } from './MultiModalConfidenceFusion.js';
 import { EpisodicMemory, Episode, EpisodeContext, EpisodeAction, SimilarityMatch, EpisodeStats } from './EpisodicMemory.js';
 import { CallPatternAnalyzer } from './CallPatternAnalyzer.js';
+import { RustAttributeArg } from './RustAttributes.js';
+import { RelationshipExtractor } from './RelationshipExtractor.js';
+import { CustomNodeKindRegistry, CustomNodeStyle } from './CustomNodeKinds.js';
+import { RustParseOptions, RustDiagnostic, RustDiagnosticSeverity, RustLiteralKind } from './RustAnalyzer.js';
+import { Writable } from 'stream';
+import { PositionIndex } from './PositionIndex.js';
+import { GraphSnapshotStore, SnapshotInfo, SnapshotDiff, GraphDelta, ChangedSinceOptions } from './GraphSnapshotStore.js';
+import { compareDirectoryGraphs, DirectoryComparison, publicSurface, publicSurfaceHash } from './ApiDiff.js';
+import { ExternalCrateSummary, ExternalPathEquivalence, ExternalPolicy, DEFAULT_PATH_EQUIVALENCES } from './ExternalCrates.js';
+import { toMermaid, toDot, toJson, toD3, GraphExportOptions, ExportFilter, JsonGraphExport, D3GraphExport } from './GraphExport.js';
+import { toSarif, LintFinding, SarifLog, SarifOptions } from './SarifReport.js';
+import { resolveCrossFileCalls } from './RustCallResolver.js';
 
 // Service imports
 import { QueryService } from './services/QueryService.js';
 import { AnalysisService } from './services/AnalysisService.js';
 import { ConfigurationService } from './services/ConfigurationService.js';
 import { LearningService } from './services/LearningService.js';
-import { ScanningService } from './services/ScanningService.js';
+import { ScanningService, FileRescanResult, ScanOptions } from './services/ScanningService.js';
 import { DocumentIntelligenceService } from './services/DocumentIntelligenceService.js';
-
-import { MindMapNode, MindMapEdge, QueryOptions, QueryResult, FileInfo, ErrorPrediction, RiskAssessment, FixSuggestion, FixContext, HistoricalFix, FixGroup, ArchitecturalInsight, CacheStats, ProcessingProgress, InhibitionResult, ScalabilityConfig, ProjectScale, ResourceUsage, UserPreferences, CustomPatternRule, ProjectLearningConfig, PrivacySettings, UserFeedback } from '../types/index.js';
-import { join } from 'path';
+import { GraphAnalysisService, ContextPack, DuplicateFunctionGroup, AnalysisCacheStats, QueryLimits, TraversalResult, EdgeExplanation, ConnectionPath, EntryPointReach, EdgeFilter, EdgeQuery, BlastRadius, RelatedTest, DependencyClosure, CallChain, RiskCentralityOptions, HighFanNode, StepRules } from './services/GraphAnalysisService.js';
+import { RustAnalysisService, DeriveConsistencyIssue, TraitCoverage, DefaultMethodUsage, MethodOverride, DefaultValues, IgnoredResult, ShadowingSite, ExhaustivePublicEnum, PanicSite, PanicSiteOptions, RedundantClone, ConstFnCandidate, UnnecessaryOwnership, LayeringRules, LayeringViolation, LiteralUse, AsyncMismatch, MethodShadowing, NonExhaustiveMatch, LockAcrossAwait, FfiEntryPoint, EnvVarUsage, ModuleMetrics, ModuleSplit, MisplacedItem, MisplacedItemOptions, UndocumentedItem, UnusedAsyncFunction, NamingRules, NamingViolation, TypeUsage, TypeHeatmapOptions, TestGraph, MissingImpl, BoxedErrorSite, BoxedErrorOptions, DynDispatchSite, DispatchProfile, DipCandidate, ThreadSafetyIssue, ModuleErrorStyle, TraitImplementor, ModuleImplementors, PathResolution, FuzzyPathOptions, GenericBloatCandidate, VisibilitySmell, UnusedField, RedundantBound, RedundantLifetime, TraitRequirements, ErrorVariantUsage, LongParameterList, LongParameterListOptions, UntestedApiItem, TypeMethod, TypeMethodFilter, ApiReachability } from './services/RustAnalysisService.js';
+import { TypeParityService, TypeParityReport, CrossLanguageMatch } from './services/TypeParityService.js';
+import { IdentifierNormalizationRule } from './IdentifierNormalizer.js';
+import { RefactoringService, RenamePreview } from './services/RefactoringService.js';
+import { FreshnessService, FreshnessOptions, NodeFreshness } from './services/FreshnessService.js';
+import { HotspotService, HotspotOptions, Hotspot } from './services/HotspotService.js';
+import { SymbolService, SymbolInformation, NodeSpan, NodePredicate, NodeSpanFilter } from './services/SymbolService.js';
+import { SymbolKind, SymbolNodeKind } from './SymbolKinds.js';
+import { RustLint } from './AnalysisProfiles.js';
+import { GraphEventBus, GraphChange, GraphChangeListener, GraphSubscription } from './GraphEvents.js';
+import { CommitGraphs, CommitGraph, SymbolTimelineEntry, SymbolTimelineOptions } from './CommitGraphs.js';
+import { AnnotationRecord, ImportAnnotationsOptions, AnnotationImportResult, combineAnnotations } from './Annotations.js';
+import { CargoWorkspace } from './CargoWorkspace.js';
+import { validateGraph, GraphViolation } from './GraphValidation.js';
+
+import { MindMapGraph, MindMapNode, MindMapEdge, QueryOptions, QueryResult, FileInfo, ErrorPrediction, RiskAssessment, FixSuggestion, FixContext, HistoricalFix, FixGroup, ArchitecturalInsight, CacheStats, ProcessingProgress, InhibitionResult, ScalabilityConfig, ProjectScale, ResourceUsage, UserPreferences, CustomPatternRule, ProjectLearningConfig, PrivacySettings, UserFeedback } from '../types/index.js';
+import { join, relative, resolve } from 'path';
 
 export class MindMapEngine {
   private storage: MindMapStorage;
@@ -48,6 +78,10 @@ export class MindMapEngine {
   private enhancedFrameworkDetector: EnhancedFrameworkDetector;
   private activationNetwork: ActivationNetwork;
   private queryCache: QueryCache;
+  private graphQueryCache: GraphQueryCache;
+  private textSearchIndex: TextSearchIndex;
+  private focusSession: FocusSession = new FocusSession();
+  private focusSessionId?: string;
   private parallelProcessor: ParallelFileProcessor;
   private inhibitoryLearning: InhibitoryLearningSystem;
   private hebbianLearning: HebbianLearningSystem;
@@ -61,6 +95,11 @@ export class MindMapEngine {
   private multiModalFusion: MultiModalConfidenceFusion;
   private episodicMemory: EpisodicMemory;
   private callPatternAnalyzer: CallPatternAnalyzer;
+  private positionIndex: PositionIndex;
+  private snapshotStore: GraphSnapshotStore;
+  private graphEvents = new GraphEventBus();
+  private commitGraphs: CommitGraphs;
+  private customNodeKinds = new CustomNodeKindRegistry();
   private projectRoot: string;
 
   // Services
@@ -70,6 +109,13 @@ export class MindMapEngine {
   private learningService: LearningService;
   private scanningService: ScanningService;
   private documentIntelligenceService: DocumentIntelligenceService;
+  private graphAnalysisService: GraphAnalysisService;
+  private rustAnalysisService: RustAnalysisService;
+  private typeParityService: TypeParityService;
+  private refactoringService: RefactoringService;
+  private freshnessService: FreshnessService;
+  private hotspotService: HotspotService;
+  private symbolService: SymbolService;
 
   constructor(projectRoot: string) {
     this.projectRoot = projectRoot;
@@ -91,6 +137,8 @@ export class MindMapEngine {
     this.enhancedFrameworkDetector = new EnhancedFrameworkDetector(this.storage);
     this.activationNetwork = new ActivationNetwork(this.storage);
     this.queryCache = new QueryCache();
+    this.graphQueryCache = new GraphQueryCache(this.storage);
+    this.textSearchIndex = new TextSearchIndex(this.storage);
     this.parallelProcessor = new ParallelFileProcessor(this.projectRoot, {
       chunkSize: 100,
       maxWorkers: 3,
@@ -177,6 +225,29 @@ export class MindMapEngine {
       this.patternPrediction,
       this.projectRoot
     );
+
+    this.graphAnalysisService = new GraphAnalysisService(
+      this.storage,
+      this.activationNetwork
+    );
+
+    this.rustAnalysisService = new RustAnalysisService(this.storage);
+    this.typeParityService = new TypeParityService(this.storage, this.codeAnalyzer, this.projectRoot);
+    this.refactoringService = new RefactoringService(this.storage, this.projectRoot);
+    this.freshnessService = new FreshnessService(this.storage, this.projectRoot);
+    this.hotspotService = new HotspotService(this.storage, this.freshnessService);
+    this.symbolService = new SymbolService(this.storage, this.projectRoot);
+
+    this.snapshotStore = new GraphSnapshotStore(
+      this.storage,
+      join(this.projectRoot, '.mindmap-cache', 'snapshots.json')
+    );
+
+    this.commitGraphs = new CommitGraphs(this.projectRoot, directory => this.scanDirectory(directory, true, true));
+
+    this.positionIndex = new PositionIndex(filePath =>
+      this.storage.findNodes(node => node.path === filePath)
+    );
   }
 
 
@@ -328,11 +399,159 @@ export class MindMapEngine {
   }
 
   // Project Scanning Methods - delegate to ScanningService
-  async scanProject(forceRescan: boolean = false, includeAnalysis: boolean = true): Promise<any> {
-    return await this.scanningService.scanProject(forceRescan, includeAnalysis);
+  async scanProject(forceRescan: boolean = false, includeAnalysis: boolean = true, options: ScanOptions = {}): Promise<any> {
+    const before = this.graphEvents.hasListeners() ? new Set(this.storage.getGraph().nodes.keys()) : undefined;
+    const result = await this.scanningService.scanProject(forceRescan, includeAnalysis, options);
+    this.graphAnalysisService.invalidateAll();
+    this.positionIndex.invalidateAll();
+    if (before) this.emitReplaced('scan', [], before, Array.from(this.storage.getGraph().nodes.keys()));
+    return result;
+  }
+
+  /**
+   * Be notified after every edit, scan, merge, annotation import, snapshot restore, external crate change,
+   * traversal and decay, once the graph and its indexes are consistent again
+   */
+  subscribe(listener: GraphChangeListener | ((change: GraphChange) => void)): GraphSubscription {
+    return this.graphEvents.subscribe(listener);
+  }
+
+  /**
+   * Re-index one edited file (from disk, or from the given content) and mark the affected
+   * graph regions dirty so derived analyses refresh incrementally
+   */
+  async applyEdit(filePath: string, content?: string): Promise<FileRescanResult> {
+    const revisionBefore = this.storage.getRevision();
+    const result = await this.scanningService.rescanFile(filePath, content);
+    this.textSearchIndex.update([...result.added, ...result.removed, ...result.changed], revisionBefore);
+    this.graphAnalysisService.markDirty(
+      [...result.topologyChanged, ...result.added, ...result.removed],
+      [result.filePath],
+      revisionBefore
+    );
+    this.positionIndex.invalidateFile(result.filePath);
+    this.freshnessService.invalidate(result.filePath);
+    this.invalidateCache([result.filePath]);
+    this.graphEvents.emit({
+      cause: 'edit',
+      filePaths: [result.filePath],
+      added: result.added,
+      removed: result.removed,
+      changed: result.changed,
+      revision: this.storage.getRevision()
+    });
+    return result;
   }
 
   async scanProjectWithRoot(projectRoot: string, forceRescan: boolean = false, includeAnalysis: boolean = true): Promise<void> {
+    const tempStorage = await this.scanDirectory(projectRoot, forceRescan, includeAnalysis);
+
+    // Copy the results to the main storage
+    const tempGraph = tempStorage.getGraph();
+    const before = this.graphEvents.hasListeners() ? new Set(this.storage.getGraph().nodes.keys()) : undefined;
+    this.storage.replaceScan(projectRoot, tempGraph);
+
+    // Save the updated graph
+    await this.storage.save();
+    this.graphAnalysisService.invalidateAll();
+    this.positionIndex.invalidateAll();
+    this.invalidateCache();
+    if (before) this.emitReplaced('scan', [projectRoot], before, Array.from(tempGraph.nodes.keys()));
+  }
+
+  private emitReplaced(cause: GraphChange['cause'], filePaths: string[], before: Set<string>, written: string[]): void {
+    // Whole files were rewritten, so every node written counts as changed unless it is new
+    const after = new Set(this.storage.getGraph().nodes.keys());
+    this.graphEvents.emit({
+      cause,
+      filePaths,
+      added: written.filter(id => !before.has(id)),
+      removed: Array.from(before).filter(id => !after.has(id)),
+      changed: written.filter(id => before.has(id)),
+      revision: this.storage.getRevision()
+    });
+  }
+
+  private captureTopology(): { nodes: Set<string>; edges: Map<string, MindMapEdge> } | undefined {
+    if (!this.graphEvents.hasListeners()) return undefined;
+    const graph = this.storage.getGraph();
+    return { nodes: new Set(graph.nodes.keys()), edges: new Map(graph.edges) };
+  }
+
+  private emitTopologyChange(cause: GraphChange['cause'], before: { nodes: Set<string>; edges: Map<string, MindMapEdge> }): void {
+    // Nodes that stay but gain or lose an edge count as changed
+    const graph = this.storage.getGraph();
+    const touched = new Set<string>();
+    const touch = (edge: MindMapEdge) => {
+      for (const nodeId of [edge.source, edge.target]) {
+        if (before.nodes.has(nodeId) && graph.nodes.has(nodeId)) touched.add(nodeId);
+      }
+    };
+    for (const [edgeId, edge] of graph.edges) {
+      if (!before.edges.has(edgeId)) touch(edge);
+    }
+    for (const [edgeId, edge] of before.edges) {
+      if (!graph.edges.has(edgeId)) touch(edge);
+    }
+    this.graphEvents.emit({
+      cause,
+      filePaths: [],
+      added: Array.from(graph.nodes.keys()).filter(id => !before.nodes.has(id)).sort(),
+      removed: Array.from(before.nodes).filter(id => !graph.nodes.has(id)).sort(),
+      changed: Array.from(touched).sort(),
+      revision: this.storage.getRevision()
+    });
+  }
+
+  /**
+   * Scan two directories on their own and report how their items differ, e.g. a template and a service
+   * generated from it. Both are rescanned from scratch; the current graph is left untouched.
+   */
+  async compareDirectories(a: string, b: string): Promise<DirectoryComparison> {
+    const storageA = await this.scanDirectory(a, true, true);
+    const storageB = await this.scanDirectory(b, true, true);
+    return compareDirectoryGraphs(a, storageA.getGraph(), b, storageB.getGraph());
+  }
+
+  /**
+   * A read-only mind map of the project as committed at a revision (hash, branch or tag), scanned on
+   * first use and cached by commit hash. The working tree and the current graph are left untouched.
+   */
+  async atCommit(revision: string): Promise<CommitGraph> {
+    return this.commitGraphs.at(revision);
+  }
+
+  /**
+   * Structural problems in the graph itself, such as edges to missing nodes or spans past the end of
+   * their file; a debugging aid for the analyzers, empty after a healthy scan
+   */
+  async validate(): Promise<GraphViolation[]> {
+    return validateGraph(this.storage.getGraph());
+  }
+
+  /**
+   * The commits at which a symbol was created, changed signature or body, moved or was deleted, oldest
+   * first, followed across renames and moves. Each commit is scanned as `atCommit` scans it.
+   */
+  async symbolTimeline(nodeId: string, options: SymbolTimelineOptions = {}): Promise<SymbolTimelineEntry[]> {
+    return this.commitGraphs.symbolTimeline(nodeId, options);
+  }
+
+  /**
+   * Sorted signatures of the public Rust items; what `publicSurfaceHash` hashes
+   */
+  publicSurface(): string[] {
+    return publicSurface(this.storage.getGraph());
+  }
+
+  /**
+   * Hash of the public API, unchanged by edits to private items or function bodies
+   */
+  publicSurfaceHash(): string {
+    return publicSurfaceHash(this.storage.getGraph());
+  }
+
+  private async scanDirectory(projectRoot: string, forceRescan: boolean, includeAnalysis: boolean): Promise<MindMapStorage> {
     // Create temporary scanning components for the specified project root
     const tempStorage = new MindMapStorage(projectRoot);
     const tempScanner = new FileScanner(projectRoot);
@@ -351,39 +570,101 @@ export class MindMapEngine {
       this.scalabilityManager,
       projectRoot
     );
+    for (const extractor of this.scanningService.getRelationshipExtractors()) {
+      tempScanningService.registerRelationshipExtractor(extractor);
+    }
+    for (const [crateName, summary] of this.scanningService.getExternalCrates()) {
+      tempScanningService.registerExternalCrate(crateName, summary);
+    }
+    tempScanningService.setExternalEquivalences(this.scanningService.getExternalEquivalences());
+    tempScanningService.setExternalPolicy(this.scanningService.getExternalPolicy());
 
     // Perform the scan with the temporary service
     await tempScanningService.scanProject(forceRescan, includeAnalysis);
 
-    // Copy the results to the main storage
-    const tempGraph = tempStorage.getGraph();
-    const mainGraph = this.storage.getGraph();
+    return tempStorage;
+  }
 
-    // Clear existing nodes and edges for the project root being scanned
-    const projectRootNormalized = projectRoot.replace(/\\/g, '/');
-    for (const [nodeId, node] of mainGraph.nodes) {
-      if (node.path && (node.path.startsWith(projectRootNormalized) || node.path.startsWith(projectRoot))) {
-        mainGraph.nodes.delete(nodeId);
-      }
-    }
+  async scanProjectLegacy(): Promise<void> {
+    await this.scanningService.scanProjectLegacy();
+  }
 
-    // Add nodes and edges from temp storage
-    for (const [nodeId, node] of tempGraph.nodes) {
-      mainGraph.nodes.set(nodeId, node);
-    }
-    for (const [edgeId, edge] of tempGraph.edges) {
-      mainGraph.edges.set(edgeId, edge);
-    }
+  /**
+   * Register a custom relationship extractor; it applies to files indexed after registration
+   */
+  registerRelationshipExtractor(extractor: RelationshipExtractor): void {
+    this.scanningService.registerRelationshipExtractor(extractor);
+  }
 
-    // Update scan metadata
-    mainGraph.lastScan = tempGraph.lastScan;
+  unregisterRelationshipExtractor(name: string): boolean {
+    return this.scanningService.unregisterRelationshipExtractor(name);
+  }
 
-    // Save the updated graph
-    await this.storage.save();
+  /**
+   * Register a user-defined node kind and how exports draw it. Extractors emit nodes of the kind with
+   * `createCustomNode` or tag existing nodes with `withCustomKind`.
+   */
+  registerCustomNodeKind(name: string, style?: Partial<CustomNodeStyle>): CustomNodeStyle {
+    return this.customNodeKinds.register(name, style);
   }
 
-  async scanProjectLegacy(): Promise<void> {
-    await this.scanningService.scanProjectLegacy();
+  getCustomNodeKinds(): string[] {
+    return this.customNodeKinds.list();
+  }
+
+  /**
+   * Nodes of a user-defined kind, whether added as custom nodes or tagged, sorted by ID
+   */
+  nodesOfCustomKind(kind: string): MindMapNode[] {
+    return this.storage.findNodes(node => node.customKind === kind).sort((a, b) => a.id.localeCompare(b.id));
+  }
+
+  /**
+   * Describe an external crate's public items so imports, signatures and calls into it link to
+   * nodes with kinds and signatures; paths into unregistered crates stay unresolved
+   */
+  registerExternal(crateName: string, summary: ExternalCrateSummary): number {
+    const before = this.captureTopology();
+    const added = this.scanningService.registerExternalCrate(crateName, summary);
+    this.graphAnalysisService.invalidateAll();
+    this.invalidateCache();
+    if (before) this.emitTopologyChange('external', before);
+    return added;
+  }
+
+  unregisterExternal(crateName: string): boolean {
+    const before = this.captureTopology();
+    const removed = this.scanningService.unregisterExternalCrate(crateName);
+    this.graphAnalysisService.invalidateAll();
+    this.invalidateCache();
+    if (before) this.emitTopologyChange('external', before);
+    return removed;
+  }
+
+  /**
+   * Treat external paths under equivalent prefixes as one item, such as `core::error::Error` and its
+   * `std` re-export, merging the nodes already added. Extend the defaults by passing
+   * `[...DEFAULT_PATH_EQUIVALENCES, ...more]`; an empty table turns merging off for later scans.
+   */
+  setExternalEquivalences(equivalences: readonly ExternalPathEquivalence[] = DEFAULT_PATH_EQUIVALENCES): number {
+    const before = this.captureTopology();
+    const merged = this.scanningService.setExternalEquivalences(equivalences);
+    this.graphAnalysisService.invalidateAll();
+    this.invalidateCache();
+    if (before) this.emitTopologyChange('external', before);
+    return merged;
+  }
+
+  /**
+   * Keep a node per external item used ('full', the default), one node per external crate
+   * ('crate_level') for dependency-heavy projects, or no external nodes ('none')
+   */
+  setExternalPolicy(policy: ExternalPolicy): void {
+    const before = this.captureTopology();
+    this.scanningService.setExternalPolicy(policy);
+    this.graphAnalysisService.invalidateAll();
+    this.invalidateCache();
+    if (before) this.emitTopologyChange('external', before);
   }
 
   // Analysis Methods - delegate to AnalysisService
@@ -584,10 +865,12 @@ export class MindMapEngine {
     const nodeCount = graph.nodes.size;
     const edgeCount = graph.edges.size;
 
-    // Count nodes by type
+    // Count nodes by type, and nodes of user-defined kinds by kind
     const nodesByType: Record<string, number> = {};
+    const nodesByCustomKind: Record<string, number> = {};
     for (const [_, node] of graph.nodes) {
       nodesByType[node.type] = (nodesByType[node.type] || 0) + 1;
+      if (node.customKind) nodesByCustomKind[node.customKind] = (nodesByCustomKind[node.customKind] || 0) + 1;
     }
 
     // Calculate average confidence
@@ -606,10 +889,750 @@ export class MindMapEngine {
       edgeCount,
       projectRoot: this.projectRoot,
       nodesByType,
+      nodesByCustomKind,
       averageConfidence
     };
   }
 
+  // Graph Analysis Methods - delegate to GraphAnalysisService
+  getCentrality(): Map<string, number> {
+    return this.graphQueryCache.getOrCompute('centrality', null, () => this.graphAnalysisService.computeCentrality());
+  }
+
+  /**
+   * Centrality weighted towards untested code; `getCentrality` keeps the unweighted scores
+   */
+  getRiskCentrality(options?: RiskCentralityOptions): Map<string, number> {
+    return this.graphAnalysisService.riskCentrality(this.getCentrality(), options);
+  }
+
+  /**
+   * Scorer for ranking result items that mention nodes: an item's score is the centrality plus
+   * confidence of the node it names (by `id`, `nodeId` or `name`, or a leading `1. name` in text)
+   */
+  createRelevanceScorer(): (item: unknown) => number {
+    const centrality = this.getCentrality();
+    const byKey = new Map<string, MindMapNode>();
+    for (const node of this.storage.findNodes(() => true)) {
+      byKey.set(node.id, node);
+      if (!byKey.has(node.name)) byKey.set(node.name, node);
+    }
+    const score = (key: unknown) => {
+      const node = typeof key === 'string' ? byKey.get(key) : undefined;
+      return node ? (centrality.get(node.id) || 0) + node.confidence : undefined;
+    };
+
+    return item => {
+      if (typeof item === 'string') {
+        const name = item.match(/^\s*(?:\d+\.\s*)?([^\s(]+)/)?.[1];
+        return score(name) ?? 0;
+      }
+      if (item && typeof item === 'object') {
+        const record = item as Record<string, unknown>;
+        return score(record.id) ?? score(record.nodeId) ?? score(record.name) ??
+          (typeof record.confidence === 'number' ? record.confidence : 0);
+      }
+      return 0;
+    };
+  }
+
+  /**
+   * Context around the seeds; without seeds, around the current focus
+   */
+  contextPack(seeds?: string[], budgetTokens: number = 1000, limits?: Partial<QueryLimits>): ContextPack {
+    const start = seeds && seeds.length > 0 ? seeds : this.currentFocus();
+    return this.graphAnalysisService.contextPack(start, budgetTokens, limits);
+  }
+
+  // Focus Session Methods
+
+  /**
+   * Record a visit to a node (by ID or name); returns the focus set after the visit
+   */
+  focusPush(node: string): string[] {
+    const target = this.storage.getNode(node) ||
+      this.storage.findNodes(candidate => candidate.name === node && candidate.type !== 'file' && candidate.type !== 'directory')
+        .sort((a, b) => a.id.localeCompare(b.id))[0];
+    if (!target) {
+      throw new Error(`Unknown node: ${node}`);
+    }
+    this.focusSession.push(target.id);
+    return this.currentFocus();
+  }
+
+  currentFocus(): string[] {
+    this.focusSession.retain(nodeId => this.storage.getNode(nodeId) !== undefined);
+    return this.focusSession.current();
+  }
+
+  focusHistory(): FocusEntry[] {
+    this.focusSession.retain(nodeId => this.storage.getNode(nodeId) !== undefined);
+    return this.focusSession.history();
+  }
+
+  /**
+   * Start a fresh focus session. With a session ID, visits saved under that ID are restored and
+   * `saveFocusSession` persists to it; returns whether saved visits were found.
+   */
+  async startFocusSession(sessionId?: string, options?: FocusSessionOptions): Promise<boolean> {
+    if (sessionId !== undefined && !/^[A-Za-z0-9_-]+$/.test(sessionId)) {
+      throw new Error(`Invalid focus session ID: ${sessionId}`);
+    }
+    this.focusSession = new FocusSession(options);
+    this.focusSessionId = sessionId;
+    if (sessionId === undefined) return false;
+    return this.focusSession.load(this.focusSessionPath(sessionId));
+  }
+
+  async saveFocusSession(): Promise<void> {
+    if (this.focusSessionId === undefined) return;
+    await this.focusSession.save(this.focusSessionPath(this.focusSessionId));
+  }
+
+  private focusSessionPath(sessionId: string): string {
+    return join(this.projectRoot, '.mindmap-cache', 'focus', `${sessionId}.json`);
+  }
+
+  callersOf(target: string, transitive: boolean = false, limits?: Partial<QueryLimits>): TraversalResult {
+    return this.graphAnalysisService.callersOf(target, transitive, limits);
+  }
+
+  /**
+   * Nodes affected if the given node changes, grouped by how they depend on it
+   */
+  blastRadius(nodeId: string, limits?: Partial<QueryLimits>): BlastRadius {
+    return this.graphAnalysisService.blastRadius(nodeId, limits);
+  }
+
+  /**
+   * Tests exercising a node, those testing it directly first, for picking which tests to run after a change
+   */
+  relatedTests(nodeId: string, limits?: Partial<QueryLimits>): RelatedTest[] {
+    return this.graphAnalysisService.relatedTests(nodeId, limits);
+  }
+
+  /**
+   * Source spans of a node's definition and everything it needs, for a minimal reproduction
+   */
+  extractClosure(nodeId: string, limits?: Partial<QueryLimits>): DependencyClosure {
+    return this.graphAnalysisService.dependencyClosure(nodeId, limits);
+  }
+
+  neighborhood(nodeId: string, radius: number = 2, limits?: Partial<QueryLimits>): TraversalResult {
+    return this.graphAnalysisService.neighborhood(nodeId, radius, limits);
+  }
+
+  traverse(seeds: string[], rules: StepRules, limits?: Partial<QueryLimits>): TraversalResult {
+    return this.graphAnalysisService.traverseWith(seeds, rules, limits);
+  }
+
+  entryPointsWithReach(limits?: Partial<QueryLimits>): EntryPointReach[] {
+    return this.graphQueryCache.getOrCompute('entry_points', limits, () => this.graphAnalysisService.entryPointsWithReach(limits));
+  }
+
+  sharedReach(entryPoints: string[], limits?: Partial<QueryLimits>): string[] {
+    return this.graphAnalysisService.sharedReach(entryPoints, limits);
+  }
+
+  /**
+   * Edges by kind, narrowed by the kind or name of either endpoint and a minimum confidence
+   */
+  edges(query: EdgeQuery = {}): MindMapEdge[] {
+    return this.graphAnalysisService.queryEdges(query);
+  }
+
+  explainEdge(edgeId: string): EdgeExplanation | undefined {
+    return this.graphAnalysisService.explainEdge(edgeId);
+  }
+
+  explainConnection(from: string, to: string, k?: number, limits?: Partial<QueryLimits>): ConnectionPath[] {
+    return this.graphQueryCache.getOrCompute('explain_connection', { from, to, k, limits }, () =>
+      this.graphAnalysisService.explainConnection(from, to, k, limits)
+    );
+  }
+
+  topoOrder(edgeFilter: EdgeFilter): string[] {
+    return this.graphAnalysisService.topoOrder(edgeFilter);
+  }
+
+  longestCallChain(): CallChain {
+    return this.graphAnalysisService.longestCallChain();
+  }
+
+  /**
+   * Nodes with more than `threshold` dependents and dependencies combined, fan-in and fan-out reported apart
+   */
+  highFanNodes(threshold: number): HighFanNode[] {
+    return this.graphAnalysisService.highFanNodes(threshold);
+  }
+
+  setQueryLimits(limits: Partial<QueryLimits>): void {
+    this.graphAnalysisService.setQueryLimits(limits);
+  }
+
+  getQueryLimits(): QueryLimits {
+    return this.graphAnalysisService.getQueryLimits();
+  }
+
+  duplicateFunctions(): DuplicateFunctionGroup[] {
+    return this.graphAnalysisService.findDuplicateFunctions();
+  }
+
+  getAnalysisCacheStats(): AnalysisCacheStats {
+    return this.graphAnalysisService.getAnalysisCacheStats();
+  }
+
+  getCentralityRecomputeCount(nodeId: string): number {
+    return this.graphAnalysisService.getNodeRecomputeCount(nodeId);
+  }
+
+  /**
+   * Stream every node and edge as one JSON object per line, for piping into other tools
+   */
+  async writeJsonl(writer: Writable, options: { omitTimestamps?: boolean; filter?: ExportFilter } = {}): Promise<{ nodes: number; edges: number }> {
+    return this.storage.writeJsonl(writer, options);
+  }
+
+  async writeBincode(filePath: string): Promise<{ nodes: number; edges: number; bytes: number }> {
+    return this.storage.writeBinary(filePath);
+  }
+
+  async readBincode(filePath: string): Promise<{ nodes: number; edges: number }> {
+    const before = this.graphEvents.hasListeners() ? new Set(this.storage.getGraph().nodes.keys()) : undefined;
+    const result = await this.storage.readBinary(filePath);
+    this.graphAnalysisService.invalidateAll();
+    this.positionIndex.invalidateAll();
+    this.invalidateCache();
+    if (before) this.emitReplaced('scan', [], before, Array.from(this.storage.getGraph().nodes.keys()));
+    return result;
+  }
+
+  /**
+   * Fold another mind map (e.g. one built from a different part of the codebase) into this one,
+   * then resolve Rust calls that only now have a target in the combined graph
+   */
+  merge(other: MindMapEngine | MindMapGraph): { nodesAdded: number; nodesMerged: number; edgesAdded: number; edgesMerged: number; callsResolved: number } {
+    const graph = other instanceof MindMapEngine ? other.storage.getGraph() : other;
+    const before = this.graphEvents.hasListeners() ? new Set(this.storage.getGraph().nodes.keys()) : undefined;
+    const result = this.storage.merge(graph);
+    const callsResolved = resolveCrossFileCalls(this.storage);
+    this.graphAnalysisService.invalidateAll();
+    this.positionIndex.invalidateAll();
+    this.invalidateCache();
+    if (before) this.emitReplaced('merge', [], before, Array.from(graph.nodes.keys()));
+    return { ...result, callsResolved };
+  }
+
+  // Edge Timestamp Methods - delegate to storage
+  recordTraversal(edgeId: string, strength: number = 1, at: Date = new Date()): boolean {
+    const edge = this.storage.getEdge(edgeId);
+    const recorded = this.storage.recordTraversal(edgeId, strength, at, edge ? this.activationNetwork.initialWeight(edge) : 0);
+    if (recorded) this.emitEdgeChange('traverse', [edgeId]);
+    return recorded;
+  }
+
+  /**
+   * Strength activation spreads with along an edge; undefined for unknown edges
+   */
+  edgeStrength(edgeId: string): number | undefined {
+    const edge = this.storage.getEdge(edgeId);
+    return edge ? this.activationNetwork.edgeStrength(edge) : undefined;
+  }
+
+  /**
+   * Tune spreading activation: decay per hop, cut-off, hop limit, and whether edges nobody has
+   * strengthened yet start at their static confidence rather than a uniform 1.0
+   */
+  configureActivation(config: Parameters<ActivationNetwork['updateConfiguration']>[0]): void {
+    this.activationNetwork.updateConfiguration(config);
+    this.graphAnalysisService.invalidateAll();
+    this.invalidateCache();
+  }
+
+  decayEdges(ratePerHour: number, now: Date = new Date()): number {
+    // A decayed edge has its decay time replaced, so comparing before and after finds it
+    const lastDecayed = this.graphEvents.hasListeners()
+      ? new Map(this.storage.findEdges(edge => edge.weight !== undefined).map(edge => [edge.id, edge.lastDecayedAt] as const))
+      : undefined;
+    const decayed = this.storage.decayEdges(ratePerHour, now);
+    if (lastDecayed && decayed > 0) {
+      this.emitEdgeChange('decay', Array.from(lastDecayed)
+        .filter(([edgeId, at]) => this.storage.getEdge(edgeId)?.lastDecayedAt !== at)
+        .map(([edgeId]) => edgeId)
+        .sort());
+    }
+    return decayed;
+  }
+
+  private emitEdgeChange(cause: GraphChange['cause'], edgeIds: string[]): void {
+    this.graphEvents.emit({
+      cause,
+      filePaths: [],
+      added: [],
+      removed: [],
+      changed: [],
+      changedEdges: edgeIds,
+      revision: this.storage.getRevision()
+    });
+  }
+
+  edgesReinforcedSince(since: Date): MindMapEdge[] {
+    return this.storage.edgesReinforcedSince(since);
+  }
+
+  // Export Methods - render the graph for visualization
+  toMermaid(options?: GraphExportOptions): string {
+    return toMermaid(this.storage.getGraph(), this.withNodeStyles(options));
+  }
+
+  toDot(options?: GraphExportOptions): string {
+    return toDot(this.storage.getGraph(), this.withNodeStyles(options));
+  }
+
+  toJson(options?: GraphExportOptions): JsonGraphExport {
+    return toJson(this.storage.getGraph(), options);
+  }
+
+  toD3(options?: GraphExportOptions): D3GraphExport {
+    return toD3(this.storage.getGraph(), options);
+  }
+
+  /**
+   * SARIF 2.1.0 log of lint findings, one rule per finding category, located by the findings' node spans
+   */
+  toSarif(findings: LintFinding[], options?: SarifOptions): SarifLog {
+    return toSarif(this.storage.getGraph(), findings, options);
+  }
+
+  /**
+   * Mermaid flowchart drawing reinforced edges thicker and leaving out edges weaker than `minStrength`
+   */
+  toMermaidWeighted(minStrength: number = 0, options: Omit<GraphExportOptions, 'weighted' | 'minStrength'> = {}): string {
+    return toMermaid(this.storage.getGraph(), this.withNodeStyles({ ...options, weighted: true, minStrength }));
+  }
+
+  private withNodeStyles(options: GraphExportOptions = {}): GraphExportOptions {
+    // Styles given with the export take precedence over registered ones
+    return { ...options, nodeStyles: { ...this.customNodeKinds.styles(), ...options.nodeStyles } };
+  }
+
+  // Freshness Methods - delegate to FreshnessService
+  async freshness(node: string, options?: FreshnessOptions): Promise<NodeFreshness> {
+    return this.freshnessService.freshness(node, options);
+  }
+
+  async hotNodes(limit: number = 10, options?: FreshnessOptions): Promise<NodeFreshness[]> {
+    return this.freshnessService.hotNodes(limit, options);
+  }
+
+  async hotspots(options?: HotspotOptions): Promise<Hotspot[]> {
+    return this.hotspotService.hotspots(this.getCentrality(), options);
+  }
+
+  // Snapshot Methods - delegate to GraphSnapshotStore
+  async snapshot(label: string): Promise<SnapshotInfo> {
+    return this.snapshotStore.snapshot(label);
+  }
+
+  async restoreSnapshot(label: string): Promise<SnapshotInfo> {
+    const before = this.graphEvents.hasListeners() ? new Set(this.storage.getGraph().nodes.keys()) : undefined;
+    const info = await this.snapshotStore.restore(label);
+    this.graphAnalysisService.invalidateAll();
+    this.positionIndex.invalidateAll();
+    this.invalidateCache();
+    if (before) this.emitReplaced('restore', [], before, Array.from(this.storage.getGraph().nodes.keys()));
+    return info;
+  }
+
+  async diffSnapshot(from: string, to?: string): Promise<SnapshotDiff> {
+    return this.snapshotStore.diff(from, to);
+  }
+
+  /**
+   * Nodes and edges that differ from a snapshot, for clients polling for updates
+   */
+  async changedSince(label: string, options?: ChangedSinceOptions): Promise<GraphDelta> {
+    return this.snapshotStore.changedSince(label, options);
+  }
+
+  async listSnapshots(): Promise<SnapshotInfo[]> {
+    return this.snapshotStore.list();
+  }
+
+  // Position Lookup Methods
+
+  /**
+   * Innermost node whose source span encloses the given position (1-based line)
+   */
+  nodeAt(filePath: string, line: number, column?: number): MindMapNode | undefined {
+    const relativePath = relative(this.projectRoot, resolve(this.projectRoot, filePath));
+    const nodeId = this.positionIndex.nodeAt(relativePath, line, column);
+    return nodeId ? this.storage.getNode(nodeId) : undefined;
+  }
+
+  /**
+   * Nodes whose source spans overlap the lines of a range (1-based, inclusive), such as an editor
+   * selection, outermost first
+   */
+  nodesInRange(filePath: string, startLine: number, endLine: number): MindMapNode[] {
+    const relativePath = relative(this.projectRoot, resolve(this.projectRoot, filePath));
+    return this.positionIndex.nodesOverlapping(relativePath, startLine, endLine)
+      .map(nodeId => this.storage.getNode(nodeId))
+      .filter((node): node is MindMapNode => node !== undefined);
+  }
+
+  /**
+   * Overlay external data such as coverage or profiler output: each record's annotations are attached to
+   * the innermost node covering its lines, under `metadata.annotations`. Values landing on the same node
+   * are combined, numbers by adding them up.
+   */
+  importAnnotations(source: AnnotationRecord[], options: ImportAnnotationsOptions = {}): AnnotationImportResult {
+    const imported = new Map<string, Record<string, unknown>>();
+    const unmatched: AnnotationRecord[] = [];
+    for (const record of source) {
+      const filePath = relative(this.projectRoot, resolve(this.projectRoot, record.filePath));
+      const nodeId = this.positionIndex.nodeCovering(filePath, record.startLine, record.endLine ?? record.startLine);
+      if (!nodeId) {
+        unmatched.push(record);
+        continue;
+      }
+      imported.set(nodeId, combineAnnotations(imported.get(nodeId) || {}, record.annotations));
+    }
+
+    const filePaths = new Set<string>();
+    for (const [nodeId, annotations] of imported) {
+      const node = this.storage.getNode(nodeId)!;
+      const existing = node.metadata?.annotations || {};
+      this.storage.addNode({
+        ...node,
+        metadata: { ...node.metadata, annotations: options.replace ? { ...existing, ...annotations } : combineAnnotations(existing, annotations) }
+      });
+      if (node.path) filePaths.add(node.path);
+    }
+
+    if (imported.size > 0) {
+      this.invalidateCache(Array.from(filePaths));
+      this.graphEvents.emit({
+        cause: 'annotate',
+        filePaths: Array.from(filePaths).sort(),
+        added: [],
+        removed: [],
+        changed: Array.from(imported.keys()).sort(),
+        revision: this.storage.getRevision()
+      });
+    }
+    return { annotated: Array.from(imported.keys()).sort(), unmatched };
+  }
+
+  getPositionIndexBuildCount(filePath: string): number {
+    return this.positionIndex.getBuildCount(relative(this.projectRoot, resolve(this.projectRoot, filePath)));
+  }
+
+  // Symbol Methods - delegate to SymbolService
+  documentSymbols(filePath: string): SymbolInformation[] {
+    return this.symbolService.documentSymbols(relative(this.projectRoot, resolve(this.projectRoot, filePath)));
+  }
+
+  workspaceSymbols(query: string, limit?: number): SymbolInformation[] {
+    return this.symbolService.workspaceSymbols(query, limit);
+  }
+
+  /**
+   * Spans of the nodes in a file matching a predicate (e.g. `node => node.metadata.isAsync`), sorted by
+   * start line, for highlighting them together
+   */
+  spansWhere(filePath: string, predicate?: NodePredicate, filter?: NodeSpanFilter): NodeSpan[] {
+    return this.symbolService.spansWhere(relative(this.projectRoot, resolve(this.projectRoot, filePath)), predicate, filter);
+  }
+
+  cargoWorkspace(): CargoWorkspace | undefined {
+    return this.scanningService.getCargoWorkspace();
+  }
+
+  /**
+   * Override the LSP symbol kind reported for a kind of node, e.g. traits as `SymbolKind.Class`
+   */
+  setSymbolKind(kind: SymbolNodeKind, symbolKind: SymbolKind): void {
+    this.symbolService.kindMap.set(kind, symbolKind);
+  }
+
+  // Rust Analysis Methods - delegate to RustAnalysisService
+  /**
+   * Evaluate Rust `cfg` predicates as if the given features (and optionally `cfg(test)`) are enabled,
+   * and choose which function body passes run (`enabledAnalyses`; dropping `call_graph` omits calls edges).
+   * Takes effect on the next scan; items behind false predicates are kept but marked inactive.
+   * A `profile` presets all of these at once, e.g. `{ profile: 'fast', docTests: true }`.
+   */
+  setRustParseOptions(options: RustParseOptions): void {
+    this.codeAnalyzer.setRustParseOptions(options);
+  }
+
+  /**
+   * Lint queries selected by the current Rust parse options or profile
+   */
+  enabledRustLints(): RustLint[] {
+    return this.codeAnalyzer.getRustEnabledLints();
+  }
+
+  deriveConsistency(): DeriveConsistencyIssue[] {
+    return this.rustAnalysisService.checkDeriveConsistency();
+  }
+
+  traitCoverage(implNode: string, traitName?: string): TraitCoverage[] {
+    return this.graphQueryCache.getOrCompute('trait_coverage', { implNode, traitName }, () =>
+      this.rustAnalysisService.traitCoverage(implNode, traitName)
+    );
+  }
+
+  defaultMethodUsage(traitNode: string): DefaultMethodUsage[] {
+    return this.rustAnalysisService.defaultMethodUsage(traitNode);
+  }
+
+  overridesOf(traitMethod: string): MethodOverride[] {
+    return this.rustAnalysisService.overridesOf(traitMethod);
+  }
+
+  genericBloatCandidates(): GenericBloatCandidate[] {
+    return this.rustAnalysisService.genericBloatCandidates();
+  }
+
+  visibilitySmells(): VisibilitySmell[] {
+    return this.rustAnalysisService.visibilitySmells();
+  }
+
+  unusedFields(): UnusedField[] {
+    return this.rustAnalysisService.unusedFields();
+  }
+
+  redundantBounds(): RedundantBound[] {
+    return this.rustAnalysisService.redundantBounds();
+  }
+
+  redundantLifetimes(): RedundantLifetime[] {
+    return this.rustAnalysisService.redundantLifetimes();
+  }
+
+  requiredTraitsFor(typeNodeId: string, functionNodeId: string, parameter?: string): TraitRequirements | undefined {
+    return this.rustAnalysisService.requiredTraitsFor(typeNodeId, functionNodeId, parameter);
+  }
+
+  errorVariants(functionNode: string): ErrorVariantUsage[] {
+    return this.rustAnalysisService.errorVariants(functionNode);
+  }
+
+  untestedPublicApi(): UntestedApiItem[] {
+    return this.rustAnalysisService.untestedPublicApi();
+  }
+
+  typeMethods(typeNode: string, filter: TypeMethodFilter = {}): TypeMethod[] {
+    return this.rustAnalysisService.typeMethods(typeNode, filter);
+  }
+
+  associatedFunctions(typeNode: string): TypeMethod[] {
+    return this.rustAnalysisService.associatedFunctions(typeNode);
+  }
+
+  internalReachableFromApi(): ApiReachability {
+    return this.graphQueryCache.getOrCompute('api_reachability', null, () => this.rustAnalysisService.internalReachableFromApi());
+  }
+
+  longParameterLists(threshold = 5, options: LongParameterListOptions = {}): LongParameterList[] {
+    return this.rustAnalysisService.longParameterLists(threshold, options);
+  }
+
+  nodeByPath(path: string): MindMapNode | undefined {
+    return this.rustAnalysisService.nodeByPath(path);
+  }
+
+  resolvePathFuzzy(path: string, options?: FuzzyPathOptions): PathResolution {
+    return this.rustAnalysisService.resolvePathFuzzy(path, options);
+  }
+
+  canonicalPath(nodeRef: string): string | undefined {
+    return this.rustAnalysisService.canonicalPath(nodeRef);
+  }
+
+  publicPaths(nodeRef: string): string[] {
+    return this.rustAnalysisService.publicPaths(nodeRef);
+  }
+
+  boxedErrorUsage(options?: BoxedErrorOptions): BoxedErrorSite[] {
+    return this.rustAnalysisService.boxedErrorUsage(options);
+  }
+
+  findImplementors(trait: string): TraitImplementor[] {
+    return this.rustAnalysisService.findImplementors(trait);
+  }
+
+  implementorsByModule(trait: string): ModuleImplementors[] {
+    return this.rustAnalysisService.implementorsByModule(trait);
+  }
+
+  dynDispatchSites(): DynDispatchSite[] {
+    return this.rustAnalysisService.dynDispatchSites();
+  }
+
+  dispatchProfile(trait?: string): DispatchProfile[] {
+    return this.rustAnalysisService.dispatchProfile(trait);
+  }
+
+  dipCandidates(): DipCandidate[] {
+    return this.rustAnalysisService.dipCandidates();
+  }
+
+  defaultValues(structRef: string): DefaultValues | undefined {
+    return this.rustAnalysisService.defaultValues(structRef);
+  }
+
+  threadSafetyReport(): ThreadSafetyIssue[] {
+    return this.rustAnalysisService.threadSafetyReport();
+  }
+
+  errorStyleReport(module?: string): ModuleErrorStyle[] {
+    return this.rustAnalysisService.errorStyleReport(module);
+  }
+
+  missingImpls(): MissingImpl[] {
+    return this.rustAnalysisService.missingImpls();
+  }
+
+  testGraph(): TestGraph {
+    return this.rustAnalysisService.testGraph();
+  }
+
+  ignoredResults(): IgnoredResult[] {
+    return this.rustAnalysisService.ignoredResults();
+  }
+
+  shadowingSites(): ShadowingSite[] {
+    return this.rustAnalysisService.shadowingSites();
+  }
+
+  asyncMismatches(): AsyncMismatch[] {
+    return this.rustAnalysisService.asyncMismatches();
+  }
+
+  methodShadowing(): MethodShadowing[] {
+    return this.rustAnalysisService.methodShadowing();
+  }
+
+  nonExhaustiveMatches(): NonExhaustiveMatch[] {
+    return this.rustAnalysisService.nonExhaustiveMatches();
+  }
+
+  lockHeldAcrossAwait(): LockAcrossAwait[] {
+    return this.rustAnalysisService.lockHeldAcrossAwait();
+  }
+
+  exhaustivePublicEnums(): ExhaustivePublicEnum[] {
+    return this.rustAnalysisService.exhaustivePublicEnums();
+  }
+
+  nodesWithAttribute(pattern: string): MindMapNode[] {
+    return this.rustAnalysisService.nodesWithAttribute(pattern);
+  }
+
+  maxNestingDepth(functionRef: string): number | undefined {
+    return this.rustAnalysisService.maxNestingDepth(functionRef);
+  }
+
+  redundantCloneCandidates(minConfidence?: number): RedundantClone[] {
+    return this.rustAnalysisService.redundantCloneCandidates(minConfidence);
+  }
+
+  constFnCandidates(): ConstFnCandidate[] {
+    return this.rustAnalysisService.constFnCandidates();
+  }
+
+  unnecessaryOwnership(): UnnecessaryOwnership[] {
+    return this.rustAnalysisService.unnecessaryOwnership();
+  }
+
+  panicSites(options?: PanicSiteOptions): PanicSite[] {
+    return this.rustAnalysisService.panicSites(options);
+  }
+
+  literals(kind?: RustLiteralKind): LiteralUse[] {
+    return this.rustAnalysisService.literals(kind);
+  }
+
+  ffiSurface(): FfiEntryPoint[] {
+    return this.rustAnalysisService.ffiSurface();
+  }
+
+  envVars(): EnvVarUsage[] {
+    return this.rustAnalysisService.envVars();
+  }
+
+  checkLayering(rules: LayeringRules): LayeringViolation[] {
+    return this.rustAnalysisService.checkLayering(rules);
+  }
+
+  moduleMetrics(module: string): ModuleMetrics {
+    return this.rustAnalysisService.moduleMetrics(module);
+  }
+
+  allModuleMetrics(): ModuleMetrics[] {
+    return this.rustAnalysisService.allModuleMetrics();
+  }
+
+  suggestModuleSplit(module: string): ModuleSplit {
+    return this.rustAnalysisService.suggestModuleSplit(module);
+  }
+
+  misplacedItems(options?: MisplacedItemOptions): MisplacedItem[] {
+    return this.rustAnalysisService.misplacedItems(options);
+  }
+
+  namingViolations(rules?: NamingRules): NamingViolation[] {
+    return this.rustAnalysisService.namingViolations(rules);
+  }
+
+  typeHeatmap(options?: TypeHeatmapOptions): TypeUsage[] {
+    return this.graphQueryCache.getOrCompute('type_heatmap', options, () => this.rustAnalysisService.typeHeatmap(options));
+  }
+
+  unusedAsync(): UnusedAsyncFunction[] {
+    return this.rustAnalysisService.unusedAsync();
+  }
+
+  undocumentedPublicApi(minVisibility: 'pub' | 'crate' = 'crate'): UndocumentedItem[] {
+    return this.rustAnalysisService.undocumentedPublicApi(minVisibility);
+  }
+
+  attributeArgs(nodeId: string, name: string): RustAttributeArg[] {
+    return this.rustAnalysisService.attributeArgs(nodeId, name);
+  }
+
+  /**
+   * Parse diagnostics of a file, given relative to the project root or absolute
+   */
+  diagnosticsFor(filePath: string, minSeverity?: RustDiagnosticSeverity): RustDiagnostic[] {
+    return this.rustAnalysisService.diagnosticsFor(relative(this.projectRoot, resolve(this.projectRoot, filePath)), minSeverity);
+  }
+
+  // Type Parity Methods - delegate to TypeParityService
+  async typeParity(rustStruct: string, otherType: string, otherFile?: string): Promise<TypeParityReport> {
+    return this.typeParityService.typeParity(rustStruct, otherType, otherFile);
+  }
+
+  crossLanguageMatches(sourceLanguage: string, targetLanguage: string): CrossLanguageMatch[] {
+    return this.typeParityService.crossLanguageMatches(sourceLanguage, targetLanguage);
+  }
+
+  normalizeIdentifier(name: string, language?: string): string {
+    return this.typeParityService.normalizer.normalize(name, language);
+  }
+
+  setIdentifierNormalization(language: string, rule: IdentifierNormalizationRule): void {
+    this.typeParityService.normalizer.setRule(language, rule);
+  }
+
+  // Refactoring Methods - delegate to RefactoringService
+  async renamePreview(node: string, newName: string): Promise<RenamePreview> {
+    return this.refactoringService.renamePreview(node, newName);
+  }
+
   // Advanced Query System Methods
   async executeAdvancedQuery(query: string, parameters?: Record<string, any>, explain = false): Promise<any> {
     const result = await this.advancedQueryEngine.executeQuery(query, parameters);
@@ -648,6 +1671,7 @@ export class MindMapEngine {
   clearCache(): void {
     // Clear cache implementation
     (this.queryCache as any).cache?.clear();
+    this.graphQueryCache.clear();
   }
 
 
@@ -655,6 +1679,28 @@ export class MindMapEngine {
     return this.queryCache.getStats();
   }
 
+  /**
+   * Nodes whose name, signature or doc comment mention the query's words; exact name matches first
+   */
+  searchText(query: string, limit: number = 20): TextSearchHit[] {
+    return this.textSearchIndex.search(query, limit);
+  }
+
+  getTextSearchStats(): TextSearchIndexStats {
+    return this.textSearchIndex.getStats();
+  }
+
+  /**
+   * Hits and misses of the cache holding graph analysis results until the graph next changes
+   */
+  getGraphQueryCacheStats(): GraphQueryCacheStats {
+    return this.graphQueryCache.getStats();
+  }
+
+  setGraphQueryCacheSize(maxEntries: number): void {
+    this.graphQueryCache.setMaxEntries(maxEntries);
+  }
+
   getInhibitoryLearningStats(): any {
     return this.inhibitoryLearning.getStats();
   }
@@ -1,6 +1,10 @@
 import { readFile, writeFile, mkdir, access } from 'fs/promises';
-import { join, dirname, resolve, normalize, relative } from 'path';
-import { MindMapGraph, MindMapNode, MindMapEdge } from '../types/index.js';
+import { join, dirname, resolve, normalize, relative, isAbsolute } from 'path';
+import { once } from 'events';
+import { Writable } from 'stream';
+import { MindMapGraph, MindMapNode, MindMapEdge, EvidenceSpan } from '../types/index.js';
+import { encodeGraph, decodeGraph } from './BinaryGraphFormat.js';
+import { ExportFilter, filterGraph } from './GraphExport.js';
 
 // Compression interfaces for storage optimization
 interface CompressedNode {
@@ -14,6 +18,7 @@ interface CompressedNode {
   r?: any; // properties
   f?: string[]; // frameworks
   a?: number; // createdAt (timestamp)
+  k?: string; // customKind
 }
 
 interface CompressedEdge {
@@ -24,9 +29,13 @@ interface CompressedEdge {
   w?: number; // weight
   c: number; // confidence
   m?: any; // metadata
+  k?: string; // customKind
+  e?: EvidenceSpan[]; // evidence
   r?: number; // created (timestamp)
   u?: number; // lastUpdated (timestamp)
   a?: number; // createdAt (timestamp)
+  l?: number; // lastReinforcedAt (timestamp)
+  d?: number; // lastDecayedAt (timestamp)
 }
 
 interface CompressedGraph {
@@ -53,7 +62,8 @@ const TYPE_ENCODINGS: Record<string, string> = {
   'link': 'l',
   'section': 's',
   'variable': 'v',
-  'type_parameter': 'tp'
+  'type_parameter': 'tp',
+  'custom': 'cu'
 };
 
 const EDGE_TYPE_ENCODINGS: Record<string, string> = {
@@ -72,19 +82,58 @@ const EDGE_TYPE_ENCODINGS: Record<string, string> = {
   'describes': 'de',
   'used_by': 'u',
   'instantiated_as': 'ia',
-  'violates_constraint': 'vc'
+  'violates_constraint': 'vc',
+  'tests': 't',
+  'requires_bound': 'rb',
+  'wraps': 'wr',
+  'alias_of': 'ao',
+  'custom': 'cu'
 };
 
 // Reverse mappings
 const TYPE_DECODINGS = Object.fromEntries(Object.entries(TYPE_ENCODINGS).map(([k, v]) => [v, k]));
 const EDGE_TYPE_DECODINGS = Object.fromEntries(Object.entries(EDGE_TYPE_ENCODINGS).map(([k, v]) => [v, k]));
 
+// Node and edge fields that record when something happened rather than what was found
+const TIMESTAMP_FIELDS = new Set(['lastUpdated', 'created', 'createdAt', 'lastReinforcedAt', 'lastDecayedAt']);
+
+/**
+ * Union of two evidence lists, dropping spans that appear in both
+ */
+export function mergeEvidence(existing: EvidenceSpan[] = [], added: EvidenceSpan[] = []): EvidenceSpan[] {
+  const key = (span: EvidenceSpan) => `${span.filePath}:${span.startLine}-${span.endLine}:${span.kind || ''}`;
+  const seen = new Set(existing.map(key));
+  return [...existing, ...added.filter(span => !seen.has(key(span)))];
+}
+
+function sortEvidence(spans: EvidenceSpan[]): EvidenceSpan[] {
+  return [...spans].sort((a, b) =>
+    a.filePath.localeCompare(b.filePath) || a.startLine - b.startLine || a.endLine - b.endLine ||
+    (a.kind || '').localeCompare(b.kind || '')
+  );
+}
+
+function pickTime(a: Date | undefined, b: Date | undefined, choose: (x: number, y: number) => number): Date | undefined {
+  if (!a || !b) return a || b;
+  return new Date(choose(new Date(a).getTime(), new Date(b).getTime()));
+}
+
+function newerCopy<T extends { lastUpdated?: Date }>(a: T, b: T, mergedFields: string[]): T {
+  // Order-independent choice between two copies of the same node or edge
+  const time = (item: T) => item.lastUpdated ? new Date(item.lastUpdated).getTime() : 0;
+  if (time(a) !== time(b)) return time(a) > time(b) ? a : b;
+  const content = (item: T) => JSON.stringify(Object.entries(item).filter(([key]) => !mergedFields.includes(key)));
+  return content(a) >= content(b) ? a : b;
+}
+
 export class MindMapStorage {
   private graph: MindMapGraph;
   private storagePath: string;
   private projectRoot: string;
   private pathDictionary: Map<string, number> = new Map();
   private pathArray: string[] = [];
+  private revision = 0; // Bumped by every mutation made through the storage
+  private edgeTypeIndex: { revision: number; byType: Map<string, MindMapEdge[]> } | null = null;
 
   constructor(projectRoot: string, storagePath?: string) {
     // Validate and sanitize paths to prevent path traversal
@@ -216,7 +265,8 @@ export class MindMapStorage {
       u: node.lastUpdated.getTime(),
       r: node.properties && Object.keys(node.properties).length > 0 ? node.properties : undefined,
       f: node.frameworks && node.frameworks.length > 0 ? node.frameworks : undefined,
-      a: node.createdAt?.getTime()
+      a: node.createdAt?.getTime(),
+      k: node.customKind
     };
   }
 
@@ -235,7 +285,8 @@ export class MindMapStorage {
       lastUpdated: new Date(compressed.u),
       properties: compressed.r,
       frameworks: compressed.f,
-      createdAt: compressed.a ? new Date(compressed.a) : undefined
+      createdAt: compressed.a ? new Date(compressed.a) : undefined,
+      customKind: compressed.k
     };
   }
 
@@ -248,9 +299,13 @@ export class MindMapStorage {
       w: edge.weight,
       c: Math.round(edge.confidence * 100) / 100,
       m: edge.metadata && Object.keys(edge.metadata).length > 0 ? edge.metadata : undefined,
+      k: edge.customKind,
+      e: edge.evidence && edge.evidence.length > 0 ? edge.evidence : undefined,
       r: edge.created?.getTime(),
       u: edge.lastUpdated?.getTime(),
-      a: edge.createdAt?.getTime()
+      a: edge.createdAt?.getTime(),
+      l: edge.lastReinforcedAt?.getTime(),
+      d: edge.lastDecayedAt?.getTime()
     };
   }
 
@@ -263,9 +318,13 @@ export class MindMapStorage {
       weight: compressed.w,
       confidence: compressed.c,
       metadata: compressed.m,
+      customKind: compressed.k,
+      evidence: compressed.e,
       created: compressed.r ? new Date(compressed.r) : undefined,
       lastUpdated: compressed.u ? new Date(compressed.u) : undefined,
-      createdAt: compressed.a ? new Date(compressed.a) : undefined
+      createdAt: compressed.a ? new Date(compressed.a) : undefined,
+      lastReinforcedAt: compressed.l ? new Date(compressed.l) : undefined,
+      lastDecayedAt: compressed.d ? new Date(compressed.d) : undefined
     };
   }
 
@@ -283,6 +342,7 @@ export class MindMapStorage {
       } else {
         await this.loadLegacyData(serialized);
       }
+      this.revision++;
 
     } catch (error) {
       if ((error as any).code === 'ENOENT') {
@@ -298,6 +358,7 @@ export class MindMapStorage {
           lastScan: new Date(),
           version: '0.1.0'
         };
+        this.revision++;
       }
     }
   }
@@ -373,6 +434,12 @@ export class MindMapStorage {
       if (edge.createdAt && typeof edge.createdAt === 'string') {
         edge.createdAt = new Date(edge.createdAt);
       }
+      if (edge.lastReinforcedAt && typeof edge.lastReinforcedAt === 'string') {
+        edge.lastReinforcedAt = new Date(edge.lastReinforcedAt);
+      }
+      if (edge.lastDecayedAt && typeof edge.lastDecayedAt === 'string') {
+        edge.lastDecayedAt = new Date(edge.lastDecayedAt);
+      }
     }
   }
 
@@ -436,6 +503,7 @@ export class MindMapStorage {
       ...node,
       lastUpdated: new Date()
     });
+    this.revision++;
   }
 
   getNode(id: string): MindMapNode | undefined {
@@ -450,10 +518,63 @@ export class MindMapStorage {
         this.graph.edges.delete(edgeId);
       }
     }
+    this.revision++;
   }
 
   addEdge(edge: MindMapEdge): void {
+    // The same logical edge found by another extraction pass keeps the evidence of both
+    const existing = this.graph.edges.get(edge.id);
+    if (existing && existing !== edge && existing.evidence?.length && edge.evidence?.length) {
+      edge = { ...edge, evidence: mergeEvidence(existing.evidence, edge.evidence) };
+    }
+    // Re-adding an edge (e.g. on rescan) keeps its history
+    if (!edge.createdAt) edge.createdAt = existing?.createdAt || new Date();
+    if (!edge.lastReinforcedAt && existing?.lastReinforcedAt) edge.lastReinforcedAt = existing.lastReinforcedAt;
+    if (!edge.lastDecayedAt && existing?.lastDecayedAt) edge.lastDecayedAt = existing.lastDecayedAt;
     this.graph.edges.set(edge.id, edge);
+    this.revision++;
+  }
+
+  /**
+   * Strengthen an edge that was just used (e.g. followed by a query), starting from `initialWeight` when
+   * it has not been strengthened before. Returns false for unknown edges.
+   */
+  recordTraversal(id: string, strength: number = 1, at: Date = new Date(), initialWeight: number = 0): boolean {
+    const edge = this.graph.edges.get(id);
+    if (!edge) return false;
+    edge.weight = (edge.weight || initialWeight) + strength;
+    edge.lastReinforcedAt = at;
+    this.revision++;
+    return true;
+  }
+
+  /**
+   * Weaken learned edge weights exponentially by how long each edge has gone unreinforced,
+   * only counting time since the previous decay so repeated calls don't compound.
+   * Edges without a weight are structural and left alone. Returns the number of edges decayed.
+   */
+  decayEdges(ratePerHour: number, now: Date = new Date()): number {
+    let decayed = 0;
+    for (const edge of this.graph.edges.values()) {
+      if (edge.weight === undefined) continue;
+      const reinforced = edge.lastReinforcedAt || edge.createdAt || edge.lastUpdated;
+      const since = Math.max(reinforced ? reinforced.getTime() : now.getTime(), edge.lastDecayedAt?.getTime() ?? 0);
+      const hours = (now.getTime() - since) / 3_600_000;
+      if (hours <= 0) continue;
+      edge.weight *= Math.exp(-ratePerHour * hours);
+      edge.lastDecayedAt = now;
+      decayed++;
+    }
+    if (decayed > 0) this.revision++;
+    return decayed;
+  }
+
+  /**
+   * Edges traversed at or after `since`, most recently reinforced first
+   */
+  edgesReinforcedSince(since: Date): MindMapEdge[] {
+    return this.findEdges(edge => edge.lastReinforcedAt !== undefined && edge.lastReinforcedAt.getTime() >= since.getTime())
+      .sort((a, b) => b.lastReinforcedAt!.getTime() - a.lastReinforcedAt!.getTime() || a.id.localeCompare(b.id));
   }
 
   getEdge(id: string): MindMapEdge | undefined {
@@ -462,6 +583,15 @@ export class MindMapStorage {
 
   removeEdge(id: string): void {
     this.graph.edges.delete(id);
+    this.revision++;
+  }
+
+  /**
+   * Counter that changes whenever the graph is modified through this storage, so results derived
+   * from the graph can be reused while it stays the same
+   */
+  getRevision(): number {
+    return this.revision;
   }
 
   findNodes(predicate: (node: MindMapNode) => boolean): MindMapNode[] {
@@ -472,6 +602,23 @@ export class MindMapStorage {
     return Array.from(this.graph.edges.values()).filter(predicate);
   }
 
+  /**
+   * Edges of one type. Served from an index that is rebuilt on first use after the graph changes,
+   * so repeated queries between mutations never scan every edge.
+   */
+  findEdgesByType(type: string): MindMapEdge[] {
+    if (this.edgeTypeIndex?.revision !== this.revision) {
+      const byType = new Map<string, MindMapEdge[]>();
+      for (const edge of this.graph.edges.values()) {
+        const edges = byType.get(edge.type);
+        if (edges) edges.push(edge);
+        else byType.set(edge.type, [edge]);
+      }
+      this.edgeTypeIndex = { revision: this.revision, byType };
+    }
+    return [...(this.edgeTypeIndex.byType.get(type) || [])];
+  }
+
   getConnectedNodes(nodeId: string, direction: 'incoming' | 'outgoing' | 'both' = 'both'): MindMapNode[] {
     const connectedIds = new Set<string>();
     
@@ -498,17 +645,180 @@ export class MindMapStorage {
     if (node) {
       node.confidence = confidence;
       node.lastUpdated = new Date();
+      this.revision++;
     }
   }
 
+  /**
+   * Stream the graph as JSON Lines: one `{"type":"node","node":...}` object per node, then one
+   * `{"type":"edge","edge":...}` per edge, each group in ID order. Waits on backpressure so large
+   * graphs are never serialized into a single string. With `omitTimestamps` two scans of the same
+   * input write byte-identical output, for golden files and snapshot tests. A `filter` narrows the
+   * output as it does the other exports.
+   */
+  async writeJsonl(writer: Writable, options: { omitTimestamps?: boolean; filter?: ExportFilter } = {}): Promise<{ nodes: number; edges: number }> {
+    const write = async (record: object) => {
+      if (!writer.write(JSON.stringify(record) + '\n')) {
+        await once(writer, 'drain');
+      }
+    };
+    const stable = <T extends object>(item: T) => options.omitTimestamps
+      ? Object.fromEntries(Object.entries(item).filter(([key]) => !TIMESTAMP_FIELDS.has(key)))
+      : item;
+
+    const selected = filterGraph(this.graph, options.filter);
+    const nodeIds = selected.nodes.map(node => node.id).sort();
+    for (const id of nodeIds) {
+      await write({ type: 'node', node: stable(this.graph.nodes.get(id)!) });
+    }
+    const edgeIds = selected.edges.map(edge => edge.id).sort();
+    for (const id of edgeIds) {
+      await write({ type: 'edge', edge: stable(this.graph.edges.get(id)!) });
+    }
+
+    return { nodes: nodeIds.length, edges: edgeIds.length };
+  }
+
+  /**
+   * Save the whole graph in the compact binary format, independent of the JSON cache file
+   */
+  async writeBinary(filePath: string): Promise<{ nodes: number; edges: number; bytes: number }> {
+    const data = encodeGraph(this.graph);
+    await mkdir(dirname(filePath), { recursive: true });
+    await writeFile(filePath, data);
+    return { nodes: this.graph.nodes.size, edges: this.graph.edges.size, bytes: data.length };
+  }
+
+  /**
+   * Replace the graph with a binary file. Throws `BinaryGraphError` for foreign, corrupt or
+   * other-version files and leaves the current graph untouched.
+   */
+  async readBinary(filePath: string): Promise<{ nodes: number; edges: number }> {
+    const decoded = decodeGraph(await readFile(filePath));
+
+    this.graph = {
+      nodes: new Map(decoded.nodes.map(node => [node.id, node])),
+      edges: new Map(decoded.edges.map(edge => [edge.id, edge])),
+      projectRoot: this.projectRoot,
+      lastScan: decoded.lastScan,
+      version: decoded.version
+    };
+    this.revision++;
+    return { nodes: decoded.nodes.length, edges: decoded.edges.length };
+  }
+
+  /**
+   * Fold another graph into this one. Nodes and edges sharing an ID are combined the same way
+   * whichever side they come from: the newer copy wins (ties broken by content), confidence is the
+   * higher of the two, edge evidence is unioned and learned edge weights are summed. Merging is
+   * therefore commutative and associative.
+   */
+  merge(other: MindMapGraph): { nodesAdded: number; nodesMerged: number; edgesAdded: number; edgesMerged: number } {
+    const result = { nodesAdded: 0, nodesMerged: 0, edgesAdded: 0, edgesMerged: 0 };
+
+    for (const node of other.nodes.values()) {
+      const existing = this.graph.nodes.get(node.id);
+      if (!existing) {
+        this.graph.nodes.set(node.id, { ...node });
+        result.nodesAdded++;
+        continue;
+      }
+      const winner = newerCopy(existing, node, ['confidence']);
+      this.graph.nodes.set(node.id, { ...winner, confidence: Math.max(existing.confidence, node.confidence) });
+      result.nodesMerged++;
+    }
+
+    for (const edge of other.edges.values()) {
+      const existing = this.graph.edges.get(edge.id);
+      if (!existing) {
+        this.graph.edges.set(edge.id, { ...edge });
+        result.edgesAdded++;
+        continue;
+      }
+      const merged: MindMapEdge = {
+        ...newerCopy(existing, edge, ['confidence', 'evidence', 'weight', 'createdAt', 'lastReinforcedAt', 'lastDecayedAt']),
+        confidence: Math.max(existing.confidence, edge.confidence)
+      };
+      if (existing.evidence?.length || edge.evidence?.length) {
+        merged.evidence = sortEvidence(mergeEvidence(existing.evidence, edge.evidence));
+      }
+      if (existing.weight !== undefined || edge.weight !== undefined) {
+        merged.weight = (existing.weight || 0) + (edge.weight || 0);
+      }
+      merged.createdAt = pickTime(existing.createdAt, edge.createdAt, Math.min);
+      merged.lastReinforcedAt = pickTime(existing.lastReinforcedAt, edge.lastReinforcedAt, Math.max);
+      merged.lastDecayedAt = pickTime(existing.lastDecayedAt, edge.lastDecayedAt, Math.max);
+      this.graph.edges.set(edge.id, merged);
+      result.edgesMerged++;
+    }
+
+    if (new Date(other.lastScan).getTime() > new Date(this.graph.lastScan).getTime()) {
+      this.graph.lastScan = new Date(other.lastScan);
+    }
+    this.revision++;
+    return result;
+  }
+
   getGraph(): MindMapGraph {
     return this.graph;
   }
 
+  /**
+   * Replace all nodes and edges as given, keeping their timestamps (used when restoring snapshots)
+   */
+  replaceContents(nodes: MindMapNode[], edges: MindMapEdge[]): void {
+    this.graph.nodes = new Map(nodes.map(node => [node.id, node]));
+    this.graph.edges = new Map(edges.map(edge => [edge.id, edge]));
+    this.revision++;
+  }
+
+  /**
+   * Swap in a fresh scan of one project root: nodes whose path lies under `root` give way, along with every
+   * edge into or out of them, to the scanned graph's nodes and edges, which keep their timestamps, and the
+   * scan time is taken over
+   */
+  replaceScan(root: string, scanned: MindMapGraph): void {
+    const scannedRoot = resolve(this.projectRoot, root);
+    const removed = new Set<string>();
+    for (const [nodeId, node] of this.graph.nodes) {
+      if (!node.path) continue;
+      const fromRoot = relative(scannedRoot, resolve(this.projectRoot, node.path));
+      if (!fromRoot.startsWith('..') && !isAbsolute(fromRoot)) {
+        this.graph.nodes.delete(nodeId);
+        removed.add(nodeId);
+      }
+    }
+    for (const [edgeId, edge] of this.graph.edges) {
+      if (removed.has(edge.source) || removed.has(edge.target)) {
+        this.graph.edges.delete(edgeId);
+      }
+    }
+    for (const [nodeId, node] of scanned.nodes) {
+      this.graph.nodes.set(nodeId, node);
+    }
+    for (const [edgeId, edge] of scanned.edges) {
+      this.graph.edges.set(edgeId, edge);
+    }
+    this.graph.lastScan = scanned.lastScan;
+    this.revision++;
+  }
+
+  /**
+   * Rebuild the node and edge maps in ID order. Everything numbered by iteration order (the path
+   * dictionary of the JSON cache, the binary string table) is then assigned the same way for the
+   * same input, however files were scheduled during the scan.
+   */
+  canonicalizeOrder(): void {
+    const byId = <T>([a]: [string, T], [b]: [string, T]) => (a < b ? -1 : a > b ? 1 : 0);
+    this.graph.nodes = new Map(Array.from(this.graph.nodes.entries()).sort(byId));
+    this.graph.edges = new Map(Array.from(this.graph.edges.entries()).sort(byId));
+  }
+
   clear(): void {
     this.graph.nodes.clear();
     this.graph.edges.clear();
     this.graph.lastScan = new Date();
+    this.revision++;
   }
 
   getStats(): {
@@ -609,6 +919,7 @@ export class MindMapStorage {
       const edge = this.graph.edges.get(edgeId);
       if (edge && !dryRun) {
         this.graph.edges.delete(edgeId);
+        this.revision++;
         memoryReduced += this.estimateEdgeMemoryUsage(edge);
       }
       prunedEdges.push(edgeId);
@@ -794,6 +1105,7 @@ export class MindMapStorage {
             ...essentialMetadata,
             lazyLoaded: `${Object.keys(lazyMetadata).length} additional properties`
           };
+          this.revision++;
 
           memoryReduced += this.estimateNodeMemoryReduction(lazyMetadata);
           lazyLoaded++;
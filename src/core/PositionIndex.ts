@@ -0,0 +1,178 @@
+import { MindMapNode } from '../types/index.js';
+
+interface PositionSpan {
+  nodeId: string;
+  startLine: number;
+  startColumn: number;
+  endLine: number;
+  endColumn: number;
+  parent: number; // Index of the nearest enclosing span, -1 for top-level spans
+}
+
+/**
+ * Per-file index of node source spans for position hit testing.
+ * Lookups binary-search the sorted spans and walk up enclosing spans, so the cost
+ * is O(log n + nesting depth). Files are (re)indexed lazily after invalidation.
+ */
+export class PositionIndex {
+  private files = new Map<string, PositionSpan[]>(); // Sorted by start, outer spans before inner ones
+  private stale = new Set<string>();
+  private buildCounts = new Map<string, number>();
+
+  constructor(private getFileNodes: (filePath: string) => MindMapNode[]) {}
+
+  /**
+   * Return the innermost node whose span encloses the position (1-based line, optional column)
+   */
+  nodeAt(filePath: string, line: number, column?: number): string | undefined {
+    const spans = this.getFileSpans(filePath);
+    const index = this.enclosingSpan(spans, line, column ?? 0);
+    return index >= 0 ? spans[index].nodeId : undefined;
+  }
+
+  /**
+   * Return the innermost node whose span encloses every line of the range
+   */
+  nodeCovering(filePath: string, startLine: number, endLine: number): string | undefined {
+    const spans = this.getFileSpans(filePath);
+    let index = this.enclosingSpan(spans, startLine, 0);
+    while (index >= 0 && spans[index].endLine < endLine) {
+      index = spans[index].parent;
+    }
+    return index >= 0 ? spans[index].nodeId : undefined;
+  }
+
+  /**
+   * Return every node whose span overlaps the lines of the range, enclosing nodes before the nodes
+   * nested in them
+   */
+  nodesOverlapping(filePath: string, startLine: number, endLine: number): string[] {
+    const spans = this.getFileSpans(filePath);
+
+    // Spans starting before the range overlap it only if they enclose its start
+    const enclosing: number[] = [];
+    for (let index = this.enclosingSpan(spans, startLine, 0); index >= 0; index = spans[index].parent) {
+      enclosing.unshift(index);
+    }
+
+    // Every span starting within the range overlaps it; they sit together in start order
+    const overlapping = enclosing.map(index => spans[index].nodeId);
+    let first = this.firstStartingAt(spans, startLine);
+    while (first < spans.length && spans[first].startLine <= endLine) {
+      if (!enclosing.includes(first)) overlapping.push(spans[first].nodeId);
+      first++;
+    }
+    return overlapping;
+  }
+
+  /**
+   * Mark a file for reindexing on its next lookup
+   */
+  invalidateFile(filePath: string): void {
+    if (this.files.has(filePath)) {
+      this.stale.add(filePath);
+    }
+  }
+
+  invalidateAll(): void {
+    this.files.clear();
+    this.stale.clear();
+  }
+
+  getBuildCount(filePath: string): number {
+    return this.buildCounts.get(filePath) || 0;
+  }
+
+  private firstStartingAt(spans: PositionSpan[], line: number): number {
+    let low = 0;
+    let high = spans.length;
+    while (low < high) {
+      const mid = (low + high) >> 1;
+      if (spans[mid].startLine < line) low = mid + 1;
+      else high = mid;
+    }
+    return low;
+  }
+
+  private enclosingSpan(spans: PositionSpan[], line: number, col: number): number {
+    // Last span starting at or before the position
+    let low = 0;
+    let high = spans.length - 1;
+    let candidate = -1;
+    while (low <= high) {
+      const mid = (low + high) >> 1;
+      if (this.compare(spans[mid].startLine, spans[mid].startColumn, line, col) <= 0) {
+        candidate = mid;
+        low = mid + 1;
+      } else {
+        high = mid - 1;
+      }
+    }
+
+    // Spans that start earlier but ended before the position are siblings; walk up to an enclosing one
+    while (candidate >= 0) {
+      const span = spans[candidate];
+      if (this.compare(span.endLine, span.endColumn, line, col) >= 0) {
+        return candidate;
+      }
+      candidate = span.parent;
+    }
+
+    return -1;
+  }
+
+  private getFileSpans(filePath: string): PositionSpan[] {
+    const existing = this.files.get(filePath);
+    if (existing && !this.stale.has(filePath)) {
+      return existing;
+    }
+
+    const spans = this.buildSpans(this.getFileNodes(filePath));
+    this.files.set(filePath, spans);
+    this.stale.delete(filePath);
+    this.buildCounts.set(filePath, this.getBuildCount(filePath) + 1);
+    return spans;
+  }
+
+  private buildSpans(nodes: MindMapNode[]): PositionSpan[] {
+    const spans: PositionSpan[] = [];
+    for (const node of nodes) {
+      const { startLine, endLine, startColumn, endColumn } = node.metadata || {};
+      if (node.type === 'file' || node.type === 'directory') continue;
+      if (typeof startLine !== 'number' || typeof endLine !== 'number') continue;
+
+      spans.push({
+        nodeId: node.id,
+        startLine,
+        startColumn: typeof startColumn === 'number' ? startColumn : 0,
+        endLine,
+        endColumn: typeof endColumn === 'number' ? endColumn : Number.MAX_SAFE_INTEGER,
+        parent: -1
+      });
+    }
+
+    spans.sort((a, b) =>
+      this.compare(a.startLine, a.startColumn, b.startLine, b.startColumn) ||
+      this.compare(b.endLine, b.endColumn, a.endLine, a.endColumn) ||
+      a.nodeId.localeCompare(b.nodeId)
+    );
+
+    // Link each span to the nearest open span that still encloses its start
+    const open: number[] = [];
+    spans.forEach((span, index) => {
+      while (open.length > 0) {
+        const top = spans[open[open.length - 1]];
+        if (this.compare(top.endLine, top.endColumn, span.startLine, span.startColumn) >= 0) break;
+        open.pop();
+      }
+      span.parent = open.length > 0 ? open[open.length - 1] : -1;
+      open.push(index);
+    });
+
+    return spans;
+  }
+
+  private compare(lineA: number, columnA: number, lineB: number, columnB: number): number {
+    return lineA !== lineB ? lineA - lineB : columnA - columnB;
+  }
+}
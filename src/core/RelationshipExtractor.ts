@@ -0,0 +1,41 @@
+import { MindMapNode, MindMapEdge } from '../types/index.js';
+
+export interface RelationshipExtractionContext {
+  filePath: string; // Relative to the project root
+  fileContent: string;
+  fileNodes: MindMapNode[]; // Every node indexed from the file, including the file node
+  getNode(id: string): MindMapNode | undefined;
+  addNode(node: MindMapNode): void; // For targets that are not code, e.g. a SQL table
+}
+
+/**
+ * User-supplied extractor for domain-specific relationships ("this function emits this event").
+ * Called once per indexed node with the node's source text; returned edges whose endpoints do
+ * not exist once the file is processed are dropped.
+ */
+export interface RelationshipExtractor {
+  name: string;
+  extract(node: MindMapNode, source: string, context: RelationshipExtractionContext): MindMapEdge[];
+}
+
+/**
+ * Build an edge of a user-defined kind
+ */
+export function createCustomEdge(
+  source: string,
+  target: string,
+  kind: string,
+  metadata?: Record<string, any>,
+  confidence = 0.7
+): MindMapEdge {
+  return {
+    id: `${source}->${kind}->${target}`,
+    source,
+    target,
+    type: 'custom',
+    customKind: kind,
+    metadata,
+    confidence,
+    lastUpdated: new Date()
+  };
+}
@@ -1,27 +1,175 @@
-import { readFile } from 'fs/promises';
+import { dirname, resolve } from 'path';
+import * as crypto from 'crypto';
 import { CodeStructure } from '../types/index.js';
+import {
+  RustAttribute, RustAttributeSpan, CfgOptions, findAttributeSpans, parseAttribute, attributeArgs, evaluateCfg, formatAttributeArg
+} from './RustAttributes.js';
+import { RustType, DEFAULT_MAX_TYPE_DEPTH, parseRustType, isTruncatedType } from './RustTypes.js';
+import { FileReader } from './FileReader.js';
+import { AnalysisProfile, RustLint, DEFAULT_RUST_LINTS, withProfile } from './AnalysisProfiles.js';
+import { expandUseTree } from './RustImportScope.js';
+
+export interface RustGenericParam {
+  name: string; // Without the leading `'` for lifetimes
+  kind: 'type' | 'const' | 'lifetime';
+  type?: string; // Type of a const parameter, e.g. `usize`
+  defaultValue?: string; // `= ...` default, when written
+}
+
+// How a method takes `self`: `self`/`mut self`, `&self`, `&mut self`, `self: Box<Self>`, another
+// explicit `self: T` (`Rc<Self>`, `Pin<&mut Self>`), or no receiver for associated functions
+export type RustReceiver = 'value' | 'ref' | 'ref_mut' | 'boxed' | 'typed' | 'none';
+
+// The parts of a method signature that decide how calls to it type-check, parameter names aside
+export interface RustMethodShape {
+  name: string;
+  receiver: RustReceiver;
+  parameterTypes: string[]; // Without the receiver
+  returnType?: string;
+  signature?: string;
+  line: number;
+}
 
 export interface RustCodeStructure extends CodeStructure {
+  functions: Array<CodeStructure['functions'][number] & {
+    isAsync?: boolean;
+    isPublic?: boolean;
+    isUnsafe?: boolean;
+    isConst?: boolean;
+    abi?: string; // `extern "C" fn` definitions and functions declared in `extern` blocks; bare `extern` is "C"
+    isForeign?: boolean; // Declared in an `extern` block, implemented outside Rust
+    signature?: string; // Declaration header, whitespace-normalized
+    parameterTypes?: string[]; // Parallel to parameters; empty for self receivers
+    parameterTypeTrees?: Array<RustType | null>; // Parsed parameterTypes; null for self receivers
+    returnTypeTree?: RustType;
+    truncatedType?: boolean; // A parameter or return type nests deeper than the parse options allow
+    receiver?: RustReceiver;
+    receiverMutable?: boolean; // `&mut self`, `mut self` or a typed receiver through `&mut`
+    borrowsFrom?: string[]; // Parameters the returned reference borrows from, by name (`self` for the receiver)
+    implTarget?: string; // Type of the enclosing impl block, as written
+    parameterBounds?: string[][]; // Traits each parameter must implement, from generic bounds, `where` and `impl Trait`
+    typeParameters?: string[]; // Generic type parameter names; lifetimes and const generics are left out
+    genericParams?: RustGenericParam[]; // Every generic parameter in declaration order
+    genericBounds?: Array<{ parameter: string; bounds: string[]; source: 'inline' | 'where' }>; // Bound lists as written, one per clause
+    hasBody?: boolean; // False for trait method declarations ending in `;`
+    bodyHash?: string; // Hash of the comment- and whitespace-normalized body; unset for declarations
+    bodySize?: number; // Length of the normalized body
+    complexity?: number; // Cyclomatic complexity of the body: 1 + branches, loops, match arms beyond the first, `&&`/`||` and `?`
+    nestingDepth?: number; // Deepest nesting of blocks (branches, loops, match arms, closures) inside the body; 0 for a flat body
+    calls?: string[]; // Call sites in the body: `name`, `Path::name` or `.method`
+    callSites?: Array<{ call: string; line: number }>; // First line each of `calls` appears on
+    awaitedCalls?: string[]; // Calls whose future is awaited or handed to an executor
+    typeMentions?: string[]; // Capitalized identifiers in the body: struct literals, paths, patterns
+    discardedCalls?: Array<{ call: string; line: number }>; // Calls in statement position whose value is dropped
+    shadowedBindings?: Array<{ name: string; line: number; previousLine: number }>; // `let`s re-binding a name still in scope
+    argumentTypes?: Array<{ call: string; line: number; types: Array<string | null>; turbofish?: string[] }>; // Argument types inferable at call sites
+    fieldAccesses?: Array<{ receiver: string; field: string; receiverType?: string; receiverCall?: string }>; // `x.field` reads; how `x` was bound when known
+    fieldMentions?: string[]; // Field names read or written on any receiver, in struct literals or in patterns
+    errConstructions?: string[]; // Paths wrapped in `Err(..)` in the body, e.g. `MyError::NotFound`
+    methodChains?: Array<{ calls: string[]; line: number }>; // Fluent chains: the root call or binding, then each chained method in order
+    callSequences?: RustCallSequence[]; // Methods called on each receiver, in source order
+    literals?: RustLiteral[]; // String and number literals in the body, in source order
+    panicSites?: Array<{ kind: RustPanicKind; line: number }>; // `.unwrap()`, `.expect(..)` and panicking macros
+    cloneSites?: RustCloneSite[]; // `.clone()` calls whose result is only borrowed or read
+    readOnlyParameters?: string[]; // Parameters taken by value that the body only borrows or reads
+    matchSites?: RustMatchSite[]; // `match` expressions and the enum variants their arms cover
+    locksAcrossAwait?: RustLockAcrossAwait[]; // Lock guards bound by `let` and still alive at a later `.await`
+    constEligibility?: RustConstEligibility; // What stands between the body and `const fn`
+    attributes?: RustAttribute[];
+    modulePath?: string[]; // Enclosing inline modules, outermost first
+    traitName?: string; // Trait whose body declares the method; with a body this is a default method
+    visibility?: string; // 'pub', 'pub(crate)', ... or 'private'
+    docComment?: string; // Outer doc comment (`///`, `/** */` or `#[doc = ...]`)
+    docTests?: RustDocTest[]; // Runnable examples in the doc comment
+    cfg?: string[]; // `cfg` predicates on the item and its enclosing modules
+    inactive?: boolean; // A cfg predicate is false under the parse options
+  }>;
   rustImports: Array<{
     path: string;
     alias?: string;
     isExternal?: boolean;
     isGlob?: boolean;
   }>;
+  reexports?: Array<{ // Names `pub use` declarations export, one entry per name or glob
+    path: string; // As written, e.g. `config::ServerConfig`; the module for a glob
+    name: string; // Name exported, after any `as`; `*` for a glob
+    visibility: string; // 'pub', 'pub(crate)', ...
+    modulePath: string[]; // Enclosing inline modules, outermost first
+    line: number;
+  }>;
+  globImports?: Array<{ // Private `use a::*` declarations; `pub use a::*` is among the reexports
+    path: string; // The module as written, e.g. `super` or `crate::models`
+    modulePath: string[];
+    line: number;
+  }>;
   structs: Array<{
     name: string;
     startLine: number;
     endLine: number;
     fields: string[];
+    fieldTypes?: string[]; // Parallel to fields
+    fieldTypeTrees?: RustType[]; // Parsed fieldTypes
+    truncatedType?: boolean; // A field type nests deeper than the parse options allow
+    tupleFieldTypes?: string[]; // Positional field types of a tuple struct
+    reprTransparent?: boolean; // `#[repr(transparent)]`
+    wrappedType?: string; // Set for newtypes: the single field of a tuple or `repr(transparent)` struct, PhantomData aside
+    fieldVisibility?: string[]; // Parallel to fields: 'pub', 'pub(crate)', ... or 'private'
+    serdeNames?: Array<string | null>; // Serialized field names, parallel to fields; null when skipped or flattened
+    typeParameters?: string[]; // Generic type parameter names; lifetimes and const generics are left out
+    genericParams?: RustGenericParam[];
     traits: string[];
     derives: string[];
+    isPublic?: boolean;
+    modulePath?: string[];
+    attributes?: RustAttribute[];
+    visibility?: string;
+    docComment?: string;
+    docTests?: RustDocTest[];
+    cfg?: string[];
+    inactive?: boolean;
   }>;
   traits: Array<{
     name: string;
     startLine: number;
     endLine: number;
     methods: string[];
+    defaultMethods?: string[]; // Methods with a default body
     associatedTypes: string[];
+    associatedTypeBounds?: Record<string, string[]>; // Traits an associated type must implement, declared or from `where Self::E: D`
+    associatedTypeGenerics?: Record<string, RustGenericParam[]>; // Generic associated types: `type Item<'a>;` -> { Item: ['a] }
+    supertraits?: string[]; // From `trait A: B + C` and `where Self: D`
+    attributes?: RustAttribute[];
+    modulePath?: string[];
+    visibility?: string;
+    docComment?: string;
+    docTests?: RustDocTest[];
+    cfg?: string[];
+    inactive?: boolean;
+  }>;
+  traitAliases?: Array<{ // `trait Service = Handler + Send;` (unstable `trait_alias`)
+    name: string;
+    startLine: number;
+    endLine: number;
+    traits: string[]; // The composed traits, lifetimes and `?Sized` left out
+    bounds: string; // Right-hand side as written
+    genericParams?: RustGenericParam[];
+    attributes?: RustAttribute[];
+    modulePath?: string[];
+    visibility?: string;
+    docComment?: string;
+  }>;
+  enums: Array<{
+    name: string;
+    startLine: number;
+    endLine: number;
+    variants: Array<{ name: string; line: number }>;
+    derives: string[];
+    attributes?: RustAttribute[];
+    modulePath?: string[];
+    visibility?: string;
+    docComment?: string;
+    cfg?: string[];
+    inactive?: boolean;
   }>;
   impls: Array<{
     target: string;
@@ -29,6 +177,10 @@ export interface RustCodeStructure extends CodeStructure {
     startLine: number;
     endLine: number;
     methods: string[];
+    associatedTypes?: Array<{ name: string; type: string; line: number }>; // `type Error = ...;` bindings
+    defaultValues?: RustDefaultValue[]; // Field values `fn default()` builds, for `impl Default`
+    genericParams?: RustGenericParam[]; // The impl's own parameters: `T` in `impl<T: Clone> Clone for Wrapper<T>`
+    genericBounds?: Array<{ parameter: string; bounds: string[]; source: 'inline' | 'where' }>; // Bounds gating the impl
   }>;
   macros: Array<{
     name: string;
@@ -41,19 +193,270 @@ export interface RustCodeStructure extends CodeStructure {
     startLine: number;
     endLine: number;
     isPublic: boolean;
+    attributes?: RustAttribute[];
+    cfg?: string[];
+    inactive?: boolean;
+  }>;
+  constants: Array<{
+    name: string;
+    kind: 'const' | 'static';
+    type: string;
+    value?: string; // Only recorded for simple literals
+    visibility: string; // 'pub', 'pub(crate)', ... or 'private'
+    isPublic: boolean;
+    isMutable: boolean;
+    modulePath: string[]; // Enclosing inline modules, outermost first
+    implTarget?: string; // Set when declared inside an impl block
+    startLine: number;
+    endLine: number;
+    cfg?: string[]; // Inherited from enclosing modules
+    inactive?: boolean;
+  }>;
+  routes: Array<{
+    method: string;
+    path: string;
+    handler: string;
+    line: number;
+  }>;
+  macroInvocations?: Array<{ // Item-level macro invocations kept as placeholders; only set in 'opaque' macro body mode
+    name: string;
+    modulePath: string[];
+    startLine: number;
+    endLine: number;
+  }>;
+  inlineAsm?: Array<{ // `asm!`, `global_asm!` and `naked_asm!` invocations
+    macro: string;
+    startLine: number;
+    endLine: number;
+    function?: string; // Enclosing function; unset at item level
+  }>;
+  envReads?: Array<{ // `env!`, `option_env!`, `std::env::var` and `std::env::var_os` calls
+    access: 'env' | 'option_env' | 'var' | 'var_os';
+    compileTime: boolean; // Read by the compiler (`env!`, `option_env!`) rather than when the program runs
+    variable?: string; // The variable name when given as a string literal
+    argument: string; // The name argument as written, e.g. `"PORT"` or `key`
+    startLine: number;
+    function?: string; // Enclosing function; unset at item level, e.g. in a `const` initializer
+  }>;
+  includes?: Array<{ // `include!`, `include_str!` and `include_bytes!` invocations
+    macro: 'include' | 'include_str' | 'include_bytes';
+    argument: string; // As written, e.g. `concat!(env!("OUT_DIR"), "/generated.rs")`
+    literal?: string; // The path when the argument is a plain string literal, relative to the including file
+    modulePath: string[];
+    startLine: number;
+    endLine: number;
+    function?: string; // Enclosing function; unset at item level
+    items?: RustCodeStructure; // What an item-level `include!` of a readable file pastes in
   }>;
+  diagnostics?: RustDiagnostic[]; // What the parse skipped or guessed, ordered by line
   crateName?: string;
 }
 
+// A function or method as `parseFunctions` extracts it, before module paths and impl targets are filled in
+type ParsedRustFunction = RustCodeStructure['functions'][number];
+
+// Optional per-function body passes. `call_graph` covers calls, call argument types, type mentions,
+// field reads, method chains and call sequences; `await_flow` awaited calls; `duplicates` body hashes; `discarded_results` dropped calls;
+// `shadowing` `let` bindings that shadow an earlier one; `literals` string and number literals; `panics` panic points;
+// `clones` `.clone()` calls that may be redundant and owned parameters that are only read; `const_fn` what keeps a body from being `const fn`;
+// `matches` the variants `match` arms cover.
+export type RustAnalysis = 'call_graph' | 'await_flow' | 'duplicates' | 'discarded_results' | 'shadowing' | 'literals' | 'panics' | 'clones' | 'const_fn' | 'matches';
+
+export const DEFAULT_RUST_ANALYSES: readonly RustAnalysis[] = ['call_graph', 'await_flow', 'duplicates', 'discarded_results', 'shadowing', 'literals', 'panics', 'clones', 'const_fn', 'matches'];
+
+// How items written inside item-level macro invocations (`cfg_if! { .. }`) are treated: parsed like any
+// other code, ignored, or ignored with one placeholder per invocation
+export type RustMacroBodyMode = 'skip' | 'opaque' | 'best_effort';
+
+export interface RustParseOptions {
+  cfgFeatures?: Iterable<string>; // Parse as if these cargo features are enabled
+  cfgTest?: boolean; // Whether `cfg(test)` holds; defaults to true
+  enabledAnalyses?: Iterable<RustAnalysis>; // Body passes to run; defaults to DEFAULT_RUST_ANALYSES
+  docTests?: boolean; // Extract fenced Rust examples from doc comments as doc-tests; defaults to true
+  maxTypeDepth?: number; // Type nesting parsed before the rest is kept as text; defaults to DEFAULT_MAX_TYPE_DEPTH
+  macroBodyMode?: RustMacroBodyMode; // Defaults to 'best_effort'
+  lints?: Iterable<RustLint>; // Lint queries selected for reporting; defaults to DEFAULT_RUST_LINTS
+  profile?: AnalysisProfile; // Preset for the options above; options set alongside it take precedence
+  followIncludes?: boolean; // Parse the files item-level `include!("..")` names; defaults to true
+  publicOnly?: boolean; // Keep only the signatures of public items and skip every function body; defaults to false
+  presizedRead?: boolean; // Read large files into a buffer sized from the file (FileReader's presized backend); defaults to false
+  mmap?: boolean; // Memory-map large files when the optional mmap-io module is installed, else read them buffered; takes precedence over presizedRead
+}
+
+// `include!` nesting followed before further includes are left unparsed, which also ends include cycles
+const MAX_INCLUDE_DEPTH = 4;
+
+export interface RustDocTest {
+  index: number; // 1-based position among the item's doc-tests
+  code: string; // With rustdoc's hidden-line `# ` prefixes removed
+  calls: string[]; // Call sites, as for function bodies
+}
+
+export type RustDiagnosticSeverity = 'info' | 'warning' | 'error';
+
+// `unresolved-call` is reported by RustGraphBuilder, which resolves calls; the others by the analyzer
+export type RustDiagnosticCode = 'unresolved-call' | 'truncated-type' | 'macro-body-skipped';
+
+export interface RustDiagnostic {
+  severity: RustDiagnosticSeverity;
+  code: RustDiagnosticCode;
+  message: string;
+  span: { startLine: number; endLine: number };
+  item?: string; // Name of the item the diagnostic is about
+}
+
+export interface RustCallSequence {
+  receiver: string; // `self`, a binding or field path (`self.repo`), or the type of a constructor chain (`HttpServer`)
+  calls: Array<{ method: string; line: number }>; // Ordered by source position
+}
+
+export type RustLiteralKind = 'string' | 'integer' | 'float';
+
+export interface RustLiteral {
+  kind: RustLiteralKind;
+  value: string; // As written, without quotes; numbers keep suffixes and underscores (`8_080u16`)
+  line: number;
+  binding?: string; // Field, variable or named argument it is assigned to, e.g. `port` in `port: 8080`
+}
+
+export type RustPanicKind = 'unwrap' | 'expect' | 'panic' | 'unreachable' | 'todo' | 'unimplemented';
+
+// `&x.clone()` borrows a fresh copy where `&x` would do; a `let` binding of a clone that is never
+// mutated or moved only reads it. Both are heuristics: the original may be moved or mutated meanwhile.
+export type RustCloneReason = 'borrowed_clone' | 'read_only_binding';
+
+export interface RustMatchSite {
+  scrutinee: string; // The matched expression, whitespace-normalized
+  line: number;
+  enumName?: string; // Qualifier of the variant arms, e.g. `Status` for `Status::A`; unset when no arm is qualified
+  variants: string[]; // Variants an unguarded arm covers whole; a refutable payload like `A(0)` covers part
+  wildcard: boolean; // An unguarded `_` or binding arm catches everything else
+}
+
+export interface RustLockAcrossAwait {
+  binding: string; // The `let` binding holding the guard
+  acquire: string; // `state.users.lock()`
+  line: number;
+  awaitLine: number; // First `.await` reached while the guard is alive
+}
+
+export interface RustCloneSite {
+  reason: RustCloneReason;
+  expression: string; // `user.clone()`
+  binding?: string; // The `let` binding holding the clone
+  line: number;
+  confidence: number; // 0-1
+}
+
+export interface RustConstEligibility {
+  blockers: string[]; // Operations not allowed in `const fn`: `for loop`, `.await`, `closure`, `println!`, `.to_string()`
+  calls: string[]; // Function calls (`helper`, `Self::new`, `u32::max`); eligible only when every callee is const
+}
+
+export interface RustDefaultValue {
+  field: string;
+  expression: string; // As written, whitespace-normalized
+  value?: string; // For literals, including string literals turned into a `String`: `"127.0.0.1"`, `8080`
+  kind: 'literal' | 'call' | 'path' | 'expression' | 'derived';
+  line: number;
+}
+
+// Macros usable in `const fn` bodies
+const CONST_MACROS = new Set(['panic', 'assert', 'debug_assert', 'unreachable', 'todo', 'unimplemented', 'matches', 'concat', 'stringify', 'line', 'column', 'file', 'cfg', 'env', 'include_str', 'include_bytes']);
+
+// Methods on primitives, `Option` and `Result` that are const on stable Rust
+const CONST_METHODS = new Set([
+  'wrapping_add', 'wrapping_sub', 'wrapping_mul', 'wrapping_neg', 'checked_add', 'checked_sub', 'checked_mul', 'checked_div',
+  'saturating_add', 'saturating_sub', 'saturating_mul', 'pow', 'abs', 'signum', 'count_ones', 'count_zeros', 'leading_zeros',
+  'trailing_zeros', 'rotate_left', 'rotate_right', 'swap_bytes', 'to_be', 'to_le', 'is_power_of_two', 'len', 'is_empty',
+  'is_some', 'is_none', 'is_ok', 'is_err', 'unwrap', 'expect', 'as_ptr', 'is_ascii', 'is_ascii_digit', 'is_ascii_alphabetic'
+]);
+
+// Methods that take `self` by value, so calling one on a parameter consumes it
+const CONSUMING_METHODS = /^(?:into(?:_|$)|unwrap|expect|ok$|err$|map|and_then|or_else|or$|zip$|chain$|for_each$|collect$|sum$|count$|take$)/;
+
+// `Copy` primitives, cheaper to pass by value than by reference
+const PRIMITIVE_TYPES = new Set([
+  'bool', 'char', 'i8', 'i16', 'i32', 'i64', 'i128', 'isize', 'u8', 'u16', 'u32', 'u64', 'u128', 'usize', 'f32', 'f64', '()'
+]);
+
+// Macros taking their arguments by reference
+const FORMAT_MACROS = /(?:^|[^\p{XID_Continue}])(?:print|println|eprint|eprintln|format|format_args|write|writeln|panic|assert|assert_eq|assert_ne|debug_assert|debug_assert_eq|debug_assert_ne|trace|debug|info|warn|error)!\s*$/u;
+
+// Fence info tokens rustdoc accepts on Rust code blocks; any other token names a different language
+const RUSTDOC_FENCE_TAGS = new Set(['rust', 'should_panic', 'no_run', 'compile_fail', 'ignore', 'test_harness', 'allow_fail']);
+
+export interface RustParseStats {
+  functions: number;
+  bodiesExtracted: number; // Zero when no enabled analysis needs function bodies
+  callGraphScans: number;
+  presizedReads: number; // Files read through the presized backend rather than a buffered read
+  mappedReads: number; // Files decoded from a memory mapping
+}
+
+const ROUTE_METHODS = new Set(['get', 'post', 'put', 'delete', 'patch', 'head']);
+
+// An identifier as written, raw (`r#type`) or not, including non-ASCII ones (`Café`); needs the `u` flag
+const IDENT = String.raw`(?:r#)?[\p{XID_Start}_]\p{XID_Continue}*`;
+
+// What follows the name in `trait Name<T> = Bound + Other;`: optional generics, then `=` and the bounds
+const TRAIT_ALIAS_TAIL = /^\s*(?:<((?:[^<>]|<(?:[^<>]|<[^<>]*>)*>)*)>\s*)?=(?!=)([^;{]*);/u;
+
 /**
  * Rust AST Analyzer using regex-based parsing approach
  * Extracts Rust code structure including functions, structs, traits, impls, and modules
  */
 export class RustAnalyzer {
   private supportedExtensions: Set<string>;
-
-  constructor() {
+  private attributeSpanCache: { content: string; spansByEnd: Map<number, RustAttributeSpan> } | null = null;
+  private cfgOptions: CfgOptions = { features: new Set(), test: true };
+  private enabledAnalyses: Set<RustAnalysis> = new Set(DEFAULT_RUST_ANALYSES);
+  private docTests = true;
+  private maxTypeDepth = DEFAULT_MAX_TYPE_DEPTH;
+  private macroBodyMode: RustMacroBodyMode = 'best_effort';
+  private enabledLints: Set<RustLint> = new Set(DEFAULT_RUST_LINTS);
+  private followIncludes = true;
+  private publicOnly = false;
+  private fileReader = new FileReader();
+  private parseStats: RustParseStats = { functions: 0, bodiesExtracted: 0, callGraphScans: 0, presizedReads: 0, mappedReads: 0 };
+
+  constructor(options: RustParseOptions = {}) {
     this.supportedExtensions = new Set(['rs']);
+    this.setParseOptions(options);
+  }
+
+  /**
+   * Configuration for subsequently parsed files: how `cfg` predicates evaluate and which body passes run
+   */
+  setParseOptions(options: RustParseOptions): void {
+    if (options.profile) options = withProfile(options.profile, options);
+    this.cfgOptions = {
+      features: new Set(options.cfgFeatures || []),
+      test: options.cfgTest ?? true
+    };
+    this.enabledAnalyses = new Set(options.enabledAnalyses || DEFAULT_RUST_ANALYSES);
+    this.docTests = options.docTests ?? true;
+    this.maxTypeDepth = options.maxTypeDepth ?? DEFAULT_MAX_TYPE_DEPTH;
+    this.macroBodyMode = options.macroBodyMode ?? 'best_effort';
+    this.enabledLints = new Set(options.lints || DEFAULT_RUST_LINTS);
+    this.followIncludes = options.followIncludes ?? true;
+    this.publicOnly = options.publicOnly ?? false;
+    this.fileReader = new FileReader(options.mmap ? 'mmap' : options.presizedRead ? 'presized' : 'buffered');
+  }
+
+  getEnabledLints(): RustLint[] {
+    return Array.from(this.enabledLints);
+  }
+
+  /**
+   * Work done since the analyzer was created or the stats were last reset
+   */
+  getParseStats(): RustParseStats {
+    return { ...this.parseStats };
+  }
+
+  resetParseStats(): void {
+    this.parseStats = { functions: 0, bodiesExtracted: 0, callGraphScans: 0, presizedReads: 0, mappedReads: 0 };
   }
 
   canAnalyze(filePath: string): boolean {
@@ -61,21 +464,31 @@ export class RustAnalyzer {
     return extension ? this.supportedExtensions.has(extension) : false;
   }
 
-  async analyzeFile(filePath: string): Promise<RustCodeStructure | null> {
+  async analyzeFile(filePath: string, content?: string): Promise<RustCodeStructure | null> {
     if (!this.canAnalyze(filePath)) {
       return null;
     }
 
     try {
-      const content = await readFile(filePath, 'utf-8');
-      return await this.parseCode(content, filePath);
+      const source = content ?? await this.readSource(filePath);
+      return await this.parseCode(source, filePath);
     } catch (error) {
       console.warn(`Failed to analyze Rust file ${filePath}:`, error);
       return null;
     }
   }
 
-  private async parseCode(content: string, filePath: string): Promise<RustCodeStructure> {
+  private async readSource(filePath: string): Promise<string> {
+    const { text, presized, mapped } = await this.fileReader.read(filePath);
+    if (presized) this.parseStats.presizedReads++;
+    if (mapped) this.parseStats.mappedReads++;
+    return text;
+  }
+
+  private async parseCode(source: string, filePath: string, includeDepth: number = 0): Promise<RustCodeStructure> {
+    // Outside best-effort mode, macro input is blanked so none of the parsers below see it
+    const invocations = this.macroBodyMode === 'best_effort' ? [] : this.findItemMacroInvocations(source);
+    const content = this.blankMacroBodies(source, invocations);
     const lines = content.split('\n');
     
     const result: RustCodeStructure = {
@@ -86,9 +499,12 @@ export class RustAnalyzer {
       rustImports: [],
       structs: [],
       traits: [],
+      enums: [],
       impls: [],
       macros: [],
       modules: [],
+      constants: [],
+      routes: [],
     };
 
     // Add metadata for language and framework detection
@@ -109,11 +525,18 @@ export class RustAnalyzer {
     // Parse functions
     result.functions = this.parseFunctions(content, lines);
 
+    result.inlineAsm = this.parseInlineAsm(content, result.functions);
+    result.envReads = this.parseEnvReads(content, result.functions);
+
     // Parse structs
     result.structs = this.parseStructs(content, lines);
 
     // Parse traits
     result.traits = this.parseTraits(content, lines);
+    result.traitAliases = this.parseTraitAliases(content);
+
+    // Parse enums
+    result.enums = this.parseEnums(content);
 
     // Parse implementations
     result.impls = this.parseImpls(content, lines);
@@ -124,12 +547,150 @@ export class RustAnalyzer {
     // Parse modules
     result.modules = this.parseModules(content, lines);
 
+    // Parse const and static items (needs modules and impls for nesting)
+    result.constants = this.parseConstants(content, result.modules, result.impls);
+    result.reexports = this.parseReexports(content, result.modules);
+    result.globImports = this.parseGlobImports(content, result.modules);
+
+    // Record enclosing modules so items can be qualified
+    for (const struct of result.structs) {
+      struct.modulePath = this.getModulePath(struct.startLine, result.modules);
+    }
+    for (const trait of [...result.traits, ...result.traitAliases]) {
+      trait.modulePath = this.getModulePath(trait.startLine, result.modules);
+    }
+    for (const enumItem of result.enums) {
+      enumItem.modulePath = this.getModulePath(enumItem.startLine, result.modules);
+    }
+    for (const func of result.functions) {
+      func.modulePath = this.getModulePath(func.startLine, result.modules);
+      func.traitName = result.traits.find(trait => trait.startLine < func.startLine && trait.endLine >= func.endLine)?.name;
+      func.implTarget = result.impls
+        .filter(impl => impl.startLine < func.startLine && impl.endLine >= func.endLine)
+        .sort((a, b) => b.startLine - a.startLine)[0]?.target;
+    }
+
+    if (this.publicOnly) this.keepPublicApi(result);
+
+    const invocationSpans = invocations.map(invocation => {
+      const startLine = content.substring(0, invocation.start).split('\n').length;
+      return {
+        name: invocation.name,
+        modulePath: this.getModulePath(startLine, result.modules),
+        startLine,
+        endLine: content.substring(0, invocation.close).split('\n').length
+      };
+    });
+    if (this.macroBodyMode === 'opaque') {
+      result.macroInvocations = invocationSpans;
+    }
+
+    // Conditional compilation: items are kept, but flagged when a cfg predicate is false
+    for (const item of [...result.functions, ...result.structs, ...result.traits, ...result.enums, ...result.modules]) {
+      this.applyCfg(item, item.attributes, item.startLine, result.modules);
+    }
+    for (const constant of result.constants) {
+      this.applyCfg(constant, [], constant.startLine, result.modules);
+    }
+
+    if (this.docTests) {
+      for (const item of [...result.functions, ...result.structs, ...result.traits]) {
+        const docTests = this.parseDocTests(item.docComment);
+        if (docTests.length > 0) item.docTests = docTests;
+      }
+    }
+
+    // Parse HTTP route registrations, which are written in function bodies
+    result.routes = this.publicOnly ? [] : this.parseRoutes(content, result.functions);
+
+    result.includes = await this.parseIncludes(content, filePath, result, includeDepth);
+
+    result.diagnostics = this.collectDiagnostics(result, invocationSpans);
+
     // Add pattern analysis
     (result as any).patterns = this.analyzePatterns(content, result);
 
     return result;
   }
 
+  private keepPublicApi(result: RustCodeStructure): void {
+    // Items declared `pub` outside private inline modules, with their public fields; methods of kept
+    // traits and trait impls stay, as do `pub` methods of inherent impls on kept types. Visibility is
+    // taken as written: a `pub` item of a file module declared with a private `mod` is kept.
+    const hidden = result.modules.filter(module => !module.isPublic);
+    const exposed = (item: { startLine: number; visibility?: string }) =>
+      item.visibility === 'pub' && !hidden.some(module => module.startLine < item.startLine && item.startLine <= module.endLine);
+
+    result.structs = result.structs.filter(exposed);
+    result.traits = result.traits.filter(exposed);
+    result.traitAliases = result.traitAliases?.filter(exposed);
+    result.enums = result.enums.filter(exposed);
+    result.modules = result.modules.filter(module => module.isPublic);
+    result.reexports = result.reexports?.filter(reexport => exposed({ startLine: reexport.line, visibility: reexport.visibility }));
+
+    const types = new Set([...result.structs, ...result.enums, ...result.traits].map(item => item.name));
+    const typeName = (target: string) => target.replace(/<[\s\S]*$/, '').split('::').pop()!.trim();
+    result.impls = result.impls.filter(impl => types.has(typeName(impl.target)) || (impl.trait !== undefined && types.has(typeName(impl.trait))));
+    const enclosingImpl = (line: number) => result.impls
+      .filter(impl => impl.startLine < line && impl.endLine >= line)
+      .sort((a, b) => b.startLine - a.startLine)[0];
+
+    result.functions = result.functions.filter(func => {
+      if (func.traitName) return types.has(func.traitName);
+      if (!func.implTarget) return exposed(func);
+      const impl = enclosingImpl(func.startLine);
+      return impl !== undefined && (impl.trait !== undefined || func.visibility === 'pub');
+    });
+    const kept = new Set(result.functions.map(func => func.startLine));
+    for (const impl of result.impls) {
+      const methods = result.functions.filter(func => kept.has(func.startLine) && impl.startLine < func.startLine && func.endLine <= impl.endLine);
+      impl.methods = impl.methods.filter(method => methods.some(func => func.name === method));
+    }
+    result.constants = result.constants.filter(constant => constant.implTarget
+      ? constant.visibility === 'pub' && types.has(typeName(constant.implTarget))
+      : exposed(constant));
+
+    for (const struct of result.structs) {
+      const visible = struct.fields.map((_, index) => (struct.fieldVisibility?.[index] ?? 'pub') === 'pub');
+      if (visible.every(Boolean)) continue;
+      const pick = <T>(values: T[] | undefined) => values?.filter((_, index) => visible[index]);
+      struct.fields = pick(struct.fields)!;
+      struct.fieldTypes = pick(struct.fieldTypes);
+      struct.fieldTypeTrees = pick(struct.fieldTypeTrees);
+      struct.fieldVisibility = pick(struct.fieldVisibility);
+      struct.serdeNames = pick(struct.serdeNames);
+    }
+  }
+
+  private collectDiagnostics(
+    result: RustCodeStructure,
+    invocations: Array<{ name: string; startLine: number; endLine: number }>
+  ): RustDiagnostic[] {
+    const diagnostics: RustDiagnostic[] = [];
+    for (const invocation of invocations) {
+      diagnostics.push({
+        severity: 'info',
+        code: 'macro-body-skipped',
+        message: this.macroBodyMode === 'opaque'
+          ? `Items inside ${invocation.name}! are kept as a single placeholder`
+          : `Items inside ${invocation.name}! were not parsed`,
+        span: { startLine: invocation.startLine, endLine: invocation.endLine },
+        item: `${invocation.name}!`
+      });
+    }
+    for (const item of [...result.functions, ...result.structs]) {
+      if (!item.truncatedType) continue;
+      diagnostics.push({
+        severity: 'warning',
+        code: 'truncated-type',
+        message: `A type in ${item.name} nests deeper than ${this.maxTypeDepth} levels; the rest is kept as text`,
+        span: { startLine: item.startLine, endLine: item.endLine },
+        item: item.name
+      });
+    }
+    return diagnostics.sort((a, b) => a.span.startLine - b.span.startLine);
+  }
+
   private parseImports(content: string, lines: string[]): Array<{
     path: string;
     alias?: string;
@@ -179,29 +740,13 @@ export class RustAnalyzer {
     return imports;
   }
 
-  private parseFunctions(content: string, lines: string[]): Array<{
-    name: string;
-    startLine: number;
-    endLine: number;
-    parameters: string[];
-    returnType?: string;
-    isAsync?: boolean;
-    isPublic?: boolean;
-    isUnsafe?: boolean;
-  }> {
-    const functions: Array<{
-      name: string;
-      startLine: number;
-      endLine: number;
-      parameters: string[];
-      returnType?: string;
-      isAsync?: boolean;
-      isPublic?: boolean;
-      isUnsafe?: boolean;
-    }> = [];
+  private parseFunctions(content: string, lines: string[]): ParsedRustFunction[] {
+    const functions: ParsedRustFunction[] = [];
 
-    // Match function definitions
-    const fnRegex = /^(\s*)(?:(pub(?:\([^)]*\))?)\s+)?(?:(async)\s+)?(?:(unsafe)\s+)?fn\s+([a-zA-Z_][a-zA-Z0-9_]*)\s*\(([^)]*)\)(?:\s*->\s*([^{]+))?/gm;
+    // Match function definitions (return type and `where` clause stop at a body or at `;` for trait declarations).
+    // Parameters may nest parentheses two deep, as in `f: impl Fn((u32, u32))` or `#[allow(unused)] id: u64`.
+    const fnRegex = new RegExp(String.raw`^(\s*)(?:(pub(?:\([^)]*\))?)\s+)?(?:const\s+)?(?:(async)\s+)?(?:(unsafe)\s+)?(?:(extern)(?:\s+"([^"]*)")?\s+)?fn\s+(${IDENT})\s*(?:<((?:[^<>]|<(?:[^<>]|<[^<>]*>)*>)*)>\s*)?\(((?:[^()]|\((?:[^()]|\([^()]*\))*\))*)\)(\s*->\s*[^{;]+|\s*where\s[^{;]+)?`, 'gmu');
+    const externBlocks = this.findExternBlocks(content);
     let match;
 
     while ((match = fnRegex.exec(content)) !== null) {
@@ -209,18 +754,25 @@ export class RustAnalyzer {
       const isPublic = match[2] !== undefined;
       const isAsync = match[3] !== undefined;
       const isUnsafe = match[4] !== undefined;
-      const functionName = match[5];
-      const params = match[6];
-      const returnType = match[7]?.trim();
+      const isConst = /^\s*(?:pub(?:\([^)]*\))?\s+)?const\s/.test(match[0]);
+      const functionName = this.canonicalName(match[7]);
+      const generics = match[8];
+      const params = match[9];
+      const [returnPart, whereClause] = (match[10] || '').split(/\bwhere\b/);
+      const returnType = returnPart.replace(/^\s*->/, '').trim() || undefined;
 
       const startLine = content.substring(0, match.index).split('\n').length;
-      const endLine = this.findFunctionEndLine(content, match.index, lines, startLine);
-
-      // Parse parameters
-      const parameters = params
-        .split(',')
-        .map(param => param.trim())
-        .filter(param => param && !param.startsWith('//'))
+      const foreignBlock = externBlocks.find(block => block.open < match!.index && match!.index < block.close);
+      // Foreign declarations end at their `;`, not at the next block
+      const endLine = foreignBlock
+        ? content.substring(0, content.indexOf(';', match.index + match[0].length)).split('\n').length
+        : this.findFunctionEndLine(content, match.index, lines, startLine);
+
+      // Parse parameters, dropping comments and attributes (`#[allow(unused)] id: u64`) around them
+      const rawParams = this.splitTopLevel(params)
+        .map(param => param.replace(/\/\/[^\n]*|\/\*[\s\S]*?\*\/|#\[[^\]]*\]/g, '').trim())
+        .filter(param => param);
+      const parameters = rawParams
         .map(param => {
           // Handle self parameters
           if (param === 'self' || param === '&self' || param === '&mut self') {
@@ -233,6 +785,33 @@ export class RustAnalyzer {
           }
           return param;
         });
+      const parameterTypes = rawParams.map(param => {
+        const colonIndex = param.indexOf(':');
+        return colonIndex > 0 ? param.substring(colonIndex + 1).trim() : '';
+      });
+      const parameterTypeTrees = parameterTypes.map(type => type ? parseRustType(type, this.maxTypeDepth) : null);
+      const returnTypeTree = returnType ? parseRustType(returnType, this.maxTypeDepth) : undefined;
+      const receiver = this.parseReceiver(rawParams[0]);
+      const parameterBounds = this.parseParameterBounds(generics, whereClause, parameterTypes);
+      const genericBounds = this.parseGenericBounds(generics, whereClause);
+      const prelude = this.extractPrelude(content, match.index + match[0].search(/\bfn\s/));
+      // The header with the cleaned parameter list in place of the written one
+      const paramsEnd = match[0].length - (match[10] || '').length - 1;
+      const header = match[0].substring(0, paramsEnd - params.length) + rawParams.join(', ') + match[0].substring(paramsEnd);
+      const signature = header.trim().replace(/\s+/g, ' ').replace(/\(\s+/g, '(').replace(/,?\s*\)/, ')');
+
+      // Bodies are only extracted when an enabled pass reads them, and never for a public API survey
+      const analyses = this.publicOnly ? new Set<RustAnalysis>() : this.enabledAnalyses;
+      const hasBody = this.hasFunctionBody(content, match.index + match[0].length);
+      const body = hasBody && analyses.size > 0 ? this.extractFunctionBody(content, match.index + match[0].length) : undefined;
+      const normalizedBody = body !== undefined && analyses.has('duplicates') ? this.normalizeBody(body) : undefined;
+      const bodyStart = content.indexOf('{', match.index + match[0].length) + 1;
+      const bodyLine = body !== undefined ? content.substring(0, bodyStart).split('\n').length : startLine;
+      const callGraph = body !== undefined && analyses.has('call_graph');
+      const callSites = callGraph ? this.parseCallSites(body!, bodyLine) : [];
+      this.parseStats.functions++;
+      if (body !== undefined) this.parseStats.bodiesExtracted++;
+      if (callGraph) this.parseStats.callGraphScans++;
 
       functions.push({
         name: functionName,
@@ -242,54 +821,276 @@ export class RustAnalyzer {
         returnType,
         isAsync,
         isPublic,
-        isUnsafe
+        isUnsafe,
+        isConst,
+        abi: foreignBlock ? foreignBlock.abi : match[5] !== undefined ? match[6] ?? 'C' : undefined,
+        isForeign: foreignBlock !== undefined,
+        signature,
+        parameterTypes,
+        parameterTypeTrees,
+        returnTypeTree,
+        truncatedType: [...parameterTypeTrees, returnTypeTree].some(type => type && isTruncatedType(type)),
+        receiver: receiver.kind,
+        receiverMutable: receiver.mutable,
+        borrowsFrom: this.parseReturnBorrows(parameters, rawParams, returnType, receiver.kind),
+        parameterBounds,
+        typeParameters: this.parseTypeParameters(generics),
+        genericParams: this.parseGenericParams(generics),
+        genericBounds,
+        hasBody,
+        bodyHash: normalizedBody !== undefined
+          ? crypto.createHash('md5').update(normalizedBody).digest('hex').substring(0, 16)
+          : undefined,
+        bodySize: normalizedBody?.length,
+        complexity: body !== undefined ? this.cyclomaticComplexity(body) : undefined,
+        nestingDepth: body !== undefined ? this.nestingDepth(body) : undefined,
+        callSites,
+        calls: callSites.map(site => site.call),
+        awaitedCalls: body !== undefined && analyses.has('await_flow') ? this.parseAwaitedCalls(body) : [],
+        locksAcrossAwait: body !== undefined && analyses.has('await_flow') ? this.parseLocksAcrossAwait(body, bodyLine) : [],
+        typeMentions: callGraph ? this.parseTypeMentions(body!) : [],
+        discardedCalls: body !== undefined && analyses.has('discarded_results') ? this.parseDiscardedCalls(body, bodyLine) : [],
+        shadowedBindings: body !== undefined && analyses.has('shadowing') ? this.parseShadowedBindings(body, bodyLine) : [],
+        argumentTypes: callGraph ? this.parseArgumentTypes(body!, bodyLine, rawParams) : [],
+        fieldAccesses: callGraph ? this.parseFieldAccesses(body!, rawParams) : [],
+        fieldMentions: callGraph ? this.parseFieldMentions(body!) : [],
+        errConstructions: callGraph ? this.parseErrConstructions(body!) : [],
+        methodChains: callGraph ? this.parseMethodChains(body!, bodyLine) : [],
+        callSequences: callGraph ? this.parseCallSequences(body!, bodyLine) : [],
+        literals: body !== undefined && analyses.has('literals') ? this.parseLiterals(body, bodyLine) : [],
+        panicSites: body !== undefined && analyses.has('panics') ? this.parsePanicSites(body, bodyLine) : [],
+        cloneSites: body !== undefined && analyses.has('clones') ? this.parseCloneSites(body, bodyLine) : [],
+        readOnlyParameters: body !== undefined && analyses.has('clones') ? this.parseReadOnlyParameters(body, rawParams) : [],
+        matchSites: body !== undefined && analyses.has('matches') ? this.parseMatchSites(body, bodyLine) : [],
+        constEligibility: body !== undefined && analyses.has('const_fn')
+          ? this.parseConstEligibility(body, callGraph ? callSites : this.parseCallSites(body, bodyLine))
+          : undefined,
+        attributes: prelude.attributes,
+        visibility: this.normalizeVisibility(match[2]),
+        docComment: prelude.docComment
       });
     }
 
     return functions;
   }
 
+  private findExternBlocks(content: string): Array<{ abi: string; open: number; close: number }> {
+    // `extern "C" { .. }` and `unsafe extern "system" { .. }`; the ABI defaults to "C"
+    const code = this.maskNonCode(content);
+    const blocks: Array<{ abi: string; open: number; close: number }> = [];
+    const blockRegex = /^[ \t]*(?:unsafe\s+)?extern\s*(?:"([^"]*)"\s*)?\{/gm;
+    let match;
+
+    while ((match = blockRegex.exec(content)) !== null) {
+      const open = match.index + match[0].length - 1;
+      if (code[open] !== '{') continue; // Inside a comment or string
+      blocks.push({ abi: match[1] ?? 'C', open, close: this.findClosingParen(code, open, '{', '}') });
+    }
+    return blocks;
+  }
+
+  private async parseIncludes(
+    content: string,
+    filePath: string,
+    structure: RustCodeStructure,
+    depth: number
+  ): Promise<NonNullable<RustCodeStructure['includes']>> {
+    const code = this.maskNonCode(content);
+    const includeRegex = /(?<![A-Za-z0-9_])(?:(?:core|std)::)?(include|include_str|include_bytes)!\s*([({[])/g;
+    const closers: Record<string, string> = { '(': ')', '{': '}', '[': ']' };
+    const includes: NonNullable<RustCodeStructure['includes']> = [];
+    let match;
+
+    while ((match = includeRegex.exec(code)) !== null) {
+      const open = match.index + match[0].length - 1;
+      const close = this.findClosingParen(code, open, match[2], closers[match[2]]);
+      const startLine = content.substring(0, match.index).split('\n').length;
+      const argument = content.substring(open + 1, close).trim();
+      const literal = argument.match(/^"((?:[^"\\]|\\.)*)"$/)?.[1];
+      const enclosing = structure.functions
+        .filter(func => func.hasBody && func.startLine <= startLine && startLine <= func.endLine)
+        .sort((a, b) => b.startLine - a.startLine)[0];
+      const include: NonNullable<RustCodeStructure['includes']>[number] = {
+        macro: match[1] as 'include' | 'include_str' | 'include_bytes',
+        argument,
+        ...(literal !== undefined ? { literal } : {}),
+        modulePath: this.getModulePath(startLine, structure.modules),
+        startLine,
+        endLine: content.substring(0, close).split('\n').length,
+        function: enclosing?.name
+      };
+
+      // Only an item-level `include!` of a literal path can be followed; `env!("OUT_DIR")` is unknown here
+      if (include.macro === 'include' && literal !== undefined && !enclosing && this.followIncludes && depth < MAX_INCLUDE_DEPTH) {
+        const includedPath = resolve(dirname(filePath), literal);
+        const source = await this.readSource(includedPath).catch(() => undefined);
+        if (source !== undefined) include.items = await this.parseCode(source, includedPath, depth + 1);
+      }
+      includes.push(include);
+    }
+    return includes;
+  }
+
+  private parseInlineAsm(content: string, functions: RustCodeStructure['functions']): NonNullable<RustCodeStructure['inlineAsm']> {
+    const code = this.maskNonCode(content);
+    const asmRegex = /(?<![A-Za-z0-9_])(?:(?:core|std)::arch::)?(asm|global_asm|naked_asm)!\s*([({[])/g;
+    const closers: Record<string, string> = { '(': ')', '{': '}', '[': ']' };
+    const invocations: NonNullable<RustCodeStructure['inlineAsm']> = [];
+    let match;
+
+    while ((match = asmRegex.exec(code)) !== null) {
+      const open = match.index + match[0].length - 1;
+      const close = this.findClosingParen(code, open, match[2], closers[match[2]]);
+      const startLine = content.substring(0, match.index).split('\n').length;
+      const enclosing = functions
+        .filter(func => func.hasBody && func.startLine <= startLine && startLine <= func.endLine)
+        .sort((a, b) => b.startLine - a.startLine)[0];
+      invocations.push({
+        macro: match[1],
+        startLine,
+        endLine: content.substring(0, close).split('\n').length,
+        function: enclosing?.name
+      });
+    }
+    return invocations;
+  }
+
+  private parseEnvReads(content: string, functions: RustCodeStructure['functions']): NonNullable<RustCodeStructure['envReads']> {
+    // Only paths through `env` are recognized; a bare `var("PORT")` after `use std::env::var` is too ambiguous
+    const code = this.maskNonCode(content);
+    const envRegex = /(?<![A-Za-z0-9_:])(?:(?:(?:::)?(?:std|core)::)?(env|option_env)!\s*([({[])|(?:(?:::)?std::)?env::(var|var_os)\s*(\())/g;
+    const closers: Record<string, string> = { '(': ')', '{': '}', '[': ']' };
+    const reads: NonNullable<RustCodeStructure['envReads']> = [];
+    let match;
+
+    while ((match = envRegex.exec(code)) !== null) {
+      const opener = match[2] ?? match[4];
+      const open = match.index + match[0].length - 1;
+      const close = this.findClosingParen(code, open, opener, closers[opener]);
+      // `env!` takes an optional error message after the name; commas inside strings do not split
+      const nameLength = this.splitTopLevel(code.substring(open + 1, close))[0].length;
+      const argument = content.substring(open + 1, open + 1 + nameLength).trim();
+      const variable = argument.match(/^"((?:[^"\\]|\\.)*)"$/)?.[1];
+      const startLine = content.substring(0, match.index).split('\n').length;
+      const enclosing = functions
+        .filter(func => func.hasBody && func.startLine <= startLine && startLine <= func.endLine)
+        .sort((a, b) => b.startLine - a.startLine)[0];
+      const access = (match[1] ?? match[3]) as NonNullable<RustCodeStructure['envReads']>[number]['access'];
+      reads.push({
+        access,
+        compileTime: access === 'env' || access === 'option_env',
+        ...(variable !== undefined ? { variable } : {}),
+        argument,
+        startLine,
+        function: enclosing?.name
+      });
+    }
+    return reads;
+  }
+
   private parseStructs(content: string, lines: string[]): Array<{
     name: string;
     startLine: number;
     endLine: number;
     fields: string[];
+    fieldTypes?: string[];
+    fieldTypeTrees?: RustType[];
+    truncatedType?: boolean;
+    tupleFieldTypes?: string[];
+    reprTransparent?: boolean;
+    wrappedType?: string;
+    fieldVisibility?: string[];
+    serdeNames?: Array<string | null>;
+    typeParameters?: string[];
+    genericParams?: RustGenericParam[];
     traits: string[];
     derives: string[];
+    isPublic?: boolean;
+    attributes?: RustAttribute[];
+    visibility?: string;
+    docComment?: string;
   }> {
     const structs: Array<{
       name: string;
       startLine: number;
       endLine: number;
       fields: string[];
+      fieldTypes?: string[];
+      fieldTypeTrees?: RustType[];
+      truncatedType?: boolean;
+      tupleFieldTypes?: string[];
+      reprTransparent?: boolean;
+      wrappedType?: string;
+      fieldVisibility?: string[];
+      serdeNames?: Array<string | null>;
+      typeParameters?: string[];
+      genericParams?: RustGenericParam[];
       traits: string[];
       derives: string[];
+      isPublic?: boolean;
+      attributes?: RustAttribute[];
+      visibility?: string;
+      docComment?: string;
     }> = [];
 
     // Match struct definitions
-    const structRegex = /^(\s*)(?:#\[derive\([^)]+\)\]\s*)?(?:(pub(?:\([^)]*\))?)\s+)?struct\s+([a-zA-Z_][a-zA-Z0-9_]*)/gm;
+    const structRegex = new RegExp(String.raw`^(\s*)(?:#\[derive\([^)]+\)\]\s*)?(?:(pub(?:\([^)]*\))?)\s+)?struct\s+(${IDENT})`, 'gmu');
     let match;
 
     while ((match = structRegex.exec(content)) !== null) {
-      const structName = match[3];
+      const isPublic = match[2] !== undefined;
+      const structName = this.canonicalName(match[3]);
       const startLine = content.substring(0, match.index).split('\n').length;
       
-      // Find derive attributes
-      const derives = this.extractDerives(content, match.index);
+      // Find attributes, including derives
+      const keywordIndex = match.index + match[0].search(new RegExp(String.raw`\bstruct\s+${IDENT}$`, 'u'));
+      const { attributes, docComment } = this.extractPrelude(content, keywordIndex);
+      const derives = attributeArgs(attributes, 'derive').map(arg => arg.value);
       
       // Find struct body and fields
       const structBody = this.extractStructBody(content, match.index);
       const fields = this.parseStructFields(structBody);
+      const fieldTypes = this.parseStructFieldTypes(structBody);
+      const fieldTypeTrees = fieldTypes.map(type => parseRustType(type, this.maxTypeDepth));
+      const fieldVisibility = this.parseStructFieldVisibility(structBody);
+      const serdeNames = this.resolveSerdeNames(fields, attributes, this.parseStructFieldAttributes(structBody));
       
       const endLine = this.findStructEndLine(content, match.index, lines, startLine);
+      const genericsStart = match.index + match[0].length + (content.substring(match.index + match[0].length).match(/^\s*/)?.[0].length || 0);
+      const generics = content[genericsStart] === '<'
+        ? content.substring(genericsStart + 1, this.findClosingParen(content, genericsStart, '<', '>'))
+        : undefined;
+      const afterGenerics = generics !== undefined ? this.findClosingParen(content, genericsStart, '<', '>') + 1 : genericsStart;
+      const tupleStart = afterGenerics + (content.substring(afterGenerics).match(/^\s*/)?.[0].length || 0);
+      const tupleFieldTypes = content[tupleStart] === '('
+        ? this.parseTupleFieldTypes(content.substring(tupleStart + 1, this.findClosingParen(content, tupleStart)))
+        : undefined;
+      const reprTransparent = attributeArgs(attributes, 'repr').some(arg => arg.value === 'transparent');
+      // Zero-sized markers do not count against a newtype's single field
+      const dataFields = (tupleFieldTypes || (reprTransparent ? fieldTypes : []))
+        .filter(type => !/^(?:(?:std|core)::marker::)?PhantomData\s*</.test(type));
 
       structs.push({
         name: structName,
         startLine,
         endLine,
         fields,
+        fieldTypes,
+        fieldTypeTrees,
+        truncatedType: fieldTypeTrees.some(isTruncatedType),
+        tupleFieldTypes,
+        reprTransparent,
+        wrappedType: dataFields.length === 1 ? dataFields[0] : undefined,
+        fieldVisibility,
+        serdeNames,
+        typeParameters: this.parseTypeParameters(generics),
+        genericParams: this.parseGenericParams(generics),
         traits: [], // Will be populated by impl analysis
-        derives
+        derives,
+        isPublic,
+        attributes,
+        visibility: this.normalizeVisibility(match[2]),
+        docComment
       });
     }
 
@@ -301,48 +1102,145 @@ export class RustAnalyzer {
     startLine: number;
     endLine: number;
     methods: string[];
+    defaultMethods?: string[];
     associatedTypes: string[];
+    associatedTypeBounds?: Record<string, string[]>;
+    associatedTypeGenerics?: Record<string, RustGenericParam[]>;
+    supertraits?: string[];
+    attributes?: RustAttribute[];
+    visibility?: string;
+    docComment?: string;
   }> {
     const traits: Array<{
       name: string;
       startLine: number;
       endLine: number;
       methods: string[];
+      defaultMethods?: string[];
       associatedTypes: string[];
+      associatedTypeBounds?: Record<string, string[]>;
+      associatedTypeGenerics?: Record<string, RustGenericParam[]>;
+      supertraits?: string[];
+      attributes?: RustAttribute[];
+      visibility?: string;
+      docComment?: string;
     }> = [];
 
     // Match trait definitions
-    const traitRegex = /^(\s*)(?:(pub(?:\([^)]*\))?)\s+)?trait\s+([a-zA-Z_][a-zA-Z0-9_]*)/gm;
+    const traitRegex = new RegExp(String.raw`^(\s*)(?:(pub(?:\([^)]*\))?)\s+)?trait\s+(${IDENT})`, 'gmu');
     let match;
 
     while ((match = traitRegex.exec(content)) !== null) {
-      const traitName = match[3];
+      // Trait aliases have no body; parseTraitAliases covers them
+      if (TRAIT_ALIAS_TAIL.test(content.substring(match.index + match[0].length))) continue;
+      const traitName = this.canonicalName(match[3]);
       const startLine = content.substring(0, match.index).split('\n').length;
       const endLine = this.findTraitEndLine(content, match.index, lines, startLine);
       
       // Extract trait methods and associated types
       const traitBody = this.extractTraitBody(content, match.index, endLine);
       const methods = this.parseTraitMethods(traitBody);
+      const defaultMethods = this.parseTraitDefaultMethods(traitBody);
       const associatedTypes = this.parseAssociatedTypes(traitBody);
+      const supertraits = this.parseSupertraits(content, match.index + match[0].length);
+      const { attributes, docComment } = this.extractPrelude(content, match.index + match[0].search(/\btrait\s/));
 
       traits.push({
         name: traitName,
         startLine,
         endLine,
         methods,
-        associatedTypes
+        defaultMethods,
+        associatedTypes,
+        associatedTypeBounds: this.parseAssociatedTypeBounds(traitBody, associatedTypes),
+        associatedTypeGenerics: this.parseAssociatedTypeGenerics(traitBody),
+        supertraits,
+        attributes,
+        visibility: this.normalizeVisibility(match[2]),
+        docComment
       });
     }
 
     return traits;
   }
 
+  private parseTraitAliases(content: string): NonNullable<RustCodeStructure['traitAliases']> {
+    const aliases: NonNullable<RustCodeStructure['traitAliases']> = [];
+    const aliasRegex = new RegExp(String.raw`^(\s*)(?:(pub(?:\([^)]*\))?)\s+)?trait\s+(${IDENT})` + TRAIT_ALIAS_TAIL.source.replace(/^\^/, ''), 'gmu');
+    const code = this.maskNonCode(content);
+    let match;
+
+    while ((match = aliasRegex.exec(code)) !== null) {
+      const start = match.index + match[1].length;
+      const bounds = match[5].trim().replace(/\s+/g, ' ');
+      const { attributes, docComment } = this.extractPrelude(content, match.index + match[0].search(/\btrait\s/));
+      aliases.push({
+        name: this.canonicalName(match[3]),
+        startLine: content.substring(0, start).split('\n').length,
+        endLine: content.substring(0, match.index + match[0].length).split('\n').length,
+        traits: this.traitNames(bounds.split(/\bwhere\b/)[0]),
+        bounds,
+        genericParams: this.parseGenericParams(match[4]),
+        attributes,
+        visibility: this.normalizeVisibility(match[2]),
+        docComment
+      });
+    }
+
+    return aliases;
+  }
+
+  private parseEnums(content: string): RustCodeStructure['enums'] {
+    const enums: RustCodeStructure['enums'] = [];
+    const enumRegex = new RegExp(String.raw`^(\s*)(?:(pub(?:\([^)]*\))?)\s+)?enum\s+(${IDENT})[^{;]*\{`, 'gmu');
+    const code = this.maskNonCode(content);
+    let match;
+
+    while ((match = enumRegex.exec(code)) !== null) {
+      const open = match.index + match[0].length - 1;
+      const close = this.findClosingParen(code, open, '{', '}');
+      const startLine = content.substring(0, match.index).split('\n').length;
+      const { attributes, docComment } = this.extractPrelude(content, match.index + match[0].search(/\benum\s/));
+
+      enums.push({
+        name: this.canonicalName(match[3]),
+        startLine,
+        endLine: content.substring(0, close).split('\n').length,
+        variants: this.parseEnumVariants(code.substring(open + 1, close), content.substring(0, open + 1).split('\n').length),
+        derives: attributeArgs(attributes, 'derive').map(arg => arg.value),
+        attributes,
+        visibility: this.normalizeVisibility(match[2]),
+        docComment
+      });
+    }
+
+    return enums;
+  }
+
+  private parseEnumVariants(body: string, bodyLine: number): Array<{ name: string; line: number }> {
+    // Variants are the top-level comma-separated entries; attributes on them are skipped
+    const variants: Array<{ name: string; line: number }> = [];
+    let offset = 0;
+    for (const part of this.splitTopLevel(body)) {
+      const variant = part.match(new RegExp(String.raw`^\s*(?:#\[[^\]]*\]\s*)*(${IDENT})`, 'u'));
+      if (variant) {
+        const nameOffset = offset + variant[0].length - variant[1].length;
+        variants.push({ name: this.canonicalName(variant[1]), line: bodyLine + body.substring(0, nameOffset).split('\n').length - 1 });
+      }
+      offset += part.length + 1;
+    }
+    return variants;
+  }
+
   private parseImpls(content: string, lines: string[]): Array<{
     target: string;
     trait?: string;
     startLine: number;
     endLine: number;
     methods: string[];
+    associatedTypes?: Array<{ name: string; type: string; line: number }>;
+    genericParams?: RustGenericParam[];
+    genericBounds?: Array<{ parameter: string; bounds: string[]; source: 'inline' | 'where' }>;
   }> {
     const impls: Array<{
       target: string;
@@ -350,28 +1248,44 @@ export class RustAnalyzer {
       startLine: number;
       endLine: number;
       methods: string[];
+      associatedTypes?: Array<{ name: string; type: string; line: number }>;
+      genericParams?: RustGenericParam[];
+      genericBounds?: Array<{ parameter: string; bounds: string[]; source: 'inline' | 'where' }>;
     }> = [];
 
     // Match impl blocks
-    const implRegex = /^(\s*)impl(?:\s*<[^>]*>)?\s+(?:([a-zA-Z_][a-zA-Z0-9_:<>]*)\s+for\s+)?([a-zA-Z_][a-zA-Z0-9_:<>]*)/gm;
+    const implRegex = /^(\s*)(?:unsafe\s+)?impl(?:\s*<((?:[^<>]|<(?:[^<>]|<[^<>]*>)*>)*)>)?\s+(?:((?:r#)?[\p{XID_Start}_][\p{XID_Continue}:<>#]*)\s+for\s+)?((?:r#)?[\p{XID_Start}_][\p{XID_Continue}:<>#]*)/gmu;
     let match;
 
     while ((match = implRegex.exec(content)) !== null) {
-      const trait = match[2];
-      const target = match[3];
+      const trait = match[3] && this.canonicalName(match[3]);
+      const target = this.canonicalName(match[4]);
+      // A where clause sits between the impl header and its body
+      const bodyStart = content.indexOf('{', match.index + match[0].length);
+      const header = content.substring(match.index + match[0].length, bodyStart < 0 ? content.length : bodyStart);
+      const whereClause = header.match(/\bwhere\b([\s\S]*)$/)?.[1];
+      const genericParams = this.parseGenericParams(match[2]);
       const startLine = content.substring(0, match.index).split('\n').length;
       const endLine = this.findImplEndLine(content, match.index, lines, startLine);
       
       // Extract impl methods
       const implBody = this.extractImplBody(content, match.index, endLine);
       const methods = this.parseImplMethods(implBody);
+      const associatedTypes = this.parseAssociatedTypeBindings(implBody, startLine);
+      const defaultValues = trait && !this.publicOnly && /(?:^|::)Default$/.test(trait) ? this.parseDefaultValues(implBody, startLine, target) : undefined;
 
       impls.push({
         target,
         trait,
         startLine,
         endLine,
-        methods
+        methods,
+        associatedTypes,
+        ...(defaultValues ? { defaultValues } : {}),
+        genericParams,
+        // Bounds on the type's own parameters (`where Self: Sized`) do not gate the impl
+        genericBounds: this.parseGenericBounds(match[2], whereClause)
+          .filter(bound => genericParams.some(param => param.kind === 'type' && param.name === bound.parameter))
       });
     }
 
@@ -392,11 +1306,11 @@ export class RustAnalyzer {
     }> = [];
 
     // Match macro definitions
-    const macroRegex = /^(\s*)(?:(pub(?:\([^)]*\))?)\s+)?macro_rules!\s+([a-zA-Z_][a-zA-Z0-9_]*)/gm;
+    const macroRegex = new RegExp(String.raw`^(\s*)(?:(pub(?:\([^)]*\))?)\s+)?macro_rules!\s+(${IDENT})`, 'gmu');
     let match;
 
     while ((match = macroRegex.exec(content)) !== null) {
-      const macroName = match[3];
+      const macroName = this.canonicalName(match[3]);
       const startLine = content.substring(0, match.index).split('\n').length;
       const endLine = this.findMacroEndLine(content, match.index, lines, startLine);
 
@@ -416,21 +1330,23 @@ export class RustAnalyzer {
     startLine: number;
     endLine: number;
     isPublic: boolean;
+    attributes?: RustAttribute[];
   }> {
     const modules: Array<{
       name: string;
       startLine: number;
       endLine: number;
       isPublic: boolean;
+      attributes?: RustAttribute[];
     }> = [];
 
     // Match module definitions
-    const modRegex = /^(\s*)(?:(pub(?:\([^)]*\))?)\s+)?mod\s+([a-zA-Z_][a-zA-Z0-9_]*)/gm;
+    const modRegex = new RegExp(String.raw`^(\s*)(?:(pub(?:\([^)]*\))?)\s+)?mod\s+(${IDENT})`, 'gmu');
     let match;
 
     while ((match = modRegex.exec(content)) !== null) {
       const isPublic = match[2] !== undefined;
-      const moduleName = match[3];
+      const moduleName = this.canonicalName(match[3]);
       const startLine = content.substring(0, match.index).split('\n').length;
       const endLine = this.findModuleEndLine(content, match.index, lines, startLine);
 
@@ -438,13 +1354,165 @@ export class RustAnalyzer {
         name: moduleName,
         startLine,
         endLine,
-        isPublic
+        isPublic,
+        attributes: this.extractAttributes(content, match.index + match[0].search(/\bmod\s/))
       });
     }
 
     return modules;
   }
 
+  private parseConstants(
+    content: string,
+    modules: RustCodeStructure['modules'],
+    impls: RustCodeStructure['impls']
+  ): RustCodeStructure['constants'] {
+    const constants: RustCodeStructure['constants'] = [];
+
+    // Match const/static items; `const fn` is excluded because a type annotation is required
+    const constRegex = new RegExp(String.raw`^(\s*)(?:(pub(?:\([^)]*\))?)\s+)?(const|static)\s+(mut\s+)?(${IDENT})\s*:\s*([^=;]+?)\s*(?:=\s*([^;]+))?;`, 'gmu');
+    let match;
+
+    while ((match = constRegex.exec(content)) !== null) {
+      const visibility = match[2] || 'private';
+      const kind = match[3] as 'const' | 'static';
+      const rawValue = match[7]?.trim();
+      const startLine = content.substring(0, match.index + match[1].length).split('\n').length;
+      const endLine = content.substring(0, match.index + match[0].length).split('\n').length;
+
+      // Innermost impl block wins over module for nesting
+      const enclosingImpl = impls
+        .filter(impl => impl.startLine < startLine && impl.endLine >= startLine)
+        .sort((a, b) => b.startLine - a.startLine)[0];
+
+      constants.push({
+        name: this.canonicalName(match[5]),
+        kind,
+        type: match[6].trim(),
+        value: rawValue && this.isSimpleLiteral(rawValue) ? rawValue : undefined,
+        visibility,
+        isPublic: match[2] !== undefined,
+        isMutable: match[4] !== undefined,
+        modulePath: this.getModulePath(startLine, modules),
+        implTarget: enclosingImpl?.target,
+        startLine,
+        endLine
+      });
+    }
+
+    return constants;
+  }
+
+  private parseRoutes(content: string, functions: RustCodeStructure['functions']): RustCodeStructure['routes'] {
+    const routes: RustCodeStructure['routes'] = [];
+
+    // actix-web builder style: .route("/users", web::post().to(create_user_handler))
+    const builderRegex = /\.route\(\s*"([^"]+)"\s*,\s*web::(get|post|put|delete|patch|head)\(\)\s*\.to\(\s*([a-zA-Z_][a-zA-Z0-9_:]*)\s*\)/g;
+    let match;
+
+    while ((match = builderRegex.exec(content)) !== null) {
+      routes.push({
+        method: match[2].toUpperCase(),
+        path: match[1],
+        handler: match[3].split('::').pop() || match[3],
+        line: content.substring(0, match.index).split('\n').length
+      });
+    }
+
+    // Attribute style: #[get("/users")] async fn list_users(...)
+    for (const func of functions) {
+      for (const attribute of func.attributes || []) {
+        const method = attribute.name.split('::').pop()!;
+        const path = attribute.args.find(arg => arg.key === undefined && !arg.args)?.value;
+        if (!ROUTE_METHODS.has(method) || !path?.startsWith('/')) continue;
+
+        routes.push({
+          method: method.toUpperCase(),
+          path,
+          handler: func.name,
+          line: attribute.line
+        });
+      }
+    }
+
+    return routes;
+  }
+
+  private isSimpleLiteral(value: string): boolean {
+    return /^-?\d[\d_]*(?:\.\d[\d_]*)?(?:[eE][+-]?\d+)?(?:[iu](?:8|16|32|64|128|size)|f32|f64)?$/.test(value) ||
+      /^-?0[xob][0-9a-fA-F_]+(?:[iu](?:8|16|32|64|128|size))?$/.test(value) ||
+      /^b?"(?:[^"\\]|\\.)*"$/.test(value) ||
+      /^b?'(?:[^'\\]|\\.)+'$/.test(value) ||
+      value === 'true' || value === 'false';
+  }
+
+  private applyCfg(
+    item: { cfg?: string[]; inactive?: boolean },
+    attributes: RustAttribute[] | undefined,
+    line: number,
+    modules: RustCodeStructure['modules']
+  ): void {
+    const enclosing = modules
+      .filter(mod => mod.startLine < line && mod.endLine >= line)
+      .sort((a, b) => a.startLine - b.startLine);
+    const predicates = [...enclosing.flatMap(mod => attributeArgs(mod.attributes, 'cfg')), ...attributeArgs(attributes, 'cfg')];
+    if (predicates.length === 0) return;
+
+    item.cfg = predicates.map(formatAttributeArg);
+    item.inactive = predicates.some(predicate => evaluateCfg(predicate, this.cfgOptions) === false);
+  }
+
+  private canonicalName(identifier: string): string {
+    // `r#type` names the item `type`; raw prefixes inside paths (`a::r#match::B`) are dropped too
+    return identifier.replace(/(^|::|<|\s)r#/g, '$1');
+  }
+
+  private normalizeVisibility(visibility: string | undefined): string {
+    // `pub ( crate )` -> `pub(crate)`; no modifier means private
+    return visibility ? visibility.replace(/\s+/g, '') : 'private';
+  }
+
+  private parseReexports(content: string, modules: RustCodeStructure['modules']): NonNullable<RustCodeStructure['reexports']> {
+    const reexports: NonNullable<RustCodeStructure['reexports']> = [];
+    const reexportRegex = /^\s*(pub(?:\([^)]*\))?)\s+use\s+([^;]+);/gm;
+    let match;
+
+    while ((match = reexportRegex.exec(content)) !== null) {
+      const line = content.substring(0, match.index + match[0].indexOf(match[1])).split('\n').length;
+      const modulePath = this.getModulePath(line, modules);
+      for (const entry of expandUseTree(match[2].replace(/^::/, ''))) {
+        // `pub use Trait as _` exports nothing
+        if (entry.name === '_') continue;
+        reexports.push({ path: entry.path, name: entry.name, visibility: match[1].replace(/\s+/g, ''), modulePath, line });
+      }
+    }
+
+    return reexports;
+  }
+
+  private parseGlobImports(content: string, modules: RustCodeStructure['modules']): NonNullable<RustCodeStructure['globImports']> {
+    const globs: NonNullable<RustCodeStructure['globImports']> = [];
+    const useRegex = /^(\s*)use\s+([^;]+);/gm;
+    let match;
+
+    while ((match = useRegex.exec(content)) !== null) {
+      const line = content.substring(0, match.index + match[1].length).split('\n').length;
+      const modulePath = this.getModulePath(line, modules);
+      for (const entry of expandUseTree(match[2].replace(/^::/, ''))) {
+        if (entry.glob) globs.push({ path: entry.path, modulePath, line });
+      }
+    }
+
+    return globs;
+  }
+
+  private getModulePath(line: number, modules: RustCodeStructure['modules']): string[] {
+    return modules
+      .filter(mod => mod.startLine < line && mod.endLine >= line)
+      .sort((a, b) => a.startLine - b.startLine)
+      .map(mod => mod.name);
+  }
+
   private detectFramework(content: string, filePath: string): string | undefined {
     const frameworks: Array<{ name: string; patterns: RegExp[] }> = [
       {
@@ -576,19 +1644,80 @@ export class RustAnalyzer {
     return undefined;
   }
 
-  private extractDerives(content: string, startIndex: number): string[] {
-    const beforeStruct = content.substring(Math.max(0, startIndex - 200), startIndex);
-    const deriveMatch = beforeStruct.match(/#\[derive\(([^)]+)\)\]/);
-    if (deriveMatch) {
-      return deriveMatch[1].split(',').map(d => d.trim());
+  private extractAttributes(content: string, keywordIndex: number): RustAttribute[] {
+    return this.extractPrelude(content, keywordIndex).attributes;
+  }
+
+  private extractPrelude(content: string, keywordIndex: number): { attributes: RustAttribute[]; docComment?: string } {
+    // Walk back from the item keyword over qualifiers, comments and the attributes directly above it
+    const spansByEnd = this.getAttributeSpans(content);
+    const attributes: RustAttribute[] = [];
+    const docLines: string[] = [];
+    let before = content.substring(0, keywordIndex);
+
+    while (true) {
+      // Only the tail can hold qualifiers; keep the regex off the rest of the file
+      const tail = before.slice(-200);
+      const qualifiers = tail.match(/(?:\b(?:pub(?:\s*\([^)]*\))?|async|unsafe|const|default|extern(?:\s+"[^"]*")?)\s*)+$/);
+      before = before.substring(0, before.length - (qualifiers ? qualifiers[0].length : 0)).trimEnd();
+
+      const lastLine = before.substring(before.lastIndexOf('\n') + 1).trim();
+      if (lastLine.startsWith('//')) {
+        // `///` is an outer doc comment, `////` and plain `//` are not
+        if (/^\/\/\/(?!\/)/.test(lastLine)) {
+          docLines.unshift(lastLine.substring(3).replace(/^ /, ''));
+        }
+        before = before.substring(0, Math.max(0, before.lastIndexOf('\n')));
+        continue;
+      }
+
+      if (before.endsWith('*/')) {
+        const blockStart = before.lastIndexOf('/*');
+        if (blockStart === -1) break;
+        const block = before.substring(blockStart, before.length);
+        if (/^\/\*\*(?![*/])/.test(block)) {
+          const text = block.substring(3, block.length - 2).split('\n')
+            .map(line => line.trim().replace(/^\* ?/, ''))
+            .join('\n')
+            .trim();
+          docLines.unshift(...text.split('\n'));
+        }
+        before = before.substring(0, blockStart);
+        continue;
+      }
+
+      const span = before.endsWith(']') ? spansByEnd.get(before.length) : undefined;
+      if (!span) break;
+
+      const attribute = parseAttribute(span.text, content.substring(0, span.start).split('\n').length);
+      attributes.unshift(attribute);
+      if (attribute.name === 'doc' && attribute.value !== undefined) {
+        docLines.unshift(attribute.value);
+      }
+      before = before.substring(0, span.start);
+    }
+
+    const docComment = docLines.join('\n').trim();
+    return { attributes, docComment: docComment || undefined };
+  }
+
+  private getAttributeSpans(content: string): Map<number, RustAttributeSpan> {
+    // Spans, keyed by end index, are shared by every item in the file being parsed
+    let cache = this.attributeSpanCache;
+    if (!cache || cache.content !== content) {
+      cache = { content, spansByEnd: new Map(findAttributeSpans(content).map(span => [span.end, span] as const)) };
+      this.attributeSpanCache = cache;
     }
-    return [];
+    return cache.spansByEnd;
   }
 
   private extractStructBody(content: string, startIndex: number): string {
     const afterStruct = content.substring(startIndex);
     const braceIndex = afterStruct.indexOf('{');
     if (braceIndex === -1) return '';
+    // Tuple and unit structs end at `;` and have no braced body of their own
+    const semicolonIndex = afterStruct.indexOf(';');
+    if (semicolonIndex !== -1 && semicolonIndex < braceIndex) return '';
     
     let braceCount = 0;
     let endIndex = braceIndex;
@@ -606,6 +1735,13 @@ export class RustAnalyzer {
     return afterStruct.substring(braceIndex + 1, endIndex);
   }
 
+  private parseTupleFieldTypes(fieldList: string): string[] {
+    // `pub(crate) u64, #[serde(skip)] PhantomData<T>` -> `u64`, `PhantomData<T>`
+    return this.splitTopLevel(fieldList)
+      .map(field => field.replace(/#\[[^\]]*\]/g, '').replace(/^\s*pub(?:\([^)]*\))?\s+/, '').trim())
+      .filter(field => field);
+  }
+
   private parseStructFields(structBody: string): string[] {
     const fields: string[] = [];
     const lines = structBody.split('\n');
@@ -615,7 +1751,7 @@ export class RustAnalyzer {
       if (trimmed && !trimmed.startsWith('//') && !trimmed.startsWith('#')) {
         const colonIndex = trimmed.indexOf(':');
         if (colonIndex > 0) {
-          const fieldName = trimmed.substring(0, colonIndex).trim();
+          const fieldName = trimmed.substring(0, colonIndex).replace(/^pub(?:\([^)]*\))?\s+/, '').trim();
           if (fieldName) {
             fields.push(fieldName);
           }
@@ -626,36 +1762,1273 @@ export class RustAnalyzer {
     return fields;
   }
 
-  private extractTraitBody(content: string, startIndex: number, endLine: number): string {
-    const lines = content.split('\n');
-    const startLineIndex = content.substring(0, startIndex).split('\n').length - 1;
-    return lines.slice(startLineIndex, endLine).join('\n');
-  }
+  private parseStructFieldTypes(structBody: string): string[] {
+    const fieldTypes: string[] = [];
+    const lines = structBody.split('\n');
 
-  private parseTraitMethods(traitBody: string): string[] {
-    const methods: string[] = [];
-    const fnRegex = /fn\s+([a-zA-Z_][a-zA-Z0-9_]*)/g;
-    let match;
-    
+    // Mirrors parseStructFields line by line so the arrays stay aligned
+    for (const line of lines) {
+      const trimmed = line.trim();
+      if (trimmed && !trimmed.startsWith('//') && !trimmed.startsWith('#')) {
+        const colonIndex = trimmed.indexOf(':');
+        if (colonIndex > 0 && trimmed.substring(0, colonIndex).trim()) {
+          fieldTypes.push(trimmed.substring(colonIndex + 1).replace(/,\s*$/, '').trim());
+        }
+      }
+    }
+
+    return fieldTypes;
+  }
+
+  private parseStructFieldVisibility(structBody: string): string[] {
+    const fieldVisibility: string[] = [];
+
+    // Mirrors parseStructFields
+    for (const line of structBody.split('\n')) {
+      const trimmed = line.trim();
+      if (trimmed && !trimmed.startsWith('//') && !trimmed.startsWith('#')) {
+        const colonIndex = trimmed.indexOf(':');
+        const declaration = colonIndex > 0 ? trimmed.substring(0, colonIndex) : '';
+        if (declaration.replace(/^pub(?:\([^)]*\))?\s+/, '').trim()) {
+          fieldVisibility.push(this.normalizeVisibility(declaration.match(/^pub(?:\s*\([^)]*\))?(?=\s)/)?.[0]));
+        }
+      }
+    }
+
+    return fieldVisibility;
+  }
+
+  private parseStructFieldAttributes(structBody: string): RustAttribute[][] {
+    const fieldAttributes: RustAttribute[][] = [];
+    let pending: RustAttribute[] = [];
+
+    // Mirrors parseStructFields; attribute lines apply to the next field
+    structBody.split('\n').forEach((line, index) => {
+      const trimmed = line.trim();
+      if (trimmed.startsWith('#[')) {
+        for (const span of findAttributeSpans(trimmed)) {
+          pending.push(parseAttribute(span.text, index + 1));
+        }
+      } else if (trimmed && !trimmed.startsWith('//')) {
+        const colonIndex = trimmed.indexOf(':');
+        if (colonIndex > 0 && trimmed.substring(0, colonIndex).replace(/^pub(?:\([^)]*\))?\s+/, '').trim()) {
+          fieldAttributes.push(pending);
+          pending = [];
+        }
+      }
+    });
+
+    return fieldAttributes;
+  }
+
+  private resolveSerdeNames(fields: string[], structAttributes: RustAttribute[], fieldAttributes: RustAttribute[][]): Array<string | null> {
+    const renameAll = attributeArgs(structAttributes, 'serde').find(arg => arg.key === 'rename_all')?.value;
+
+    return fields.map((field, index) => {
+      const serde = attributeArgs(fieldAttributes[index], 'serde');
+      if (serde.some(arg => arg.key === undefined && ['skip', 'skip_serializing', 'flatten'].includes(arg.value))) {
+        return null;
+      }
+      const rename = serde.find(arg => arg.key === 'rename')?.value;
+      if (rename) return rename;
+
+      const identifier = field.replace(/^r#/, '');
+      return renameAll ? this.applyRenameRule(identifier, renameAll) : identifier;
+    });
+  }
+
+  private applyRenameRule(identifier: string, rule: string): string {
+    const words = identifier.split('_').filter(word => word);
+    const capitalize = (word: string) => word.charAt(0).toUpperCase() + word.slice(1);
+
+    switch (rule) {
+      case 'lowercase': return identifier.toLowerCase();
+      case 'UPPERCASE': return identifier.toUpperCase();
+      case 'PascalCase': return words.map(capitalize).join('');
+      case 'camelCase': return words.map((word, i) => i === 0 ? word : capitalize(word)).join('');
+      case 'SCREAMING_SNAKE_CASE': return identifier.toUpperCase();
+      case 'kebab-case': return words.join('-');
+      case 'SCREAMING-KEBAB-CASE': return words.join('-').toUpperCase();
+      default: return identifier; // snake_case and unknown rules keep the Rust name
+    }
+  }
+
+  private hasFunctionBody(content: string, afterSignature: number): boolean {
+    // Trait method declarations end in `;` before any body opens
+    const braceIndex = content.indexOf('{', afterSignature);
+    const semicolonIndex = content.indexOf(';', afterSignature);
+    return braceIndex !== -1 && (semicolonIndex === -1 || braceIndex < semicolonIndex);
+  }
+
+  private extractFunctionBody(content: string, afterSignature: number): string | undefined {
+    if (!this.hasFunctionBody(content, afterSignature)) {
+      return undefined;
+    }
+    const rest = content.substring(afterSignature);
+    const braceIndex = rest.indexOf('{');
+
+    let braceCount = 0;
+    for (let i = braceIndex; i < rest.length; i++) {
+      if (rest[i] === '{') braceCount++;
+      else if (rest[i] === '}') {
+        braceCount--;
+        if (braceCount === 0) {
+          return rest.substring(braceIndex + 1, i);
+        }
+      }
+    }
+    return rest.substring(braceIndex + 1);
+  }
+
+  private nestingDepth(body: string): number {
+    // Struct literals (`User { .. }`, `Self { .. }`) use braces without opening a block. ALL_CAPS names are
+    // constants ending a condition, as in `while n < LIMIT {`
+    const code = this.maskNonCode(body);
+    const braceRegex = new RegExp(String.raw`(\b[A-Z][A-Z0-9_]*[a-z]\p{XID_Continue}*(?:\s*<[^<>{};]*>)?\s*)?\{|\}`, 'gu');
+    const stack: boolean[] = []; // Whether each open brace is a block
+    let depth = 0;
+    let deepest = 0;
+    let match;
+
+    while ((match = braceRegex.exec(code)) !== null) {
+      if (match[0] === '}') {
+        if (stack.pop()) depth--;
+        continue;
+      }
+      const isBlock = match[1] === undefined;
+      stack.push(isBlock);
+      if (isBlock) deepest = Math.max(deepest, ++depth);
+    }
+    return deepest;
+  }
+
+  private cyclomaticComplexity(body: string): number {
+    // Counted over code only; closures and nested functions count towards the enclosing function
+    const code = this.maskNonCode(body);
+    const count = (regex: RegExp) => (code.match(regex) || []).length;
+    const decisions = count(/\b(?:if|while|for)\b/g) + count(/&&|\|\|/g) + count(/[A-Za-z0-9_)\]>]\?/g);
+    const extraArms = Math.max(0, count(/=>/g) - count(/\bmatch\b/g));
+    return 1 + decisions + extraArms;
+  }
+
+  private normalizeBody(body: string): string {
+    return body
+      .replace(/\/\*[\s\S]*?\*\//g, '')
+      .replace(/\/\/.*$/gm, '')
+      .replace(/\s+/g, ' ')
+      .trim();
+  }
+
+  private parseCalls(body: string): string[] {
+    return this.parseCallSites(body, 1).map(site => site.call);
+  }
+
+  private parseCallSites(body: string, bodyLine: number): Array<{ call: string; line: number }> {
+    // Call sites as `name`, `Path::name` or `.method`, each with the first line it appears on;
+    // macros (`name!(`) and tuple-like constructors are skipped
+    const keywords = new Set(['if', 'while', 'match', 'for', 'return', 'in', 'as', 'move', 'loop', 'let', 'else', 'where', 'unsafe', 'async', 'await']);
+    const code = body
+      .replace(/\/\*[\s\S]*?\*\//g, comment => comment.replace(/[^\n]/g, ''))
+      .replace(/\/\/.*$/gm, '')
+      .replace(/"(?:[^"\\]|\\.)*"/g, '""');
+    const callRegex = /(\.\s*)?(?<![A-Za-z0-9_])((?:[A-Za-z_][A-Za-z0-9_]*::)*)([a-z_][A-Za-z0-9_]*)\s*(?:::<[^>]*>\s*)?\(/g;
+    const sites = new Map<string, number>();
+    let match;
+
+    while ((match = callRegex.exec(code)) !== null) {
+      const [, methodDot, path, name] = match;
+      if (keywords.has(name)) continue;
+      // Nested function declarations look like calls
+      if (/\bfn\s+$/.test(code.substring(Math.max(0, match.index - 16), match.index))) continue;
+
+      const call = methodDot ? `.${name}` : `${path}${name}`;
+      if (!sites.has(call)) {
+        sites.set(call, bodyLine + (code.substring(0, match.index).match(/\n/g) || []).length);
+      }
+    }
+
+    return Array.from(sites, ([call, line]) => ({ call, line }));
+  }
+
+  private parseTypeMentions(body: string): string[] {
+    const mentions = new Set(this.maskNonCode(body).match(/(?<![A-Za-z0-9_])[A-Z][A-Za-z0-9_]*/g) || []);
+    mentions.delete('Self');
+    return Array.from(mentions).sort();
+  }
+
+  private parseAwaitedCalls(body: string): string[] {
+    // Calls consumed as futures: `f(x).await`, or passed straight to `spawn(..)`, `block_on(..)`, `join!(..)`
+    const keywords = new Set(['if', 'while', 'match', 'for', 'return', 'in', 'as', 'move', 'loop', 'let', 'else', 'where', 'unsafe', 'async', 'await']);
+    const code = this.maskNonCode(body);
+    const executors: Array<[number, number]> = [];
+    const executorRegex = /(?<![A-Za-z0-9_])(?:spawn|spawn_local|block_on|join|try_join|select|join_all)\s*!?\s*\(/g;
+    let match;
+
+    while ((match = executorRegex.exec(code)) !== null) {
+      const open = match.index + match[0].length - 1;
+      executors.push([open, this.findClosingParen(code, open)]);
+    }
+
+    const callRegex = /(\.\s*)?(?<![A-Za-z0-9_])((?:[A-Za-z_][A-Za-z0-9_]*::)*)([a-z_][A-Za-z0-9_]*)\s*(?:::<[^>]*>\s*)?\(/g;
+    const awaited = new Set<string>();
+
+    while ((match = callRegex.exec(code)) !== null) {
+      const [, methodDot, path, name] = match;
+      if (keywords.has(name)) continue;
+
+      const close = this.findClosingParen(code, match.index + match[0].length - 1);
+      const isAwaited = /^\s*\.\s*await\b/.test(code.substring(close + 1, close + 32));
+      const isSpawned = executors.some(([open, end]) => match!.index > open && match!.index < end);
+      if (isAwaited || isSpawned) {
+        awaited.add(methodDot ? `.${name}` : `${path}${name}`);
+      }
+    }
+
+    return Array.from(awaited);
+  }
+
+  private parseLocksAcrossAwait(body: string, bodyLine: number): RustLockAcrossAwait[] {
+    // `let g = m.lock().unwrap();` keeps a blocking guard until the end of the enclosing block or `drop(g)`;
+    // guards of async locks (`m.lock().await`) are meant to be held across awaits and are skipped
+    const code = this.maskNonCode(body);
+    const lockRegex = new RegExp(String.raw`(?<![\p{XID_Continue}])let\s+(?:mut\s+)?(${IDENT})\s*(?::[^=;]+)?=\s*([^;]*?\.\s*(?:lock|read|write|try_lock|try_read|try_write)\s*\(\s*\))((?:\s*\.\s*(?:unwrap|expect)\s*\([^;]*?\)|\s*\?)*)\s*;`, 'gu');
+    const lineOf = (index: number) => bodyLine + (code.substring(0, index).match(/\n/g) || []).length;
+    const sites: RustLockAcrossAwait[] = [];
+    let match;
+
+    while ((match = lockRegex.exec(code)) !== null) {
+      const [statement, binding, acquire] = match;
+      if (binding === '_') continue;
+      const start = match.index + statement.length;
+
+      // The guard lives until the block holding the `let` closes
+      let depth = 0;
+      let end = code.length;
+      for (let i = start; i < code.length; i++) {
+        if (code[i] === '{') depth++;
+        else if (code[i] === '}' && --depth < 0) {
+          end = i;
+          break;
+        }
+      }
+      const scope = code.substring(start, end);
+      const dropped = scope.search(new RegExp(String.raw`(?<![\p{XID_Continue}])(?:std::mem::|mem::)?drop\s*\(\s*${binding}\s*\)`, 'u'));
+      const awaited = scope.search(/\.\s*await(?![\p{XID_Continue}])/u);
+      if (awaited < 0 || (dropped >= 0 && dropped < awaited)) continue;
+
+      sites.push({
+        binding,
+        acquire: acquire.replace(/\s+/g, ''),
+        line: lineOf(match.index),
+        awaitLine: lineOf(start + awaited)
+      });
+    }
+    return sites;
+  }
+
+  private findClosingParen(code: string, openIndex: number, open = '(', close = ')'): number {
+    let depth = 0;
+    for (let i = openIndex; i < code.length; i++) {
+      if (code[i] === open) depth++;
+      else if (code[i] === close && --depth === 0) return i;
+    }
+    return code.length;
+  }
+
+  private parseParameterBounds(generics: string | undefined, whereClause: string | undefined, parameterTypes: string[]): string[][] {
+    // Generic parameter -> trait names (last path segment), from `<T: A + B>` and `where T: C`
+    const bounds = new Map<string, string[]>();
+    const clauses = [...this.splitTopLevel(generics || ''), ...this.splitTopLevel(whereClause || '')];
+    for (const clause of clauses) {
+      const bound = clause.trim().match(/^([A-Z][A-Za-z0-9_]*)\s*:([\s\S]+)$/);
+      if (bound) {
+        bounds.set(bound[1], [...(bounds.get(bound[1]) || []), ...this.traitNames(bound[2])]);
+      }
+    }
+
+    return parameterTypes.map(type => {
+      const inner = type.replace(/^&\s*(?:'[A-Za-z_][A-Za-z0-9_]*\s+)?(?:mut\s+)?/, '').trim();
+      if (inner.startsWith('impl ')) return this.traitNames(inner.substring(5));
+      return bounds.get(inner) || [];
+    });
+  }
+
+  private parseGenericParams(generics: string | undefined): RustGenericParam[] {
+    // `'a, T: Clone = String, const N: usize = 4`
+    const params: RustGenericParam[] = [];
+    for (const raw of this.splitTopLevel(generics || '')) {
+      const param = raw.trim();
+      if (!param) continue;
+      // A default follows the first `=` outside angle brackets (`Item = u8` in a bound is not one)
+      let depth = 0;
+      let equals = -1;
+      for (let i = 0; i < param.length && equals < 0; i++) {
+        if (param[i] === '<') depth++;
+        else if (param[i] === '>') depth--;
+        else if (param[i] === '=' && depth === 0) equals = i;
+      }
+      const declaration = (equals >= 0 ? param.substring(0, equals) : param).trim();
+      const defaultValue = equals >= 0 ? param.substring(equals + 1).trim() : undefined;
+      const constParam = declaration.match(/^const\s+([A-Za-z_][A-Za-z0-9_]*)\s*:\s*([\s\S]+)$/);
+      if (constParam) {
+        params.push({ name: constParam[1], kind: 'const', type: constParam[2].trim(), defaultValue });
+      } else if (declaration.startsWith("'")) {
+        params.push({ name: declaration.split(':')[0].trim().substring(1), kind: 'lifetime' });
+      } else {
+        params.push({ name: declaration.split(':')[0].trim(), kind: 'type', defaultValue });
+      }
+    }
+    return params;
+  }
+
+  private parseTypeParameters(generics: string | undefined): string[] {
+    return this.splitTopLevel(generics || '')
+      .map(param => param.trim())
+      .filter(param => param && !param.startsWith("'") && !param.startsWith('const '))
+      .map(param => param.split(/[:=]/)[0].trim());
+  }
+
+  private parseGenericBounds(
+    generics: string | undefined,
+    whereClause: string | undefined
+  ): Array<{ parameter: string; bounds: string[]; source: 'inline' | 'where' }> {
+    // `<T: A + B>` and `where T: C` clauses on plain type parameters; lifetimes and `Vec<T>: X` are left out
+    const clauses = [
+      ...this.splitTopLevel(generics || '').map(clause => ({ clause, source: 'inline' as const })),
+      ...this.splitTopLevel(whereClause || '').map(clause => ({ clause, source: 'where' as const }))
+    ];
+    const result: Array<{ parameter: string; bounds: string[]; source: 'inline' | 'where' }> = [];
+    for (const { clause, source } of clauses) {
+      const bound = clause.trim().match(/^([A-Z][A-Za-z0-9_]*)\s*:([\s\S]+)$/);
+      if (!bound) continue;
+      const bounds = this.traitNames(bound[2]);
+      if (bounds.length > 0) result.push({ parameter: bound[1], bounds, source });
+    }
+    return result;
+  }
+
+  private parseSupertraits(content: string, headerStart: number): string[] {
+    // The header runs from after the trait name to its body: `<T>: A + B where Self: C {`
+    const bodyStart = content.indexOf('{', headerStart);
+    let header = content.substring(headerStart, bodyStart < 0 ? content.length : bodyStart).trim();
+    if (header.startsWith('<')) header = header.substring(this.findClosingParen(header, 0, '<', '>') + 1).trim();
+    const [inlinePart, wherePart] = header.split(/\bwhere\b/);
+    const supertraits = inlinePart.trim().startsWith(':') ? this.traitNames(inlinePart.trim().substring(1)) : [];
+    for (const clause of this.splitTopLevel(wherePart || '')) {
+      const bound = clause.trim().match(/^Self\s*:([\s\S]+)$/);
+      if (bound) supertraits.push(...this.traitNames(bound[1]));
+    }
+    return Array.from(new Set(supertraits));
+  }
+
+  private traitNames(bounds: string): string[] {
+    // `Serialize + Into<String> + ?Sized + 'a` -> ['Serialize', 'Into']
+    return bounds.split('+')
+      .map(bound => bound.trim())
+      .filter(bound => bound && !bound.startsWith('?') && !bound.startsWith("'"))
+      .map(bound => bound.replace(/<[\s\S]*$/, '').split('::').pop()!.trim())
+      .filter(name => /^[A-Za-z_][A-Za-z0-9_]*$/.test(name));
+  }
+
+  private parseArgumentTypes(
+    body: string,
+    bodyLine: number,
+    rawParams: string[]
+  ): Array<{ call: string; line: number; types: Array<string | null>; turbofish?: string[] }> {
+    // Best-effort types of free and path call arguments: literals, struct literals, `Type::ctor(..)`
+    // and variables bound to any of these, plus explicit turbofish type arguments
+    const keywords = new Set(['if', 'while', 'match', 'for', 'return', 'in', 'as', 'move', 'loop', 'let', 'else', 'where', 'unsafe', 'async', 'await']);
+    const code = this.maskNonCode(body);
+    const bindings = new Map<string, string>();
+
+    for (const param of rawParams) {
+      const typed = param.match(/^(?:mut\s+)?([a-z_][A-Za-z0-9_]*)\s*:\s*(.+)$/);
+      const type = typed && this.simpleTypeName(typed[2]);
+      if (type) bindings.set(typed![1], type);
+    }
+    const letRegex = /\blet\s+(?:mut\s+)?([a-z_][A-Za-z0-9_]*)\s*(?::\s*([^=;]+?))?\s*=\s*([^;]+)/g;
+    let match;
+    while ((match = letRegex.exec(code)) !== null) {
+      const type = match[2] ? this.simpleTypeName(match[2]) : this.expressionType(match[3], bindings);
+      if (type) bindings.set(match[1], type);
+    }
+
+    const callRegex = /(\.\s*)?(?<![A-Za-z0-9_])((?:[A-Za-z_][A-Za-z0-9_]*::)*)([a-z_][A-Za-z0-9_]*)\s*(?:::<((?:[^<>]|<(?:[^<>]|<[^<>]*>)*>)*)>\s*)?\(/g;
+    const calls: Array<{ call: string; line: number; types: Array<string | null>; turbofish?: string[] }> = [];
+
+    while ((match = callRegex.exec(code)) !== null) {
+      const [, methodDot, path, name, typeArgs] = match;
+      if (methodDot || keywords.has(name)) continue;
+
+      const open = match.index + match[0].length - 1;
+      const args = code.substring(open + 1, this.findClosingParen(code, open));
+      const types = this.splitTopLevel(args)
+        .map(arg => arg.trim())
+        .filter(arg => arg)
+        .map(arg => this.expressionType(arg, bindings));
+      const turbofish = typeArgs !== undefined ? this.splitTopLevel(typeArgs).map(arg => arg.trim()) : undefined;
+      if (types.some(type => type) || turbofish) {
+        calls.push({
+          call: `${path}${name}`,
+          line: bodyLine + (code.substring(0, match.index).match(/\n/g) || []).length,
+          types,
+          ...(turbofish ? { turbofish } : {})
+        });
+      }
+    }
+
+    return calls;
+  }
+
+  private expressionType(expression: string, bindings: Map<string, string>): string | null {
+    const expr = expression.trim().replace(/^&\s*(?:mut\s+)?/, '');
+
+    // Literals take their suffix, or the type an unconstrained literal defaults to
+    if (/^"[^"]*"$/.test(expr)) return '&str';
+    if (/^'(?:[^'\\]|\\.+)'$/.test(expr)) return 'char';
+    if (expr === 'true' || expr === 'false') return 'bool';
+    const number = expr.match(/^-?\d[\d_]*(\.\d[\d_]*)?(?:e-?\d+)?(i8|i16|i32|i64|i128|isize|u8|u16|u32|u64|u128|usize|f32|f64)?$/);
+    if (number) return number[2] || (number[1] || /e/.test(expr) ? 'f64' : 'i32');
+
+    const variable = expr.match(/^[a-z_][A-Za-z0-9_]*$/);
+    if (variable) return bindings.get(expr) || null;
+
+    // `Type { .. }` or `Type::new(..)`, optionally followed by `?`, and nothing after
+    const literal = expr.match(/^(?:[A-Za-z_][A-Za-z0-9_]*::)*([A-Z][A-Za-z0-9_]*)\s*\{/);
+    if (literal) {
+      const open = literal[0].length - 1;
+      return expr.substring(this.findClosingParen(expr, open, '{', '}') + 1).trim() === '' ? literal[1] : null;
+    }
+    const constructor = expr.match(/^(?:[A-Za-z_][A-Za-z0-9_]*::)*([A-Z][A-Za-z0-9_]*)::[a-z_][A-Za-z0-9_]*\s*\(/);
+    if (constructor) {
+      const open = constructor[0].length - 1;
+      return /^\??$/.test(expr.substring(this.findClosingParen(expr, open) + 1).trim()) ? constructor[1] : null;
+    }
+
+    return null;
+  }
+
+  private parseReceiver(firstParam: string | undefined): { kind: RustReceiver; mutable: boolean } {
+    // `&'a mut self` -> ref_mut; `self: Box<Self>` -> boxed; anything not named `self` is no receiver
+    const param = (firstParam || '').replace(/\s+/g, ' ').trim();
+    const shorthand = param.match(/^(&\s*(?:'\S+\s+)?)?(mut\s+)?self$/);
+    if (shorthand) {
+      const mutable = shorthand[2] !== undefined;
+      return shorthand[1] ? { kind: mutable ? 'ref_mut' : 'ref', mutable } : { kind: 'value', mutable };
+    }
+    const typed = param.match(/^(mut\s+)?self\s*:\s*([\s\S]+)$/);
+    if (!typed) return { kind: 'none', mutable: false };
+
+    const type = typed[2].replace(/\s+/g, '');
+    const reference = type.match(/^&(?:'[^&]*?(?=mut|Self))?(mut)?Self$/);
+    if (type === 'Self') return { kind: 'value', mutable: typed[1] !== undefined };
+    if (reference) return { kind: reference[1] ? 'ref_mut' : 'ref', mutable: reference[1] !== undefined };
+    if (/^(?:(?:std|alloc)::boxed::)?Box<Self>$/.test(type)) return { kind: 'boxed', mutable: typed[1] !== undefined };
+    return { kind: 'typed', mutable: type.includes('&mut') };
+  }
+
+  private parseReturnBorrows(parameters: string[], rawParams: string[], returnType: string | undefined, receiver: RustReceiver): string[] {
+    // Named lifetimes in the return type tie it to every parameter mentioning them; an elided one (`&T`, `'_`)
+    // borrows from a `&self` receiver, or else from the only parameter holding a reference
+    if (!returnType) return [];
+    const lifetimePattern = /'[\p{XID_Start}_]\p{XID_Continue}*/gu;
+    const lifetimes = returnType.match(lifetimePattern) || [];
+    const elided = /&(?!\s*')/.test(returnType) || lifetimes.includes("'_");
+    const named = new Set(lifetimes.filter(lifetime => lifetime !== "'static" && lifetime !== "'_"));
+
+    // Self shorthands carry their lifetime in the parameter itself: `&'a self`
+    const inputs = rawParams.map((param, index) => ({
+      name: parameters[index].replace(/^&\s*(?:'\S+\s+)?/, '').replace(/^mut\s+/, ''),
+      type: param.includes(':') ? param.substring(param.indexOf(':') + 1) : param
+    }));
+    const borrows = new Set(inputs
+      .filter(input => (input.type.match(lifetimePattern) || []).some(lifetime => named.has(lifetime)))
+      .map(input => input.name));
+    if (elided) {
+      const referenceInputs = inputs.filter(input => /[&']/.test(input.type));
+      if (receiver === 'ref' || receiver === 'ref_mut') borrows.add('self');
+      else if (referenceInputs.length === 1) borrows.add(referenceInputs[0].name);
+    }
+
+    return Array.from(borrows);
+  }
+
+  private simpleTypeName(type: string): string | null {
+    // `&mut models::User` -> `User`, `u8` -> `u8`; generic or compound types are not tracked
+    const path = type.trim().replace(/^&\s*(?:'[A-Za-z_][A-Za-z0-9_]*\s+)?(?:mut\s+)?/, '');
+    if (/^(?:[iu](?:8|16|32|64|128|size)|f32|f64|bool|char|str)$/.test(path)) return path;
+    return /^(?:[A-Za-z_][A-Za-z0-9_]*::)*[A-Z][A-Za-z0-9_]*$/.test(path) ? path.split('::').pop()! : null;
+  }
+
+  private parseFieldAccesses(body: string, rawParams: string): Array<{ receiver: string; field: string; receiverType?: string; receiverCall?: string }> {
+    // Field reads on parameters and local bindings (`user.name`), with the binding's type from an annotation
+    // or literal, or else the first call its initializer makes: `let user = repo.create_user(r).await?;`
+    const code = this.maskNonCode(body);
+    const bindings = new Map<string, { receiverType?: string; receiverCall?: string }>();
+    for (const param of this.splitTopLevel(rawParams)) {
+      const typed = param.match(/^\s*(?:mut\s+)?([a-z_][A-Za-z0-9_]*)\s*:\s*([\s\S]+)$/);
+      const type = typed && this.simpleTypeName(typed[2]);
+      if (type) bindings.set(typed[1], { receiverType: type });
+    }
+    const letRegex = /\blet\s+(?:mut\s+)?([a-z_][A-Za-z0-9_]*)\s*(?::\s*([^=;]+?))?\s*=\s*([^;]+);/g;
+    let match;
+    while ((match = letRegex.exec(code)) !== null) {
+      const [, name, annotation, initializer] = match;
+      const receiverType = (annotation && this.simpleTypeName(annotation)) ||
+        initializer.trim().match(/^(?:[A-Za-z_][A-Za-z0-9_]*::)*([A-Z][A-Za-z0-9_]*)\s*(?:\{|::)/)?.[1];
+      const receiverCall = initializer.match(/(?<![A-Za-z0-9_])([a-z_][A-Za-z0-9_]*)\s*(?:::<[^>]*>\s*)?\(/)?.[1];
+      bindings.set(name, receiverType ? { receiverType } : receiverCall ? { receiverCall } : {});
+    }
+
+    const accesses = new Map<string, { receiver: string; field: string; receiverType?: string; receiverCall?: string }>();
+    const accessRegex = /(?<![A-Za-z0-9_.])([a-z_][A-Za-z0-9_]*)\s*\.\s*([a-z_][A-Za-z0-9_]*)(?![A-Za-z0-9_])(?!\s*(?:\(|::|!))/g;
+    while ((match = accessRegex.exec(code)) !== null) {
+      const [, receiver, field] = match;
+      if (field === 'await' || !bindings.has(receiver)) continue;
+      accesses.set(`${receiver}.${field}`, { receiver, field, ...bindings.get(receiver) });
+    }
+    return Array.from(accesses.values());
+  }
+
+  private parseDocTests(docComment: string | undefined): RustDocTest[] {
+    // Fenced blocks rustdoc compiles: untagged or tagged only with rustdoc attributes, `ignore` excepted
+    const docTests: RustDocTest[] = [];
+    const fenceRegex = /^[ \t]*(```+|~~~+)[ \t]*([^\n]*)\n([\s\S]*?)^[ \t]*\1[ \t]*$/gm;
+    let match;
+    while (docComment && (match = fenceRegex.exec(docComment)) !== null) {
+      const tags = match[2].split(/[\s,]+/).filter(tag => tag.length > 0);
+      const rust = tags.every(tag => RUSTDOC_FENCE_TAGS.has(tag) || /^edition\d+$/.test(tag));
+      if (!rust || tags.includes('ignore')) continue;
+
+      const code = match[3].split('\n')
+        .map(line => line.replace(/^(\s*)#(?: |$)/, '$1'))
+        .join('\n')
+        .trimEnd();
+      docTests.push({ index: docTests.length + 1, code, calls: this.parseCalls(code) });
+    }
+    return docTests;
+  }
+
+  private parseFieldMentions(body: string): string[] {
+    // Receiver-independent: `a.b.field`, `self.0`, `Type { field: x, short, .. }` (literal or pattern)
+    const code = this.maskNonCode(body);
+    const mentions = new Set<string>();
+    const accessRegex = /\.\s*([a-z_][A-Za-z0-9_]*|\d+)(?![A-Za-z0-9_])(?!\s*(?:\(|::<|!))/g;
+    let match;
+    while ((match = accessRegex.exec(code)) !== null) {
+      if (match[1] !== 'await') mentions.add(match[1]);
+    }
+
+    const literalRegex = /(?<![A-Za-z0-9_])[A-Z][A-Za-z0-9_]*\s*\{/g;
+    while ((match = literalRegex.exec(code)) !== null) {
+      const open = match.index + match[0].length - 1;
+      const close = this.findClosingParen(code, open, '{', '}');
+      for (const entry of this.splitTopLevel(code.substring(open + 1, close))) {
+        const field = entry.trim().match(/^(?:ref\s+)?(?:mut\s+)?([a-z_][A-Za-z0-9_]*|\d+)\s*(?::|$)/)?.[1];
+        if (field) mentions.add(field);
+      }
+    }
+    return Array.from(mentions).sort();
+  }
+
+  private parseErrConstructions(body: string): string[] {
+    // `Err(MyError::NotFound)`, `Err(MyError::Io(e))`, `Err(MyError::Parse { .. }.into())`
+    const code = this.maskNonCode(body);
+    const constructions = new Set<string>();
+    const errRegex = /(?<![A-Za-z0-9_:])Err\s*\(\s*((?:[A-Za-z_][A-Za-z0-9_]*::)+[A-Za-z_][A-Za-z0-9_]*)/g;
+    let match;
+    while ((match = errRegex.exec(code)) !== null) {
+      constructions.add(match[1]);
+    }
+    return Array.from(constructions).sort();
+  }
+
+  private parseMethodChains(body: string, bodyLine: number): Array<{ calls: string[]; line: number }> {
+    // `Root::new(..).a(..)?.b(..).await` -> [Root::new, a, b]; `?` and `.await` between links are skipped.
+    // A chain needs at least two chained methods, so `x.clone()` or `a().b()` alone is not one.
+    const code = this.maskNonCode(body);
+    const chains: Array<{ calls: string[]; line: number }> = [];
+    const rootRegex = /(?<![A-Za-z0-9_.:])((?:[A-Za-z_][A-Za-z0-9_]*::)*[A-Za-z_][A-Za-z0-9_]*)\s*(?:::<[^>]*>\s*)?(?=[(.])/g;
+    const linkRegex = /^\s*\.\s*([a-z_][A-Za-z0-9_]*)\s*(?:::<[^>]*>\s*)?\(/;
+    const skipRegex = /^(?:\s*\?|\s*\.\s*await(?![A-Za-z0-9_]))+/;
+    let match;
+
+    while ((match = rootRegex.exec(code)) !== null) {
+      const root = match[1];
+      let index = match.index + match[0].length;
+      if (code[index] === '(') {
+        index = this.findClosingParen(code, index) + 1;
+      }
+
+      const calls = [root];
+      let link;
+      for (;;) {
+        index += code.substring(index).match(skipRegex)?.[0].length || 0;
+        if (!(link = code.substring(index).match(linkRegex))) break;
+        calls.push(link[1]);
+        index = this.findClosingParen(code, index + link[0].length - 1) + 1;
+      }
+
+      if (calls.length >= 3) {
+        chains.push({ calls, line: bodyLine + (code.substring(0, match.index).match(/\n/g) || []).length });
+      }
+    }
+
+    return chains;
+  }
+
+  private parseCallSequences(body: string, bodyLine: number): RustCallSequence[] {
+    // Calls grouped by what they are made on: `x.a(); x.b()` -> x: [a, b]. A chain continues on its root,
+    // so `HttpServer::new(..).bind(..)?.run()` -> HttpServer: [new, bind, run], as builders are used.
+    // Calls on the result of a free function (`make().go()`) have no receiver to group by.
+    const code = this.maskNonCode(body);
+    const sequences = new Map<string, Array<{ method: string; line: number; index: number }>>();
+    const rootRegex = /(?<![A-Za-z0-9_.:])(?:((?:[A-Za-z_][A-Za-z0-9_]*::)*[A-Z][A-Za-z0-9_]*)::|(self|[a-z_][A-Za-z0-9_]*(?:\s*\.\s*[a-z_][A-Za-z0-9_]*(?![A-Za-z0-9_]|\s*(?:::|\()))*)\s*\.\s*)([a-z_][A-Za-z0-9_]*)\s*(?:::<[^>]*>\s*)?\(/g;
+    const linkRegex = /^\s*\.\s*([a-z_][A-Za-z0-9_]*)\s*(?:::<[^>]*>\s*)?\(/;
+    const skipRegex = /^(?:\s*\?|\s*\.\s*await(?![A-Za-z0-9_]))+/;
+    const lineAt = (index: number) => bodyLine + (code.substring(0, index).match(/\n/g) || []).length;
+    let match;
+
+    while ((match = rootRegex.exec(code)) !== null) {
+      const receiver = (match[1] || match[2]).replace(/\s+/g, '');
+      const calls = sequences.get(receiver) || [];
+      const methodIndex = match.index + match[0].lastIndexOf(match[3]);
+      calls.push({ method: match[3], line: lineAt(methodIndex), index: methodIndex });
+
+      let index = this.findClosingParen(code, match.index + match[0].length - 1) + 1;
+      let link;
+      for (;;) {
+        index += code.substring(index).match(skipRegex)?.[0].length || 0;
+        if (!(link = code.substring(index).match(linkRegex))) break;
+        const linkIndex = index + link[0].indexOf(link[1]);
+        calls.push({ method: link[1], line: lineAt(linkIndex), index: linkIndex });
+        index = this.findClosingParen(code, index + link[0].length - 1) + 1;
+      }
+      sequences.set(receiver, calls);
+    }
+
+    return Array.from(sequences, ([receiver, calls]) => ({
+      receiver,
+      calls: calls.sort((a, b) => a.index - b.index).map(({ method, line }) => ({ method, line }))
+    })).sort((a, b) => a.calls[0].line - b.calls[0].line);
+  }
+
+  private parseDiscardedCalls(body: string, bodyLine: number): Array<{ call: string; line: number }> {
+    // A statement that is nothing but a (possibly awaited) call: `do_thing(x);`, `self.save().await;`.
+    // Anything that consumes the value (`let`, `?`, `.unwrap()`, `match`, assignment) does not match.
+    const code = this.maskNonCode(body);
+    const statementRegex = /(^|[;{}])(\s*)((?:[A-Za-z_][A-Za-z0-9_]*\s*(?:::|\.)\s*)*)([a-z_][A-Za-z0-9_]*)\s*\((?:[^;{}()]|\((?:[^;{}()]|\([^;{}()]*\))*\))*\)\s*(?:\.await\s*)?;/g;
+    const discarded: Array<{ call: string; line: number }> = [];
+    let match;
+
+    while ((match = statementRegex.exec(code)) !== null) {
+      const [, delimiter, leading, path, name] = match;
+      const start = match.index + delimiter.length + leading.length;
+      const qualifier = path.replace(/\s+/g, '');
+
+      discarded.push({
+        call: qualifier.endsWith('.') ? `.${name}` : `${qualifier}${name}`,
+        line: bodyLine + (code.substring(0, start).match(/\n/g) || []).length
+      });
+      // Let the closing `;` start the next statement
+      statementRegex.lastIndex = match.index + match[0].length - 1;
+    }
+
+    return discarded;
+  }
+
+  private parseLiterals(body: string, bodyLine: number): RustLiteral[] {
+    // Strings are found in the masked code, which keeps their quotes, and read back from the body.
+    // Numbers after a single `.` are tuple fields (`pair.0`, but not `0..10`); those inside identifiers
+    // or char literals are skipped.
+    const code = this.maskNonCode(body);
+    const literalRegex = /"[^"]*"|(?<![\p{XID_Continue}'])(?<![^.]\.)(0x[0-9A-Fa-f_]+|0o[0-7_]+|0b[01_]+|\d[\d_]*(\.\d[\d_]*)?(?:[eE][+-]?\d+)?)((?:[iu](?:8|16|32|64|128|size))|f32|f64)?(?![\p{XID_Continue}])/gu;
+    const literals: RustLiteral[] = [];
+    let match;
+
+    while ((match = literalRegex.exec(code)) !== null) {
+      const lineStart = code.lastIndexOf('\n', match.index) + 1;
+      const binding = code.substring(lineStart, match.index).match(new RegExp(String.raw`(?:^|[\s({,])(${IDENT})\s*(?::|=|:\s*[^=;,{}()]+=)\s*$`, 'u'));
+      const kind: RustLiteralKind = match[0].startsWith('"') ? 'string' : match[2] || match[3]?.startsWith('f') ? 'float' : 'integer';
+      literals.push({
+        kind,
+        value: kind === 'string' ? body.substring(match.index + 1, match.index + match[0].length - 1) : match[0],
+        line: bodyLine + (code.substring(0, match.index).match(/\n/g) || []).length,
+        binding: binding && binding[1] !== 'mut' ? binding[1] : undefined
+      });
+    }
+    return literals;
+  }
+
+  private parsePanicSites(body: string, bodyLine: number): Array<{ kind: RustPanicKind; line: number }> {
+    // `.unwrap()` and `.expect(..)` calls, and `panic!`, `unreachable!`, `todo!` and `unimplemented!`
+    // invocations; `unwrap_or` and friends do not panic and are left out
+    const code = this.maskNonCode(body);
+    const panicRegex = /\.\s*(unwrap|expect)\s*\(|(?<![\p{XID_Continue}])(?:(?:std|core)::)?(panic|unreachable|todo|unimplemented)!\s*[({[]/gu;
+    const sites: Array<{ kind: RustPanicKind; line: number }> = [];
+    let match;
+
+    while ((match = panicRegex.exec(code)) !== null) {
+      sites.push({
+        kind: (match[1] || match[2]) as RustPanicKind,
+        line: bodyLine + (code.substring(0, match.index).match(/\n/g) || []).length
+      });
+    }
+    return sites;
+  }
+
+  private parseCloneSites(body: string, bodyLine: number): RustCloneSite[] {
+    const code = this.maskNonCode(body);
+    const cloneRegex = /\.\s*clone\s*\(\s*\)/g;
+    const sites: RustCloneSite[] = [];
+    let match;
+
+    while ((match = cloneRegex.exec(code)) !== null) {
+      const start = this.receiverStart(code, match.index);
+      if (start === match.index) continue;
+      const end = match.index + match[0].length;
+      const expression = `${code.substring(start, match.index).replace(/\s+/g, '')}.clone()`;
+      const line = bodyLine + (code.substring(0, match.index).match(/\n/g) || []).length;
+      const before = code.substring(0, start);
+      const after = code.substring(end);
+
+      // `&x.clone()` as a whole expression, not the start of a longer chain or `?`
+      if (/(?<!&)&\s*$/.test(before) && !/^\s*[.?[]/.test(after)) {
+        sites.push({ reason: 'borrowed_clone', expression, line, confidence: 0.8 });
+        continue;
+      }
+
+      const binding = before.match(new RegExp(String.raw`(?<![\p{XID_Continue}])let\s+(${IDENT})\s*(?::[^=;]+)?=\s*$`, 'u'));
+      if (binding && /^\s*;/.test(after) && this.onlyReadAfter(code.substring(end), binding[1])) {
+        sites.push({ reason: 'read_only_binding', expression, binding: binding[1], line, confidence: 0.5 });
+      }
+    }
+    return sites;
+  }
+
+  private parseMatchSites(body: string, bodyLine: number): RustMatchSite[] {
+    const code = this.maskNonCode(body);
+    const matchRegex = /(?<![\p{XID_Continue}])match\s/gu;
+    const sites: RustMatchSite[] = [];
+    let match;
+
+    while ((match = matchRegex.exec(code)) !== null) {
+      const start = match.index + match[0].length;
+      const open = this.matchArmsOpen(code, start);
+      if (open < 0) continue;
+      const close = this.findClosingParen(code, open, '{', '}');
+      const variants = new Set<string>();
+      const qualifiers: string[] = [];
+      let wildcard = false;
+
+      for (const arm of this.matchArms(code.substring(open + 1, close))) {
+        const guard = arm.search(/(?<![\p{XID_Continue}])if(?![\p{XID_Continue}])/u);
+        const pattern = guard >= 0 ? arm.substring(0, guard) : arm;
+        for (const alternative of this.splitAlternatives(pattern)) {
+          const written = alternative.trim().replace(/^(?:ref\s+)?(?:mut\s+)?[a-z_]\p{XID_Continue}*\s*@\s*/u, '');
+          if (/^(?:_|(?:ref\s+)?(?:mut\s+)?(?!(?:true|false)$)[a-z_]\p{XID_Continue}*)$/u.test(written)) {
+            if (guard < 0) wildcard = true;
+            continue;
+          }
+          const variant = written.match(/^((?:(?:r#)?[\p{XID_Start}_]\p{XID_Continue}*\s*::\s*)*)([A-Z]\p{XID_Continue}*)\s*(\([\s\S]*\)|\{[\s\S]*\})?$/u);
+          if (!variant) continue;
+          const qualifier = variant[1].replace(/\s/g, '').split('::').filter(Boolean).pop();
+          if (qualifier) qualifiers.push(qualifier);
+          if (guard < 0 && this.irrefutablePayload(variant[3])) variants.add(variant[2]);
+        }
+      }
+
+      sites.push({
+        scrutinee: code.substring(start, open).trim().replace(/\s+/g, ' '),
+        line: bodyLine + (code.substring(0, match.index).match(/\n/g) || []).length,
+        enumName: qualifiers.sort((a, b) => qualifiers.filter(q => q === b).length - qualifiers.filter(q => q === a).length)[0],
+        variants: Array.from(variants),
+        wildcard
+      });
+    }
+    return sites;
+  }
+
+  private matchArmsOpen(code: string, start: number): number {
+    // The `{` opening the arms: the first one outside parentheses and brackets in the scrutinee
+    let depth = 0;
+    for (let i = start; i < code.length; i++) {
+      const char = code[i];
+      if (char === '(' || char === '[') depth++;
+      else if (char === ')' || char === ']') depth--;
+      else if (char === ';' && depth === 0) return -1;
+      else if (char === '{' && depth === 0) return i;
+    }
+    return -1;
+  }
+
+  private matchArms(arms: string): string[] {
+    // The pattern (with any guard) of each arm; arm bodies end at a closing brace or a top-level comma
+    const patterns: string[] = [];
+    let pos = 0;
+    while (pos < arms.length) {
+      let depth = 0;
+      let arrow = -1;
+      for (let i = pos; i < arms.length - 1; i++) {
+        const char = arms[i];
+        if ('([{'.includes(char)) depth++;
+        else if (')]}'.includes(char)) depth--;
+        else if (char === '=' && arms[i + 1] === '>' && depth === 0) {
+          arrow = i;
+          break;
+        }
+      }
+      if (arrow < 0) break;
+      patterns.push(arms.substring(pos, arrow).trim());
+
+      let end = arrow + 2;
+      while (end < arms.length && /\s/.test(arms[end])) end++;
+      if (arms[end] === '{') {
+        end = this.findClosingParen(arms, end, '{', '}') + 1;
+        while (end < arms.length && /[\s,]/.test(arms[end])) end++;
+      } else {
+        depth = 0;
+        for (; end < arms.length; end++) {
+          const char = arms[end];
+          if ('([{'.includes(char)) depth++;
+          else if (')]}'.includes(char)) depth--;
+          else if (char === ',' && depth === 0) break;
+        }
+        end++;
+      }
+      pos = end;
+    }
+    return patterns;
+  }
+
+  private splitAlternatives(pattern: string): string[] {
+    // `A | B(..)`, splitting on `|` outside payloads
+    const alternatives: string[] = [];
+    let depth = 0;
+    let current = '';
+    for (const char of pattern) {
+      if ('([{'.includes(char)) depth++;
+      else if (')]}'.includes(char)) depth--;
+      if (char === '|' && depth === 0) {
+        alternatives.push(current);
+        current = '';
+      } else {
+        current += char;
+      }
+    }
+    alternatives.push(current);
+    return alternatives.filter(alternative => alternative.trim());
+  }
+
+  private irrefutablePayload(payload: string | undefined): boolean {
+    // `(x, _)`, `{ id, .. }` or `{ id: value }` match every value of the variant; literals and nested variants do not
+    if (!payload) return true;
+    return this.splitAlternatives(payload.slice(1, -1).replace(/,/g, '|'))
+      .map(part => part.includes(':') ? part.substring(part.indexOf(':') + 1) : part)
+      .every(part => /^\s*(?:_|\.\.|(?:ref\s+)?(?:mut\s+)?[a-z_]\p{XID_Continue}*)\s*$/u.test(part));
+  }
+
+  private parseReadOnlyParameters(body: string, rawParams: string[]): string[] {
+    // `name: Type` parameters whose every use borrows, reads or calls a borrowing method; `mut` and
+    // pattern parameters, references, `impl`/`dyn` types and primitives are left out
+    const code = this.maskNonCode(body);
+    const readOnly: string[] = [];
+    for (const param of rawParams) {
+      const match = param.match(new RegExp(String.raw`^(${IDENT})\s*:\s*([\s\S]+)$`, 'u'));
+      if (!match || match[1].startsWith('_') || match[1] === 'self') continue;
+      const type = match[2].trim();
+      if (/^(?:&|\*|impl\s|dyn\s|fn\s*\(|\()/.test(type) || PRIMITIVE_TYPES.has(type)) continue;
+      if (this.onlyBorrowed(code, match[1])) readOnly.push(match[1]);
+    }
+    return readOnly;
+  }
+
+  private onlyBorrowed(code: string, name: string): boolean {
+    // Unlike `onlyReadAfter`, a field read as a value counts as a move: `User { name: request.name }`
+    const useRegex = new RegExp(String.raw`(?<![\p{XID_Continue}.:])${name}(?![\p{XID_Continue}])`, 'gu');
+    let uses = 0;
+    let use;
+    while ((use = useRegex.exec(code)) !== null) {
+      const before = code.substring(0, use.index);
+      const after = code.substring(use.index + use[0].length);
+      if (/(?<![\p{XID_Continue}])let\s+(?:mut\s+)?$/u.test(before)) break; // Shadowed from here on
+      if (/^\s*:(?!:)/.test(after) && /[{,]\s*$/.test(before)) continue; // A field name in a struct literal
+      uses++;
+      if (/&\s*mut\s+$/.test(before) || /^\s*(?:[-+*/%|&^]|<<|>>)?=(?!=)/.test(after)) return false;
+      if (/(?<!&)&\s*$/.test(before)) continue;
+
+      const members = after.match(/^(?:\s*\.\s*[\p{XID_Start}_]\p{XID_Continue}*)+/u);
+      if (members) {
+        const rest = after.substring(members[0].length);
+        if (/^\s*(?:::\s*<[^>]*>\s*)?\(/.test(rest)) {
+          // A method taking `self` consumes the parameter, or moves the field it is called on
+          if (CONSUMING_METHODS.test(members[0].replace(/^[\s\S]*\.\s*/, ''))) return false;
+          continue;
+        }
+        if (/^\s*(?:\[|==|!=|<=|>=|<(?!<)|>(?!>))/.test(rest)) continue;
+        if (FORMAT_MACROS.test(before.substring(0, this.openParenBefore(before)))) continue;
+        return false;
+      }
+      if (/^\s*(?:\[|==|!=|<=|>=|<(?!<)|>(?!>))/.test(after)) continue;
+      if (FORMAT_MACROS.test(before.substring(0, this.openParenBefore(before)))) continue;
+      return false;
+    }
+    return uses > 0;
+  }
+
+  private parseConstEligibility(body: string, callSites: Array<{ call: string }>): RustConstEligibility {
+    const code = this.maskNonCode(body);
+    const blockers = new Set<string>();
+    if (/(?<![\p{XID_Continue}])for\s[^{]*?(?<![\p{XID_Continue}])in(?![\p{XID_Continue}])/u.test(code)) blockers.add('for loop');
+    if (/\.\s*await(?![\p{XID_Continue}])|(?<![\p{XID_Continue}])async\s*(?:move\s*)?\{/u.test(code)) blockers.add('.await');
+    if (/(?:^|[=(,{;]|=>|return)\s*(?:move\s+)?\|[^|]*\|/.test(code)) blockers.add('closure');
+    if (/(?<![\p{XID_Continue}])dyn\s/u.test(code)) blockers.add('dyn');
+    for (const macro of code.matchAll(new RegExp(String.raw`(?<![\p{XID_Continue}])(${IDENT})!\s*[({[]`, 'gu'))) {
+      if (!CONST_MACROS.has(macro[1])) blockers.add(`${macro[1]}!`);
+    }
+
+    const calls: string[] = [];
+    for (const { call } of callSites) {
+      if (!call.startsWith('.')) calls.push(call);
+      else if (!CONST_METHODS.has(call.substring(1))) blockers.add(`${call}()`);
+    }
+    return { blockers: Array.from(blockers), calls };
+  }
+
+  private receiverStart(code: string, dotIndex: number): number {
+    // Walk back over `a.b`, `a::b`, calls and indexing: `self.users.get(&id)` before `.clone()`
+    let pos = dotIndex;
+    for (;;) {
+      while (pos > 0 && /\s/.test(code[pos - 1])) pos--;
+      if (pos > 0 && (code[pos - 1] === ')' || code[pos - 1] === ']')) {
+        const close = code[pos - 1];
+        const open = close === ')' ? '(' : '[';
+        let depth = 0;
+        let i = pos - 1;
+        for (; i >= 0; i--) {
+          if (code[i] === close) depth++;
+          else if (code[i] === open && --depth === 0) break;
+        }
+        if (i < 0) return dotIndex;
+        pos = i;
+        continue;
+      }
+      const ident = code.substring(0, pos).match(/(?:r#)?[\p{XID_Start}_]\p{XID_Continue}*$/u);
+      if (!ident) return pos;
+      pos -= ident[0].length;
+      const separator = code.substring(0, pos).match(/(?:\.|::)\s*$/);
+      if (!separator) return pos;
+      pos -= separator[0].length;
+    }
+  }
+
+  private onlyReadAfter(rest: string, name: string): boolean {
+    // Every later use borrows, calls a method, reads a field, compares or is formatted; moving the
+    // clone (passing or returning it by value) or consuming it with `into*` may need the copy
+    const useRegex = new RegExp(String.raw`(?<![\p{XID_Continue}.:])${name}(?![\p{XID_Continue}])`, 'gu');
+    let use;
+    while ((use = useRegex.exec(rest)) !== null) {
+      const before = rest.substring(0, use.index);
+      const after = rest.substring(use.index + use[0].length);
+      if (/(?<![\p{XID_Continue}])let\s+(?:mut\s+)?$/u.test(before)) return true; // Shadowed from here on
+      if (/&\s*mut\s+$/.test(before)) return false;
+      if (/(?<!&)&\s*$/.test(before)) continue;
+      const member = after.match(/^\s*\.\s*([\p{XID_Start}_]\p{XID_Continue}*)/u);
+      if (member) {
+        if (/^into(?:_|$)/.test(member[1])) return false;
+        continue;
+      }
+      if (/^\s*(?:\[|==|!=|<=|>=|<(?!<)|>(?!>))/.test(after)) continue;
+      if (FORMAT_MACROS.test(before.substring(0, this.openParenBefore(before)))) continue;
+      return false;
+    }
+    return true;
+  }
+
+  private openParenBefore(text: string): number {
+    // Index of the innermost unclosed `(`, or 0
+    let depth = 0;
+    for (let i = text.length - 1; i >= 0; i--) {
+      if (text[i] === ')') depth++;
+      else if (text[i] === '(' && depth-- === 0) return i;
+    }
+    return 0;
+  }
+
+  private parseShadowedBindings(body: string, bodyLine: number): Array<{ name: string; line: number; previousLine: number }> {
+    // `let` bindings (plain or tuple patterns) of a name already bound in the same or an enclosing block.
+    // `if let` and `while let` bindings only live in their own block and are left out.
+    const code = this.maskNonCode(body);
+    const tokenRegex = new RegExp(String.raw`[{}]|(?<![\p{XID_Continue}])((?:if|while)\s+)?let\s+(\([^=;{}]*\)|(?:mut\s+)?${IDENT})`, 'gu');
+    const scopes: Array<Map<string, number>> = [new Map()];
+    const shadowed: Array<{ name: string; line: number; previousLine: number }> = [];
+    let match;
+
+    while ((match = tokenRegex.exec(code)) !== null) {
+      if (match[0] === '{') {
+        scopes.push(new Map());
+        continue;
+      }
+      if (match[0] === '}') {
+        if (scopes.length > 1) scopes.pop();
+        continue;
+      }
+      // `if let`, and enum or struct patterns such as `let Some(x) = .. else { .. }`
+      if (match[1] || /^\s*(?:::|[({])/.test(code.substring(tokenRegex.lastIndex))) continue;
+
+      const line = bodyLine + (code.substring(0, match.index).match(/\n/g) || []).length;
+      const names = (match[2].match(new RegExp(IDENT, 'gu')) || [])
+        .filter(name => name !== 'mut' && name !== 'ref' && name !== '_');
+      for (const name of names) {
+        const previous = scopes.map(scope => scope.get(name)).reverse().find(found => found !== undefined);
+        if (previous !== undefined) shadowed.push({ name, line, previousLine: previous });
+        scopes[scopes.length - 1].set(name, line);
+      }
+    }
+
+    return shadowed;
+  }
+
+  private findItemMacroInvocations(content: string): Array<{ name: string; start: number; open: number; close: number }> {
+    // Invocations outside function bodies, such as `cfg_if! { .. }` or `thread_local!(..);`; `macro_rules!` definitions are skipped
+    const code = this.maskNonCode(content);
+    const invocationRegex = new RegExp(String.raw`(${IDENT})\s*!\s*(?:(${IDENT})\s*)?[({[]`, 'uy');
+    const closers: Record<string, string> = { '(': ')', '[': ']', '{': '}' };
+    const invocations: Array<{ name: string; start: number; open: number; close: number }> = [];
+    const inFunction: boolean[] = []; // Per open brace, whether it is a function body or nested in one
+    let statementStart = 0;
+
+    for (let i = 0; i < code.length; i++) {
+      const char = code[i];
+      if (char === '{') {
+        inFunction.push(inFunction[inFunction.length - 1] || /\bfn\b/.test(code.substring(statementStart, i)));
+        statementStart = i + 1;
+      } else if (char === '}') {
+        inFunction.pop();
+        statementStart = i + 1;
+      } else if (char === ';') {
+        statementStart = i + 1;
+      } else if (!inFunction[inFunction.length - 1] && /[\p{XID_Start}_]/u.test(char) && !/[\p{XID_Continue}#]/u.test(code[i - 1] || '')) {
+        invocationRegex.lastIndex = i;
+        const match = invocationRegex.exec(code);
+        if (!match || (match[2] && match[1] !== 'macro_rules')) continue;
+        // Only invocations in item position, not `concat!` in an attribute or `max!` in a const initializer
+        if (!/^(?:\s|#!?\[[^\]]*\])*$/.test(code.substring(statementStart, i))) continue;
+        const open = i + match[0].length - 1;
+        const close = this.findClosingParen(code, open, code[open], closers[code[open]]);
+        if (!match[2]) {
+          invocations.push({ name: this.canonicalName(match[1]), start: i, open, close });
+        }
+        i = close;
+        statementStart = close + 1;
+      }
+    }
+
+    return invocations;
+  }
+
+  private blankMacroBodies(content: string, invocations: Array<{ open: number; close: number }>): string {
+    // Keeps the delimiters and line breaks so offsets and line numbers still match the source
+    let blanked = '';
+    let copied = 0;
+    for (const { open, close } of invocations) {
+      blanked += content.substring(copied, open + 1) + content.substring(open + 1, close).replace(/[^\n]/g, ' ');
+      copied = close;
+    }
+    return blanked + content.substring(copied);
+  }
+
+  private maskNonCode(text: string): string {
+    // Blank out comments and string literals, keeping offsets and line breaks intact
+    const blank = (segment: string) => segment.replace(/[^\n]/g, ' ');
+    return text
+      .replace(/\/\*[\s\S]*?\*\//g, blank)
+      .replace(/\/\/.*$/gm, blank)
+      .replace(/"(?:[^"\\]|\\.)*"/g, segment => `"${blank(segment.slice(1, -1))}"`);
+  }
+
+  private splitTopLevel(text: string): string[] {
+    // Split on commas that are not nested inside <>, (), [] or {}
+    const parts: string[] = [];
+    let depth = 0;
+    let current = '';
+
+    for (const char of text) {
+      if (char === '<' || char === '(' || char === '[' || char === '{') depth++;
+      else if (char === '>' || char === ')' || char === ']' || char === '}') depth = Math.max(0, depth - 1);
+
+      if (char === ',' && depth === 0) {
+        parts.push(current);
+        current = '';
+      } else {
+        current += char;
+      }
+    }
+    parts.push(current);
+
+    return parts;
+  }
+
+  private extractTraitBody(content: string, startIndex: number, endLine: number): string {
+    const lines = content.split('\n');
+    const startLineIndex = content.substring(0, startIndex).split('\n').length - 1;
+    return lines.slice(startLineIndex, endLine).join('\n');
+  }
+
+  private parseTraitMethods(traitBody: string): string[] {
+    const methods: string[] = [];
+    const fnRegex = new RegExp(String.raw`fn\s+(${IDENT})`, 'gu');
+    let match;
+    
     while ((match = fnRegex.exec(traitBody)) !== null) {
-      methods.push(match[1]);
+      methods.push(this.canonicalName(match[1]));
     }
     
     return methods;
   }
 
+  private parseTraitDefaultMethods(traitBody: string): string[] {
+    // A method has a default body when its signature is followed by `{` rather than `;`
+    const methods: string[] = [];
+    const fnRegex = new RegExp(String.raw`fn\s+(${IDENT})[^;{]*([;{])`, 'gu');
+    let match;
+
+    while ((match = fnRegex.exec(traitBody)) !== null) {
+      if (match[2] === '{') {
+        methods.push(this.canonicalName(match[1]));
+      }
+    }
+
+    return methods;
+  }
+
   private parseAssociatedTypes(traitBody: string): string[] {
     const types: string[] = [];
-    const typeRegex = /type\s+([a-zA-Z_][a-zA-Z0-9_]*)/g;
+    const typeRegex = new RegExp(String.raw`type\s+(${IDENT})`, 'gu');
     let match;
     
     while ((match = typeRegex.exec(traitBody)) !== null) {
-      types.push(match[1]);
+      types.push(this.canonicalName(match[1]));
     }
     
     return types;
   }
 
+  private parseAssociatedTypeGenerics(traitBody: string): Record<string, RustGenericParam[]> {
+    // `type Item<'a> where Self: 'a;` -> { Item: ['a] }; plain associated types are left out
+    const generics: Record<string, RustGenericParam[]> = {};
+    const typeRegex = new RegExp(String.raw`\btype\s+(${IDENT})\s*<((?:[^<>]|<[^<>]*>)*)>`, 'gu');
+    for (const match of this.maskNonCode(traitBody).matchAll(typeRegex)) {
+      generics[this.canonicalName(match[1])] = this.parseGenericParams(match[2]);
+    }
+    return generics;
+  }
+
+  private parseAssociatedTypeBounds(traitBody: string, associatedTypes: string[]): Record<string, string[]> {
+    // `type Error: Display + Send;` and `where Self::Error: Display` (or `<Self as R>::Error: Display`) -> { Error: [Display, Send] }
+    const code = this.maskNonCode(traitBody);
+    const bounds: Record<string, string[]> = {};
+    const addBounds = (name: string, text: string) => {
+      const traits = this.traitNames(text.split(/\bwhere\b/)[0]);
+      if (associatedTypes.includes(name) && traits.length > 0) {
+        bounds[name] = Array.from(new Set([...(bounds[name] || []), ...traits]));
+      }
+    };
+
+    const declaredRegex = new RegExp(String.raw`\btype\s+(${IDENT})\s*(?:<[^>]*>\s*)?:([^;=]+)`, 'gu');
+    for (const match of code.matchAll(declaredRegex)) {
+      addBounds(this.canonicalName(match[1]), match[2]);
+    }
+
+    // A where-clause bound runs to the next top-level `,`, `;` or `{`
+    const clauseRegex = new RegExp(String.raw`(?:\bSelf|<\s*Self\s+as\s+[^<>]+>)\s*::\s*(${IDENT})\s*:(?!:)`, 'gu');
+    for (const match of code.matchAll(clauseRegex)) {
+      const start = match.index! + match[0].length;
+      let end = start;
+      for (let depth = 0; end < code.length; end++) {
+        const char = code[end];
+        if (char === '<' || char === '(') depth++;
+        else if ((char === '>' && code[end - 1] !== '-') || char === ')') depth--;
+        else if (depth === 0 && (char === ',' || char === ';' || char === '{')) break;
+      }
+      addBounds(this.canonicalName(match[1]), code.substring(start, end));
+    }
+
+    return bounds;
+  }
+
+  private parseAssociatedTypeBindings(implBody: string, startLine: number): Array<{ name: string; type: string; line: number }> {
+    const bindings: Array<{ name: string; type: string; line: number }> = [];
+    const bindingRegex = new RegExp(String.raw`^\s*type\s+(${IDENT})\s*=\s*([^;]+);`, 'gmu');
+    let match;
+
+    while ((match = bindingRegex.exec(implBody)) !== null) {
+      bindings.push({
+        name: this.canonicalName(match[1]),
+        type: match[2].trim().replace(/\s+/g, ' '),
+        line: startLine + (implBody.substring(0, match.index).match(/\n/g) || []).length
+      });
+    }
+
+    return bindings;
+  }
+
+  private parseDefaultValues(implBody: string, startLine: number, target: string): RustDefaultValue[] {
+    // The struct literal `fn default()` returns: `Self { .. }` or `ServerConfig { .. }`
+    const masked = this.maskNonCode(implBody);
+    const fn = /\bfn\s+default\s*\(\s*\)[^{]*\{/.exec(masked);
+    if (!fn) return [];
+    const typeName = target.replace(/<[\s\S]*$/, '').split('::').pop()!;
+    const literal = new RegExp(String.raw`\b(?:Self|${typeName})\s*\{`, 'g');
+    literal.lastIndex = fn.index + fn[0].length;
+    const start = literal.exec(masked);
+    if (!start) return [];
+
+    const open = start.index + start[0].length - 1;
+    let depth = 0;
+    let close = open;
+    for (; close < masked.length; close++) {
+      if (masked[close] === '{') depth++;
+      else if (masked[close] === '}' && --depth === 0) break;
+    }
+
+    // Split at commas outside (), [] and {}, on the masked text so commas in strings do not count
+    const values: RustDefaultValue[] = [];
+    let nesting = 0;
+    let partStart = open + 1;
+    for (let index = open + 1; index <= close; index++) {
+      const char = masked[index];
+      if (char === '(' || char === '[' || char === '{') nesting++;
+      else if ((char === ')' || char === ']' || char === '}') && index < close) nesting--;
+      if ((char !== ',' || nesting > 0) && index < close) continue;
+
+      const part = implBody.substring(partStart, index);
+      const field = part.match(new RegExp(String.raw`^\s*(${IDENT})\s*(?::\s*([\s\S]+?))?\s*$`, 'u'));
+      if (field) {
+        const expression = (field[2] ?? field[1]).replace(/\s+/g, ' ');
+        values.push({
+          field: this.canonicalName(field[1]),
+          expression,
+          ...this.classifyDefaultValue(expression),
+          line: startLine + (implBody.substring(0, partStart + part.search(/\S/)).match(/\n/g) || []).length
+        });
+      }
+      partStart = index + 1;
+    }
+    return values;
+  }
+
+  private classifyDefaultValue(expression: string): Pick<RustDefaultValue, 'kind' | 'value'> {
+    // `"a".to_string()`, `"a".into()` and `String::from("a")` hold the literal they convert
+    const text = expression.match(/^("(?:[^"\\]|\\.)*")(?:\s*\.\s*(?:to_string|to_owned|into)\s*\(\s*\))?$/) ||
+      expression.match(/^String::from\s*\(\s*("(?:[^"\\]|\\.)*")\s*\)$/);
+    if (text) return { kind: 'literal', value: text[1] };
+    if (/^-?[0-9][\w.]*$|^(?:true|false)$|^'(?:[^'\\]|\\.)'$/.test(expression)) return { kind: 'literal', value: expression };
+    if (/^(?:[A-Za-z_]\w*\s*::\s*)*[A-Za-z_]\w*(?:\s*::\s*<[^>]*>)?\s*\(/.test(expression) && expression.endsWith(')')) return { kind: 'call' };
+    if (/^(?:[A-Za-z_]\w*\s*::\s*)*[A-Za-z_]\w*$/.test(expression)) return { kind: 'path' };
+    return { kind: 'expression' };
+  }
+
   private extractImplBody(content: string, startIndex: number, endLine: number): string {
     const lines = content.split('\n');
     const startLineIndex = content.substring(0, startIndex).split('\n').length - 1;
@@ -664,11 +3037,11 @@ export class RustAnalyzer {
 
   private parseImplMethods(implBody: string): string[] {
     const methods: string[] = [];
-    const fnRegex = /fn\s+([a-zA-Z_][a-zA-Z0-9_]*)/g;
+    const fnRegex = new RegExp(String.raw`fn\s+(${IDENT})`, 'gu');
     let match;
     
     while ((match = fnRegex.exec(implBody)) !== null) {
-      methods.push(match[1]);
+      methods.push(this.canonicalName(match[1]));
     }
     
     return methods;
@@ -695,6 +3068,10 @@ export class RustAnalyzer {
   }
 
   private findModuleEndLine(content: string, startIndex: number, lines: string[], startLine: number): number {
+    // `mod name;` declares a module kept in its own file; the next brace belongs to a later item
+    const rest = content.substring(startIndex);
+    const semicolon = rest.indexOf(';');
+    if (semicolon >= 0 && (rest.indexOf('{') === -1 || semicolon < rest.indexOf('{'))) return startLine;
     return this.findBlockEndLine(content, startIndex, lines, startLine);
   }
 
@@ -0,0 +1,216 @@
+/**
+ * Parsing of Rust outer attributes (`#[...]`) into structured arguments
+ */
+
+export interface RustAttributeArg {
+  key?: string; // Set for `key = value` pairs
+  value: string; // Positional token, or the pair's value; string literals are unquoted
+  args?: RustAttributeArg[]; // Nested list, e.g. `all(test, unix)`
+}
+
+export interface RustAttribute {
+  name: string; // Attribute path, e.g. `derive`, `serde`, `tokio::main`
+  args: RustAttributeArg[];
+  value?: string; // `#[name = "value"]` form
+  line: number;
+}
+
+export interface RustAttributeSpan {
+  start: number; // Index of `#`
+  end: number; // Index just past the closing `]`
+  text: string; // Contents between `#[` and `]`
+}
+
+/**
+ * Locate every outer attribute in the source, balancing nested brackets and skipping string literals.
+ * Inner attributes (`#![...]`) are not item attributes and are ignored.
+ */
+export function findAttributeSpans(content: string): RustAttributeSpan[] {
+  const spans: RustAttributeSpan[] = [];
+  let index = content.indexOf('#[');
+
+  while (index !== -1) {
+    const end = findClosing(content, index + 1);
+    if (end === -1) break;
+    spans.push({ start: index, end: end + 1, text: content.substring(index + 2, end) });
+    index = content.indexOf('#[', end + 1);
+  }
+
+  return spans;
+}
+
+/**
+ * Parse the contents of an attribute, e.g. `serde(rename_all = "camelCase")`
+ */
+export function parseAttribute(text: string, line: number): RustAttribute {
+  const trimmed = text.trim();
+  const nameMatch = trimmed.match(/^[A-Za-z_][A-Za-z0-9_]*(?:\s*::\s*[A-Za-z_][A-Za-z0-9_]*)*/);
+  const name = nameMatch ? nameMatch[0].replace(/\s+/g, '') : trimmed;
+  const rest = trimmed.substring(nameMatch ? nameMatch[0].length : trimmed.length).trim();
+
+  if (rest.startsWith('=')) {
+    return { name, args: [], value: unquote(rest.substring(1).trim()), line };
+  }
+  if (rest.startsWith('(') || rest.startsWith('[') || rest.startsWith('{')) {
+    const close = findClosing(rest, 0);
+    return { name, args: parseAttributeArgs(rest.substring(1, close === -1 ? rest.length : close)), line };
+  }
+  return { name, args: [], line };
+}
+
+/**
+ * Split an attribute argument list into positional tokens, `key = value` pairs and nested lists
+ */
+export function parseAttributeArgs(text: string): RustAttributeArg[] {
+  const args: RustAttributeArg[] = [];
+
+  for (const part of splitTopLevelArgs(text)) {
+    const token = part.trim();
+    if (!token) continue;
+
+    const pair = token.match(/^([A-Za-z_][A-Za-z0-9_:]*)\s*=\s*([\s\S]+)$/);
+    if (pair) {
+      args.push({ key: pair[1], value: unquote(pair[2].trim()) });
+      continue;
+    }
+
+    const list = token.match(/^([A-Za-z_][A-Za-z0-9_:]*)\s*\(([\s\S]*)\)$/);
+    if (list) {
+      args.push({ value: list[1], args: parseAttributeArgs(list[2]) });
+      continue;
+    }
+
+    args.push({ value: unquote(token) });
+  }
+
+  return args;
+}
+
+/**
+ * Arguments of every attribute with the given name (full path or last segment), in source order
+ */
+export function attributeArgs(attributes: RustAttribute[] | undefined, name: string): RustAttributeArg[] {
+  return (attributes || [])
+    .filter(attribute => attribute.name === name || attribute.name.split('::').pop() === name)
+    .flatMap(attribute => attribute.args);
+}
+
+/**
+ * Whether an attribute satisfies a pattern written like one, e.g. `tokio::test`, `derive(Serialize)` or
+ * `cfg(test)`. Paths match in full or by last segment, so `test` also matches `tokio::test` and
+ * `Serialize` matches `serde::Serialize`. Each pattern argument must appear among the attribute's
+ * arguments at any nesting depth, so `cfg(test)` matches `cfg(all(test, unix))`.
+ */
+export function matchesAttribute(attribute: RustAttribute, pattern: string): boolean {
+  const wanted = parseAttribute(pattern, 0);
+  if (!pathMatches(attribute.name, wanted.name)) return false;
+  if (wanted.value !== undefined && attribute.value !== wanted.value) return false;
+  return wanted.args.every(arg => containsArg(attribute.args, arg));
+}
+
+function pathMatches(path: string, wanted: string): boolean {
+  return path === wanted || path.split('::').pop() === wanted;
+}
+
+function containsArg(args: RustAttributeArg[], wanted: RustAttributeArg): boolean {
+  return args.some(arg => {
+    const matches = wanted.key !== undefined
+      ? arg.key !== undefined && pathMatches(arg.key, wanted.key) && arg.value === wanted.value
+      : arg.key === undefined && pathMatches(arg.value, wanted.value) && (wanted.args || []).every(nested => containsArg(arg.args || [], nested));
+    return matches || containsArg(arg.args || [], wanted);
+  });
+}
+
+export interface CfgOptions {
+  features: Set<string>; // Enabled cargo features
+  test: boolean; // Whether `cfg(test)` holds
+}
+
+/**
+ * Evaluate a `cfg` predicate. Returns undefined when it depends on configuration we do not model
+ * (target, debug_assertions, ...), so callers only act on predicates known to be false.
+ */
+export function evaluateCfg(predicate: RustAttributeArg, options: CfgOptions): boolean | undefined {
+  if (predicate.key !== undefined) {
+    return predicate.key === 'feature' ? options.features.has(predicate.value) : undefined;
+  }
+
+  if (predicate.args) {
+    const results = predicate.args.map(arg => evaluateCfg(arg, options));
+    switch (predicate.value) {
+      case 'not':
+        return results.length === 1 && results[0] !== undefined ? !results[0] : undefined;
+      case 'all':
+        return results.includes(false) ? false : results.includes(undefined) ? undefined : true;
+      case 'any':
+        return results.includes(true) ? true : results.includes(undefined) ? undefined : false;
+      default:
+        return undefined;
+    }
+  }
+
+  return predicate.value === 'test' ? options.test : undefined;
+}
+
+/**
+ * Render a parsed argument back to source form, e.g. `all(unix, feature = "extra")`
+ */
+export function formatAttributeArg(arg: RustAttributeArg): string {
+  if (arg.key !== undefined) return `${arg.key} = "${arg.value}"`;
+  if (arg.args) return `${arg.value}(${arg.args.map(formatAttributeArg).join(', ')})`;
+  return arg.value;
+}
+
+function findClosing(text: string, openIndex: number): number {
+  // Index of the bracket closing the one at openIndex, or -1 when unbalanced
+  let depth = 0;
+  for (let i = openIndex; i < text.length; i++) {
+    const char = text[i];
+    if (char === '"') {
+      i = skipString(text, i);
+    } else if (char === '(' || char === '[' || char === '{') {
+      depth++;
+    } else if (char === ')' || char === ']' || char === '}') {
+      depth--;
+      if (depth === 0) return i;
+    }
+  }
+  return -1;
+}
+
+function splitTopLevelArgs(text: string): string[] {
+  const parts: string[] = [];
+  let depth = 0;
+  let start = 0;
+
+  for (let i = 0; i < text.length; i++) {
+    const char = text[i];
+    if (char === '"') {
+      i = skipString(text, i);
+    } else if (char === '(' || char === '[' || char === '{') {
+      depth++;
+    } else if (char === ')' || char === ']' || char === '}') {
+      depth = Math.max(0, depth - 1);
+    } else if (char === ',' && depth === 0) {
+      parts.push(text.substring(start, i));
+      start = i + 1;
+    }
+  }
+  parts.push(text.substring(start));
+
+  return parts;
+}
+
+function skipString(text: string, quoteIndex: number): number {
+  // Index of the closing quote (or end of text)
+  for (let i = quoteIndex + 1; i < text.length; i++) {
+    if (text[i] === '\\') i++;
+    else if (text[i] === '"') return i;
+  }
+  return text.length;
+}
+
+function unquote(value: string): string {
+  const match = value.match(/^"((?:[^"\\]|\\.)*)"$/);
+  return match ? match[1] : value;
+}
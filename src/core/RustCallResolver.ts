@@ -0,0 +1,151 @@
+import { MindMapStorage } from './MindMapStorage.js';
+import { MindMapNode } from '../types/index.js';
+
+/**
+ * Turn the unresolved path calls kept on Rust function nodes (`externalCalls`) into `calls` edges
+ * to functions defined in other files. Runs over the whole graph, so the result only depends on
+ * which nodes are present: a full scan and a merge of partial scans resolve the same calls.
+ * Integration tests reach the library through its crate name (`my_crate::f`), and their calls
+ * into it also get `tests` edges. Globs (`use local::*`) naming a module in the graph are expanded
+ * to the names the module brings into scope, listed on the glob's node as `members`, and calls
+ * recorded as possibly coming through a glob (`globCalls`) resolve through them. Globs of modules
+ * outside the graph stay opaque. Returns the number of edges added; running it again adds none.
+ */
+export function resolveCrossFileCalls(storage: MindMapStorage): number {
+  const functions = new Map<string, MindMapNode[]>(); // `module::name` -> functions
+  const types = new Map<string, MindMapNode[]>(); // `module::name` -> structs and traits (both `class` nodes)
+  const modules = new Map<string, MindMapNode[]>(); // Module -> the items it declares, for glob expansion
+  for (const node of storage.findNodes(node => node.metadata?.language === 'rust' && typeof node.metadata.module === 'string')) {
+    const index = node.type === 'function' ? functions : node.type === 'class' ? types : undefined;
+    if (index) {
+      const key = `${node.metadata.module}::${node.name}`;
+      index.set(key, [...(index.get(key) || []), node]);
+    }
+    if (isModuleItem(node)) modules.set(node.metadata.module, [...(modules.get(node.metadata.module) || []), node]);
+  }
+
+  let added = 0;
+  const link = (caller: MindMapNode, callee: MindMapNode, metadata: Record<string, unknown>) => {
+    const types: Array<'calls' | 'tests'> = caller.metadata.isIntegrationTest && !callee.metadata.isIntegrationTest ? ['calls', 'tests'] : ['calls'];
+    for (const type of types) {
+      const id = `${caller.id}->${type}->${callee.id}`;
+      if (storage.getEdge(id)) continue;
+      storage.addEdge({
+        id,
+        source: caller.id,
+        target: callee.id,
+        type,
+        metadata: { crossFile: true, ...metadata },
+        confidence: 0.7,
+        lastUpdated: new Date()
+      });
+      added++;
+    }
+  };
+
+  for (const caller of storage.findNodes(node => node.type === 'function' && node.metadata?.externalCalls?.length > 0)) {
+    for (const call of caller.metadata.externalCalls as string[]) {
+      const callee = resolveCall(call, caller, functions, types);
+      if (callee && callee.id !== caller.id) link(caller, callee, { call });
+    }
+  }
+
+  const globs = expandGlobs(storage, modules);
+  for (const caller of storage.findNodes(node => node.type === 'function' && node.metadata?.globCalls?.length > 0)) {
+    const expanded = (globs.get(caller.path!) || []).filter(glob => glob.metadata.expanded);
+    for (const call of caller.metadata.globCalls as string[]) {
+      // The first glob bringing the name in; rustc rejects a name two globs bring in, so order rarely matters
+      const glob = expanded.find(candidate => candidate.metadata.members.includes(call.split('::')[0]));
+      const callee = glob ? resolveCall(`${glob.metadata.resolvedPath}::${call}`, caller, functions, types) : undefined;
+      if (callee && callee.id !== caller.id) link(caller, callee, { call, glob: glob!.metadata.source });
+    }
+  }
+  return added;
+}
+
+function isModuleItem(node: MindMapNode): boolean {
+  // Names a module declares: free functions, types and traits, consts and statics, but not methods or variants
+  if (node.type === 'function') return !node.metadata.implTarget && !node.metadata.traitName;
+  if (node.type === 'class') return true;
+  return node.type === 'variable' && ['const', 'static'].includes(node.metadata.variableType) && !node.metadata.implTarget;
+}
+
+function expandGlobs(storage: MindMapStorage, modules: Map<string, MindMapNode[]>): Map<string, MindMapNode[]> {
+  // Glob imports and glob re-exports by file, with the members of those naming a module in the graph
+  const byFile = new Map<string, MindMapNode[]>();
+  const globs = storage.findNodes(node => node.type === 'pattern' && node.metadata?.isGlob === true &&
+    (node.metadata.patternType === 'glob_import' || node.metadata.patternType === 'reexport'));
+  for (const glob of globs.sort((a, b) => (a.metadata.startLine || 0) - (b.metadata.startLine || 0) || a.id.localeCompare(b.id))) {
+    const module = (glob.metadata.resolvedPath as string).replace(/^crate(?:::|$)/, '') || 'crate';
+    const items = modules.get(module);
+    if (items) {
+      // Private items are visible inside the module and its descendants only
+      const importer: string = glob.metadata.module;
+      const inside = module === 'crate' || importer === module || importer.startsWith(`${module}::`);
+      const members = items.filter(item => inside || (item.metadata.visibility || 'private') !== 'private').map(item => item.name);
+      glob.metadata.members = Array.from(new Set(members)).sort();
+      glob.metadata.expanded = true;
+    } else {
+      delete glob.metadata.members;
+      glob.metadata.expanded = false;
+    }
+    byFile.set(glob.path!, [...(byFile.get(glob.path!) || []), glob]);
+  }
+  return byFile;
+}
+
+function resolveCall(
+  call: string,
+  caller: MindMapNode,
+  functions: Map<string, MindMapNode[]>,
+  types: Map<string, MindMapNode[]>
+): MindMapNode | undefined {
+  const segments = call.split('::');
+  const name = segments.pop()!;
+  if (segments.length === 0) return undefined;
+
+  for (const modulePath of candidateModules(segments, caller.metadata.module, caller.metadata.isIntegrationTest === true)) {
+    const direct = pick(functions.get(`${moduleKey(modulePath)}::${name}`), caller);
+    if (direct) return direct;
+
+    // `Type::function` resolves to an associated function declared beside the type
+    if (modulePath.length === 0) continue;
+    const typeName = modulePath[modulePath.length - 1];
+    const typeModule = moduleKey(modulePath.slice(0, -1));
+    for (const type of types.get(`${typeModule}::${typeName}`) || []) {
+      const method = pick(functions.get(`${typeModule}::${name}`)?.filter(func => func.path === type.path), caller);
+      if (method) return method;
+    }
+  }
+  return undefined;
+}
+
+function candidateModules(qualifier: string[], callerModule: string, integrationTest: boolean): string[][] {
+  // Crate-relative module paths the qualifier may name, most specific first
+  const current = callerModule === 'crate' ? [] : callerModule.split('::');
+  const [first, ...rest] = qualifier;
+  if (first === 'crate') return integrationTest ? [] : [rest];
+  if (integrationTest && first !== 'self' && first !== 'super') return [rest]; // `my_crate::...` names the library under test
+  if (first === 'self') return [[...current, ...rest]];
+  if (first === 'super') {
+    const base = [...current];
+    let index = 0;
+    while (qualifier[index] === 'super') {
+      base.pop();
+      index++;
+    }
+    return [[...base, ...qualifier.slice(index)]];
+  }
+  return current.length > 0 ? [[...current, ...qualifier], qualifier] : [qualifier];
+}
+
+function moduleKey(modulePath: string[]): string {
+  return modulePath.length > 0 ? modulePath.join('::') : 'crate';
+}
+
+function pick(candidates: MindMapNode[] | undefined, caller: MindMapNode): MindMapNode | undefined {
+  // Only calls into other files are resolved here; ties go to the lowest ID so the choice is stable
+  return (candidates || [])
+    .filter(candidate => candidate.path !== caller.path)
+    .sort((a, b) => a.id.localeCompare(b.id))[0];
+}
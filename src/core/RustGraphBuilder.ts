@@ -0,0 +1,1357 @@
+import { posix } from 'path';
+import { MindMapNode, MindMapEdge, EvidenceSpan } from '../types/index.js';
+import { mergeEvidence } from './MindMapStorage.js';
+import { RustCodeStructure, RustGenericParam, RustDiagnostic, RustDefaultValue, RustMethodShape } from './RustAnalyzer.js';
+import { RustImportScope, ImportScopeOptions } from './RustImportScope.js';
+import { RustAttribute } from './RustAttributes.js';
+
+// `#[test]`, `#[tokio::test]`, `#[rstest]` and similar
+const TEST_ATTRIBUTE = /(?:^|::)(?:test|rstest|test_case)$/;
+
+// `#[no_mangle]` and `#[export_name = ".."]`, also in their edition 2024 `#[unsafe(..)]` form
+const FFI_EXPORT_ATTRIBUTES = new Set(['no_mangle', 'export_name']);
+
+function exportsSymbol(attribute: RustAttribute): boolean {
+  const names = attribute.name === 'unsafe' ? attribute.args.map(arg => arg.key ?? arg.value) : [attribute.name];
+  return names.some(name => FFI_EXPORT_ATTRIBUTES.has(name));
+}
+
+// Derives that bound every type parameter by the derived trait (`#[derive(Clone)] struct W<T>` needs `T: Clone`)
+const STD_BOUNDING_DERIVES = new Set(['Clone', 'Copy', 'Debug', 'Default', 'PartialEq', 'Eq', 'PartialOrd', 'Ord', 'Hash']);
+
+// Derives that only bound the type parameters appearing in field types
+const FIELD_BOUNDING_DERIVES = new Set(['Serialize', 'Deserialize']);
+
+// Traits through which a newtype exposes or converts to its inner type
+const NEWTYPE_TRAITS = new Set(['Deref', 'DerefMut', 'AsRef', 'AsMut', 'Borrow', 'BorrowMut', 'From', 'TryFrom', 'Into']);
+
+// Crates whose calls can never resolve to project code
+const EXTERNAL_CRATE_ROOTS = new Set(['std', 'core', 'alloc']);
+
+// Free functions in scope everywhere through the std prelude
+const PRELUDE_FUNCTIONS = new Set(['drop']);
+
+// Calls running a closure on another thread or task: `thread::spawn`, `tokio::spawn`, `handle.spawn`, ...
+const SPAWN_CALL = /^(?:\.|(?:[\w:]+::)?)spawn(?:_blocking)?$/;
+
+/**
+ * Converts a RustCodeStructure into mind map nodes and edges
+ */
+export class RustGraphBuilder {
+  constructor(private importScopeOptions: ImportScopeOptions = {}) {}
+
+  /**
+   * Nodes and edges for a parsed file. `fileModule` is the module the file's items land in, derived from
+   * the path unless given, as for the items an `include!` pastes into another module.
+   */
+  createMindMapElements(
+    structure: RustCodeStructure,
+    filePath: string,
+    fileModule: string[] = this.fileModulePath(filePath)
+  ): { nodes: MindMapNode[], edges: MindMapEdge[], diagnostics: RustDiagnostic[] } {
+    const nodes: MindMapNode[] = [];
+    const edges: MindMapEdge[] = [];
+    const diagnostics: RustDiagnostic[] = [...(structure.diagnostics || [])];
+    const scope = new RustImportScope(structure.rustImports, this.importScopeOptions);
+    this.expandLocalGlobs(scope, structure, fileModule);
+
+    // Traits implemented per type name, including traits defined outside this file
+    const implementedTraits = new Map<string, string[]>();
+    const associatedTypeBindings = new Map<string, Array<{ trait: string; name: string; type: string; line: number }>>();
+    const conditionalImpls = new Map<string, Array<{ trait: string; genericParams: string[]; conditions: string[]; line: number }>>();
+    const defaultValues = new Map<string, RustDefaultValue[]>(); // From manual `impl Default`
+    const asyncMethodsWithin = (block: { startLine: number; endLine: number }) => structure.functions
+      .filter(func => func.isAsync && func.startLine > block.startLine && func.endLine <= block.endLine)
+      .map(func => func.name);
+    const methodShapesWithin = (block: { startLine: number; endLine: number }): RustMethodShape[] => structure.functions
+      .filter(func => func.startLine > block.startLine && func.endLine <= block.endLine)
+      .map(func => this.methodShape(func));
+    const inherentMethods = new Map<string, RustMethodShape[]>(); // Methods of impl blocks without a trait
+    for (const impl of structure.impls) {
+      if (!impl.trait) {
+        const targetName = this.baseTypeName(impl.target);
+        inherentMethods.set(targetName, [...(inherentMethods.get(targetName) || []), ...methodShapesWithin(impl)]);
+        continue;
+      }
+      const targetName = this.baseTypeName(impl.target);
+      const traitName = this.baseTypeName(impl.trait);
+      implementedTraits.set(targetName, [...(implementedTraits.get(targetName) || []), traitName]);
+      associatedTypeBindings.set(targetName, [
+        ...(associatedTypeBindings.get(targetName) || []),
+        ...(impl.associatedTypes || []).map(binding => ({ trait: traitName, ...binding }))
+      ]);
+      if (impl.defaultValues) defaultValues.set(targetName, impl.defaultValues);
+      const conditions = this.implConditions(impl);
+      if (conditions.length > 0) {
+        conditionalImpls.set(targetName, [...(conditionalImpls.get(targetName) || []), {
+          trait: traitName,
+          genericParams: (impl.genericParams || []).filter(param => param.kind === 'type').map(param => param.name),
+          conditions,
+          line: impl.startLine
+        }]);
+      }
+    }
+    const resultImport = this.resultImport(scope);
+
+    // Type nodes (structs, traits), keyed by bare and qualified name for type resolution
+    const typeIds = new Map<string, string>();
+    for (const struct of structure.structs) {
+      const qualifiedName = this.qualify(struct.modulePath || [], struct.name);
+      const structId = `${filePath}#struct:${qualifiedName}`;
+
+      nodes.push({
+        id: structId,
+        type: 'class',
+        name: struct.name,
+        path: filePath,
+        metadata: {
+          rustKind: 'struct',
+          qualifiedName,
+          module: this.moduleName(fileModule, struct.modulePath),
+          startLine: struct.startLine,
+          endLine: struct.endLine,
+          properties: struct.fields,
+          propertyTypes: struct.fieldTypes || [],
+          truncatedType: struct.truncatedType || false,
+          isNewtype: struct.wrappedType !== undefined,
+          wrappedType: struct.wrappedType,
+          reprTransparent: struct.reprTransparent || false,
+          newtypeImpls: struct.wrappedType !== undefined ? this.newtypeImpls(structure, struct.name) : [],
+          propertyVisibility: struct.fieldVisibility || [],
+          constructors: this.constructors(structure, struct.name),
+          serdeNames: struct.serdeNames || struct.fields,
+          typeParameters: struct.typeParameters || [],
+          derives: struct.derives,
+          importedReferences: this.importedReferences(this.mentionedNames(struct.fieldTypes || [], struct.derives), scope),
+          attributes: struct.attributes || [],
+          implementedTraits: implementedTraits.get(struct.name) || [],
+          inherentMethods: inherentMethods.get(struct.name) || [],
+          associatedTypeBindings: associatedTypeBindings.get(struct.name) || [],
+          conditionalImpls: conditionalImpls.get(struct.name) || [],
+          defaultImpl: (struct.derives || []).includes('Default') ? 'derived' : defaultValues.has(struct.name) ? 'manual' : undefined,
+          defaultValues: defaultValues.get(struct.name) || [],
+          isExported: struct.isPublic || false,
+          visibility: struct.visibility || 'private',
+          docComment: struct.docComment,
+          cfg: struct.cfg || [],
+          inactive: struct.inactive || false,
+          language: 'rust'
+        },
+        confidence: 0.9,
+        lastUpdated: new Date()
+      });
+      edges.push(this.containsEdge(filePath, structId));
+
+      const structParams = struct.genericParams || (struct.typeParameters || []).map((name): RustGenericParam => ({ name, kind: 'type' }));
+      const fieldSites = (struct.fieldTypes || []).map((type, index) => ({ site: { field: struct.fields[index] }, type }));
+      const structGenerics = this.genericParamElements(filePath, structId, struct.name, qualifiedName, structParams, struct.startLine, fieldSites);
+      nodes.push(...structGenerics.nodes);
+      edges.push(...structGenerics.edges);
+
+      typeIds.set(qualifiedName, structId);
+      if (!typeIds.has(struct.name)) {
+        typeIds.set(struct.name, structId);
+      }
+    }
+
+    for (const trait of structure.traits) {
+      const traitId = `${filePath}#trait:${trait.name}`;
+
+      nodes.push({
+        id: traitId,
+        type: 'class',
+        name: trait.name,
+        path: filePath,
+        metadata: {
+          rustKind: 'trait',
+          qualifiedName: trait.name,
+          module: this.moduleName(fileModule, trait.modulePath),
+          startLine: trait.startLine,
+          endLine: trait.endLine,
+          methods: trait.methods,
+          defaultMethods: trait.defaultMethods || [],
+          methodShapes: methodShapesWithin(trait),
+          asyncMethods: structure.functions.filter(func => func.traitName === trait.name && func.isAsync).map(func => func.name),
+          associatedTypes: trait.associatedTypes,
+          associatedTypeBounds: trait.associatedTypeBounds || {},
+          supertraits: trait.supertraits || [],
+          attributes: trait.attributes || [],
+          visibility: trait.visibility || 'private',
+          docComment: trait.docComment,
+          cfg: trait.cfg || [],
+          inactive: trait.inactive || false,
+          language: 'rust'
+        },
+        confidence: 0.9,
+        lastUpdated: new Date()
+      });
+      edges.push(this.containsEdge(filePath, traitId));
+
+      if (!typeIds.has(trait.name)) {
+        typeIds.set(trait.name, traitId);
+      }
+    }
+
+    // Trait aliases stand for their composed traits; local ones are linked
+    for (const alias of structure.traitAliases || []) {
+      const aliasId = `${filePath}#trait_alias:${alias.name}`;
+      nodes.push({
+        id: aliasId,
+        type: 'class',
+        name: alias.name,
+        path: filePath,
+        metadata: {
+          rustKind: 'trait_alias',
+          qualifiedName: this.qualify(alias.modulePath || [], alias.name),
+          module: this.moduleName(fileModule, alias.modulePath),
+          startLine: alias.startLine,
+          endLine: alias.endLine,
+          aliasOf: alias.traits,
+          bounds: alias.bounds,
+          genericParams: alias.genericParams || [],
+          attributes: alias.attributes || [],
+          visibility: alias.visibility || 'private',
+          docComment: alias.docComment,
+          language: 'rust'
+        },
+        confidence: 0.9,
+        lastUpdated: new Date()
+      });
+      edges.push(this.containsEdge(filePath, aliasId));
+
+      for (const traitName of alias.traits) {
+        const traitId = typeIds.get(traitName);
+        if (!traitId?.includes('#trait:')) continue;
+        edges.push({
+          id: `${aliasId}->alias_of->${traitId}`,
+          source: aliasId,
+          target: traitId,
+          type: 'alias_of',
+          metadata: { trait: traitName },
+          confidence: 0.9,
+          lastUpdated: new Date()
+        });
+      }
+      if (!typeIds.has(alias.name)) {
+        typeIds.set(alias.name, aliasId);
+      }
+    }
+
+    // Associated types get a node per trait, linked to the local traits their bounds require
+    for (const trait of structure.traits) {
+      const traitId = `${filePath}#trait:${trait.name}`;
+      for (const name of trait.associatedTypes) {
+        const assocId = `${filePath}#assoc_type:${trait.name}::${name}`;
+        const bounds = trait.associatedTypeBounds?.[name] || [];
+        nodes.push({
+          id: assocId,
+          type: 'type_parameter',
+          name,
+          path: filePath,
+          metadata: {
+            kind: 'associated_type',
+            owner: trait.name,
+            ownerId: traitId,
+            bounds,
+            genericParams: trait.associatedTypeGenerics?.[name] || [],
+            startLine: trait.startLine,
+            endLine: trait.endLine,
+            language: 'rust'
+          },
+          confidence: 0.9,
+          lastUpdated: new Date()
+        });
+        edges.push(this.containsEdge(traitId, assocId));
+
+        for (const bound of bounds) {
+          const boundId = typeIds.get(bound);
+          if (!boundId?.includes('#trait:')) continue;
+          edges.push({
+            id: `${assocId}->requires_bound->${boundId}`,
+            source: assocId,
+            target: boundId,
+            type: 'requires_bound',
+            metadata: { trait: bound, bound: `${name}: ${bound}` },
+            confidence: 0.85,
+            lastUpdated: new Date()
+          });
+        }
+      }
+    }
+
+    // Enum nodes contain a node per variant
+    const enumVariants = new Map<string, { name: string; variantIds: Map<string, string> }>();
+    for (const enumItem of structure.enums || []) {
+      const qualifiedName = this.qualify(enumItem.modulePath || [], enumItem.name);
+      const enumId = `${filePath}#enum:${qualifiedName}`;
+
+      nodes.push({
+        id: enumId,
+        type: 'class',
+        name: enumItem.name,
+        path: filePath,
+        metadata: {
+          rustKind: 'enum',
+          qualifiedName,
+          module: this.moduleName(fileModule, enumItem.modulePath),
+          startLine: enumItem.startLine,
+          endLine: enumItem.endLine,
+          variants: enumItem.variants.map(variant => variant.name),
+          derives: enumItem.derives,
+          attributes: enumItem.attributes || [],
+          implementedTraits: implementedTraits.get(enumItem.name) || [],
+          inherentMethods: inherentMethods.get(enumItem.name) || [],
+          conditionalImpls: conditionalImpls.get(enumItem.name) || [],
+          visibility: enumItem.visibility || 'private',
+          isExported: enumItem.visibility === 'pub',
+          docComment: enumItem.docComment,
+          cfg: enumItem.cfg || [],
+          inactive: enumItem.inactive || false,
+          language: 'rust'
+        },
+        confidence: 0.9,
+        lastUpdated: new Date()
+      });
+      edges.push(this.containsEdge(filePath, enumId));
+
+      const variantIds = new Map<string, string>();
+      for (const variant of enumItem.variants) {
+        const variantId = `${filePath}#variant:${qualifiedName}::${variant.name}`;
+        nodes.push({
+          id: variantId,
+          type: 'variable',
+          name: variant.name,
+          path: filePath,
+          metadata: {
+            variableType: 'enum_variant',
+            qualifiedName: `${qualifiedName}::${variant.name}`,
+            enum: enumItem.name,
+            enumId,
+            module: this.moduleName(fileModule, enumItem.modulePath),
+            startLine: variant.line,
+            endLine: variant.line,
+            visibility: enumItem.visibility || 'private', // Variants are as visible as their enum
+            cfg: enumItem.cfg || [],
+            inactive: enumItem.inactive || false,
+            language: 'rust'
+          },
+          confidence: 0.9,
+          lastUpdated: new Date()
+        });
+        edges.push(this.containsEdge(enumId, variantId));
+        variantIds.set(variant.name, variantId);
+      }
+      enumVariants.set(enumId, { name: enumItem.name, variantIds });
+
+      typeIds.set(qualifiedName, enumId);
+      if (!typeIds.has(enumItem.name)) {
+        typeIds.set(enumItem.name, enumId);
+      }
+    }
+
+    // Struct field types
+    for (const struct of structure.structs) {
+      const structId = typeIds.get(this.qualify(struct.modulePath || [], struct.name));
+      if (!structId) continue;
+
+      for (const fieldType of struct.fieldTypes || []) {
+        for (const typeId of this.resolveTypeReferences(fieldType, typeIds, scope)) {
+          if (typeId === structId) continue;
+          edges.push(this.referenceEdge(structId, typeId, 'field_type'));
+        }
+      }
+    }
+
+    // Newtypes wrap the project types named by their single field
+    for (const struct of structure.structs) {
+      const structId = typeIds.get(this.qualify(struct.modulePath || [], struct.name));
+      if (!structId || struct.wrappedType === undefined) continue;
+
+      for (const typeId of this.resolveTypeReferences(struct.wrappedType, typeIds, scope)) {
+        if (typeId === structId) continue;
+        edges.push({
+          id: `${structId}->wraps->${typeId}`,
+          source: structId,
+          target: typeId,
+          type: 'wraps',
+          metadata: { wrappedType: struct.wrappedType, reprTransparent: struct.reprTransparent || false, impls: this.newtypeImpls(structure, struct.name) },
+          evidence: [{ filePath, startLine: struct.startLine, endLine: struct.startLine, kind: 'newtype' }],
+          confidence: 0.9,
+          lastUpdated: new Date()
+        });
+      }
+    }
+
+    // Trait implementations, from impl blocks and from derives of local traits
+    const implementsEdges = new Map<string, MindMapEdge>();
+    const addImplements = (targetId: string, traitId: string, evidence: EvidenceSpan, metadata?: Record<string, any>) => {
+      const id = `${targetId}->implements->${traitId}`;
+      const existing = implementsEdges.get(id);
+      if (existing) {
+        existing.evidence = mergeEvidence(existing.evidence, [evidence]);
+        existing.metadata = { ...existing.metadata, ...metadata };
+        return;
+      }
+      implementsEdges.set(id, {
+        id,
+        source: targetId,
+        target: traitId,
+        type: 'implements',
+        metadata,
+        evidence: [evidence],
+        confidence: 0.9,
+        lastUpdated: new Date()
+      });
+    };
+
+    for (const impl of structure.impls) {
+      if (!impl.trait) continue;
+      const targetId = typeIds.get(this.baseTypeName(impl.target));
+      const traitId = typeIds.get(this.baseTypeName(impl.trait));
+      if (targetId && traitId) {
+        addImplements(
+          targetId,
+          traitId,
+          { filePath, startLine: impl.startLine, endLine: impl.endLine, kind: 'impl' },
+          {
+            startLine: impl.startLine,
+            endLine: impl.endLine,
+            methods: impl.methods,
+            asyncMethods: asyncMethodsWithin(impl),
+            methodShapes: methodShapesWithin(impl),
+            // `impl<T: Clone> Clone for Wrapper<T>` only holds when its bounds do
+            conditional: this.implConditions(impl).length > 0,
+            conditions: this.implConditions(impl)
+          }
+        );
+      }
+    }
+    // Derives generate trait impls: record each as a synthetic impl node marked with its macro
+    for (const struct of structure.structs) {
+      const qualifiedName = this.qualify(struct.modulePath || [], struct.name);
+      const structId = typeIds.get(qualifiedName);
+      for (const attribute of struct.attributes || []) {
+        if (!structId || this.baseTypeName(attribute.name) !== 'derive') continue;
+        for (const arg of attribute.args) {
+          const traitName = this.baseTypeName(arg.value);
+          const implId = `${filePath}#impl:${traitName} for ${qualifiedName}`;
+          const generatedBy = { kind: 'derive', macro: arg.value, line: attribute.line };
+
+          nodes.push({
+            id: implId,
+            type: 'pattern',
+            name: `${traitName} for ${struct.name}`,
+            path: filePath,
+            metadata: {
+              patternType: 'trait_impl',
+              trait: arg.value,
+              target: struct.name,
+              targetId: structId,
+              generated: true,
+              generatedBy,
+              startLine: attribute.line,
+              endLine: attribute.line,
+              language: 'rust'
+            },
+            confidence: 0.85,
+            lastUpdated: new Date()
+          });
+          edges.push(this.containsEdge(filePath, implId));
+
+          // The generated impl only exists when the bounded type parameters implement the trait too
+          for (const param of this.deriveBoundParameters(traitName, struct)) {
+            const paramId = this.typeParameterId(filePath, qualifiedName, param);
+            edges.push({
+              id: `${paramId}->requires_bound->${implId}`,
+              source: paramId,
+              target: implId,
+              type: 'requires_bound',
+              metadata: { trait: traitName, bound: `${param}: ${traitName}`, generatedBy },
+              evidence: [{ filePath, startLine: attribute.line, endLine: attribute.line, kind: 'derive' }],
+              confidence: 0.85,
+              lastUpdated: new Date()
+            });
+          }
+
+          const traitId = typeIds.get(traitName);
+          if (traitId?.includes('#trait:')) {
+            addImplements(
+              structId,
+              traitId,
+              { filePath, startLine: attribute.line, endLine: attribute.line, kind: 'derive' },
+              { implNode: implId, generatedBy }
+            );
+          }
+        }
+      }
+    }
+    for (const edge of implementsEdges.values()) {
+      // A hand-written impl block makes the edge real source even when a derive also exists
+      if (edge.metadata?.generatedBy && edge.evidence?.some(span => span.kind === 'impl')) {
+        delete edge.metadata.generatedBy;
+      }
+    }
+    edges.push(...implementsEdges.values());
+
+    // Function nodes, enriched with Rust signature details
+    for (const func of structure.functions) {
+      const functionId = `${filePath}#function:${func.name}`;
+
+      nodes.push({
+        id: functionId,
+        type: 'function',
+        name: func.name,
+        path: filePath,
+        metadata: {
+          module: this.moduleName(fileModule, func.modulePath),
+          startLine: func.startLine,
+          endLine: func.endLine,
+          traitName: func.traitName,
+          implTarget: func.implTarget,
+          implTrait: this.enclosingTraitImpl(structure, func.startLine),
+          receiver: func.receiver || 'none',
+          receiverMutable: func.receiverMutable || false,
+          borrowsFrom: func.borrowsFrom || [],
+          isTraitDefault: func.traitName !== undefined && func.hasBody === true,
+          parameters: func.parameters || [],
+          returnType: func.returnType,
+          resultAlias: this.resultAlias(func.returnType, resultImport),
+          errorType: this.resultErrorType(func.returnType),
+          signature: func.signature,
+          parameterTypes: func.parameterTypes || [],
+          truncatedType: func.truncatedType || false,
+          parameterBounds: func.parameterBounds || [],
+          genericBounds: func.genericBounds || [],
+          genericParams: func.genericParams || [],
+          typeParameters: func.typeParameters || [],
+          bodyHash: func.bodyHash,
+          bodySize: func.bodySize,
+          complexity: func.complexity,
+          nestingDepth: func.nestingDepth,
+          awaitedCalls: func.awaitedCalls || [],
+          spawns: (func.calls || []).some(call => SPAWN_CALL.test(call)),
+          typeMentions: func.typeMentions || [],
+          discardedCalls: func.discardedCalls || [],
+          shadowedBindings: func.shadowedBindings || [],
+          argumentTypes: func.argumentTypes || [],
+          fieldMentions: func.fieldMentions || [],
+          importedReferences: this.importedReferences(
+            this.mentionedNames([...(func.parameterTypes || []), func.returnType], [...(func.calls || []), ...(func.typeMentions || [])]),
+            scope
+          ),
+          methodChains: func.methodChains || [],
+          callSequences: func.callSequences || [],
+          literals: func.literals || [],
+          panicSites: func.panicSites || [],
+          cloneSites: func.cloneSites || [],
+          matchSites: func.matchSites || [],
+          locksAcrossAwait: func.locksAcrossAwait || [],
+          readOnlyParameters: func.readOnlyParameters || [],
+          constEligibility: func.constEligibility,
+          isBuilderMethod: this.isBuilderMethod(func.parameters || [], func.returnType),
+          isAsync: func.isAsync || false,
+          isUnsafe: func.isUnsafe || false,
+          isConst: func.isConst || false,
+          abi: func.abi,
+          isForeign: func.isForeign || false,
+          // Callable across the language boundary, in either direction
+          ffiBoundary: func.abi !== undefined || (func.attributes || []).some(exportsSymbol),
+          isExported: func.isPublic || false,
+          visibility: func.visibility || 'private',
+          docComment: func.docComment,
+          cfg: func.cfg || [],
+          inactive: func.inactive || false,
+          attributes: func.attributes || [],
+          language: 'rust'
+        },
+        confidence: 0.9,
+        lastUpdated: new Date()
+      });
+
+      // Only const parameters of functions get nodes, so their uses as array lengths can be linked
+      const signatureSites = [
+        ...(func.parameterTypes || []).map((type, index) => ({ site: { parameter: func.parameters[index] }, type })),
+        ...(func.returnType ? [{ site: { returnType: true }, type: func.returnType }] : [])
+      ];
+      const constParams = (func.genericParams || []).filter(param => param.kind === 'const');
+      const functionGenerics = this.genericParamElements(filePath, functionId, func.name, func.name, constParams, func.startLine, signatureSites);
+      nodes.push(...functionGenerics.nodes);
+      edges.push(...functionGenerics.edges);
+
+      for (const parameterType of func.parameterTypes || []) {
+        for (const typeId of this.resolveTypeReferences(parameterType, typeIds, scope)) {
+          edges.push(this.referenceEdge(functionId, typeId, 'parameter_type'));
+        }
+      }
+      if (func.returnType) {
+        for (const typeId of this.resolveTypeReferences(func.returnType, typeIds, scope)) {
+          edges.push(this.referenceEdge(functionId, typeId, 'return_type'));
+        }
+      }
+
+      // Variants of a local error enum the body constructs with `Err(..)`
+      const errorType = this.resultErrorType(func.returnType);
+      for (const enumId of errorType ? this.resolveTypeReferences(errorType, typeIds, scope) : []) {
+        const errorEnum = enumVariants.get(enumId);
+        for (const construction of errorEnum ? func.errConstructions || [] : []) {
+          const [variant, owner] = construction.split('::').reverse();
+          const variantId = errorEnum!.variantIds.get(variant);
+          if (variantId && owner === errorEnum!.name) {
+            edges.push(this.referenceEdge(functionId, variantId, 'error_variant'));
+          }
+        }
+      }
+    }
+
+    // Calls between functions defined in this file, including those made from trait default bodies.
+    // Free and path calls that do not resolve here are kept on the caller for cross-file resolution.
+    const moduleNames = new Set(structure.modules.map(module => module.name));
+    const structFields = new Map<string, string[]>(nodes.filter(node => node.metadata.rustKind === 'struct').map(node => [node.id, node.metadata.properties]));
+    const functionNodes = new Map(nodes.filter(node => node.type === 'function').map(node => [node.id, node]));
+    for (const func of structure.functions) {
+      const callerId = `${filePath}#function:${func.name}`;
+      const awaitedCalls = new Set(func.awaitedCalls || []);
+      const callees = new Map<string, boolean>(); // Callee ID -> awaited at some call site
+      const externalCalls = new Set<string>(functionNodes.get(callerId)?.metadata.externalCalls || []);
+      const globCalls = new Set<string>(); // Calls that may come through a glob of another file's module
+
+      for (const written of func.calls || []) {
+        // A call through a renamed import names the original item: `use a::compute as calc; calc()`
+        const call = scope.resolve(written.split('::')[0])?.via === 'alias' ? scope.expand(written)! : written;
+        const calleeName = call.replace(/^\./, '').split('::').pop()!;
+        const callee = structure.functions.find(candidate => candidate.name === calleeName);
+        const external = !call.startsWith('.') && !EXTERNAL_CRATE_ROOTS.has(call.split('::')[0]);
+        if (!callee) {
+          const path = this.importedPath(call, scope);
+          if (external && path && !EXTERNAL_CRATE_ROOTS.has(path.split('::')[0])) externalCalls.add(path);
+          // Names not imported by name may come through an opaque glob, resolved once every module is known
+          if (external && scope.resolve(call.split('::')[0])?.ambiguous && !PRELUDE_FUNCTIONS.has(call) && !(func.parameters || []).includes(call)) {
+            globCalls.add(call);
+            continue;
+          }
+          // A bare call that is neither defined here nor imported; parameters may be closures
+          if (!path && !call.startsWith('.') && !func.inactive && !PRELUDE_FUNCTIONS.has(call) && !(func.parameters || []).includes(call)) {
+            const line = func.callSites?.find(site => site.call === written)?.line ?? func.startLine;
+            diagnostics.push({
+              severity: 'info',
+              code: 'unresolved-call',
+              message: `${call}() in ${func.name} matches no function in this file or its imports`,
+              span: { startLine: line, endLine: line },
+              item: func.name
+            });
+          }
+          continue;
+        }
+        if (callee.name === func.name) continue;
+
+        if (call.startsWith('.')) {
+          // Method calls only resolve to functions taking a self receiver
+          if (!(callee.parameters || []).some(param => param.endsWith('self'))) continue;
+        } else if (call.includes('::')) {
+          // Path calls only resolve through local types and modules
+          const segments = call.split('::');
+          const qualifier = segments[segments.length - 2];
+          if (!['Self', 'self', 'super', 'crate'].includes(qualifier) && !typeIds.has(qualifier) && !moduleNames.has(qualifier)) {
+            const path = this.importedPath(call, scope)!;
+            if (external && !EXTERNAL_CRATE_ROOTS.has(path.split('::')[0])) externalCalls.add(path);
+            continue;
+          }
+        }
+        const calleeId = `${filePath}#function:${callee.name}`;
+        callees.set(calleeId, callees.get(calleeId) || awaitedCalls.has(written));
+      }
+
+      const callerNode = functionNodes.get(callerId);
+      if (callerNode) {
+        callerNode.metadata.externalCalls = Array.from(externalCalls).sort();
+        callerNode.metadata.globCalls = Array.from(globCalls).sort();
+      }
+
+      for (const [calleeId, awaited] of callees) {
+        edges.push({
+          id: `${callerId}->calls->${calleeId}`,
+          source: callerId,
+          target: calleeId,
+          type: 'calls',
+          metadata: awaited ? { awaited: true } : undefined,
+          confidence: 0.8,
+          lastUpdated: new Date()
+        });
+      }
+
+      // Test functions also link to the production code they exercise: callees and the fields they read
+      if (!(func.attributes || []).some(attribute => TEST_ATTRIBUTE.test(attribute.name))) continue;
+      const testedFields = new Map<string, Set<string>>(); // Struct ID -> fields
+      for (const access of func.fieldAccesses || []) {
+        const candidates = access.receiverType
+          ? [typeIds.get(access.receiverType)]
+          : this.resolveTypeReferences(structure.functions.find(candidate => candidate.name === access.receiverCall)?.returnType || '', typeIds);
+        const structId = candidates.find(id => id && (structFields.get(id) || []).includes(access.field));
+        if (structId) testedFields.set(structId, (testedFields.get(structId) || new Set()).add(access.field));
+      }
+      const testedFunctions = Array.from(callees.keys()).filter(calleeId => {
+        const callee = functionNodes.get(calleeId);
+        return !callee?.metadata.attributes.some((attribute: { name: string }) => TEST_ATTRIBUTE.test(attribute.name));
+      });
+      for (const targetId of [...testedFunctions, ...testedFields.keys()]) {
+        const fields = testedFields.get(targetId);
+        edges.push({
+          id: `${callerId}->tests->${targetId}`,
+          source: callerId,
+          target: targetId,
+          type: 'tests',
+          metadata: fields ? { fields: Array.from(fields).sort() } : undefined,
+          confidence: 0.7,
+          lastUpdated: new Date()
+        });
+      }
+    }
+
+    // HTTP routes and their handlers
+    for (const route of structure.routes || []) {
+      const routeName = `${route.method} ${route.path}`;
+      const routeId = `${filePath}#route:${routeName}`;
+
+      nodes.push({
+        id: routeId,
+        type: 'pattern',
+        name: routeName,
+        path: filePath,
+        metadata: {
+          patternType: 'route',
+          method: route.method,
+          routePath: route.path,
+          handler: route.handler,
+          startLine: route.line,
+          endLine: route.line,
+          language: 'rust'
+        },
+        confidence: 0.85,
+        lastUpdated: new Date()
+      });
+      edges.push(this.containsEdge(filePath, routeId));
+
+      const handlerId = `${filePath}#function:${route.handler}`;
+      if (structure.functions.some(func => func.name === route.handler)) {
+        edges.push(this.referenceEdge(routeId, handlerId, 'route_handler'));
+      }
+    }
+
+    // Const and static items
+    for (const constant of structure.constants || []) {
+      const qualifiedName = this.qualify(
+        constant.implTarget ? [...constant.modulePath, this.baseTypeName(constant.implTarget)] : constant.modulePath,
+        constant.name
+      );
+      const constId = `${filePath}#${constant.kind}:${qualifiedName}`;
+
+      nodes.push({
+        id: constId,
+        type: 'variable',
+        name: constant.name,
+        path: filePath,
+        metadata: {
+          variableType: constant.kind,
+          qualifiedName,
+          dataType: constant.type,
+          initialValue: constant.value,
+          visibility: constant.visibility,
+          isExported: constant.isPublic,
+          isMutable: constant.isMutable,
+          scope: constant.modulePath.length > 0 ? constant.modulePath.join('::') : 'crate',
+          module: this.moduleName(fileModule, constant.modulePath),
+          implTarget: constant.implTarget,
+          cfg: constant.cfg || [],
+          inactive: constant.inactive || false,
+          startLine: constant.startLine,
+          endLine: constant.endLine,
+          language: 'rust'
+        },
+        confidence: 0.9,
+        lastUpdated: new Date()
+      });
+
+      // Associated consts hang off their impl target, everything else off the file
+      const ownerId = constant.implTarget ? typeIds.get(this.baseTypeName(constant.implTarget)) : undefined;
+      edges.push(this.containsEdge(ownerId || filePath, constId));
+
+      for (const typeId of this.resolveTypeReferences(constant.type, typeIds, scope)) {
+        edges.push(this.referenceEdge(constId, typeId, 'field_type'));
+      }
+    }
+
+    // Opaque macro invocations stand in for whatever items they expand to
+    for (const invocation of structure.macroInvocations || []) {
+      const invocationId = `${filePath}#macro_invocation:${invocation.name}:${invocation.startLine}`;
+      nodes.push({
+        id: invocationId,
+        type: 'pattern',
+        name: `${invocation.name}!`,
+        path: filePath,
+        metadata: {
+          patternType: 'macro_invocation',
+          macro: invocation.name,
+          opaque: true,
+          module: this.moduleName(fileModule, invocation.modulePath),
+          startLine: invocation.startLine,
+          endLine: invocation.endLine,
+          language: 'rust'
+        },
+        confidence: 0.6,
+        lastUpdated: new Date()
+      });
+      edges.push(this.containsEdge(filePath, invocationId));
+    }
+
+    // `pub use` declarations: the name a module exports and the crate path it stands for
+    for (const reexport of structure.reexports || []) {
+      const reexportId = `${filePath}#reexport:${this.qualify(reexport.modulePath, reexport.name)}:${reexport.line}`;
+      nodes.push({
+        id: reexportId,
+        type: 'pattern',
+        name: reexport.name,
+        path: filePath,
+        metadata: {
+          patternType: 'reexport',
+          source: reexport.path,
+          resolvedPath: this.crateUsePath([...fileModule, ...reexport.modulePath], reexport.path),
+          isGlob: reexport.name === '*',
+          visibility: reexport.visibility,
+          module: this.moduleName(fileModule, reexport.modulePath),
+          startLine: reexport.line,
+          endLine: reexport.line,
+          language: 'rust'
+        },
+        confidence: 0.85,
+        lastUpdated: new Date()
+      });
+      edges.push(this.containsEdge(filePath, reexportId));
+    }
+
+    // Private glob imports: the module each names, expanded to its items once the whole graph is known
+    for (const glob of structure.globImports || []) {
+      const resolvedPath = this.crateUsePath([...fileModule, ...glob.modulePath], glob.path);
+      const globId = `${filePath}#glob_import:${resolvedPath}:${glob.line}`;
+      nodes.push({
+        id: globId,
+        type: 'pattern',
+        name: `${glob.path}::*`,
+        path: filePath,
+        metadata: {
+          patternType: 'glob_import',
+          source: glob.path,
+          resolvedPath,
+          isGlob: true,
+          module: this.moduleName(fileModule, glob.modulePath),
+          startLine: glob.line,
+          endLine: glob.line,
+          language: 'rust'
+        },
+        confidence: 0.85,
+        lastUpdated: new Date()
+      });
+      edges.push(this.containsEdge(filePath, globId));
+    }
+
+    // `include!` and friends mark where outside content lands. The items of a followed `include!` nest
+    // under its node, at its lines, with their place in the included file kept as `includedFrom`; an
+    // include of a path only known at build time (`env!("OUT_DIR")`) stays an opaque placeholder.
+    for (const include of structure.includes || []) {
+      const includeId = `${filePath}#generated_include:${include.startLine}`;
+      const includedPath = include.literal !== undefined ? posix.join(posix.dirname(filePath), include.literal) : undefined;
+      nodes.push({
+        id: includeId,
+        type: 'pattern',
+        name: `${include.macro}!`,
+        path: filePath,
+        metadata: {
+          patternType: 'generated_include',
+          macro: include.macro,
+          argument: include.argument,
+          includedPath,
+          opaque: include.items === undefined,
+          function: include.function,
+          module: this.moduleName(fileModule, include.modulePath),
+          startLine: include.startLine,
+          endLine: include.endLine,
+          language: 'rust'
+        },
+        confidence: include.items ? 0.85 : 0.6,
+        lastUpdated: new Date()
+      });
+      edges.push(this.containsEdge(filePath, includeId));
+      if (!include.items) continue;
+
+      const included = this.createMindMapElements(include.items, includeId, [...fileModule, ...include.modulePath]);
+      for (const node of included.nodes) {
+        node.path = filePath;
+        node.metadata = {
+          ...node.metadata,
+          includedFrom: { filePath: includedPath, startLine: node.metadata?.startLine, endLine: node.metadata?.endLine },
+          startLine: include.startLine,
+          endLine: include.endLine
+        };
+      }
+      nodes.push(...included.nodes);
+      edges.push(...included.edges);
+      diagnostics.push(...included.diagnostics.map(diagnostic => ({ ...diagnostic, span: { startLine: include.startLine, endLine: include.endLine } })));
+    }
+
+    // Inline assembly is always unsafe and opaque to analysis, so it gets its own node
+    for (const asm of structure.inlineAsm || []) {
+      const asmId = `${filePath}#inline_asm:${asm.startLine}`;
+      const ownerId = asm.function ? `${filePath}#function:${asm.function}` : filePath;
+      nodes.push({
+        id: asmId,
+        type: 'pattern',
+        name: `${asm.macro}!`,
+        path: filePath,
+        metadata: {
+          patternType: 'inline_asm',
+          macro: asm.macro,
+          function: asm.function,
+          isUnsafe: true,
+          ffiBoundary: true,
+          module: fileModule,
+          startLine: asm.startLine,
+          endLine: asm.endLine,
+          language: 'rust'
+        },
+        confidence: 0.8,
+        lastUpdated: new Date()
+      });
+      edges.push(this.containsEdge(ownerId, asmId));
+    }
+
+    // Environment variables the file reads: one node per variable name, linked from every function reading
+    // it. A read through a computed name (`env::var(key)`) gets a node of its own at its line.
+    const envVars = new Map<string, { node: MindMapNode; readers: Set<string> }>();
+    for (const read of structure.envReads || []) {
+      const envId = read.variable !== undefined ? `${filePath}#env_var:${read.variable}` : `${filePath}#env_var:${read.startLine}`;
+      if (!envVars.has(envId)) {
+        const node: MindMapNode = {
+          id: envId,
+          type: 'pattern',
+          name: read.variable ?? read.argument,
+          path: filePath,
+          metadata: {
+            patternType: 'env_var',
+            variable: read.variable,
+            dynamic: read.variable === undefined,
+            reads: [],
+            module: fileModule,
+            startLine: read.startLine,
+            endLine: read.startLine,
+            language: 'rust'
+          },
+          confidence: read.variable !== undefined ? 0.85 : 0.5,
+          lastUpdated: new Date()
+        };
+        envVars.set(envId, { node, readers: new Set() });
+        nodes.push(node);
+        edges.push(this.containsEdge(filePath, envId));
+      }
+      const envVar = envVars.get(envId)!;
+      envVar.node.metadata.reads.push({ access: read.access, compileTime: read.compileTime, function: read.function, line: read.startLine });
+      const readerId = read.function ? `${filePath}#function:${read.function}` : undefined;
+      if (readerId && !envVar.readers.has(readerId)) {
+        envVar.readers.add(readerId);
+        edges.push(this.referenceEdge(readerId, envId, 'reads_env'));
+      }
+    }
+
+    // Doc-test examples document their item and test the functions of this file they call
+    const documented = [
+      ...structure.functions.map(func => ({ item: func, id: `${filePath}#function:${func.name}`, modulePath: func.modulePath })),
+      ...structure.structs.map(struct => ({ item: struct, id: `${filePath}#struct:${this.qualify(struct.modulePath || [], struct.name)}`, modulePath: struct.modulePath })),
+      ...structure.traits.map(trait => ({ item: trait, id: `${filePath}#trait:${trait.name}`, modulePath: trait.modulePath }))
+    ];
+    for (const { item, id: itemId, modulePath } of documented) {
+      for (const docTest of item.docTests || []) {
+        const docTestId = `${filePath}#doctest:${this.qualify(modulePath || [], item.name)}:${docTest.index}`;
+        nodes.push({
+          id: docTestId,
+          type: 'pattern',
+          name: `${item.name} doc-test ${docTest.index}`,
+          path: filePath,
+          metadata: {
+            patternType: 'doc_test',
+            isDocTest: true,
+            documentedItem: itemId,
+            code: docTest.code,
+            calls: docTest.calls,
+            module: this.moduleName(fileModule, modulePath),
+            startLine: item.startLine,
+            endLine: item.startLine,
+            language: 'rust'
+          },
+          confidence: 0.8,
+          lastUpdated: new Date()
+        });
+        edges.push(this.containsEdge(filePath, docTestId));
+        edges.push({
+          id: `${docTestId}->documents->${itemId}`,
+          source: docTestId,
+          target: itemId,
+          type: 'documents',
+          confidence: 0.9,
+          lastUpdated: new Date()
+        });
+
+        const tested = new Set(docTest.calls
+          .map(call => structure.functions.find(func => func.name === call.replace(/^\./, '').split('::').pop()))
+          .filter(func => func && !(func.attributes || []).some(attribute => TEST_ATTRIBUTE.test(attribute.name)))
+          .map(func => `${filePath}#function:${func!.name}`));
+        for (const targetId of tested) {
+          edges.push({
+            id: `${docTestId}->tests->${targetId}`,
+            source: docTestId,
+            target: targetId,
+            type: 'tests',
+            metadata: { docTest: true },
+            confidence: 0.7,
+            lastUpdated: new Date()
+          });
+        }
+      }
+    }
+
+    // Files under a crate's `tests/` directory are integration tests
+    if (this.isIntegrationTestPath(filePath)) {
+      for (const node of nodes) node.metadata.isIntegrationTest = true;
+    }
+
+    diagnostics.sort((a, b) => a.span.startLine - b.span.startLine || a.code.localeCompare(b.code));
+    return { nodes, edges, diagnostics };
+  }
+
+  private isIntegrationTestPath(filePath: string): boolean {
+    // `tests/api.rs` and `tests/common/mod.rs`, but not `src/tests/mod.rs` or `tests/fixtures/app/src/lib.rs`
+    const segments = filePath.replace(/\\/g, '/').split('/');
+    const testsIndex = segments.lastIndexOf('tests');
+    return testsIndex >= 0 && testsIndex < segments.length - 1 && !segments.slice(0, testsIndex).includes('src') &&
+      !segments.slice(testsIndex + 1).includes('src');
+  }
+
+  private qualify(modulePath: string[], name: string): string {
+    return [...modulePath, name].join('::');
+  }
+
+  private fileModulePath(filePath: string): string[] {
+    // `src/a/b.rs` and `src/a/b/mod.rs` -> [a, b]; crate roots map to []
+    const segments = filePath.replace(/\\/g, '/').replace(/\.rs$/, '').split('/');
+    const srcIndex = segments.lastIndexOf('src');
+    const modulePath = srcIndex >= 0 ? segments.slice(srcIndex + 1) : segments.slice(-1);
+    if (['main', 'lib', 'mod'].includes(modulePath[modulePath.length - 1])) {
+      modulePath.pop();
+    }
+    return modulePath;
+  }
+
+  private moduleName(fileModule: string[], inlineModules: string[] = []): string {
+    // Crate-relative module path, `crate` for the crate root
+    const modulePath = [...fileModule, ...inlineModules];
+    return modulePath.length > 0 ? modulePath.join('::') : 'crate';
+  }
+
+  private expandLocalGlobs(scope: RustImportScope, structure: RustCodeStructure, fileModule: string[]): void {
+    // Globs of this file's module or of its inline modules can be expanded from this file alone;
+    // the rest wait for cross-file resolution
+    const enclosing = (line: number, endLine: number) => structure.modules
+      .filter(mod => mod.startLine < line && mod.endLine >= endLine)
+      .sort((a, b) => a.startLine - b.startLine)
+      .map(mod => mod.name);
+    const members = new Map<string, Array<{ name: string; visibility?: string }>>();
+    const add = (modulePath: string[] = [], name: string, visibility?: string) => {
+      const module = this.moduleName(fileModule, modulePath);
+      members.set(module, [...(members.get(module) || []), { name, visibility }]);
+    };
+    const local = new Set([this.moduleName(fileModule)]);
+    for (const mod of structure.modules.filter(mod => mod.endLine > mod.startLine)) {
+      const parent = enclosing(mod.startLine, mod.endLine);
+      add(parent, mod.name, mod.isPublic ? 'pub' : 'private');
+      local.add(this.moduleName(fileModule, [...parent, mod.name]));
+    }
+    structure.functions.filter(func => !func.implTarget && !func.traitName).forEach(func => add(func.modulePath, func.name, func.visibility));
+    [...structure.structs, ...structure.traits, ...(structure.traitAliases || []), ...structure.enums].forEach(item => add(item.modulePath, item.name, item.visibility));
+    (structure.constants || []).filter(constant => !constant.implTarget).forEach(constant => add(constant.modulePath, constant.name, constant.visibility));
+
+    const globs = [...(structure.globImports || []), ...(structure.reexports || []).filter(reexport => reexport.name === '*')];
+    for (const glob of globs) {
+      const importer = this.moduleName(fileModule, glob.modulePath);
+      const module = this.crateUsePath([...fileModule, ...glob.modulePath], glob.path).replace(/^crate(?:::|$)/, '') || 'crate';
+      if (!local.has(module)) continue;
+      // A module's private items are visible to the module itself and its descendants
+      const inside = module === 'crate' || importer === module || importer.startsWith(`${module}::`);
+      scope.expandGlob(glob.path, (members.get(module) || [])
+        .filter(member => inside || (member.visibility || 'private') !== 'private')
+        .map(member => member.name));
+    }
+  }
+
+  private crateUsePath(modulePath: string[], path: string): string {
+    // `crate::`, `self::` and `super::` are anchored as written; any other path is taken relative to
+    // the declaring module, which is what it names unless it names an external crate
+    const segments = path.split('::');
+    if (segments[0] === 'crate') return path;
+    const base = [...modulePath];
+    if (segments[0] === 'self') {
+      segments.shift();
+    } else {
+      while (segments[0] === 'super') {
+        segments.shift();
+        base.pop();
+      }
+    }
+    return ['crate', ...base, ...segments].join('::');
+  }
+
+  private implConditions(impl: RustCodeStructure['impls'][number]): string[] {
+    // `T: Clone + Debug`, one per bounded parameter of the impl, in the order written
+    return (impl.genericBounds || []).map(bound => `${bound.parameter}: ${bound.bounds.join(' + ')}`);
+  }
+
+  private baseTypeName(typeName: string): string {
+    // Strip generics and path prefixes: `crate::a::Foo<T>` -> `Foo`
+    const withoutGenerics = typeName.replace(/<.*$/, '').trim();
+    const segments = withoutGenerics.split('::');
+    return segments[segments.length - 1];
+  }
+
+  private enclosingTraitImpl(structure: RustCodeStructure, line: number): string | undefined {
+    // Trait named by the `impl Trait for Type` block declaring the function, if any
+    const impl = structure.impls.find(candidate => candidate.trait && candidate.startLine < line && line <= candidate.endLine);
+    return impl ? this.baseTypeName(impl.trait!) : undefined;
+  }
+
+  private newtypeImpls(structure: RustCodeStructure, typeName: string): string[] {
+    // `Deref`, `From<u64>` implemented for the newtype, and conversions out of it such as `From<UserId> for u64`
+    const impls = new Set<string>();
+    for (const impl of structure.impls) {
+      if (!impl.trait || !NEWTYPE_TRAITS.has(this.baseTypeName(impl.trait))) continue;
+      const traitArg = impl.trait.match(/<([\s\S]*)>$/)?.[1];
+      if (this.baseTypeName(impl.target) === typeName) {
+        impls.add(impl.trait);
+      } else if (traitArg && this.baseTypeName(traitArg) === typeName) {
+        impls.add(`${impl.trait} for ${impl.target}`);
+      }
+    }
+    return Array.from(impls).sort();
+  }
+
+  private isBuilderMethod(parameters: string[], returnType: string | undefined): boolean {
+    // `fn with_port(mut self, ..) -> Self` or `fn port(&mut self, ..) -> &mut Self`
+    return ['self', 'mut self', '&mut self'].includes(parameters[0]) &&
+      /^(?:&\s*(?:'[A-Za-z_][A-Za-z0-9_]*\s+)?mut\s+)?Self$/.test(returnType || '');
+  }
+
+  private constructors(structure: RustCodeStructure, typeName: string): string[] {
+    // Public functions in inherent impls of the type that return it, e.g. `pub fn new() -> Self`
+    const returnsType = new RegExp(`\\b(?:Self|${typeName})\\b`);
+    const names = new Set<string>();
+    for (const impl of structure.impls) {
+      if (impl.trait || this.baseTypeName(impl.target) !== typeName) continue;
+      for (const func of structure.functions) {
+        if (func.startLine < impl.startLine || func.endLine > impl.endLine) continue;
+        if (func.visibility === 'pub' && func.returnType && returnsType.test(func.returnType)) names.add(func.name);
+      }
+    }
+    return Array.from(names).sort();
+  }
+
+  private importedPath(call: string, scope: RustImportScope): string | undefined {
+    // Expand the first segment through `use`: `create_user` -> `crate::repo::create_user`, `User::new` -> `crate::models::User::new`
+    return scope.expand(call) || (call.includes('::') ? call : undefined);
+  }
+
+  private resultImport(scope: RustImportScope): string | undefined {
+    // Path of a `Result` brought in by `use`, e.g. `actix_web::Result` from `use actix_web::{web, Result};`
+    const path = scope.expand('Result');
+    return path && path !== 'Result' ? path : undefined;
+  }
+
+  private importedReferences(names: Iterable<string>, scope: RustImportScope): Array<{ name: string; path: string; ambiguous?: boolean }> {
+    // Where the imported names an item mentions come from; glob attributions are marked ambiguous
+    const references = new Map<string, { name: string; path: string; ambiguous?: boolean }>();
+    for (const name of names) {
+      const resolution = scope.resolve(name);
+      if (!resolution || references.has(name)) continue;
+      references.set(name, resolution.ambiguous ? { name, path: resolution.path, ambiguous: true } : { name, path: resolution.path });
+    }
+    return Array.from(references.values()).sort((a, b) => a.name.localeCompare(b.name));
+  }
+
+  private mentionedNames(typeTexts: Array<string | undefined>, paths: string[] = []): string[] {
+    // First segments of the paths and type names an item writes, e.g. `web::Json<User>` -> web, User
+    const names: string[] = [];
+    for (const text of typeTexts) {
+      for (const candidate of this.typeCandidates(text || '')) names.push(candidate.split('::')[0]);
+    }
+    for (const path of paths) {
+      if (!path.startsWith('.')) names.push(path.split('::')[0]);
+    }
+    return names;
+  }
+
+  private resultAlias(returnType: string | undefined, resultImport: string | undefined): string | undefined {
+    // A single-argument `Result<T>` is a crate alias whose error type is implied
+    const result = returnType?.match(/^((?:[A-Za-z_][A-Za-z0-9_]*::)*)Result\s*<([\s\S]*)>$/);
+    if (!result || this.splitTypeArgs(result[2]).length !== 1) return undefined;
+    return result[1] ? `${result[1]}Result` : resultImport;
+  }
+
+  private deriveBoundParameters(traitName: string, struct: RustCodeStructure['structs'][number]): string[] {
+    const params = struct.typeParameters || [];
+    if (STD_BOUNDING_DERIVES.has(traitName)) return params;
+    if (!FIELD_BOUNDING_DERIVES.has(traitName)) return [];
+    const fieldTypes = (struct.fieldTypes || []).join(' ');
+    return params.filter(param => new RegExp(String.raw`(?<![\p{XID_Continue}])${param}(?![\p{XID_Continue}])`, 'u').test(fieldTypes));
+  }
+
+  private genericParamElements(
+    filePath: string,
+    ownerId: string,
+    ownerName: string,
+    qualifiedOwner: string,
+    params: RustGenericParam[],
+    line: number,
+    sites: Array<{ site: Record<string, string | boolean>; type: string }>
+  ): { nodes: MindMapNode[]; edges: MindMapEdge[] } {
+    // A node per type and const parameter; const parameters are linked from the types they size
+    const nodes: MindMapNode[] = [];
+    const edges: MindMapEdge[] = [];
+    for (const param of params) {
+      if (param.kind === 'lifetime') continue;
+      const paramId = this.typeParameterId(filePath, qualifiedOwner, param.name);
+      nodes.push({
+        id: paramId,
+        type: 'type_parameter',
+        name: param.name,
+        path: filePath,
+        metadata: {
+          genericName: param.name,
+          kind: param.kind,
+          constType: param.type,
+          defaultValue: param.defaultValue,
+          owner: ownerName,
+          ownerId,
+          startLine: line,
+          endLine: line,
+          language: 'rust'
+        },
+        confidence: 0.9,
+        lastUpdated: new Date()
+      });
+      edges.push(this.containsEdge(ownerId, paramId));
+
+      if (param.kind !== 'const') continue;
+      const usages = sites.flatMap(({ site, type }) => this.constParamRoles(type, param.name).map(role => ({ ...site, role })));
+      if (usages.length > 0) {
+        edges.push({
+          id: `${ownerId}->references->${paramId}`,
+          source: ownerId,
+          target: paramId,
+          type: 'references',
+          metadata: { relationship: 'const_generic', usages },
+          confidence: 0.85,
+          lastUpdated: new Date()
+        });
+      }
+    }
+    return { nodes, edges };
+  }
+
+  private constParamRoles(typeText: string, param: string): Array<'array_length' | 'type_argument'> {
+    // `[u8; N]` sizes an array; `Buffer<N>` or `Buffer<{ N }>` passes it on to another type
+    const name = String.raw`(?<![\p{XID_Continue}])${param}(?![\p{XID_Continue}])`;
+    const roles: Array<'array_length' | 'type_argument'> = [];
+    if (new RegExp(String.raw`;\s*\{?\s*${name}\s*\}?\s*\]`, 'u').test(typeText)) roles.push('array_length');
+    if (new RegExp(String.raw`[<,]\s*\{?\s*${name}\s*\}?\s*[>,]`, 'u').test(typeText)) roles.push('type_argument');
+    return roles;
+  }
+
+  private typeParameterId(filePath: string, ownerName: string, param: string): string {
+    return `${filePath}#type_param:${ownerName}::${param}`;
+  }
+
+  private resultErrorType(returnType: string | undefined): string | undefined {
+    // `Result<T, E>` -> `E`; aliases with an implied error type have none
+    const result = returnType?.match(/^(?:(?:[A-Za-z_][A-Za-z0-9_]*::)*)Result\s*<([\s\S]*)>$/);
+    const args = result ? this.splitTypeArgs(result[1]) : [];
+    return args.length === 2 ? args[1].trim() : undefined;
+  }
+
+  private splitTypeArgs(args: string): string[] {
+    const parts: string[] = [];
+    let depth = 0;
+    let current = '';
+    for (const char of args) {
+      if (char === '<' || char === '(' || char === '[') depth++;
+      else if (char === '>' || char === ')' || char === ']') depth--;
+      if (char === ',' && depth === 0) {
+        parts.push(current);
+        current = '';
+      } else {
+        current += char;
+      }
+    }
+    parts.push(current);
+    return parts.filter(part => part.trim());
+  }
+
+  private resolveTypeReferences(typeText: string, typeIds: Map<string, string>, scope?: RustImportScope): string[] {
+    // Look through references and wrappers (Arc<dyn Trait>, web::Json<T>) for local types,
+    // seeing through renamed imports of them: `use self::models::User as Person;`
+    const ids = new Set<string>();
+    for (const candidate of this.typeCandidates(typeText)) {
+      let id = typeIds.get(candidate) || typeIds.get(this.baseTypeName(candidate));
+      const origin = !id && scope?.resolve(candidate.split('::')[0])?.via === 'alias' ? scope.expand(candidate) : undefined;
+      if (origin && /^(?:crate|self|super)::/.test(origin)) {
+        const local = origin.replace(/^(?:(?:crate|self|super)::)+/, '');
+        id = typeIds.get(local) || typeIds.get(this.baseTypeName(local));
+      }
+      if (id) ids.add(id);
+    }
+    return Array.from(ids);
+  }
+
+  private typeCandidates(typeText: string): string[] {
+    return typeText.replace(/(^|[^\p{XID_Continue}])r#/gu, '$1')
+      .match(/[\p{XID_Start}_]\p{XID_Continue}*(?:::[\p{XID_Start}_]\p{XID_Continue}*)*/gu) || [];
+  }
+
+  private referenceEdge(source: string, target: string, relationship: string): MindMapEdge {
+    return {
+      id: `${source}->references->${target}`,
+      source,
+      target,
+      type: 'references',
+      metadata: { relationship },
+      confidence: 0.8,
+      lastUpdated: new Date()
+    };
+  }
+
+  private methodShape(func: RustCodeStructure['functions'][number]): RustMethodShape {
+    const parameters = func.parameters || [];
+    return {
+      name: func.name,
+      receiver: func.receiver || 'none',
+      parameterTypes: (func.parameterTypes || []).filter((_, index) => !/^&?\s*(?:'\w+\s+)?(?:mut\s+)?self$/.test(parameters[index] || '')),
+      returnType: func.returnType,
+      signature: func.signature,
+      line: func.startLine
+    };
+  }
+
+  private containsEdge(source: string, target: string): MindMapEdge {
+    return {
+      id: `${source}->contains->${target}`,
+      source,
+      target,
+      type: 'contains',
+      confidence: 0.9,
+      lastUpdated: new Date()
+    };
+  }
+}
@@ -0,0 +1,142 @@
+import { RustCodeStructure } from './RustAnalyzer.js';
+
+export interface ImportResolution {
+  path: string; // Where the name comes from, e.g. `actix_web::web`
+  via: 'use' | 'alias' | 'glob'; // `use a::b`, `use a::b as c` or `use a::*`
+  ambiguous: boolean; // Set for attributions to unexpanded globs: the name may come from another glob or the prelude
+  globs?: string[]; // Every glob source the name may come from, when ambiguous
+}
+
+export interface ImportScopeOptions {
+  globs?: boolean; // Attribute names not imported by name to the file's glob imports (default: true)
+}
+
+/**
+ * The names a file's `use` declarations bring into scope. Groups (`a::{b, c::d}`), `self`,
+ * `as` renames and globs are expanded; `use Trait as _` binds nothing. A glob from a module whose
+ * items are known can be expanded to the names it brings in; other globs stay opaque. Scopes are
+ * per file, so a `use` inside an inline module is visible to the whole file.
+ */
+// Path keywords, primitive types and standard prelude names; a glob is not taken to be their source
+const BUILTIN_NAMES = new Set([
+  'crate', 'self', 'super', 'Self', 'bool', 'char', 'str', 'u8', 'u16', 'u32', 'u64', 'u128', 'usize',
+  'i8', 'i16', 'i32', 'i64', 'i128', 'isize', 'f32', 'f64', 'dyn', 'impl', 'mut', 'const', 'Option', 'Some', 'None', 'Result', 'Ok', 'Err', 'String', 'Vec', 'Box', 'ToString', 'ToOwned',
+  'Clone', 'Copy', 'Default', 'Drop', 'Eq', 'PartialEq', 'Ord', 'PartialOrd', 'Iterator', 'IntoIterator',
+  'From', 'Into', 'TryFrom', 'TryInto', 'AsRef', 'AsMut', 'Fn', 'FnMut', 'FnOnce', 'Send', 'Sync', 'Sized'
+]);
+
+export class RustImportScope {
+  private names = new Map<string, { path: string; via: 'use' | 'alias' | 'glob' }>();
+  private globs: string[] = [];
+
+  constructor(imports: RustCodeStructure['rustImports'], private options: ImportScopeOptions = {}) {
+    for (const imp of imports) {
+      // The analyzer strips a trailing `*` from globs and splits `a::{b as c}` at ` as `
+      const tree = imp.isGlob ? (imp.path.endsWith('::') ? `${imp.path}*` : imp.path) : imp.alias ? `${imp.path} as ${imp.alias}` : imp.path;
+      for (const entry of expandUseTree(tree)) {
+        if (entry.glob) {
+          this.globs.push(entry.path);
+          continue;
+        }
+        if (entry.name === '_' || !/^(?:r#)?[\p{XID_Start}_]\p{XID_Continue}*$/u.test(entry.name) || this.names.has(entry.name)) continue;
+        this.names.set(entry.name, { path: entry.path, via: entry.alias ? 'alias' : 'use' });
+      }
+    }
+  }
+
+  /**
+   * Origin of a name used in the file; names imported by name win over globs
+   */
+  resolve(name: string): ImportResolution | undefined {
+    const named = this.names.get(name);
+    if (named) return { ...named, ambiguous: false };
+    if (this.options.globs === false || this.globs.length === 0 || BUILTIN_NAMES.has(name)) return undefined;
+    return { path: `${this.globs[0]}::${name}`, via: 'glob', ambiguous: true, globs: [...this.globs] };
+  }
+
+  /**
+   * Expand the first segment of a path through the imports: `Baz::new` -> `foo::Bar::new`.
+   * Attributions to unexpanded globs are only used when `includeGlobs` is set.
+   */
+  expand(path: string, includeGlobs: boolean = false): string | undefined {
+    const [first, ...rest] = path.split('::');
+    const resolution = this.resolve(first);
+    if (!resolution || (resolution.ambiguous && !includeGlobs)) return undefined;
+    return [resolution.path, ...rest].join('::');
+  }
+
+  /**
+   * Bind the names a glob brings in, given the items of its module (`path` as written in the `use`);
+   * the glob is no longer opaque. Names imported by name keep their own origin.
+   */
+  expandGlob(path: string, members: string[]): void {
+    if (!this.globs.includes(path)) return;
+    this.globs = this.globs.filter(glob => glob !== path);
+    for (const name of members) {
+      if (!this.names.has(name)) this.names.set(name, { path: `${path}::${name}`, via: 'glob' });
+    }
+  }
+
+  /**
+   * Names imported by name or through an expanded glob, sorted
+   */
+  entries(): Array<[string, ImportResolution]> {
+    return Array.from(this.names.keys()).sort().map(name => [name, this.resolve(name)!]);
+  }
+
+  // Globs not expanded
+  getGlobs(): string[] {
+    return [...this.globs];
+  }
+}
+
+export interface UseTreeEntry {
+  path: string; // Full path of the name, or of the module for a glob
+  name: string; // Name bound, after any `as`; `*` for a glob
+  alias: boolean;
+  glob: boolean;
+}
+
+/**
+ * The names one `use` tree binds, with groups (`a::{b, c::d}`) and `self` expanded
+ */
+export function expandUseTree(tree: string, prefix: string[] = []): UseTreeEntry[] {
+  tree = tree.trim();
+  const open = tree.indexOf('{');
+  if (open >= 0 && tree.endsWith('}')) {
+    const base = tree.substring(0, open).replace(/::\s*$/, '');
+    const nested = [...prefix, ...(base ? base.split('::').map(segment => segment.trim()) : [])];
+    return splitTopLevel(tree.substring(open + 1, tree.length - 1))
+      .filter(item => item.trim())
+      .flatMap(item => expandUseTree(item, nested));
+  }
+
+  const aliased = tree.match(/^([\s\S]+?)\s+as\s+(\S+)$/);
+  const segments = [...prefix, ...(aliased ? aliased[1] : tree).split('::').map(segment => segment.trim())];
+  const last = segments[segments.length - 1];
+  if (last === '*') {
+    return [{ path: segments.slice(0, -1).join('::'), name: '*', alias: false, glob: true }];
+  }
+
+  // `use a::{self}` imports `a` itself
+  const path = (last === 'self' ? segments.slice(0, -1) : segments).join('::');
+  return [{ path, name: aliased ? aliased[2] : path.split('::').pop()!, alias: Boolean(aliased), glob: false }];
+}
+
+function splitTopLevel(text: string): string[] {
+  const parts: string[] = [];
+  let depth = 0;
+  let current = '';
+  for (const char of text) {
+    if (char === '{') depth++;
+    else if (char === '}') depth--;
+    if (char === ',' && depth === 0) {
+      parts.push(current);
+      current = '';
+    } else {
+      current += char;
+    }
+  }
+  parts.push(current);
+  return parts;
+}
@@ -0,0 +1,347 @@
+/**
+ * Recursive-descent parsing of Rust type expressions into trees, bounded by a nesting depth
+ */
+
+export interface RustType {
+  kind: 'path' | 'reference' | 'pointer' | 'slice' | 'array' | 'tuple' | 'trait_object' | 'impl_trait' | 'fn' | 'never' | 'infer' | 'lifetime' | 'const' | 'opaque';
+  text: string; // As written, whitespace-normalized
+  name?: string; // Path of a `path` type, e.g. `std::sync::Arc`
+  args?: RustType[]; // Generic arguments, pointee, element, tuple members, bounds or fn inputs
+  bindings?: Array<{ name: string; type: RustType }>; // Associated type bindings, e.g. `Error = Box<dyn Error>`
+  output?: RustType; // Return type of `fn(..) -> T` and `Fn(..) -> T`
+  mutable?: boolean; // `&mut T` and `*mut T`
+  truncated?: boolean; // Set on `opaque` nodes cut off at the depth limit
+}
+
+// Nesting levels parsed before the rest of a type is kept as text
+export const DEFAULT_MAX_TYPE_DEPTH = 32;
+
+const IDENT = /(?:r#)?[\p{XID_Start}_]\p{XID_Continue}*/uy;
+const LIFETIME = /'[\p{XID_Start}_]\p{XID_Continue}*/uy;
+const LITERAL = /-?[0-9][\w.]*|'(?:\\.|[^'\\])'|"(?:\\.|[^"\\])*"|(?:true|false)(?![\p{XID_Continue}])/uy;
+const BINDING = /((?:r#)?[\p{XID_Start}_]\p{XID_Continue}*)\s*=(?![=>])/uy;
+
+/**
+ * Parse a type as written in a signature or field. Past `maxDepth` levels of nesting the parser stops
+ * descending and keeps the rest of that type as an `opaque` node marked `truncated`; text it cannot
+ * read at all becomes an `opaque` node without the mark.
+ */
+export function parseRustType(text: string, maxDepth = DEFAULT_MAX_TYPE_DEPTH): RustType {
+  const parser = new TypeParser(text, maxDepth);
+  const type = parser.parseType(1);
+  return parser.atEnd() ? type : { kind: 'opaque', text: normalize(text) };
+}
+
+/**
+ * Whether the depth limit cut off any part of the type
+ */
+export function isTruncatedType(type: RustType): boolean {
+  return type.truncated === true ||
+    (type.args || []).some(isTruncatedType) ||
+    (type.bindings || []).some(binding => isTruncatedType(binding.type)) ||
+    (type.output !== undefined && isTruncatedType(type.output));
+}
+
+class TypeParser {
+  private pos = 0;
+
+  constructor(private text: string, private maxDepth: number) {}
+
+  atEnd(): boolean {
+    this.skipSpace();
+    return this.pos >= this.text.length;
+  }
+
+  parseType(depth: number, allowBounds = true): RustType {
+    this.skipSpace();
+    const start = this.pos;
+    if (depth > this.maxDepth) {
+      this.skipToTypeEnd();
+      return this.node('opaque', start, { truncated: true });
+    }
+
+    const type = this.parseTypeAt(depth, start, allowBounds);
+    if (type) return type;
+    this.pos = start;
+    this.skipToTypeEnd();
+    return this.node('opaque', start);
+  }
+
+  private parseTypeAt(depth: number, start: number, allowBounds: boolean): RustType | null {
+    const char = this.text[this.pos];
+
+    if (char === '&') {
+      this.pos++;
+      this.skipSpace();
+      this.eat(LIFETIME);
+      this.skipSpace();
+      const mutable = this.eatKeyword('mut');
+      const pointee = this.parseType(depth + 1, allowBounds);
+      return this.node('reference', start, { args: [pointee], ...(mutable ? { mutable } : {}) });
+    }
+    if (char === '*') {
+      this.pos++;
+      this.skipSpace();
+      const mutable = this.eatKeyword('mut');
+      if (!mutable && !this.eatKeyword('const')) return null;
+      const pointee = this.parseType(depth + 1, allowBounds);
+      return this.node('pointer', start, { args: [pointee], ...(mutable ? { mutable } : {}) });
+    }
+    if (char === '[') {
+      this.pos++;
+      const element = this.parseType(depth + 1);
+      this.skipSpace();
+      if (this.eat(';')) {
+        this.skipToTypeEnd();
+        return this.eat(']') ? this.node('array', start, { args: [element] }) : null;
+      }
+      return this.eat(']') ? this.node('slice', start, { args: [element] }) : null;
+    }
+    if (char === '(') {
+      this.pos++;
+      const members = this.parseList(')', depth);
+      return members ? this.node('tuple', start, { args: members }) : null;
+    }
+    if (char === '!') {
+      this.pos++;
+      return this.node('never', start);
+    }
+    if (this.eatKeyword('_')) return this.node('infer', start);
+    if (this.eatKeyword('dyn')) {
+      const bounds = this.parseBounds(depth);
+      return bounds ? this.node('trait_object', start, { args: bounds }) : null;
+    }
+    if (this.eatKeyword('impl')) {
+      const bounds = this.parseBounds(depth);
+      return bounds ? this.node('impl_trait', start, { args: bounds }) : null;
+    }
+    if (/^(?:for\s*<|unsafe\b|extern\b|fn\s*\()/.test(this.text.substring(this.pos, this.pos + 8))) {
+      return this.parseFnPointer(depth, start);
+    }
+
+    const path = this.parsePath(depth, start);
+    if (!path || !allowBounds) return path;
+    // Bare trait objects, e.g. `Box<Error + Send>`
+    this.skipSpace();
+    if (this.text[this.pos] !== '+') return path;
+    const rest = this.parseBounds(depth, true);
+    return rest ? this.node('trait_object', start, { args: [path, ...rest] }) : null;
+  }
+
+  private parsePath(depth: number, start: number): RustType | null {
+    const segments: string[] = [];
+    let args: RustType[] | undefined;
+    let bindings: Array<{ name: string; type: RustType }> | undefined;
+    let output: RustType | undefined;
+
+    if (this.text[this.pos] === '<') {
+      // Qualified path, e.g. `<T as Iterator>::Item`
+      const close = this.findClose(this.pos);
+      if (close === -1) return null;
+      segments.push(normalize(this.text.substring(this.pos, close + 1)));
+      this.pos = close + 1;
+      if (!this.eat('::')) return null;
+    } else if (this.eat('::')) {
+      segments.push('');
+    }
+
+    for (;;) {
+      this.skipSpace();
+      const ident = this.eat(IDENT);
+      if (!ident) return null;
+      segments.push(ident);
+      this.skipSpace();
+
+      if (this.text.startsWith('::<', this.pos) || this.text[this.pos] === '<') {
+        this.pos += this.text[this.pos] === '<' ? 1 : 3;
+        const generic = this.parseGenericArgs(depth);
+        if (!generic) return null;
+        ({ args, bindings } = generic);
+        this.skipSpace();
+      } else if (this.text[this.pos] === '(') {
+        // Parenthesized sugar, e.g. `Fn(u32) -> bool`
+        this.pos++;
+        args = this.parseList(')', depth) || undefined;
+        if (!args) return null;
+        this.skipSpace();
+        if (this.eat('->')) output = this.parseType(depth + 1, false);
+        break;
+      }
+
+      if (!this.text.startsWith('::', this.pos)) break;
+      this.pos += 2;
+    }
+
+    return this.node('path', start, {
+      name: segments.join('::'),
+      ...(args && (args.length > 0 || !bindings?.length) ? { args } : {}),
+      ...(bindings && bindings.length > 0 ? { bindings } : {}),
+      ...(output ? { output } : {})
+    });
+  }
+
+  private parseGenericArgs(depth: number): { args: RustType[]; bindings: Array<{ name: string; type: RustType }> } | null {
+    const args: RustType[] = [];
+    const bindings: Array<{ name: string; type: RustType }> = [];
+
+    for (;;) {
+      this.skipSpace();
+      if (this.eat('>')) return { args, bindings };
+      const start = this.pos;
+
+      if (this.eat(LIFETIME)) {
+        args.push(this.node('lifetime', start));
+      } else if (this.text[this.pos] === '{') {
+        const close = this.findClose(this.pos);
+        if (close === -1) return null;
+        this.pos = close + 1;
+        args.push(this.node('const', start));
+      } else if (this.eat(LITERAL)) {
+        args.push(this.node('const', start));
+      } else {
+        BINDING.lastIndex = this.pos;
+        const binding = BINDING.exec(this.text);
+        if (binding) {
+          this.pos = BINDING.lastIndex;
+          bindings.push({ name: binding[1], type: this.parseType(depth + 1) });
+        } else {
+          args.push(this.parseType(depth + 1));
+        }
+      }
+
+      this.skipSpace();
+      if (this.eat(',')) continue;
+      if (this.eat('>')) return { args, bindings };
+      return null;
+    }
+  }
+
+  private parseBounds(depth: number, leadingPlus = false): RustType[] | null {
+    const bounds: RustType[] = [];
+    for (let first = !leadingPlus; ; first = false) {
+      this.skipSpace();
+      if (!first && !this.eat('+')) return bounds;
+      this.skipSpace();
+      const start = this.pos;
+      if (this.eat(LIFETIME)) {
+        bounds.push(this.node('lifetime', start));
+        continue;
+      }
+      this.eat('?');
+      this.skipSpace();
+      if (this.text.startsWith('for', this.pos) && /^for\s*</.test(this.text.substring(this.pos, this.pos + 8))) {
+        this.pos = this.text.indexOf('<', this.pos);
+        const close = this.findClose(this.pos);
+        if (close === -1) return null;
+        this.pos = close + 1;
+      }
+      bounds.push(this.parseType(depth + 1, false));
+    }
+  }
+
+  private parseFnPointer(depth: number, start: number): RustType | null {
+    this.skipSpace();
+    if (this.text.startsWith('for', this.pos)) {
+      this.pos = this.text.indexOf('<', this.pos);
+      const close = this.findClose(this.pos);
+      if (close === -1) return null;
+      this.pos = close + 1;
+      this.skipSpace();
+    }
+    this.eatKeyword('unsafe');
+    this.skipSpace();
+    if (this.eatKeyword('extern')) {
+      this.skipSpace();
+      this.eat(LITERAL);
+      this.skipSpace();
+    }
+    if (!this.eatKeyword('fn')) return null;
+    this.skipSpace();
+    if (!this.eat('(')) return null;
+    const inputs = this.parseList(')', depth);
+    if (!inputs) return null;
+    this.skipSpace();
+    const output = this.eat('->') ? this.parseType(depth + 1, false) : undefined;
+    return this.node('fn', start, { args: inputs, ...(output ? { output } : {}) });
+  }
+
+  private parseList(close: string, depth: number): RustType[] | null {
+    const items: RustType[] = [];
+    for (;;) {
+      this.skipSpace();
+      if (this.eat(close)) return items;
+      items.push(this.parseType(depth + 1));
+      this.skipSpace();
+      if (this.eat(',')) continue;
+      if (this.eat(close)) return items;
+      return null;
+    }
+  }
+
+  private skipToTypeEnd(): void {
+    // Up to the `,`, `;` or closing bracket ending the current type, without recursing
+    let level = 0;
+    while (this.pos < this.text.length) {
+      const char = this.text[this.pos];
+      if (char === '-' && this.text[this.pos + 1] === '>') {
+        this.pos += 2;
+        continue;
+      }
+      if (char === '<' || char === '(' || char === '[' || char === '{') {
+        level++;
+      } else if (char === '>' || char === ')' || char === ']' || char === '}') {
+        if (level === 0) return;
+        level--;
+      } else if (level === 0 && (char === ',' || char === ';')) {
+        return;
+      }
+      this.pos++;
+    }
+  }
+
+  private findClose(open: number): number {
+    const saved = this.pos;
+    this.pos = open + 1;
+    this.skipToTypeEnd();
+    while (this.pos < this.text.length && (this.text[this.pos] === ',' || this.text[this.pos] === ';')) {
+      this.pos++;
+      this.skipToTypeEnd();
+    }
+    const close = this.pos < this.text.length ? this.pos : -1;
+    this.pos = saved;
+    return close;
+  }
+
+  private eat(token: string | RegExp): string | null {
+    if (typeof token === 'string') {
+      if (!this.text.startsWith(token, this.pos)) return null;
+      this.pos += token.length;
+      return token;
+    }
+    token.lastIndex = this.pos;
+    const match = token.exec(this.text);
+    if (!match) return null;
+    this.pos = token.lastIndex;
+    return match[0];
+  }
+
+  private eatKeyword(keyword: string): boolean {
+    if (!this.text.startsWith(keyword, this.pos) || /[\p{XID_Continue}]/u.test(this.text[this.pos + keyword.length] || '')) {
+      return false;
+    }
+    this.pos += keyword.length;
+    return true;
+  }
+
+  private skipSpace(): void {
+    while (this.pos < this.text.length && /\s/.test(this.text[this.pos])) this.pos++;
+  }
+
+  private node(kind: RustType['kind'], start: number, extra: Partial<RustType> = {}): RustType {
+    return { kind, text: normalize(this.text.substring(start, this.pos)), ...extra };
+  }
+}
+
+function normalize(text: string): string {
+  return text.trim().replace(/\s+/g, ' ');
+}
@@ -0,0 +1,126 @@
+import { MindMapGraph } from '../types/index.js';
+
+/**
+ * SARIF 2.1.0 logs of lint findings, for code scanning platforms such as GitHub code scanning. Each
+ * finding category becomes a rule; findings tied to a node take their file and lines from its span.
+ */
+
+export type SarifLevel = 'error' | 'warning' | 'note';
+
+export interface LintFinding {
+  rule: string; // Category, e.g. `panic_site` or `naming_violation`; becomes the SARIF rule ID
+  message: string;
+  level?: SarifLevel; // Defaults to the rule's level, then to `warning`
+  nodeId?: string; // Node the finding is about; fills in the location when it is not given
+  filePath?: string; // Relative to the scanned directory
+  line?: number; // 1-based; defaults to the node's span
+  endLine?: number;
+}
+
+export interface SarifRuleDescription {
+  description?: string; // Defaults to the rule ID with underscores as spaces
+  level?: SarifLevel;
+  helpUri?: string;
+}
+
+export interface SarifOptions {
+  toolName?: string; // Defaults to `mind-map`
+  toolVersion?: string;
+  rules?: Record<string, SarifRuleDescription>;
+}
+
+export const SARIF_VERSION = '2.1.0';
+export const SARIF_SCHEMA = 'https://json.schemastore.org/sarif-2.1.0.json';
+
+export interface SarifLog {
+  $schema: string;
+  version: typeof SARIF_VERSION;
+  runs: SarifRun[];
+}
+
+interface SarifRun {
+  tool: { driver: { name: string; version?: string; rules: SarifRule[] } };
+  results: SarifResult[];
+}
+
+interface SarifRule {
+  id: string;
+  shortDescription: { text: string };
+  defaultConfiguration: { level: SarifLevel };
+  helpUri?: string;
+}
+
+interface SarifResult {
+  ruleId: string;
+  ruleIndex: number;
+  level: SarifLevel;
+  message: { text: string };
+  locations?: Array<{
+    physicalLocation: {
+      artifactLocation: { uri: string; uriBaseId: string };
+      region?: { startLine: number; endLine?: number };
+    };
+    logicalLocations?: Array<{ fullyQualifiedName: string }>;
+  }>;
+}
+
+export function toSarif(graph: MindMapGraph, findings: LintFinding[], options: SarifOptions = {}): SarifLog {
+  const ruleIds = Array.from(new Set(findings.map(finding => finding.rule))).sort();
+  const rules: SarifRule[] = ruleIds.map(id => {
+    const described = options.rules?.[id] || {};
+    return {
+      id,
+      shortDescription: { text: described.description || id.replace(/_/g, ' ') },
+      defaultConfiguration: { level: described.level || 'warning' },
+      ...(described.helpUri ? { helpUri: described.helpUri } : {})
+    };
+  });
+  const ruleIndex = new Map(ruleIds.map((id, index) => [id, index]));
+
+  const results = findings.map((finding): SarifResult => {
+    const node = finding.nodeId ? graph.nodes.get(finding.nodeId) : undefined;
+    const filePath = finding.filePath || node?.path;
+    const startLine = finding.line ?? node?.metadata?.startLine;
+    // A line given without an end is a single line, whatever the node spans
+    const endLine = finding.line !== undefined ? finding.endLine : finding.endLine ?? node?.metadata?.endLine;
+    const index = ruleIndex.get(finding.rule)!;
+
+    const result: SarifResult = {
+      ruleId: finding.rule,
+      ruleIndex: index,
+      level: finding.level || rules[index].defaultConfiguration.level,
+      message: { text: finding.message }
+    };
+    if (filePath) {
+      const validStart = typeof startLine === 'number' && startLine >= 1;
+      result.locations = [{
+        physicalLocation: {
+          artifactLocation: { uri: artifactUri(filePath), uriBaseId: '%SRCROOT%' },
+          ...(validStart ? { region: { startLine, ...(typeof endLine === 'number' && endLine >= startLine ? { endLine } : {}) } } : {})
+        },
+        ...(finding.nodeId ? { logicalLocations: [{ fullyQualifiedName: node?.metadata?.qualifiedName || node?.name || finding.nodeId }] } : {})
+      }];
+    }
+    return result;
+  });
+
+  return {
+    $schema: SARIF_SCHEMA,
+    version: SARIF_VERSION,
+    runs: [{
+      tool: {
+        driver: {
+          name: options.toolName || 'mind-map',
+          ...(options.toolVersion ? { version: options.toolVersion } : {}),
+          rules
+        }
+      },
+      results
+    }]
+  };
+}
+
+function artifactUri(filePath: string): string {
+  // Relative URI references with forward slashes; spaces and other reserved characters escaped per segment
+  return filePath.replace(/\\/g, '/').replace(/^\.\//, '').split('/').map(encodeURIComponent).join('/');
+}
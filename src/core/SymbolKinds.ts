@@ -0,0 +1,116 @@
+import { MindMapNode } from '../types/index.js';
+
+/**
+ * Mapping from mind map node kinds to LSP `SymbolKind`s for document and workspace symbol queries.
+ * Clients disagree on some kinds (traits as interfaces or classes, macros as operators or functions),
+ * so the defaults can be overridden per kind.
+ */
+
+// Values as numbered by the Language Server Protocol specification
+export const SymbolKind = {
+  File: 1,
+  Module: 2,
+  Namespace: 3,
+  Package: 4,
+  Class: 5,
+  Method: 6,
+  Property: 7,
+  Field: 8,
+  Constructor: 9,
+  Enum: 10,
+  Interface: 11,
+  Function: 12,
+  Variable: 13,
+  Constant: 14,
+  String: 15,
+  Number: 16,
+  Boolean: 17,
+  Array: 18,
+  Object: 19,
+  Key: 20,
+  Null: 21,
+  EnumMember: 22,
+  Struct: 23,
+  Event: 24,
+  Operator: 25,
+  TypeParameter: 26
+} as const;
+
+export type SymbolKind = typeof SymbolKind[keyof typeof SymbolKind];
+
+export type SymbolNodeKind =
+  | 'file' | 'struct' | 'enum' | 'trait' | 'trait_alias' | 'class' | 'function' | 'method' | 'macro'
+  | 'impl' | 'enum_variant' | 'const' | 'static' | 'variable' | 'associated_type' | 'type_parameter';
+
+export const DEFAULT_SYMBOL_KINDS: Record<SymbolNodeKind, SymbolKind> = {
+  file: SymbolKind.File,
+  struct: SymbolKind.Struct,
+  enum: SymbolKind.Enum,
+  trait: SymbolKind.Interface,
+  trait_alias: SymbolKind.Interface,
+  class: SymbolKind.Class,
+  function: SymbolKind.Function,
+  method: SymbolKind.Method,
+  macro: SymbolKind.Operator,
+  impl: SymbolKind.Object,
+  enum_variant: SymbolKind.EnumMember,
+  const: SymbolKind.Constant,
+  static: SymbolKind.Variable,
+  variable: SymbolKind.Variable,
+  associated_type: SymbolKind.TypeParameter,
+  type_parameter: SymbolKind.TypeParameter
+};
+
+/**
+ * The symbol kind of a node, or undefined for nodes that are not symbols (directories, memories,
+ * routes, documentation sections)
+ */
+export function symbolNodeKind(node: MindMapNode): SymbolNodeKind | undefined {
+  const meta = node.metadata || {};
+  switch (node.type) {
+    case 'file':
+      return 'file';
+    case 'function':
+      return meta.implTarget || meta.traitName ? 'method' : 'function';
+    case 'class':
+      return ['struct', 'enum', 'trait', 'trait_alias'].includes(meta.rustKind) ? meta.rustKind : 'class';
+    case 'variable':
+      if (meta.variableType === 'enum_variant' || meta.variableType === 'const' || meta.variableType === 'static') {
+        return meta.variableType;
+      }
+      return 'variable';
+    case 'type_parameter':
+      if (meta.kind === 'lifetime') return undefined;
+      return meta.kind === 'associated_type' ? 'associated_type' : 'type_parameter';
+    case 'pattern':
+      if (meta.patternType === 'trait_impl') return 'impl';
+      return meta.patternType === 'macro_invocation' ? 'macro' : undefined;
+    default:
+      return undefined;
+  }
+}
+
+export class SymbolKindMap {
+  private kinds: Record<SymbolNodeKind, SymbolKind>;
+
+  constructor(overrides: Partial<Record<SymbolNodeKind, SymbolKind>> = {}) {
+    this.kinds = { ...DEFAULT_SYMBOL_KINDS, ...overrides };
+  }
+
+  set(kind: SymbolNodeKind, symbolKind: SymbolKind): void {
+    this.kinds[kind] = symbolKind;
+  }
+
+  get(kind: SymbolNodeKind): SymbolKind {
+    return this.kinds[kind];
+  }
+
+  reset(): void {
+    this.kinds = { ...DEFAULT_SYMBOL_KINDS };
+  }
+
+  kindOf(node: MindMapNode): SymbolKind | undefined {
+    const kind = symbolNodeKind(node);
+    return kind ? this.kinds[kind] : undefined;
+  }
+}
@@ -0,0 +1,166 @@
+import { MindMapStorage } from './MindMapStorage.js';
+import { MindMapNode } from '../types/index.js';
+
+export interface TextSearchHit {
+  nodeId: string;
+  name: string;
+  type: MindMapNode['type'];
+  score: number;
+  matchedFields: Array<'name' | 'signature' | 'doc'>;
+  exactName: boolean; // The name is the query, ignoring case and separators
+}
+
+export interface TextSearchIndexStats {
+  documents: number;
+  terms: number;
+  fullBuilds: number;
+  incrementalUpdates: number;
+}
+
+type SearchField = 'name' | 'signature' | 'doc';
+
+// A term found in a name outranks one found in a signature, which outranks one in a doc comment
+const FIELD_WEIGHTS: Record<SearchField, number> = { name: 10, signature: 3, doc: 1 };
+const EXACT_NAME_BONUS = 20;
+
+const INDEXED_TYPES = new Set<MindMapNode['type']>(['function', 'class', 'variable', 'document', 'section']);
+
+/**
+ * In-memory inverted index over node names, signatures and doc comments. Identifiers are split on
+ * case and separators (`CreateUserRequest` -> create, user, request) and plural `s` is dropped,
+ * so "create user" finds snake_case and CamelCase names alike. Built on first search; edits
+ * applied through `update` patch it in place, any other graph change triggers a rebuild.
+ */
+export class TextSearchIndex {
+  private postings = new Map<string, Map<string, Set<SearchField>>>(); // Term -> node ID -> fields
+  private documents = new Map<string, { terms: string[]; nameTerms: string[] }>(); // Node ID -> indexed terms
+  private builtRevision: number | null = null;
+  private fullBuilds = 0;
+  private incrementalUpdates = 0;
+
+  constructor(private storage: MindMapStorage) {}
+
+  search(query: string, limit: number = 20): TextSearchHit[] {
+    this.ensureFresh();
+    const queryTerms = Array.from(new Set(tokenize(query)));
+    if (queryTerms.length === 0) return [];
+
+    const matches = new Map<string, Map<string, Set<SearchField>>>(); // Node ID -> term -> fields
+    for (const term of queryTerms) {
+      for (const [nodeId, fields] of this.postings.get(term) || []) {
+        if (!matches.has(nodeId)) matches.set(nodeId, new Map());
+        matches.get(nodeId)!.set(term, fields);
+      }
+    }
+
+    const queryKey = queryTerms.join(' ');
+    const hits: Array<TextSearchHit & { nameLength: number }> = [];
+    for (const [nodeId, terms] of matches) {
+      const node = this.storage.getNode(nodeId);
+      if (!node) continue;
+      const document = this.documents.get(nodeId)!;
+      const matchedFields = new Set<SearchField>();
+      let weight = 0;
+      for (const fields of terms.values()) {
+        weight += Math.max(...Array.from(fields).map(field => FIELD_WEIGHTS[field]));
+        fields.forEach(field => matchedFields.add(field));
+      }
+      // Hits covering only some of the query terms are ranked down quadratically
+      const coverage = terms.size / queryTerms.length;
+      const exactName = document.nameTerms.join(' ') === queryKey;
+      hits.push({
+        nodeId,
+        name: node.name,
+        type: node.type,
+        score: weight * coverage * coverage + (exactName ? EXACT_NAME_BONUS : 0),
+        matchedFields: (['name', 'signature', 'doc'] as SearchField[]).filter(field => matchedFields.has(field)),
+        exactName,
+        nameLength: node.name.length
+      });
+    }
+
+    return hits
+      .sort((a, b) => b.score - a.score || a.nameLength - b.nameLength || a.nodeId.localeCompare(b.nodeId))
+      .slice(0, limit)
+      .map(({ nameLength, ...hit }) => hit);
+  }
+
+  /**
+   * Re-index the nodes an edit added, changed or removed. `revisionBefore` is the storage revision
+   * before the edit; if the graph also changed some other way since the last build, the index is
+   * left stale and rebuilt on the next search.
+   */
+  update(nodeIds: string[], revisionBefore: number): void {
+    if (this.builtRevision === null || this.builtRevision !== revisionBefore) return;
+    for (const nodeId of nodeIds) {
+      this.remove(nodeId);
+      const node = this.storage.getNode(nodeId);
+      if (node) this.add(node);
+    }
+    this.builtRevision = this.storage.getRevision();
+    this.incrementalUpdates++;
+  }
+
+  getStats(): TextSearchIndexStats {
+    return {
+      documents: this.documents.size,
+      terms: this.postings.size,
+      fullBuilds: this.fullBuilds,
+      incrementalUpdates: this.incrementalUpdates
+    };
+  }
+
+  private ensureFresh(): void {
+    if (this.builtRevision === this.storage.getRevision()) return;
+    this.postings.clear();
+    this.documents.clear();
+    for (const node of this.storage.findNodes(node => INDEXED_TYPES.has(node.type))) {
+      this.add(node);
+    }
+    this.builtRevision = this.storage.getRevision();
+    this.fullBuilds++;
+  }
+
+  private add(node: MindMapNode): void {
+    if (!INDEXED_TYPES.has(node.type)) return;
+    const fields: Record<SearchField, string[]> = {
+      name: tokenize(node.name),
+      signature: tokenize(node.metadata?.signature || ''),
+      doc: tokenize(node.metadata?.docComment || '')
+    };
+
+    const terms = new Set<string>();
+    for (const field of Object.keys(fields) as SearchField[]) {
+      for (const term of fields[field]) {
+        if (!this.postings.has(term)) this.postings.set(term, new Map());
+        const nodes = this.postings.get(term)!;
+        if (!nodes.has(node.id)) nodes.set(node.id, new Set());
+        nodes.get(node.id)!.add(field);
+        terms.add(term);
+      }
+    }
+    this.documents.set(node.id, { terms: Array.from(terms), nameTerms: fields.name });
+  }
+
+  private remove(nodeId: string): void {
+    const document = this.documents.get(nodeId);
+    if (!document) return;
+    for (const term of document.terms) {
+      const nodes = this.postings.get(term);
+      nodes?.delete(nodeId);
+      if (nodes?.size === 0) this.postings.delete(term);
+    }
+    this.documents.delete(nodeId);
+  }
+}
+
+function tokenize(text: string): string[] {
+  // `parseHTTPRequest_v2` -> parse, http, request, v2
+  return text
+    .replace(/([a-z0-9])([A-Z])/g, '$1 $2')
+    .replace(/([A-Z]+)([A-Z][a-z])/g, '$1 $2')
+    .toLowerCase()
+    .split(/[^a-z0-9]+/)
+    .filter(term => term.length > 0)
+    .map(term => term.length > 3 && term.endsWith('s') && !term.endsWith('ss') ? term.slice(0, -1) : term);
+}
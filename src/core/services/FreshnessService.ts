@@ -0,0 +1,130 @@
+import { MindMapStorage } from '../MindMapStorage.js';
+import { GitBlame } from '../GitBlame.js';
+import { MindMapNode } from '../../types/index.js';
+
+export interface FreshnessOptions {
+  recencyWeight?: number; // Share of the score from source changes; the rest comes from traversals (default: 0.5)
+  editHalfLifeDays?: number; // Age at which a source change counts half (default: 14)
+  activityHalfLifeDays?: number; // Age at which the last traversal counts half (default: 3)
+  activitySaturation?: number; // Traversal strength on the node's edges at which frequency reaches ~63% (default: 5)
+  now?: Date;
+}
+
+export interface NodeFreshness {
+  nodeId: string;
+  name: string;
+  score: number; // 0-1: recency and activity blended by `recencyWeight`
+  recency: number; // 0-1 from the last change to the node's source lines
+  activity: number; // 0-1 from how recently and how strongly its edges were traversed
+  lastChanged?: Date; // Latest of git blame and edits applied through the mind map
+  lastTraversed?: Date;
+}
+
+/**
+ * Scores how actively a node is evolving: how recently its source changed and how recently and
+ * often the edges around it were traversed. Both halves decay exponentially with age.
+ */
+export class FreshnessService {
+  private blame: GitBlame;
+
+  constructor(
+    private storage: MindMapStorage,
+    projectRoot: string
+  ) {
+    this.blame = new GitBlame(projectRoot);
+  }
+
+  async freshness(node: string, options: FreshnessOptions = {}): Promise<NodeFreshness> {
+    const target = this.storage.getNode(node) ||
+      this.storage.findNodes(candidate => candidate.name === node && !this.isStructural(candidate))
+        .sort((a, b) => a.id.localeCompare(b.id))[0];
+    if (!target) {
+      throw new Error(`Unknown node: ${node}`);
+    }
+    return this.score(target, options);
+  }
+
+  /**
+   * The freshest functions and types, for a "what's hot" view
+   */
+  async hotNodes(limit: number = 10, options: FreshnessOptions = {}): Promise<NodeFreshness[]> {
+    const candidates = this.storage.findNodes(node => node.type === 'function' || node.type === 'class');
+    const scored = await Promise.all(candidates.map(node => this.score(node, options)));
+    return scored
+      .filter(entry => entry.score > 0)
+      .sort((a, b) => b.score - a.score || a.nodeId.localeCompare(b.nodeId))
+      .slice(0, limit);
+  }
+
+  /**
+   * Distinct commits behind the node's current source lines, or undefined without blame information
+   */
+  async churn(node: MindMapNode): Promise<number | undefined> {
+    const filePath = node.type === 'file' ? node.id : node.path;
+    if (!filePath || node.type === 'directory') return undefined;
+    if (node.type === 'file') return this.blame.commitCount(filePath);
+    const startLine = node.metadata?.startLine;
+    if (typeof startLine !== 'number') return undefined;
+    return this.blame.commitCount(filePath, startLine, node.metadata.endLine ?? startLine);
+  }
+
+  /**
+   * Drop cached blame after files changed on disk
+   */
+  invalidate(filePath?: string): void {
+    this.blame.invalidate(filePath);
+  }
+
+  private async score(node: MindMapNode, options: FreshnessOptions): Promise<NodeFreshness> {
+    const now = (options.now || new Date()).getTime();
+    const recencyWeight = Math.min(1, Math.max(0, options.recencyWeight ?? 0.5));
+    const dayMs = 24 * 60 * 60 * 1000;
+    const decay = (time: number, halfLifeDays: number) => Math.pow(0.5, Math.max(0, now - time) / (halfLifeDays * dayMs));
+
+    const lastChanged = this.latest([await this.blamedChange(node), this.parseDate(node.metadata?.lastEditedAt)]);
+    const recency = lastChanged ? decay(lastChanged.getTime(), options.editHalfLifeDays ?? 14) : 0;
+
+    const traversed = this.storage.findEdges(edge =>
+      (edge.source === node.id || edge.target === node.id) && edge.lastReinforcedAt !== undefined
+    );
+    const lastTraversed = this.latest(traversed.map(edge => this.parseDate(edge.lastReinforcedAt)));
+    const strength = traversed.reduce((sum, edge) => sum + (edge.weight || 0), 0);
+    const frequency = 1 - Math.exp(-strength / (options.activitySaturation ?? 5));
+    const activity = lastTraversed ? decay(lastTraversed.getTime(), options.activityHalfLifeDays ?? 3) * frequency : 0;
+
+    return {
+      nodeId: node.id,
+      name: node.name,
+      score: recencyWeight * recency + (1 - recencyWeight) * activity,
+      recency,
+      activity,
+      lastChanged,
+      lastTraversed
+    };
+  }
+
+  private async blamedChange(node: MindMapNode): Promise<Date | undefined> {
+    const filePath = node.type === 'file' ? node.id : node.path;
+    if (!filePath || node.type === 'directory') return undefined;
+    if (node.type === 'file') return this.blame.lastChanged(filePath);
+    const startLine = node.metadata?.startLine;
+    if (typeof startLine !== 'number') return undefined;
+    return this.blame.lastChanged(filePath, startLine, node.metadata.endLine ?? startLine);
+  }
+
+  private latest(dates: Array<Date | undefined>): Date | undefined {
+    const times = dates.filter((date): date is Date => date !== undefined).map(date => date.getTime());
+    return times.length > 0 ? new Date(Math.max(...times)) : undefined;
+  }
+
+  private parseDate(value: unknown): Date | undefined {
+    // Dates read back from JSON storage are strings
+    if (value === undefined || value === null) return undefined;
+    const date = value instanceof Date ? value : new Date(value as string);
+    return isNaN(date.getTime()) ? undefined : date;
+  }
+
+  private isStructural(node: MindMapNode): boolean {
+    return node.type === 'file' || node.type === 'directory';
+  }
+}
@@ -0,0 +1,1278 @@
+import { MindMapStorage } from '../MindMapStorage.js';
+import { ActivationNetwork } from '../ActivationNetwork.js';
+import { MindMapNode, MindMapEdge, EvidenceSpan } from '../../types/index.js';
+
+export interface DuplicateFunctionGroup {
+  bodyHash: string;
+  functions: Array<{
+    nodeId: string;
+    name: string;
+    path?: string;
+    startLine?: number;
+  }>;
+}
+
+export interface AnalysisCacheStats {
+  centralityFullRecomputes: number;
+  centralityIncrementalUpdates: number;
+  centralityNodesRecomputed: number;
+  duplicateFilesRecomputed: number;
+  pendingDirtyNodes: number;
+  pendingDirtyFiles: number;
+}
+
+export interface ContextPackItem {
+  nodeId: string;
+  name: string;
+  type: MindMapNode['type'];
+  text: string;
+  score: number;
+  tokens: number;
+  isSeed: boolean;
+}
+
+export interface ContextPack {
+  seeds: string[];
+  text: string;
+  items: ContextPackItem[];
+  tokensUsed: number;
+  budgetTokens: number;
+  omitted: number; // Relevant nodes that did not fit the budget
+  truncated: boolean; // Spreading stopped early at a query limit
+  limitHit?: QueryLimitHit;
+}
+
+export interface QueryLimits {
+  maxDepth: number; // Hops from the start nodes
+  maxVisited: number; // Nodes reached before stopping
+  deadlineMs: number; // Wall-clock budget per query
+}
+
+export type QueryLimitHit = 'max_depth' | 'max_visited' | 'deadline';
+
+export interface TraversalNode {
+  nodeId: string;
+  name: string;
+  type: MindMapNode['type'];
+  path?: string;
+  depth: number;
+}
+
+export interface TraversalResult {
+  start: string[];
+  nodes: TraversalNode[]; // Ordered by depth, then node ID; start nodes excluded
+  truncated: boolean; // Partial result: a limit stopped the traversal
+  limitHit?: QueryLimitHit;
+}
+
+export interface EdgeExplanation {
+  edgeId: string;
+  type: string; // Edge type, or the custom kind for custom edges
+  source: string;
+  target: string;
+  sourceName?: string;
+  targetName?: string;
+  confidence: number;
+  evidence: EvidenceSpan[]; // Every recorded location supporting the edge, in file and line order
+}
+
+export interface ConnectionHop {
+  edgeId: string;
+  type: string; // Edge type, or the custom kind for custom edges
+  source: string;
+  target: string;
+  confidence: number;
+}
+
+export interface ConnectionPath {
+  nodes: string[]; // Node IDs from the start to the end of the connection
+  hops: ConnectionHop[]; // The edge followed between each consecutive pair of nodes
+  confidence: number; // Product of the hop confidences
+  description: string; // `main -calls-> initialize -calls-> create_user -references-> User`
+}
+
+export interface EntryPointReach {
+  nodeId: string;
+  name: string;
+  kind: 'main' | 'handler' | 'test';
+  path?: string;
+  reachable: string[]; // Node IDs reachable through calls, sorted
+  truncated: boolean;
+  limitHit?: QueryLimitHit;
+}
+
+export type BlastRadiusKind = 'callers' | 'implementors' | 'dependents' | 'routes' | 'tests';
+
+export interface BlastRadiusNode extends TraversalNode {
+  via: string; // The affected node this one depends on
+}
+
+export interface BlastRadius {
+  start: string[];
+  groups: Record<BlastRadiusKind, BlastRadiusNode[]>; // Each ordered by depth, then node ID
+  total: number;
+  truncated: boolean;
+  limitHit?: QueryLimitHit;
+}
+
+export interface RelatedTest extends TraversalNode {
+  direct: boolean; // Linked to the start node by its own `tests` edge
+  via: string; // The node it calls or tests on the way to the start node
+}
+
+export interface RiskCentralityOptions {
+  testWeight?: number; // 0-1: how much test coverage lowers a node's score and its absence raises it (default: 0.5)
+}
+
+export interface CallChain {
+  nodes: string[]; // Caller first
+  names: string[];
+  length: number; // Calls along the chain
+}
+
+export interface HighFanNode {
+  nodeId: string;
+  name: string;
+  kind: string; // Rust item kind (`struct`, `trait`, `enum`) or node type
+  fanIn: number; // Distinct nodes with an edge into this one: what depends on it
+  fanOut: number; // Distinct nodes it has an edge to: what it depends on
+  total: number;
+  dominant: 'fan_in' | 'fan_out' | 'balanced'; // Which side is more than twice the other
+  path?: string;
+}
+
+export interface ClosureSpan {
+  nodeId: string;
+  name: string;
+  kind: string; // Rust item kind (`struct`, `trait`, `enum`) or node type
+  path: string;
+  startLine: number;
+  endLine: number;
+}
+
+export interface DependencyClosure {
+  start: string[];
+  spans: ClosureSpan[]; // Definitions to copy, ordered by path, then start line
+  imports: string[]; // External paths the definitions use, kept as `use` items, e.g. `actix_web::HttpResponse`
+  truncated: boolean;
+  limitHit?: QueryLimitHit;
+}
+
+export type EdgeFilter = string | string[] | ((edge: MindMapEdge) => boolean);
+
+export type StepDirection = 'forward' | 'backward' | 'both';
+
+// Edge kind -> the direction to follow it in, or `false` not to follow it; kinds left out are not followed.
+// A custom edge is matched by its customKind.
+export type StepRules = Record<string, StepDirection | false>;
+
+export interface EdgeQuery {
+  kind?: string | string[]; // Edge type(s), e.g. 'implements'; a custom edge also matches its customKind
+  sourceKind?: string; // Node type of the source, or its Rust item kind (`struct`, `trait`, `enum`)
+  sourceName?: string; // Source node name or ID
+  targetKind?: string;
+  targetName?: string;
+  minConfidence?: number;
+}
+
+/**
+ * Raised by `topoOrder` when the selected edges contain a cycle
+ */
+export class GraphCycleError extends Error {
+  constructor(
+    public readonly cycle: string[], // Node IDs in edge order; the last node points back to the first
+    names: string[]
+  ) {
+    super(`Graph contains a cycle: ${[...names, names[0]].join(' -> ')}`);
+    this.name = 'GraphCycleError';
+  }
+}
+
+export const DEFAULT_QUERY_LIMITS: QueryLimits = {
+  maxDepth: 10,
+  maxVisited: 10000,
+  deadlineMs: 5000
+};
+
+// Edges along which an item needs another item's definition
+const CLOSURE_EDGE_TYPES = new Set<MindMapEdge['type']>(['calls', 'references', 'requires_bound', 'wraps']);
+
+/**
+ * Graph-wide analyses over the mind map: centrality, duplicate detection and LLM context packing.
+ * Expensive results are cached and refreshed incrementally from the regions marked dirty by edits.
+ */
+export class GraphAnalysisService {
+  private static readonly PAGERANK_DAMPING = 0.85;
+  private static readonly PAGERANK_ITERATIONS = 30;
+  private static readonly REPROPAGATION_HOPS = 2; // Neighborhood re-ranked around changed edges
+  private static readonly MIN_DUPLICATE_BODY_SIZE = 40; // Ignore trivial bodies like `Self { .. }`
+
+  // Centrality cache (raw PageRank, normalized on read)
+  private centralityRanks: Map<string, number> | null = null;
+  private danglingShare = 0;
+  private centralityRevision = -1; // Storage revision the cached ranks reflect
+  private centralityAccounted = 0; // Revisions since then that markDirty explained
+  private dirtyNodes = new Set<string>();
+  private nodeRecomputeCounts = new Map<string, number>();
+
+  // Duplicate detection cache: function body hashes per file
+  private functionHashesByFile: Map<string, Array<{ nodeId: string; bodyHash: string }>> | null = null;
+  private duplicatesRevision = -1;
+  private duplicatesAccounted = 0;
+  private dirtyFiles = new Set<string>();
+
+  private queryLimits: QueryLimits = { ...DEFAULT_QUERY_LIMITS };
+
+  private stats = {
+    centralityFullRecomputes: 0,
+    centralityIncrementalUpdates: 0,
+    centralityNodesRecomputed: 0,
+    duplicateFilesRecomputed: 0
+  };
+
+  constructor(
+    private storage: MindMapStorage,
+    private activationNetwork: ActivationNetwork
+  ) {}
+
+  /**
+   * Mark nodes whose edges changed and files whose contents changed since the last analysis. Pass the
+   * storage revision from before the change so the revisions it made are not taken for unexplained changes.
+   */
+  markDirty(nodeIds: string[], filePaths: string[] = [], revisionBefore: number = this.storage.getRevision()): void {
+    nodeIds.forEach(id => this.dirtyNodes.add(id));
+    filePaths.forEach(path => this.dirtyFiles.add(path));
+    const accounted = this.storage.getRevision() - revisionBefore;
+    this.centralityAccounted += accounted;
+    this.duplicatesAccounted += accounted;
+  }
+
+  /**
+   * Drop all cached analyses, e.g. after a full rescan
+   */
+  invalidateAll(): void {
+    this.centralityRanks = null;
+    this.functionHashesByFile = null;
+    this.dirtyNodes.clear();
+    this.dirtyFiles.clear();
+  }
+
+  getAnalysisCacheStats(): AnalysisCacheStats {
+    return {
+      ...this.stats,
+      pendingDirtyNodes: this.dirtyNodes.size,
+      pendingDirtyFiles: this.dirtyFiles.size
+    };
+  }
+
+  /**
+   * Number of times a node's centrality has been (re)computed, for verifying cache reuse
+   */
+  getNodeRecomputeCount(nodeId: string): number {
+    return this.nodeRecomputeCounts.get(nodeId) || 0;
+  }
+
+  /**
+   * PageRank over the graph with edges treated as undirected, normalized so the top node scores 1.0.
+   * Cached ranks are reused; nodes near dirty edges are re-propagated instead of recomputing everything.
+   */
+  computeCentrality(): Map<string, number> {
+    const centrality = new Map<string, number>();
+
+    // Changes that bypassed markDirty leave no trail, so fall back to a full recompute
+    const unexplainedChange = this.storage.getRevision() - this.centralityRevision > this.centralityAccounted;
+    if (this.centralityRanks === null || unexplainedChange) {
+      this.centralityRanks = this.runFullPageRank();
+    } else if (this.dirtyNodes.size > 0) {
+      this.repropagate(this.centralityRanks);
+    }
+    this.dirtyNodes.clear();
+    this.centralityRevision = this.storage.getRevision();
+    this.centralityAccounted = 0;
+
+    const ranks = this.centralityRanks;
+    if (ranks.size === 0) return centrality;
+
+    const maxRank = Math.max(...ranks.values());
+    for (const id of Array.from(ranks.keys()).sort()) {
+      centrality.set(id, maxRank > 0 ? ranks.get(id)! / maxRank : 0);
+    }
+
+    return centrality;
+  }
+
+  /**
+   * Centrality weighted by test coverage, normalized so the top node scores 1.0: functions and types
+   * that tests exercise are scaled down by `1 - testWeight`, untested ones up by `1 + testWeight`. Tests
+   * themselves and nodes that are not code keep their centrality, so important untested code rises.
+   */
+  riskCentrality(centrality: Map<string, number>, options: RiskCentralityOptions = {}): Map<string, number> {
+    const weight = Math.min(1, Math.max(0, options.testWeight ?? 0.5));
+    const testEdges = this.storage.findEdgesByType('tests');
+    const tested = new Set(testEdges.map(edge => edge.target));
+    const tests = new Set(testEdges.map(edge => edge.source));
+
+    const weighted = new Map<string, number>();
+    for (const [id, score] of centrality) {
+      const node = this.storage.getNode(id);
+      const testable = (node?.type === 'function' || node?.type === 'class') && !tests.has(id) &&
+        !node.metadata?.isIntegrationTest && !node.metadata?.isDocTest;
+      weighted.set(id, testable ? score * (tested.has(id) ? 1 - weight : 1 + weight) : score);
+    }
+
+    const maxScore = Math.max(0, ...weighted.values());
+    const risk = new Map<string, number>();
+    for (const id of Array.from(weighted.keys()).sort()) {
+      risk.set(id, maxScore > 0 ? weighted.get(id)! / maxScore : 0);
+    }
+    return risk;
+  }
+
+  /**
+   * Set the default limits applied to transitive queries; per-call limits override these
+   */
+  setQueryLimits(limits: Partial<QueryLimits>): void {
+    this.queryLimits = { ...this.queryLimits, ...limits };
+  }
+
+  getQueryLimits(): QueryLimits {
+    return { ...this.queryLimits };
+  }
+
+  /**
+   * Functions calling the target, optionally following callers of callers
+   */
+  callersOf(target: string, transitive: boolean = false, limits: Partial<QueryLimits> = {}): TraversalResult {
+    const startIds = this.resolveSeeds([target]);
+    const callers = new Map<string, string[]>();
+    for (const edge of this.storage.findEdges(edge => edge.type === 'calls')) {
+      if (!callers.has(edge.target)) callers.set(edge.target, []);
+      callers.get(edge.target)!.push(edge.source);
+    }
+
+    return this.traverse(startIds, id => callers.get(id) || [], transitive ? Infinity : 1, limits);
+  }
+
+  /**
+   * Everything a change to the node may affect, transitively: callers (including awaiting ones),
+   * implementors of a trait, dependents of a type, routes served by an affected handler and the tests
+   * covering any of them. Test functions are grouped as tests however they were reached.
+   */
+  blastRadius(nodeId: string, limits: Partial<QueryLimits> = {}): BlastRadius {
+    const startIds = this.resolveSeeds([nodeId]);
+    const dependents = new Map<string, Array<{ source: string; kind: BlastRadiusKind }>>();
+    for (const edge of this.storage.getGraph().edges.values()) {
+      const kind = this.blastRadiusKind(edge);
+      if (!kind || edge.source === edge.target) continue;
+      if (!dependents.has(edge.target)) dependents.set(edge.target, []);
+      dependents.get(edge.target)!.push({ source: edge.source, kind });
+    }
+
+    // The first edge reaching a node decides its group, matching the order the traversal visits them
+    const reachedBy = new Map<string, { via: string; kind: BlastRadiusKind }>();
+    const result = this.traverse(startIds, id => (dependents.get(id) || []).map(({ source, kind }) => {
+      if (!reachedBy.has(source)) reachedBy.set(source, { via: id, kind });
+      return source;
+    }), Infinity, limits);
+
+    const groups: Record<BlastRadiusKind, BlastRadiusNode[]> = { callers: [], implementors: [], dependents: [], routes: [], tests: [] };
+    for (const reached of result.nodes) {
+      const { via, kind } = reachedBy.get(reached.nodeId)!;
+      const node = this.storage.getNode(reached.nodeId);
+      const isTest = node && this.entryPointKind(node, new Set()) === 'test';
+      groups[isTest ? 'tests' : kind].push({ ...reached, via });
+    }
+
+    return { start: startIds, groups, total: result.nodes.length, truncated: result.truncated, limitHit: result.limitHit };
+  }
+
+  /**
+   * Test functions exercising the node, directly or through the functions they call: tests with a
+   * `tests` edge to the node come first, then the rest by how many calls away they are
+   */
+  relatedTests(nodeId: string, limits: Partial<QueryLimits> = {}): RelatedTest[] {
+    const startIds = this.resolveSeeds([nodeId]);
+    const starts = new Set(startIds);
+    const exercisedBy = new Map<string, string[]>();
+    const direct = new Set<string>();
+    for (const edge of this.storage.findEdges(edge => (edge.type === 'calls' || edge.type === 'tests') && edge.source !== edge.target)) {
+      if (!exercisedBy.has(edge.target)) exercisedBy.set(edge.target, []);
+      exercisedBy.get(edge.target)!.push(edge.source);
+      if (edge.type === 'tests' && starts.has(edge.target)) direct.add(edge.source);
+    }
+
+    const reachedVia = new Map<string, string>();
+    const result = this.traverse(startIds, id => (exercisedBy.get(id) || []).map(source => {
+      if (!reachedVia.has(source)) reachedVia.set(source, id);
+      return source;
+    }), Infinity, limits);
+
+    return result.nodes
+      .filter(reached => {
+        const node = this.storage.getNode(reached.nodeId);
+        return node !== undefined && this.entryPointKind(node, new Set()) === 'test';
+      })
+      .map(reached => ({ ...reached, direct: direct.has(reached.nodeId), via: reachedVia.get(reached.nodeId)! }))
+      .sort((a, b) => Number(b.direct) - Number(a.direct) || a.depth - b.depth || a.nodeId.localeCompare(b.nodeId));
+  }
+
+  /**
+   * The definitions a node needs to be self-contained: its own plus those of the internal items it
+   * transitively calls or references. Variants and generic parameters stand for the item declaring them,
+   * and methods bring the type or trait they belong to. External items are left as imports.
+   */
+  dependencyClosure(nodeId: string, limits: Partial<QueryLimits> = {}): DependencyClosure {
+    const startIds = this.resolveSeeds([nodeId]);
+    const graph = this.storage.getGraph();
+    const dependencies = new Map<string, string[]>();
+    for (const edge of graph.edges.values()) {
+      if (!CLOSURE_EDGE_TYPES.has(edge.type) || edge.source === edge.target) continue;
+      if (!dependencies.has(edge.source)) dependencies.set(edge.source, []);
+      dependencies.get(edge.source)!.push(edge.target);
+    }
+    const typesByName = new Map<string, string>();
+    for (const node of graph.nodes.values()) {
+      if (node.type === 'class' && node.path) typesByName.set(`${node.path}#${node.name}`, node.id);
+    }
+
+    const owner = (node: MindMapNode): string | undefined => {
+      const declaring = node.metadata?.enumId || node.metadata?.ownerId;
+      if (declaring) return declaring;
+      const typeName = node.type === 'function' ? node.metadata?.implTarget || node.metadata?.traitName : undefined;
+      return typeName ? typesByName.get(`${node.path}#${String(typeName).replace(/<[\s\S]*$/, '').split('::').pop()}`) : undefined;
+    };
+    const result = this.traverse(startIds, id => {
+      const node = graph.nodes.get(id);
+      const declaring = node && owner(node);
+      return [...(dependencies.get(id) || []), ...(declaring ? [declaring] : [])];
+    }, Infinity, limits);
+
+    const spans: ClosureSpan[] = [];
+    const imports = new Set<string>();
+    for (const id of [...startIds, ...result.nodes.map(reached => reached.nodeId)]) {
+      const node = graph.nodes.get(id);
+      if (!node) continue;
+      if (node.metadata?.external === true) {
+        imports.add(node.metadata.qualifiedName);
+        continue;
+      }
+      // Variants and generic parameters are part of their declaring item's span
+      if (node.metadata?.enumId || !node.path || !['function', 'class', 'variable'].includes(node.type) || node.metadata?.startLine === undefined) continue;
+
+      spans.push({
+        nodeId: id,
+        name: node.name,
+        kind: node.metadata.rustKind || node.type,
+        path: node.path,
+        startLine: node.metadata.startLine,
+        endLine: node.metadata.endLine ?? node.metadata.startLine
+      });
+      for (const reference of node.metadata.importedReferences || []) {
+        if (!/^(?:crate|self|super)::/.test(reference.path)) imports.add(reference.path);
+      }
+    }
+
+    return {
+      start: startIds,
+      spans: spans.sort((a, b) => a.path.localeCompare(b.path) || a.startLine - b.startLine || a.nodeId.localeCompare(b.nodeId)),
+      imports: Array.from(imports).sort(),
+      truncated: result.truncated,
+      limitHit: result.limitHit
+    };
+  }
+
+  /**
+   * Entry points (main functions, route handlers, tests) with everything each one reaches through calls
+   */
+  entryPointsWithReach(limits: Partial<QueryLimits> = {}): EntryPointReach[] {
+    const callees = new Map<string, string[]>();
+    for (const edge of this.storage.findEdges(edge => edge.type === 'calls')) {
+      if (!callees.has(edge.source)) callees.set(edge.source, []);
+      callees.get(edge.source)!.push(edge.target);
+    }
+
+    const handlerIds = new Set(this.storage.findEdges(edge => edge.metadata?.relationship === 'route_handler').map(edge => edge.target));
+    const entries: EntryPointReach[] = [];
+
+    for (const node of this.storage.findNodes(node => node.type === 'function')) {
+      const kind = this.entryPointKind(node, handlerIds);
+      if (!kind) continue;
+
+      const result = this.traverse([node.id], id => callees.get(id) || [], Infinity, limits);
+      entries.push({
+        nodeId: node.id,
+        name: node.name,
+        kind,
+        path: node.path,
+        reachable: result.nodes.map(reached => reached.nodeId).sort(),
+        truncated: result.truncated,
+        limitHit: result.limitHit
+      });
+    }
+
+    return entries.sort((a, b) => a.kind.localeCompare(b.kind) || a.nodeId.localeCompare(b.nodeId));
+  }
+
+  /**
+   * Nodes reached by every one of the given entry points: the core code they share
+   */
+  sharedReach(entryPoints: string[], limits: Partial<QueryLimits> = {}): string[] {
+    const ids = new Set(this.resolveSeeds(entryPoints));
+    const reaches = this.entryPointsWithReach(limits).filter(entry => ids.has(entry.nodeId));
+    if (reaches.length === 0) return [];
+
+    return reaches
+      .map(entry => entry.reachable)
+      .reduce((shared, reachable) => shared.filter(id => reachable.includes(id)));
+  }
+
+  /**
+   * Topological order of the nodes joined by the selected edges (an edge type, several types or a
+   * predicate). Edge targets come before their sources, so over `calls` every callee precedes its
+   * callers and over `imports` a module precedes its importers. Self-edges such as direct recursion
+   * are ignored; any longer cycle throws a `GraphCycleError` naming its nodes.
+   */
+  topoOrder(edgeFilter: EdgeFilter): string[] {
+    const matches = typeof edgeFilter === 'function'
+      ? edgeFilter
+      : (edge: MindMapEdge) => (Array.isArray(edgeFilter) ? edgeFilter : [edgeFilter]).includes(edge.type);
+
+    const successors = new Map<string, string[]>();
+    for (const edge of this.storage.findEdges(edge => matches(edge))) {
+      if (!this.storage.getNode(edge.source) || !this.storage.getNode(edge.target)) continue;
+      if (!successors.has(edge.source)) successors.set(edge.source, []);
+      if (!successors.has(edge.target)) successors.set(edge.target, []);
+      if (edge.source !== edge.target) successors.get(edge.source)!.push(edge.target);
+    }
+    for (const targets of successors.values()) targets.sort();
+
+    // Iterative depth-first search; a node is emitted once everything it points to has been
+    const order: string[] = [];
+    const state = new Map<string, 'active' | 'done'>();
+    for (const root of Array.from(successors.keys()).sort()) {
+      if (state.has(root)) continue;
+
+      const stack: Array<{ id: string; next: number }> = [{ id: root, next: 0 }];
+      state.set(root, 'active');
+      while (stack.length > 0) {
+        const frame = stack[stack.length - 1];
+        const targets = successors.get(frame.id)!;
+        if (frame.next === targets.length) {
+          stack.pop();
+          state.set(frame.id, 'done');
+          order.push(frame.id);
+          continue;
+        }
+
+        const target = targets[frame.next++];
+        const targetState = state.get(target);
+        if (targetState === 'active') {
+          const cycle = stack.slice(stack.findIndex(entry => entry.id === target)).map(entry => entry.id);
+          throw new GraphCycleError(cycle, cycle.map(id => this.storage.getNode(id)?.name || id));
+        }
+        if (!targetState) {
+          state.set(target, 'active');
+          stack.push({ id: target, next: 0 });
+        }
+      }
+    }
+
+    return order;
+  }
+
+  /**
+   * The longest path through the call graph, as node IDs from the outermost caller down. Recursion is
+   * cut where a call leads back to a function already on the path; ties go to the path whose IDs sort
+   * first. Empty when nothing calls anything.
+   */
+  longestCallChain(): CallChain {
+    const successors = new Map<string, string[]>();
+    for (const edge of this.storage.findEdges(edge => edge.type === 'calls' && edge.source !== edge.target)) {
+      if (!this.storage.getNode(edge.source) || !this.storage.getNode(edge.target)) continue;
+      if (!successors.has(edge.source)) successors.set(edge.source, []);
+      if (!successors.has(edge.target)) successors.set(edge.target, []);
+      successors.get(edge.source)!.push(edge.target);
+    }
+    for (const targets of successors.values()) targets.sort();
+
+    // Post-order DFS: a node's chain is itself plus the longest chain of a callee not on the stack
+    const longest = new Map<string, string[]>();
+    const active = new Set<string>();
+    for (const root of Array.from(successors.keys()).sort()) {
+      if (longest.has(root)) continue;
+      const stack: Array<{ id: string; next: number }> = [{ id: root, next: 0 }];
+      active.add(root);
+      while (stack.length > 0) {
+        const frame = stack[stack.length - 1];
+        const targets = successors.get(frame.id)!;
+        if (frame.next === targets.length) {
+          stack.pop();
+          active.delete(frame.id);
+          const best = targets
+            .filter(target => longest.has(target))
+            .map(target => longest.get(target)!)
+            .reduce<string[]>((chosen, chain) => chain.length > chosen.length ? chain : chosen, []);
+          longest.set(frame.id, [frame.id, ...best]);
+          continue;
+        }
+
+        const target = targets[frame.next++];
+        if (!active.has(target) && !longest.has(target)) {
+          active.add(target);
+          stack.push({ id: target, next: 0 });
+        }
+      }
+    }
+
+    const nodes = Array.from(longest.keys()).sort()
+      .map(id => longest.get(id)!)
+      .reduce<string[]>((chosen, chain) => chain.length > chosen.length ? chain : chosen, []);
+    return { nodes, names: nodes.map(id => this.storage.getNode(id)?.name || id), length: Math.max(0, nodes.length - 1) };
+  }
+
+  /**
+   * Candidate god objects: nodes whose fan-in plus fan-out exceeds the threshold, most connected
+   * first. Neighbours are counted once per direction whatever the number of edges; `contains` edges,
+   * files, directories and external items are left out.
+   */
+  highFanNodes(threshold: number): HighFanNode[] {
+    const fanIn = new Map<string, Set<string>>();
+    const fanOut = new Map<string, Set<string>>();
+    const counted = (id: string) => {
+      const node = this.storage.getNode(id);
+      return node !== undefined && !this.isStructural(node);
+    };
+    for (const edge of this.storage.findEdges(edge => edge.type !== 'contains' && edge.source !== edge.target)) {
+      if (!counted(edge.source) || !counted(edge.target)) continue;
+      if (!fanOut.has(edge.source)) fanOut.set(edge.source, new Set());
+      if (!fanIn.has(edge.target)) fanIn.set(edge.target, new Set());
+      fanOut.get(edge.source)!.add(edge.target);
+      fanIn.get(edge.target)!.add(edge.source);
+    }
+
+    const nodes: HighFanNode[] = [];
+    for (const id of new Set([...fanIn.keys(), ...fanOut.keys()])) {
+      const node = this.storage.getNode(id)!;
+      const incoming = fanIn.get(id)?.size || 0;
+      const outgoing = fanOut.get(id)?.size || 0;
+      if (node.metadata?.external || incoming + outgoing <= threshold) continue;
+      nodes.push({
+        nodeId: id,
+        name: node.name,
+        kind: node.metadata?.rustKind || node.type,
+        fanIn: incoming,
+        fanOut: outgoing,
+        total: incoming + outgoing,
+        dominant: incoming > 2 * outgoing ? 'fan_in' : outgoing > 2 * incoming ? 'fan_out' : 'balanced',
+        path: node.path
+      });
+    }
+    return nodes.sort((a, b) => b.total - a.total || b.fanIn - a.fanIn || a.nodeId.localeCompare(b.nodeId));
+  }
+
+  /**
+   * Edges matching every given filter, sorted by ID. With `kind` set only edges of those types are
+   * looked at, through the storage's edge-type index.
+   */
+  queryEdges(query: EdgeQuery = {}): MindMapEdge[] {
+    const kinds = query.kind === undefined ? undefined : Array.isArray(query.kind) ? query.kind : [query.kind];
+    const candidates = kinds
+      ? [...kinds, ...(kinds.includes('custom') ? [] : ['custom'])].flatMap(kind => this.storage.findEdgesByType(kind))
+      : this.storage.findEdges(() => true);
+
+    const nodeMatches = (nodeId: string, kind?: string, name?: string) => {
+      if (kind === undefined && name === undefined) return true;
+      const node = this.storage.getNode(nodeId);
+      if (!node) return false;
+      return (kind === undefined || node.type === kind || node.metadata?.rustKind === kind) &&
+        (name === undefined || node.name === name || node.id === name);
+    };
+
+    return candidates
+      .filter(edge => !kinds || kinds.includes(edge.type) || (edge.type === 'custom' && kinds.includes(edge.customKind || '')))
+      .filter(edge => query.minConfidence === undefined || edge.confidence >= query.minConfidence)
+      .filter(edge => nodeMatches(edge.source, query.sourceKind, query.sourceName))
+      .filter(edge => nodeMatches(edge.target, query.targetKind, query.targetName))
+      .sort((a, b) => (a.id < b.id ? -1 : a.id > b.id ? 1 : 0));
+  }
+
+  /**
+   * Why an edge exists: its endpoints and every source span recorded as evidence for it
+   */
+  explainEdge(edgeId: string): EdgeExplanation | undefined {
+    const edge = this.storage.getEdge(edgeId);
+    if (!edge) return undefined;
+
+    return {
+      edgeId: edge.id,
+      type: edge.type === 'custom' && edge.customKind ? edge.customKind : edge.type,
+      source: edge.source,
+      target: edge.target,
+      sourceName: this.storage.getNode(edge.source)?.name,
+      targetName: this.storage.getNode(edge.target)?.name,
+      confidence: edge.confidence,
+      evidence: [...(edge.evidence || [])].sort((a, b) => a.filePath.localeCompare(b.filePath) || a.startLine - b.startLine)
+    };
+  }
+
+  /**
+   * Why `from` affects `to`: up to `k` shortest distinct directed paths between them over every
+   * edge kind, shortest first and then most confident. Paths never pass through file or
+   * directory nodes and are at most `maxDepth` edges long. Names match every node so named.
+   */
+  explainConnection(from: string, to: string, k: number = 3, limits: Partial<QueryLimits> = {}): ConnectionPath[] {
+    const { maxDepth, deadlineMs } = { ...this.queryLimits, ...limits };
+    const deadline = Date.now() + deadlineMs;
+
+    // Strongest edge per ordered node pair, leaving out file and directory nodes
+    const strongest = new Map<string, Map<string, MindMapEdge>>();
+    for (const edge of this.storage.findEdges(edge => edge.source !== edge.target)) {
+      const source = this.storage.getNode(edge.source);
+      const target = this.storage.getNode(edge.target);
+      if (!source || !target || this.isStructural(source) || this.isStructural(target)) continue;
+      if (!strongest.has(edge.source)) strongest.set(edge.source, new Map());
+      const current = strongest.get(edge.source)!.get(edge.target);
+      if (!current || edge.confidence > current.confidence || (edge.confidence === current.confidence && edge.id < current.id)) {
+        strongest.get(edge.source)!.set(edge.target, edge);
+      }
+    }
+    const successors = new Map(Array.from(strongest.entries()).map(([source, targets]) => [source, Array.from(targets.keys()).sort()]));
+    const confidence = (path: string[]) => path.slice(1).reduce((product, id, index) => product * strongest.get(path[index])!.get(id)!.confidence, 1);
+    const rank = (a: string[], b: string[]) => a.length - b.length || confidence(b) - confidence(a) || a.join('\n').localeCompare(b.join('\n'));
+
+    const shortest = (start: string, end: string, blockedNodes: Set<string>, blockedHops: Set<string>, budget: number): string[] | undefined => {
+      const previous = new Map<string, string | null>([[start, null]]);
+      let frontier = [start];
+      for (let depth = 0; depth < budget && frontier.length > 0; depth++) {
+        const next: string[] = [];
+        for (const id of frontier) {
+          for (const target of successors.get(id) || []) {
+            if (previous.has(target) || blockedNodes.has(target) || blockedHops.has(`${id}\n${target}`)) continue;
+            previous.set(target, id);
+            if (target === end) {
+              const path = [end];
+              for (let at = previous.get(end); at; at = previous.get(at)) path.unshift(at);
+              return path;
+            }
+            next.push(target);
+          }
+        }
+        frontier = next;
+      }
+      return undefined;
+    };
+
+    const found: string[][] = [];
+    for (const start of this.resolveSeeds([from])) {
+      for (const end of this.resolveSeeds([to])) {
+        if (start === end) continue;
+
+        // Yen's k shortest loopless paths
+        const first = shortest(start, end, new Set(), new Set(), maxDepth);
+        if (!first) continue;
+        const paths = [first];
+        const candidates: string[][] = [];
+        const seen = new Set([first.join('\n')]);
+        while (paths.length < k && Date.now() <= deadline) {
+          const last = paths[paths.length - 1];
+          for (let i = 0; i < last.length - 1; i++) {
+            const root = last.slice(0, i + 1);
+            const blockedHops = new Set(paths
+              .filter(path => path.length > i + 1 && root.every((id, index) => path[index] === id))
+              .map(path => `${path[i]}\n${path[i + 1]}`));
+            const spur = shortest(last[i], end, new Set(root.slice(0, -1)), blockedHops, maxDepth - i);
+            if (!spur) continue;
+            const path = [...root.slice(0, -1), ...spur];
+            if (seen.has(path.join('\n'))) continue;
+            seen.add(path.join('\n'));
+            candidates.push(path);
+          }
+          if (candidates.length === 0) break;
+          candidates.sort(rank);
+          paths.push(candidates.shift()!);
+        }
+        found.push(...paths);
+      }
+    }
+
+    return found.sort(rank).slice(0, k).map(path => {
+      const hops = path.slice(1).map((id, index) => {
+        const edge = strongest.get(path[index])!.get(id)!;
+        return {
+          edgeId: edge.id,
+          type: edge.type === 'custom' && edge.customKind ? edge.customKind : edge.type,
+          source: edge.source,
+          target: edge.target,
+          confidence: edge.confidence
+        };
+      });
+      const name = (id: string) => this.storage.getNode(id)?.name || id;
+      return {
+        nodes: path,
+        hops,
+        confidence: confidence(path),
+        description: [name(path[0]), ...hops.map(hop => `-${hop.type}-> ${name(hop.target)}`)].join(' ')
+      };
+    });
+  }
+
+  /**
+   * Walk from the seeds following each edge kind in the direction its rule gives, e.g. `implements`
+   * backward to a trait's implementors and `calls` forward from there, in one breadth-first traversal.
+   * Nodes are visited once, at the first depth reaching them, within the query limits.
+   */
+  traverseWith(seeds: string[], rules: StepRules, limits: Partial<QueryLimits> = {}): TraversalResult {
+    const startIds = this.resolveSeeds(seeds);
+    const kinds = Object.keys(rules).filter(kind => rules[kind] !== false);
+    const edges = [...kinds, ...(kinds.includes('custom') ? [] : ['custom'])].flatMap(kind => this.storage.findEdgesByType(kind));
+
+    const steps = new Map<string, Set<string>>();
+    const step = (from: string, to: string) => {
+      if (!steps.has(from)) steps.set(from, new Set());
+      steps.get(from)!.add(to);
+    };
+    for (const edge of edges) {
+      const direction = rules[edge.type === 'custom' && edge.customKind && edge.customKind in rules ? edge.customKind : edge.type];
+      if (!direction || edge.source === edge.target) continue;
+      if (direction !== 'backward') step(edge.source, edge.target);
+      if (direction !== 'forward') step(edge.target, edge.source);
+    }
+
+    return this.traverse(startIds, id => Array.from(steps.get(id) || []), Infinity, limits);
+  }
+
+  /**
+   * Nodes within `radius` hops of a node, ignoring file/directory containment
+   */
+  neighborhood(nodeId: string, radius: number = 2, limits: Partial<QueryLimits> = {}): TraversalResult {
+    const startIds = this.resolveSeeds([nodeId]);
+    const graph = this.storage.getGraph();
+    const adjacency = this.buildAdjacency(Array.from(graph.edges.values()).filter(edge => {
+      const source = graph.nodes.get(edge.source);
+      const target = graph.nodes.get(edge.target);
+      return source && target && !this.isStructural(source) && !this.isStructural(target);
+    }));
+
+    return this.traverse(startIds, id => Array.from(adjacency.get(id) || []), radius, limits);
+  }
+
+  /**
+   * Breadth-first traversal bounded by the query limits. `depth` is the depth the caller asked
+   * for; stopping there is not truncation, stopping earlier because of a limit is.
+   */
+  private traverse(
+    startIds: string[],
+    next: (nodeId: string) => string[],
+    depth: number,
+    overrides: Partial<QueryLimits>
+  ): TraversalResult {
+    const limits = { ...this.queryLimits, ...overrides };
+    const deadline = Date.now() + limits.deadlineMs;
+    const maxDepth = Math.min(depth, limits.maxDepth);
+
+    const depths = new Map<string, number>(startIds.map(id => [id, 0]));
+    let frontier = [...startIds].sort();
+    let limitHit: QueryLimitHit | undefined;
+
+    traversal:
+    for (let level = 1; frontier.length > 0; level++) {
+      const nextFrontier: string[] = [];
+
+      for (const sourceId of frontier) {
+        if (Date.now() > deadline) {
+          limitHit = 'deadline';
+          break traversal;
+        }
+
+        for (const nodeId of [...next(sourceId)].sort()) {
+          if (depths.has(nodeId)) continue;
+          if (level > maxDepth) {
+            // More remains below the cut-off; only a limit stricter than the requested depth truncates
+            if (maxDepth < depth) limitHit = 'max_depth';
+            break traversal;
+          }
+          if (depths.size - startIds.length >= limits.maxVisited) {
+            limitHit = 'max_visited';
+            break traversal;
+          }
+          depths.set(nodeId, level);
+          nextFrontier.push(nodeId);
+        }
+      }
+
+      frontier = nextFrontier.sort();
+    }
+
+    const nodes: TraversalNode[] = Array.from(depths.entries())
+      .filter(([, nodeDepth]) => nodeDepth > 0)
+      .map(([nodeId, nodeDepth]) => {
+        const node = this.storage.getNode(nodeId);
+        return { nodeId, name: node?.name || nodeId, type: node?.type || 'function', path: node?.path, depth: nodeDepth };
+      })
+      .sort((a, b) => a.depth - b.depth || a.nodeId.localeCompare(b.nodeId));
+
+    return { start: startIds, nodes, truncated: limitHit !== undefined, limitHit };
+  }
+
+  /**
+   * Group functions whose normalized bodies hash identically. Only files marked dirty are re-hashed.
+   */
+  findDuplicateFunctions(): DuplicateFunctionGroup[] {
+    const unexplainedChange = this.storage.getRevision() - this.duplicatesRevision > this.duplicatesAccounted;
+    if (this.functionHashesByFile === null || unexplainedChange) {
+      this.functionHashesByFile = new Map();
+      const paths = new Set(this.storage.findNodes(node => node.type === 'function' && !!node.path).map(node => node.path!));
+      paths.forEach(path => this.functionHashesByFile!.set(path, this.hashFunctionsInFile(path)));
+    } else {
+      for (const path of this.dirtyFiles) {
+        this.functionHashesByFile.set(path, this.hashFunctionsInFile(path));
+      }
+    }
+    this.dirtyFiles.clear();
+    this.duplicatesRevision = this.storage.getRevision();
+    this.duplicatesAccounted = 0;
+
+    const groups = new Map<string, string[]>();
+    for (const entries of this.functionHashesByFile.values()) {
+      for (const { nodeId, bodyHash } of entries) {
+        groups.set(bodyHash, [...(groups.get(bodyHash) || []), nodeId]);
+      }
+    }
+
+    return Array.from(groups.entries())
+      .filter(([, nodeIds]) => nodeIds.length > 1)
+      .map(([bodyHash, nodeIds]) => ({
+        bodyHash,
+        functions: nodeIds.sort().map(nodeId => {
+          const node = this.storage.getNode(nodeId);
+          return { nodeId, name: node?.name || nodeId, path: node?.path, startLine: node?.metadata.startLine };
+        })
+      }))
+      .sort((a, b) => a.functions[0].nodeId.localeCompare(b.functions[0].nodeId));
+  }
+
+  private hashFunctionsInFile(path: string): Array<{ nodeId: string; bodyHash: string }> {
+    this.stats.duplicateFilesRecomputed++;
+    return this.storage.findNodes(node =>
+      node.type === 'function' &&
+      node.path === path &&
+      !!node.metadata.bodyHash &&
+      (node.metadata.bodySize || 0) >= GraphAnalysisService.MIN_DUPLICATE_BODY_SIZE
+    ).map(node => ({ nodeId: node.id, bodyHash: node.metadata.bodyHash }));
+  }
+
+  private runFullPageRank(): Map<string, number> {
+    const graph = this.storage.getGraph();
+    const nodeIds = Array.from(graph.nodes.keys()).sort();
+    const neighbors = this.buildAdjacency(graph.edges.values());
+    const nodeCount = nodeIds.length;
+
+    this.stats.centralityFullRecomputes++;
+    this.stats.centralityNodesRecomputed += nodeCount;
+    nodeIds.forEach(id => this.nodeRecomputeCounts.set(id, (this.nodeRecomputeCounts.get(id) || 0) + 1));
+
+    if (nodeCount === 0) return new Map();
+
+    const damping = GraphAnalysisService.PAGERANK_DAMPING;
+    let ranks = new Map<string, number>(nodeIds.map(id => [id, 1 / nodeCount]));
+
+    for (let iteration = 0; iteration < GraphAnalysisService.PAGERANK_ITERATIONS; iteration++) {
+      const next = new Map<string, number>(nodeIds.map(id => [id, (1 - damping) / nodeCount]));
+      let danglingRank = 0;
+
+      for (const id of nodeIds) {
+        const rank = ranks.get(id) || 0;
+        const adjacent = neighbors.get(id);
+        if (!adjacent || adjacent.size === 0) {
+          danglingRank += rank;
+          continue;
+        }
+        const share = (damping * rank) / adjacent.size;
+        for (const neighborId of adjacent) {
+          if (next.has(neighborId)) {
+            next.set(neighborId, next.get(neighborId)! + share);
+          }
+        }
+      }
+
+      // Spread rank from isolated nodes uniformly so the total stays at 1
+      const danglingShare = (damping * danglingRank) / nodeCount;
+      for (const id of nodeIds) {
+        next.set(id, next.get(id)! + danglingShare);
+      }
+      ranks = next;
+      this.danglingShare = danglingShare;
+    }
+
+    return ranks;
+  }
+
+  /**
+   * Re-run PageRank only within a bounded neighborhood of the dirty nodes, holding ranks outside it fixed
+   */
+  private repropagate(ranks: Map<string, number>): void {
+    const graph = this.storage.getGraph();
+    const neighbors = this.buildAdjacency(graph.edges.values());
+    const nodeCount = graph.nodes.size;
+    const damping = GraphAnalysisService.PAGERANK_DAMPING;
+
+    for (const id of Array.from(ranks.keys())) {
+      if (!graph.nodes.has(id)) ranks.delete(id);
+    }
+
+    const region = new Set<string>();
+    let frontier = Array.from(this.dirtyNodes).filter(id => graph.nodes.has(id));
+    frontier.forEach(id => region.add(id));
+    for (let hop = 0; hop < GraphAnalysisService.REPROPAGATION_HOPS; hop++) {
+      const next: string[] = [];
+      for (const id of frontier) {
+        // Containment hubs are re-ranked but not expanded through, or every region would span the file
+        const node = graph.nodes.get(id);
+        if (node && this.isStructural(node)) continue;
+        for (const neighborId of neighbors.get(id) || []) {
+          if (!region.has(neighborId)) {
+            region.add(neighborId);
+            next.push(neighborId);
+          }
+        }
+      }
+      frontier = next;
+    }
+
+    const regionIds = Array.from(region).sort();
+    regionIds.forEach(id => {
+      if (!ranks.has(id)) ranks.set(id, 1 / nodeCount);
+    });
+
+    for (let iteration = 0; iteration < GraphAnalysisService.PAGERANK_ITERATIONS; iteration++) {
+      for (const id of regionIds) {
+        let incoming = 0;
+        for (const neighborId of neighbors.get(id) || []) {
+          const degree = neighbors.get(neighborId)?.size || 1;
+          incoming += (ranks.get(neighborId) || 0) / degree;
+        }
+        ranks.set(id, (1 - damping) / nodeCount + this.danglingShare + damping * incoming);
+      }
+    }
+
+    this.stats.centralityIncrementalUpdates++;
+    this.stats.centralityNodesRecomputed += regionIds.length;
+    regionIds.forEach(id => this.nodeRecomputeCounts.set(id, (this.nodeRecomputeCounts.get(id) || 0) + 1));
+  }
+
+  /**
+   * Build a compact, relevance-ranked summary of the nodes around the seeds that fits a token budget.
+   * Tokens are estimated as characters / 4.
+   */
+  contextPack(seeds: string[], budgetTokens: number = 1000, limits: Partial<QueryLimits> = {}): ContextPack {
+    const seedIds = this.resolveSeeds(seeds);
+    const { activation, limitHit } = this.spreadFromSeeds(seedIds, { ...this.queryLimits, ...limits });
+    const centrality = this.computeCentrality();
+
+    const ranked = Array.from(activation.entries())
+      .map(([nodeId, strength]) => ({
+        node: this.storage.getNode(nodeId)!,
+        isSeed: seedIds.includes(nodeId),
+        score: strength * (1 + (centrality.get(nodeId) || 0))
+      }))
+      .filter(entry => entry.node && !this.isStructural(entry.node))
+      .sort((a, b) => {
+        if (a.isSeed !== b.isSeed) return a.isSeed ? -1 : 1;
+        if (a.isSeed) return seedIds.indexOf(a.node.id) - seedIds.indexOf(b.node.id);
+        return b.score - a.score || a.node.id.localeCompare(b.node.id);
+      });
+
+    const header = `# Context for ${seeds.join(', ')}`;
+    let tokensUsed = this.estimateTokens(header);
+    const items: ContextPackItem[] = [];
+    let omitted = 0;
+
+    for (const entry of ranked) {
+      const text = this.describeNode(entry.node);
+      const tokens = this.estimateTokens(text);
+
+      // Seeds are always included so the pack is never empty
+      if (!entry.isSeed && tokensUsed + tokens > budgetTokens) {
+        omitted++;
+        continue;
+      }
+
+      items.push({
+        nodeId: entry.node.id,
+        name: entry.node.name,
+        type: entry.node.type,
+        text,
+        score: entry.score,
+        tokens,
+        isSeed: entry.isSeed
+      });
+      tokensUsed += tokens;
+    }
+
+    return {
+      seeds: seedIds,
+      text: [header, ...items.map(item => item.text)].join('\n'),
+      items,
+      tokensUsed,
+      budgetTokens,
+      omitted,
+      truncated: limitHit !== undefined,
+      limitHit
+    };
+  }
+
+  private blastRadiusKind(edge: MindMapEdge): BlastRadiusKind | undefined {
+    // Edges point from the dependent node to the one it depends on
+    switch (edge.type) {
+      case 'calls': return 'callers';
+      case 'implements': return 'implementors';
+      case 'tests': return 'tests';
+      case 'references': return edge.metadata?.relationship === 'route_handler' ? 'routes' : 'dependents';
+      case 'wraps': return 'dependents';
+      default: return undefined;
+    }
+  }
+
+  private entryPointKind(node: MindMapNode, handlerIds: Set<string>): EntryPointReach['kind'] | undefined {
+    const attributes: Array<{ name: string }> = node.metadata?.attributes || [];
+    if (attributes.some(attribute => attribute.name === 'test' || attribute.name.endsWith('::test'))) return 'test';
+    if (handlerIds.has(node.id)) return 'handler';
+    if (node.name === 'main') return 'main';
+    return undefined;
+  }
+
+  private resolveSeeds(seeds: string[]): string[] {
+    const resolved: string[] = [];
+
+    for (const seed of seeds) {
+      if (this.storage.getNode(seed)) {
+        resolved.push(seed);
+        continue;
+      }
+      const matches = this.storage.findNodes(node => node.name === seed && !this.isStructural(node))
+        .map(node => node.id)
+        .sort();
+      resolved.push(...matches);
+    }
+
+    return Array.from(new Set(resolved));
+  }
+
+  /**
+   * Deterministic spreading activation using the activation network's decay settings.
+   * Structural file/directory containment is skipped so siblings in the same file are not
+   * pulled in just for sharing a file.
+   */
+  private spreadFromSeeds(seedIds: string[], limits: QueryLimits): { activation: Map<string, number>; limitHit?: QueryLimitHit } {
+    const { activationDecay, activationThreshold, maxHops } = this.activationNetwork.getConfiguration();
+    const deadline = Date.now() + limits.deadlineMs;
+    const hops = Math.min(maxHops, limits.maxDepth);
+    let limitHit: QueryLimitHit | undefined;
+    const graph = this.storage.getGraph();
+    const semanticEdges = Array.from(graph.edges.values()).filter(edge => {
+      const source = graph.nodes.get(edge.source);
+      const target = graph.nodes.get(edge.target);
+      return source && target && !this.isStructural(source) && !this.isStructural(target);
+    });
+
+    const adjacency = new Map<string, Array<{ nodeId: string; edge: MindMapEdge }>>();
+    for (const edge of semanticEdges) {
+      if (!adjacency.has(edge.source)) adjacency.set(edge.source, []);
+      if (!adjacency.has(edge.target)) adjacency.set(edge.target, []);
+      adjacency.get(edge.source)!.push({ nodeId: edge.target, edge });
+      adjacency.get(edge.target)!.push({ nodeId: edge.source, edge });
+    }
+
+    const activation = new Map<string, number>(seedIds.map(id => [id, 1.0]));
+    let frontier = [...seedIds];
+
+    spreading:
+    for (let hop = 1; hop <= hops && frontier.length > 0; hop++) {
+      const nextFrontier: string[] = [];
+
+      for (const sourceId of frontier.sort()) {
+        if (Date.now() > deadline) {
+          limitHit = 'deadline';
+          break spreading;
+        }
+
+        const sourceStrength = activation.get(sourceId) || 0;
+        for (const { nodeId, edge } of adjacency.get(sourceId) || []) {
+          const strength = sourceStrength * activationDecay * this.activationNetwork.edgeStrength(edge) * edge.confidence;
+          if (strength < activationThreshold) continue;
+          if (!activation.has(nodeId) && activation.size - seedIds.length >= limits.maxVisited) {
+            limitHit = 'max_visited';
+            break spreading;
+          }
+          if (strength > (activation.get(nodeId) || 0)) {
+            activation.set(nodeId, strength);
+            nextFrontier.push(nodeId);
+          }
+        }
+      }
+
+      frontier = Array.from(new Set(nextFrontier));
+    }
+
+    // Still spreading when the hops ran out; only a depth limit stricter than the network's own truncates
+    if (!limitHit && frontier.length > 0 && limits.maxDepth < maxHops) limitHit = 'max_depth';
+
+    return { activation, limitHit };
+  }
+
+  private describeNode(node: MindMapNode): string {
+    const metadata = node.metadata || {};
+    const location = node.path ? ` (${node.path}${metadata.startLine ? `:${metadata.startLine}` : ''})` : '';
+
+    switch (node.type) {
+      case 'function': {
+        const signature = metadata.signature ||
+          `${node.name}(${(metadata.parameters || []).map((name: string, index: number) =>
+            metadata.parameterTypes?.[index] ? `${name}: ${metadata.parameterTypes[index]}` : name).join(', ')})${metadata.returnType ? ` -> ${metadata.returnType}` : ''}`;
+        return `- fn ${node.name}: ${signature}${location}`;
+      }
+      case 'class': {
+        const kind = metadata.rustKind || 'class';
+        const members = metadata.methods?.length
+          ? ` methods: ${metadata.methods.join(', ')}`
+          : metadata.properties?.length ? ` fields: ${metadata.properties.join(', ')}` : '';
+        return `- ${kind} ${node.name}${members}${location}`;
+      }
+      case 'variable': {
+        const kind = metadata.variableType || 'var';
+        const declaredType = metadata.dataType ? `: ${metadata.dataType}` : '';
+        const value = metadata.initialValue !== undefined ? ` = ${metadata.initialValue}` : '';
+        return `- ${kind} ${node.name}${declaredType}${value}${location}`;
+      }
+      case 'pattern':
+        if (metadata.patternType === 'route') {
+          return `- route ${node.name} -> ${metadata.handler}${location}`;
+        }
+        return `- pattern ${node.name}${location}`;
+      default:
+        return `- ${node.type} ${node.name}${location}`;
+    }
+  }
+
+  private buildAdjacency(edges: Iterable<MindMapEdge>): Map<string, Set<string>> {
+    const adjacency = new Map<string, Set<string>>();
+
+    for (const edge of edges) {
+      if (edge.source === edge.target) continue;
+      if (!adjacency.has(edge.source)) adjacency.set(edge.source, new Set());
+      if (!adjacency.has(edge.target)) adjacency.set(edge.target, new Set());
+      adjacency.get(edge.source)!.add(edge.target);
+      adjacency.get(edge.target)!.add(edge.source);
+    }
+
+    return adjacency;
+  }
+
+  private isStructural(node: MindMapNode): boolean {
+    return node.type === 'file' || node.type === 'directory';
+  }
+
+  private estimateTokens(text: string): number {
+    return Math.ceil(text.length / 4);
+  }
+}
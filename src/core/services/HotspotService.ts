@@ -0,0 +1,76 @@
+import { MindMapStorage } from '../MindMapStorage.js';
+import { MindMapNode } from '../../types/index.js';
+import { FreshnessService } from './FreshnessService.js';
+
+export interface HotspotOptions {
+  complexityWeight?: number; // Relative weight of cyclomatic complexity (default: 1)
+  churnWeight?: number; // Relative weight of the number of commits behind the code (default: 1)
+  centralityWeight?: number; // Relative weight of PageRank centrality (default: 1)
+  limit?: number; // Functions returned, highest score first (default: 20)
+}
+
+export interface Hotspot {
+  nodeId: string;
+  name: string;
+  path?: string;
+  score: number; // 0-1: the weighted mean of the three factors below
+  complexity: number; // Cyclomatic complexity; 1 when unknown
+  churn: number; // Distinct commits behind the function's lines; 0 without blame information
+  centrality: number; // 0-1
+  factors: { complexity: number; churn: number; centrality: number }; // Each scaled to 0-1 across the candidates
+}
+
+/**
+ * Ranks functions where complexity, frequent change and importance to the rest of the code coincide,
+ * the places where refactoring pays off most
+ */
+export class HotspotService {
+  constructor(
+    private storage: MindMapStorage,
+    private freshnessService: FreshnessService
+  ) {}
+
+  async hotspots(centrality: Map<string, number>, options: HotspotOptions = {}): Promise<Hotspot[]> {
+    const weights = {
+      complexity: Math.max(0, options.complexityWeight ?? 1),
+      churn: Math.max(0, options.churnWeight ?? 1),
+      centrality: Math.max(0, options.centralityWeight ?? 1)
+    };
+    const totalWeight = weights.complexity + weights.churn + weights.centrality;
+
+    const candidates = this.storage.findNodes(node => this.isCandidate(node));
+    const measured = await Promise.all(candidates.map(async node => ({
+      node,
+      complexity: typeof node.metadata.complexity === 'number' ? node.metadata.complexity : 1,
+      churn: (await this.freshnessService.churn(node)) ?? 0,
+      centrality: centrality.get(node.id) ?? 0
+    })));
+
+    // Complexity and churn are unbounded, so they are scaled by the largest value seen
+    const maxComplexity = Math.max(1, ...measured.map(entry => entry.complexity));
+    const maxChurn = Math.max(1, ...measured.map(entry => entry.churn));
+
+    return measured
+      .map(({ node, complexity, churn, centrality: rank }) => {
+        const factors = { complexity: complexity / maxComplexity, churn: churn / maxChurn, centrality: rank };
+        const weighted = weights.complexity * factors.complexity + weights.churn * factors.churn + weights.centrality * factors.centrality;
+        return {
+          nodeId: node.id,
+          name: node.name,
+          path: node.path,
+          score: totalWeight > 0 ? weighted / totalWeight : 0,
+          complexity,
+          churn,
+          centrality: rank,
+          factors
+        };
+      })
+      .sort((a, b) => b.score - a.score || a.nodeId.localeCompare(b.nodeId))
+      .slice(0, options.limit ?? 20);
+  }
+
+  private isCandidate(node: MindMapNode): boolean {
+    return node.type === 'function' && typeof node.metadata?.startLine === 'number' &&
+      !node.metadata.external && !node.metadata.inactive && !node.metadata.isForeign;
+  }
+}
@@ -0,0 +1,190 @@
+import { readFile } from 'fs/promises';
+import { join } from 'path';
+import { MindMapStorage } from '../MindMapStorage.js';
+import { MindMapNode } from '../../types/index.js';
+
+export interface RenameEdit {
+  filePath: string;
+  line: number; // 1-based
+  column: number; // 0-based offset of the first character
+  endColumn: number; // Exclusive
+  oldText: string;
+  newText: string;
+  kind: 'definition' | 'reference';
+  nodeId: string; // Node whose source span holds the edit
+}
+
+export interface RenamePreview {
+  nodeId: string;
+  oldName: string;
+  newName: string;
+  edits: RenameEdit[]; // Sorted by file, line and column
+}
+
+// Edges that do not mean the source mentions the target by name
+const NON_REFERENCE_EDGE_TYPES = new Set(['contains', 'co_activates']);
+
+interface LineSpan {
+  filePath: string;
+  startLine: number;
+  endLine: number;
+  nodeId: string;
+}
+
+/**
+ * Previews of source refactorings built from the mind map's reference edges. Nothing is written.
+ */
+export class RefactoringService {
+  constructor(
+    private storage: MindMapStorage,
+    private projectRoot: string
+  ) {}
+
+  /**
+   * Every location to edit when renaming a symbol: its definition and each whole-word mention
+   * inside the nodes (and edge evidence spans) that reference it
+   */
+  async renamePreview(node: string, newName: string): Promise<RenamePreview> {
+    if (!/^[A-Za-z_][A-Za-z0-9_]*$/.test(newName)) {
+      throw new Error(`Invalid identifier: ${newName}`);
+    }
+
+    const target = this.storage.getNode(node) ||
+      this.storage.findNodes(candidate => candidate.name === node && !this.isStructural(candidate))
+        .sort((a, b) => a.id.localeCompare(b.id))[0];
+    if (!target || !target.path || typeof target.metadata?.startLine !== 'number') {
+      throw new Error(`Symbol not found: ${node}`);
+    }
+
+    const oldName = target.name;
+    const fileLines = new Map<string, string[]>();
+    const edits = new Map<string, RenameEdit>();
+
+    const addEdits = async (span: LineSpan, kind: RenameEdit['kind'], firstOnly: boolean) => {
+      const lines = await this.getLines(span.filePath, fileLines);
+      for (let line = span.startLine; line <= Math.min(span.endLine, lines.length); line++) {
+        for (const column of this.findIdentifier(lines[line - 1], oldName)) {
+          const key = `${span.filePath}:${line}:${column}`;
+          if (!edits.has(key) || kind === 'definition') {
+            edits.set(key, {
+              filePath: span.filePath,
+              line,
+              column,
+              endColumn: column + oldName.length,
+              oldText: oldName,
+              newText: newName,
+              kind,
+              nodeId: span.nodeId
+            });
+          }
+          if (firstOnly) return;
+        }
+      }
+    };
+
+    // The definition is the first mention of the name in the node's own span
+    await addEdits(this.nodeSpan(target)!, 'definition', true);
+
+    // A trait method shares its node ID with one implementation, so the trait's declaration and those
+    // in other impls are not reached through edges: find their `fn` lines through the trait instead
+    const traitName = target.metadata?.implTrait || target.metadata?.traitName;
+    if (target.type === 'function' && traitName) {
+      for (const span of await this.traitMethodSpans(traitName, oldName, fileLines)) {
+        await addEdits(span, 'definition', true);
+      }
+    }
+
+    for (const edge of this.storage.findEdges(edge => edge.target === target.id && !NON_REFERENCE_EDGE_TYPES.has(edge.type))) {
+      const source = this.storage.getNode(edge.source);
+      const spans: LineSpan[] = (edge.evidence || []).map(span => ({ ...span, nodeId: edge.source }));
+      const sourceSpan = source && !this.isStructural(source) ? this.nodeSpan(source) : undefined;
+      if (sourceSpan) spans.push(sourceSpan);
+
+      for (const span of spans) {
+        await addEdits(span, 'reference', false);
+      }
+    }
+
+    return {
+      nodeId: target.id,
+      oldName,
+      newName,
+      edits: Array.from(edits.values()).sort((a, b) =>
+        a.filePath.localeCompare(b.filePath) || a.line - b.line || a.column - b.column
+      )
+    };
+  }
+
+  private nodeSpan(node: MindMapNode): LineSpan | undefined {
+    const { startLine, endLine } = node.metadata || {};
+    if (!node.path || typeof startLine !== 'number') return undefined;
+    return { filePath: node.path, startLine, endLine: typeof endLine === 'number' ? endLine : startLine, nodeId: node.id };
+  }
+
+  private async traitMethodSpans(traitName: string, methodName: string, fileLines: Map<string, string[]>): Promise<LineSpan[]> {
+    // The trait itself and every `impl Trait for ..` block, narrowed to lines declaring the method
+    const blocks: LineSpan[] = [];
+    for (const trait of this.storage.findNodes(node => node.metadata?.rustKind === 'trait' && node.name === traitName)) {
+      const traitSpan = this.nodeSpan(trait);
+      if (traitSpan) blocks.push(traitSpan);
+      for (const edge of this.storage.findEdges(edge => edge.type === 'implements' && edge.target === trait.id)) {
+        blocks.push(...(edge.evidence || []).filter(span => span.kind === 'impl').map(span => ({ ...span, nodeId: edge.source })));
+      }
+    }
+
+    const declaration = new RegExp(`\\bfn\\s+${methodName}(?![A-Za-z0-9_])`);
+    const spans: LineSpan[] = [];
+    for (const block of blocks) {
+      const lines = await this.getLines(block.filePath, fileLines);
+      for (let line = block.startLine; line <= Math.min(block.endLine, lines.length); line++) {
+        if (declaration.test(this.maskLine(lines[line - 1]))) spans.push({ ...block, startLine: line, endLine: line });
+      }
+    }
+    return spans;
+  }
+
+  private findIdentifier(line: string, name: string): number[] {
+    // Whole-word matches outside string literals and line comments
+    const masked = this.maskLine(line);
+    const pattern = new RegExp(`(?<![A-Za-z0-9_])${name}(?![A-Za-z0-9_])`, 'g');
+    return Array.from(masked.matchAll(pattern), match => match.index!);
+  }
+
+  private maskLine(line: string): string {
+    let masked = '';
+    for (let i = 0; i < line.length; i++) {
+      const char = line[i];
+      if (char === '/' && line[i + 1] === '/') {
+        return masked + ' '.repeat(line.length - i);
+      }
+      if (char === '"') {
+        let end = i + 1;
+        while (end < line.length && line[end] !== '"') {
+          end += line[end] === '\\' ? 2 : 1;
+        }
+        masked += ' '.repeat(Math.min(end, line.length - 1) - i + 1);
+        i = end;
+        continue;
+      }
+      masked += char;
+    }
+    return masked;
+  }
+
+  private async getLines(filePath: string, cache: Map<string, string[]>): Promise<string[]> {
+    let lines = cache.get(filePath);
+    if (!lines) {
+      try {
+        lines = (await readFile(join(this.projectRoot, filePath), 'utf-8')).split('\n');
+      } catch {
+        lines = [];
+      }
+      cache.set(filePath, lines);
+    }
+    return lines;
+  }
+
+  private isStructural(node: MindMapNode): boolean {
+    return node.type === 'file' || node.type === 'directory';
+  }
+}
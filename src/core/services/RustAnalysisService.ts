@@ -0,0 +1,3041 @@
+import { MindMapStorage } from '../MindMapStorage.js';
+import { MindMapNode, MindMapEdge } from '../../types/index.js';
+import { RustAttribute, RustAttributeArg, attributeArgs, evaluateCfg, matchesAttribute, parseAttributeArgs } from '../RustAttributes.js';
+import { RustType, parseRustType } from '../RustTypes.js';
+import { RustReceiver, RustDiagnostic, RustDiagnosticSeverity, RustLiteral, RustLiteralKind, RustPanicKind, RustCloneSite, RustConstEligibility, RustDefaultValue, RustMethodShape, RustMatchSite, RustLockAcrossAwait } from '../RustAnalyzer.js';
+
+export interface DeriveConsistencyIssue {
+  nodeId: string;
+  typeName: string;
+  filePath?: string;
+  line?: number;
+  derive: string;
+  missing: string;
+  message: string;
+}
+
+export interface TraitCoverage {
+  typeName: string;
+  traitName: string;
+  implemented: string[]; // Trait methods with a body in the impl
+  defaulted: string[]; // Trait methods left to their default body
+  missing: string[]; // Required trait methods the impl does not provide
+  complete: boolean;
+}
+
+export interface AsyncMismatch {
+  typeName: string;
+  traitName: string;
+  method: string;
+  traitAsync: boolean; // Declared `async fn` in the trait
+  implAsync: boolean; // Implemented as `async fn`
+  filePath?: string; // Of the impl block
+  line?: number;
+  message: string;
+}
+
+export interface LockAcrossAwait {
+  nodeId: string; // Enclosing async function
+  name: string;
+  binding: string;
+  acquire: string;
+  filePath?: string;
+  line: number; // Where the guard is bound
+  awaitLine: number;
+  message: string;
+}
+
+export interface NonExhaustiveMatch {
+  nodeId: string; // Enclosing function
+  name: string;
+  enumName: string;
+  enumId: string;
+  scrutinee: string;
+  missing: string[]; // Variants no arm covers, in declaration order
+  filePath?: string;
+  line: number;
+  message: string;
+}
+
+export interface MethodShadowing {
+  typeName: string;
+  typeId: string;
+  traitName: string;
+  traitId: string;
+  method: string;
+  inherentSignature?: string;
+  traitSignature?: string;
+  filePath?: string; // Of the type
+  line: number; // Of the inherent method
+  message: string;
+}
+
+export interface LiteralUse extends RustLiteral {
+  nodeId: string; // Enclosing function
+  owner: string; // `ServerConfig::default`, or the function name outside impls and traits
+  filePath?: string;
+}
+
+export interface PanicSite {
+  kind: RustPanicKind;
+  nodeId: string; // Enclosing function
+  name: string;
+  filePath?: string;
+  line: number;
+  inTest: boolean; // The enclosing function is test-only code
+}
+
+export interface RedundantClone extends RustCloneSite {
+  nodeId: string; // Enclosing function
+  name: string;
+  filePath?: string;
+  message: string;
+}
+
+// Standard library functions callable in const context
+const CONST_STD_CALLS = /^(?:(?:std|core)::mem::(?:size_of|align_of|swap|replace)|(?:u8|u16|u32|u64|u128|usize|i8|i16|i32|i64|i128|isize)::(?:from_be_bytes|from_le_bytes|from_ne_bytes|pow|min_value|max_value)|(?:String|Vec)::new)$/;
+
+export interface UnnecessaryOwnership {
+  nodeId: string;
+  name: string;
+  filePath?: string;
+  line: number;
+  parameter: string;
+  type: string; // As written, e.g. `String`
+  suggestion: string; // The borrowed type to take instead, e.g. `&str`
+  message: string;
+}
+
+export interface ConstFnCandidate {
+  nodeId: string;
+  name: string;
+  filePath?: string;
+  line: number;
+  dependsOn: string[]; // Non-const callees that would have to become `const fn` first
+  confidence: number; // 0-1; low, as the check is textual
+}
+
+export interface PanicSiteOptions {
+  excludeTests?: boolean; // Leave out sites in tests, where panicking is how failures are reported (default: false)
+  kinds?: RustPanicKind[]; // Only these kinds; defaults to all
+}
+
+export interface DefaultMethodUsage {
+  method: string;
+  overriddenBy: string[]; // Implementing types that provide their own body
+  usesDefault: string[]; // Implementing types relying on the trait's default
+}
+
+export interface MethodOverride {
+  traitName: string;
+  traitId: string;
+  method: string;
+  typeName: string; // Implementing type that provides its own body
+  typeId: string;
+  overridesDefault: boolean; // False for required methods, which every implementor provides
+  filePath?: string; // The impl block
+  line?: number;
+}
+
+export interface DefaultValues {
+  typeName: string;
+  typeId: string;
+  source: 'manual' | 'derived';
+  values: RustDefaultValue[]; // In field order for derived impls, in the order `fn default()` assigns them otherwise
+}
+
+export interface IgnoredResult {
+  nodeId: string; // Calling function
+  functionName: string;
+  filePath?: string;
+  line: number;
+  call: string; // Call as written: `name`, `Path::name` or `.method`
+  calleeId: string;
+  returnType: string;
+  message: string;
+}
+
+export interface ShadowingSite {
+  nodeId: string; // Function containing the bindings
+  functionName: string;
+  filePath?: string;
+  name: string; // The re-bound name
+  line: number;
+  previousLine: number; // Line of the binding it shadows
+  message: string;
+}
+
+export interface ExhaustivePublicEnum {
+  nodeId: string;
+  name: string;
+  variants: string[];
+  filePath?: string;
+  line?: number;
+  message: string;
+}
+
+export type FfiEntryKind = 'foreign_function' | 'extern_function' | 'exported_symbol' | 'inline_asm';
+
+export interface FfiEntryPoint {
+  nodeId: string;
+  name: string;
+  kind: FfiEntryKind; // Declared in an `extern` block, `extern "abi" fn`, `#[no_mangle]`/`#[export_name]`, or `asm!`
+  abi?: string;
+  filePath?: string;
+  line?: number;
+}
+
+export interface EnvVarRead {
+  nodeId: string; // The file's node for the variable
+  access: 'env' | 'option_env' | 'var' | 'var_os';
+  compileTime: boolean; // `env!` and `option_env!` are read when building, not at run time
+  function?: string;
+  functionId?: string;
+  filePath?: string;
+  line: number;
+}
+
+export interface EnvVarUsage {
+  name: string;
+  reads: EnvVarRead[];
+}
+
+export interface LayerDefinition {
+  name: string;
+  modules: string[]; // Module-path globs; each covers the modules it matches and their submodules. `*` is one segment, `**` any number
+}
+
+export interface LayeringRules {
+  layers: LayerDefinition[]; // An item belongs to the first layer matching its module; items in no layer are not checked
+  allow: Record<string, string[]>; // Layer -> layers it may depend on. Allowance is transitive and a layer may always depend on itself
+}
+
+export interface LayeringViolation {
+  edgeId: string;
+  edgeType: string;
+  source: string;
+  target: string;
+  sourceLayer: string;
+  targetLayer: string;
+  sourceModule: string;
+  targetModule: string;
+  filePath?: string;
+  line?: number;
+  message: string;
+}
+
+export interface ModuleMetrics {
+  module: string; // Crate-relative path, e.g. `config` or `api::handlers`; `crate` for the root
+  itemCount: number; // Items in the module and its submodules
+  internalEdges: number; // Dependencies between two items of the module
+  externalEdges: number; // Dependencies crossing the module boundary (incoming + outgoing)
+  incomingEdges: number;
+  outgoingEdges: number;
+  cohesion: number; // Internal edge density: internal / possible item pairs, 0..1
+  coupling: number; // External share of the module's dependencies, 0..1
+  refactorCandidate: boolean;
+}
+
+export interface ModuleCluster {
+  members: string[]; // Node IDs, sorted
+  names: string[];
+  internalEdges: number; // Dependencies between two members
+}
+
+export interface ModuleSplit {
+  module: string;
+  clusters: ModuleCluster[]; // Largest first; a single cluster means there is no cleaner split
+  crossEdges: number; // Dependencies between items of different clusters
+}
+
+export interface MisplacedItemOptions {
+  threshold?: number; // Share of the item's dependencies that must lead into one other module; defaults to 0.6
+  minEdges?: number; // Items with fewer dependencies are not judged; defaults to 2
+}
+
+export interface MisplacedItem {
+  nodeId: string;
+  name: string;
+  module: string;
+  suggestedModule: string;
+  crossingEdges: number; // Dependencies with items of the suggested module
+  totalEdges: number;
+  share: number; // crossingEdges / totalEdges
+  filePath?: string;
+  line?: number;
+  message: string;
+}
+
+export interface UnusedAsyncFunction {
+  nodeId: string;
+  name: string;
+  filePath?: string;
+  line?: number;
+  isPublic: boolean;
+  message: string;
+}
+
+export type NamingCase = 'PascalCase' | 'snake_case' | 'SCREAMING_SNAKE_CASE';
+export type NamingKind = 'struct' | 'trait' | 'function' | 'field' | 'const' | 'static';
+export type NamingRules = Partial<Record<NamingKind, NamingCase | null>>; // null disables a kind
+
+export interface NamingViolation {
+  nodeId: string;
+  name: string; // Offending name; for fields, the field name
+  kind: NamingKind;
+  expected: NamingCase;
+  suggestion: string;
+  filePath?: string;
+  line?: number;
+}
+
+export const DEFAULT_NAMING_RULES: Record<NamingKind, NamingCase> = {
+  struct: 'PascalCase',
+  trait: 'PascalCase',
+  function: 'snake_case',
+  field: 'snake_case',
+  const: 'SCREAMING_SNAKE_CASE',
+  static: 'SCREAMING_SNAKE_CASE'
+};
+
+const NAMING_PATTERNS: Record<NamingCase, RegExp> = {
+  'PascalCase': /^[A-Z][A-Za-z0-9]*$/,
+  'snake_case': /^_*[a-z][a-z0-9_]*$|^_+$/,
+  'SCREAMING_SNAKE_CASE': /^_*[A-Z][A-Z0-9_]*$/
+};
+
+export type TypeUsageRole = 'field' | 'parameter' | 'return' | 'constant';
+
+export interface TypeUsage {
+  typeName: string;
+  nodeId?: string; // Set when the type is defined in the project
+  count: number;
+  byRole: Record<TypeUsageRole, number>;
+  heat: number; // count relative to the most used type, 0..1
+}
+
+export interface TypeHeatmapOptions {
+  // 'node' counts each referencing item once per type (two String fields count once); 'reference' counts every mention
+  dedupe?: 'node' | 'reference';
+}
+
+// Tokens in type text that are not type names
+const TYPE_TEXT_KEYWORDS = new Set(['dyn', 'impl', 'mut', 'const', 'for', 'where', 'fn', 'Self', 'self', 'super', 'crate']);
+
+export interface UndocumentedItem {
+  nodeId: string;
+  name: string;
+  kind: string; // `function`, `struct` or `trait`
+  visibility: string;
+  filePath?: string;
+  line?: number;
+}
+
+export interface UntestedApiItem {
+  nodeId: string;
+  name: string;
+  visibility: string;
+  traitName?: string; // Set for methods that are public through a `pub` trait
+  filePath?: string;
+  line?: number;
+}
+
+export interface TypeMethod {
+  nodeId: string;
+  name: string;
+  receiver: RustReceiver; // 'none' for associated functions such as `new`
+  receiverMutable: boolean;
+  traitName?: string; // Trait declaring the method or implemented by its impl block
+  filePath?: string;
+  line?: number;
+}
+
+export interface TypeMethodFilter {
+  receiverMutable?: boolean; // Only methods whose receiver can (true) or cannot (false) mutate `self`
+  includeAssociated?: boolean; // Also list functions without a receiver (default: false)
+}
+
+export interface ApiReachability {
+  roots: string[]; // `pub` items plus main functions, route handlers and tests
+  reachable: string[]; // Everything reached from a root, roots included
+  unreachable: UnreachableItem[]; // Private and restricted items no root reaches: likely dead
+}
+
+export interface UnreachableItem {
+  nodeId: string;
+  name: string;
+  kind: string; // `function`, `struct`, `trait`, `const` or `static`
+  visibility: string;
+  filePath?: string;
+  line?: number;
+}
+
+// Edges along which an API item keeps internal code alive
+const REACHABILITY_EDGE_TYPES = new Set(['calls', 'contains', 'references', 'implements']);
+
+export interface LongParameterList {
+  nodeId: string;
+  name: string;
+  filePath?: string;
+  line?: number;
+  parameterCount: number;
+}
+
+export interface LongParameterListOptions {
+  excludeSelf?: boolean; // Leave the `self` receiver out of the count (default: false)
+}
+
+export interface UnusedField {
+  nodeId: string; // The struct
+  struct: string;
+  field: string;
+  visibility: string;
+  filePath?: string;
+  line?: number;
+}
+
+// Derives whose generated impls read every field, so a struct using one has no dead fields to find
+const FIELD_TOUCHING_DERIVES = new Set(['Debug', 'Clone', 'PartialEq', 'Eq', 'PartialOrd', 'Ord', 'Hash', 'Serialize', 'Deserialize']);
+
+export interface RedundantBound {
+  nodeId: string; // The function
+  function: string;
+  parameter: string;
+  bound: string; // The bound that can be dropped
+  reason: 'implied' | 'duplicate';
+  impliedBy?: string; // The bound whose supertraits include it, for `implied`
+  source: 'inline' | 'where'; // Where the redundant bound is written
+  filePath?: string;
+  line?: number;
+}
+
+export interface RequiredTrait {
+  trait: string;
+  requiredBy: string; // The written bound: the trait itself, or the bound it is a supertrait of
+  implemented: boolean; // Implemented or derived by the type
+}
+
+export interface TraitRequirements {
+  typeId: string;
+  typeName: string;
+  functionId: string;
+  function: string;
+  parameter: string; // The type parameter (`T`) or value parameter (`item: impl Trait`) bounded
+  required: RequiredTrait[]; // Written bounds and their supertraits, sorted by trait
+  missing: string[]; // Required traits the type neither implements nor derives
+}
+
+export interface RedundantLifetime {
+  nodeId: string;
+  function: string;
+  lifetime: string; // `'a`
+  elided: string; // The signature with the lifetime left to elision
+  filePath?: string;
+  line?: number;
+  message: string;
+}
+
+export interface ErrorVariantUsage {
+  nodeId: string; // The function
+  function: string;
+  errorType: string; // The local enum in its `Result<T, E>`
+  produced: string[]; // Variants the body constructs with `Err(..)`
+  unproduced: string[]; // Variants the type allows that the body never constructs
+}
+
+// Supertraits of standard library traits, completing the graph built from the project's traits
+const STD_SUPERTRAITS: Record<string, string[]> = {
+  Eq: ['PartialEq'], PartialOrd: ['PartialEq'], Ord: ['Eq', 'PartialOrd'], Copy: ['Clone'],
+  Error: ['Debug', 'Display'], Fn: ['FnMut'], FnMut: ['FnOnce'], DerefMut: ['Deref'],
+  DoubleEndedIterator: ['Iterator'], ExactSizeIterator: ['Iterator'], FusedIterator: ['Iterator'],
+  BufRead: ['Read'], AsyncBufRead: ['AsyncRead']
+};
+
+export interface VisibilitySmell {
+  kind: 'unconstructable_struct' | 'private_return_type';
+  nodeId: string;
+  name: string;
+  relatedId?: string; // The less visible type returned, for `private_return_type`
+  filePath?: string;
+  line?: number;
+  message: string;
+}
+
+// Traits whose impls let outside code build a value without a constructor
+const CONSTRUCTING_TRAITS = new Set(['Default', 'From', 'TryFrom', 'FromStr', 'Deserialize']);
+
+export interface TestGraph {
+  nodes: MindMapNode[]; // The mind map's own node objects, not copies
+  edges: MindMapEdge[]; // Dependency edges leaving test-only nodes
+  testNodeIds: string[];
+  productionNodeIds: string[]; // Referenced by the tests
+}
+
+export interface MissingImpl {
+  callerId: string;
+  calleeId: string;
+  typeName: string;
+  typeId: string;
+  traitName: string; // The unsatisfied bound
+  argumentIndex: number;
+  filePath?: string;
+  line: number;
+  message: string;
+}
+
+// Traits usually satisfied by auto or blanket impls, which the mind map cannot see
+const BLANKET_TRAITS = new Set([
+  'Send', 'Sync', 'Sized', 'Unpin', 'Any', 'Into', 'TryInto', 'From', 'AsRef', 'AsMut', 'Borrow', 'ToOwned', 'ToString',
+  'Fn', 'FnMut', 'FnOnce', 'Future', 'IntoFuture', 'IntoIterator'
+]);
+
+export interface BoxedErrorSite {
+  nodeId: string;
+  name: string;
+  kind: 'return' | 'parameter' | 'associated_type' | 'result_alias';
+  errorType: string; // The wide error type, e.g. `Box<dyn std::error::Error>` or `anyhow::Error`
+  typeText: string; // The full type written at the site
+  filePath?: string;
+  line?: number;
+  message: string;
+}
+
+export interface TraitImplementor {
+  nodeId: string;
+  name: string;
+  trait: string;
+  via: 'impl' | 'derive';
+  conditional: boolean; // Implemented only for some instantiations, e.g. `Wrapper<T>` when `T: Clone`
+  conditions: string[]; // Bounds gating the impl, e.g. `T: Clone`
+  filePath?: string;
+  line?: number;
+}
+
+export interface ModuleImplementors {
+  module: string; // Module path the implementing types are defined in: `crate`, `crate::config`
+  crate?: string; // Cargo workspace member, when the project has several
+  implementors: TraitImplementor[];
+}
+
+export interface DynDispatchSite {
+  nodeId: string;
+  site: string; // `AppState.user_repo`, `handler(repo)` or `handler -> return`
+  role: 'field' | 'parameter' | 'return';
+  trait: string; // Principal trait as written, e.g. `UserRepository` in `Arc<dyn UserRepository + Send>`
+  traitId?: string; // The trait's node when it is declared in the project
+  typeText: string; // The full type written at the site
+  implementors: string[]; // Types in the analyzed code implementing the trait
+  devirtualizable: boolean; // A single implementor: a generic parameter could dispatch statically
+  filePath?: string;
+  line?: number;
+}
+
+export interface DispatchSite {
+  nodeId: string;
+  site: string; // `AppState.user_repo`, `handler(repo)`, or `handler<R>` for a bounded type parameter
+  kind: 'dynamic' | 'generic';
+}
+
+export interface DispatchProfile {
+  trait: string;
+  traitId?: string; // The trait's node when it is declared in the project
+  dynamicSites: number; // Distinct sites naming the trait in a `dyn` type
+  genericSites: number; // Distinct type parameters and `impl Trait` parameters bounded by the trait
+  dynamicShare: number; // dynamicSites / (dynamicSites + genericSites)
+  sites: DispatchSite[];
+}
+
+export interface DipCandidate {
+  nodeId: string; // The struct or function depending on the concrete type
+  site: string; // `Handler.repo` or `handler(repo)`
+  role: 'field' | 'parameter';
+  concreteType: string;
+  concreteTypeId: string;
+  traits: string[]; // Project traits the concrete type implements, any of which could be depended on instead
+  typeText: string; // The full type written at the site
+  filePath?: string;
+  line?: number;
+  message: string;
+}
+
+// Auto traits and marker bounds added to trait objects, never their principal trait
+const MARKER_TRAITS = new Set(['Send', 'Sync', 'Unpin', 'UnwindSafe', 'RefUnwindSafe', 'Sized']);
+
+export type AutoTrait = 'Send' | 'Sync';
+
+export interface ThreadSafetyIssue {
+  typeName: string;
+  typeId: string;
+  sharedVia: Array<'arc' | 'spawn'>; // Held in an `Arc` (or actix `web::Data`), or moved into a spawned thread or task
+  missing: AutoTrait[]; // Auto traits the sharing needs that the type lacks
+  field: string; // Field through which the type loses them
+  culprit: string; // The type that is not thread-safe, e.g. `Rc<RefCell<Vec<String>>>`
+  filePath?: string;
+  line?: number;
+  message: string;
+}
+
+// Standard types by what they lack on their own; wrappers lacking one trait also pass on their contents' gaps
+const NOT_SEND_OR_SYNC = new Set(['Rc', 'NonNull']);
+const NOT_SYNC = new Set(['Cell', 'RefCell', 'UnsafeCell', 'OnceCell']);
+const NOT_SEND = new Set(['MutexGuard', 'RwLockReadGuard', 'RwLockWriteGuard']);
+// Types sharing their contents across threads: `Arc<T>`, and actix's `web::Data<T>` which wraps one
+const SHARING_WRAPPERS = new Set(['Arc', 'Data']);
+
+export interface BoxedErrorOptions {
+  // File paths (`src/main.rs`, or a directory ending in `/`) and module paths (`crate::bin`) exempt
+  // from the check, so binaries can keep type-erased errors while libraries stay strict
+  allow?: string[];
+}
+
+// Type-erased error types and the crate `Result` aliases that imply one
+const WIDE_ERROR_PATTERN = /Box\s*<\s*dyn\s+(?:(?:std|core)::error::)?Error\b[^>]*>|\b(?:anyhow::Error|eyre::Report)\b/;
+const WIDE_RESULT_ALIASES: Record<string, string> = {
+  'anyhow::Result': 'anyhow::Error',
+  'eyre::Result': 'eyre::Report',
+  'color_eyre::Result': 'eyre::Report',
+  'actix_web::Result': 'actix_web::Error'
+};
+
+// How a function deals with failure: the first three are the error type its `Result` carries
+export type ErrorHandlingStyle = 'result' | 'boxed_error' | 'custom_error' | 'panics' | 'ignores';
+
+const ERROR_HANDLING_STYLES: readonly ErrorHandlingStyle[] = ['result', 'boxed_error', 'custom_error', 'panics', 'ignores'];
+const RESULT_STYLES = new Set<ErrorHandlingStyle>(['result', 'boxed_error', 'custom_error']);
+
+export interface FunctionErrorStyle {
+  nodeId: string;
+  name: string;
+  styles: ErrorHandlingStyle[];
+  errorType?: string; // Error type of a returned `Result`, with `Self::Error` resolved where the impl binds it
+  filePath?: string;
+  line?: number;
+}
+
+export interface ModuleErrorStyle {
+  module: string; // `crate` or `crate::config`
+  crate?: string; // Workspace member, when there is one
+  styles: ErrorHandlingStyle[]; // Every style used in the module
+  functions: FunctionErrorStyle[]; // Functions with at least one style, by line
+  mixed: boolean; // Incompatible styles used side by side
+  message: string;
+}
+
+export interface PathSuggestion {
+  path: string;
+  nodeId: string;
+  distance: number; // Edit distance from the queried path
+}
+
+export interface PathResolution {
+  query: string;
+  nodeId?: string; // Set on an exact match
+  exact: boolean;
+  suggestions: PathSuggestion[]; // Closest paths on a miss, nearest first
+  message?: string; // "did you mean `crate::config::load_config`?"
+}
+
+export interface FuzzyPathOptions {
+  maxDistance?: number; // Largest edit distance still worth suggesting (default 3)
+  limit?: number; // Most suggestions returned (default 3)
+}
+
+export interface GenericBloatCandidate {
+  nodeId: string;
+  name: string;
+  filePath?: string;
+  line?: number;
+  typeParameters: string[];
+  instantiations: number; // Distinct type argument combinations seen at call sites
+  instantiationTypes: string[]; // e.g. `T=Meters`, one entry per distinct combination
+  sizeWeight: number; // Lines in the function, a stand-in for the code each copy adds
+  score: number; // instantiations * sizeWeight
+}
+
+// Derives that do not compile unless the listed companion traits are also implemented
+const DERIVE_REQUIREMENTS: Record<string, string[]> = {
+  'Eq': ['PartialEq'],
+  'PartialOrd': ['PartialEq'],
+  'Ord': ['PartialOrd', 'Eq'],
+  'Copy': ['Clone']
+};
+
+// Edge types that express a code dependency; containment and learned associations are excluded
+const DEPENDENCY_EDGE_TYPES = new Set(['calls', 'references', 'implements', 'imports', 'depends_on', 'used_by', 'instantiated_as']);
+
+// Longest `pub use` chain followed; a module re-exported inside itself would otherwise nest forever
+const MAX_REEXPORT_DEPTH = 8;
+
+/**
+ * Rust-specific checks and queries over the Rust items recorded in the mind map
+ */
+export class RustAnalysisService {
+  constructor(private storage: MindMapStorage) {}
+
+  /**
+   * Flag types whose derives imply companion traits that are neither derived nor implemented
+   */
+  checkDeriveConsistency(): DeriveConsistencyIssue[] {
+    const issues: DeriveConsistencyIssue[] = [];
+
+    for (const node of this.getRustTypes()) {
+      const derives: string[] = (node.metadata.derives || []).map((derive: string) => this.lastSegment(derive));
+      const implemented = new Set([...derives, ...this.getImplementedTraits(node.id)]);
+
+      for (const derive of derives) {
+        for (const required of DERIVE_REQUIREMENTS[derive] || []) {
+          if (implemented.has(required)) continue;
+          issues.push({
+            nodeId: node.id,
+            typeName: node.name,
+            filePath: node.path,
+            line: node.metadata.startLine,
+            derive,
+            missing: required,
+            message: `${node.name} derives ${derive} but not ${required}; add #[derive(${required})] or implement ${required} manually`
+          });
+        }
+      }
+    }
+
+    return issues.sort((a, b) => a.nodeId.localeCompare(b.nodeId) || a.missing.localeCompare(b.missing));
+  }
+
+  /**
+   * Report which trait methods an impl provides, leaves defaulted, or is missing.
+   * Accepts `Trait for Type`, or a type name/node ID (optionally narrowed to one trait).
+   */
+  traitCoverage(implNode: string, traitName?: string): TraitCoverage[] {
+    const implMatch = implNode.match(/^\s*(\S+)\s+for\s+(\S+)\s*$/);
+    const typeRef = implMatch ? implMatch[2] : implNode;
+    const traitRef = implMatch ? implMatch[1] : traitName;
+
+    const typeIds = new Set(this.getRustTypes()
+      .filter(node => node.id === typeRef || node.name === typeRef)
+      .map(node => node.id));
+
+    const coverage: TraitCoverage[] = [];
+    const implementsEdges = this.storage.findEdges(edge => edge.type === 'implements' && typeIds.has(edge.source));
+
+    for (const edge of implementsEdges) {
+      const trait = this.storage.getNode(edge.target);
+      if (!trait || (traitRef && trait.id !== traitRef && trait.name !== traitRef)) continue;
+
+      const traitMethods: string[] = trait.metadata.methods || [];
+      const defaultMethods = new Set<string>(trait.metadata.defaultMethods || []);
+      const implMethods = new Set<string>(edge.metadata?.methods || []);
+
+      const missing = traitMethods.filter(method => !implMethods.has(method) && !defaultMethods.has(method));
+      coverage.push({
+        typeName: this.storage.getNode(edge.source)?.name || edge.source,
+        traitName: trait.name,
+        implemented: traitMethods.filter(method => implMethods.has(method)),
+        defaulted: traitMethods.filter(method => !implMethods.has(method) && defaultMethods.has(method)),
+        missing,
+        complete: missing.length === 0
+      });
+    }
+
+    return coverage.sort((a, b) => a.typeName.localeCompare(b.typeName) || a.traitName.localeCompare(b.traitName));
+  }
+
+  /**
+   * For each default method of a trait, which implementors override it and which keep the default
+   */
+  defaultMethodUsage(traitNode: string): DefaultMethodUsage[] {
+    const trait = this.storage.findNodes(node =>
+      node.type === 'class' && node.metadata?.rustKind === 'trait' && (node.id === traitNode || node.name === traitNode)
+    ).sort((a, b) => a.id.localeCompare(b.id))[0];
+    if (!trait) return [];
+
+    const implementations = this.storage.findEdges(edge => edge.type === 'implements' && edge.target === trait.id)
+      .map(edge => ({
+        typeName: this.storage.getNode(edge.source)?.name || edge.source,
+        methods: new Set<string>(edge.metadata?.methods || [])
+      }))
+      .sort((a, b) => a.typeName.localeCompare(b.typeName));
+
+    return (trait.metadata.defaultMethods || []).map((method: string) => ({
+      method,
+      overriddenBy: implementations.filter(impl => impl.methods.has(method)).map(impl => impl.typeName),
+      usesDefault: implementations.filter(impl => !impl.methods.has(method)).map(impl => impl.typeName)
+    }));
+  }
+
+  /**
+   * Every impl providing its own body for a trait method, given as `Trait::method` or as a bare method
+   * name to search every trait declaring it. Implementors keeping a default body are left out; for a
+   * required method every implementor is listed.
+   */
+  overridesOf(traitMethod: string): MethodOverride[] {
+    const separator = traitMethod.lastIndexOf('::');
+    const traitRef = separator >= 0 ? traitMethod.substring(0, separator) : undefined;
+    const method = traitMethod.substring(separator >= 0 ? separator + 2 : 0);
+    const traits = this.storage.findNodes(node =>
+      node.type === 'class' && node.metadata?.rustKind === 'trait' && (node.metadata.methods || []).includes(method) &&
+      (!traitRef || node.id === traitRef || node.name === this.lastSegment(traitRef))
+    );
+
+    const overrides: MethodOverride[] = [];
+    for (const trait of traits) {
+      const overridesDefault = (trait.metadata.defaultMethods || []).includes(method);
+      for (const edge of this.storage.findEdges(candidate => candidate.type === 'implements' && candidate.target === trait.id)) {
+        // Derived impls list no methods: their bodies are generated, not written
+        if (!(edge.metadata?.methods || []).includes(method)) continue;
+        const span = edge.evidence?.find(evidence => evidence.kind === 'impl');
+        overrides.push({
+          traitName: trait.name,
+          traitId: trait.id,
+          method,
+          typeName: this.storage.getNode(edge.source)?.name || edge.source,
+          typeId: edge.source,
+          overridesDefault,
+          filePath: span?.filePath,
+          line: span?.startLine ?? edge.metadata!.startLine
+        });
+      }
+    }
+    return overrides.sort((a, b) => a.traitName.localeCompare(b.traitName) || a.typeName.localeCompare(b.typeName) || a.typeId.localeCompare(b.typeId));
+  }
+
+  /**
+   * The value each field of a struct takes in its `Default` impl, given the struct's node ID, name or
+   * path. A manual impl gives the expressions `fn default()` assigns; a derived one takes each field's
+   * own default, recorded as `derived`. Undefined when the struct has no `Default` impl in its file.
+   */
+  defaultValues(structRef: string): DefaultValues | undefined {
+    const node = this.itemByRef(structRef) ||
+      this.getRustTypes().filter(candidate => candidate.name === structRef).sort((a, b) => a.id.localeCompare(b.id))[0];
+    if (!node || node.metadata.rustKind !== 'struct' || !node.metadata.defaultImpl) return undefined;
+
+    const source: 'manual' | 'derived' = node.metadata.defaultImpl;
+    const values: RustDefaultValue[] = source === 'derived'
+      ? (node.metadata.properties || []).map((field: string) => ({ field, expression: 'Default::default()', kind: 'derived', line: node.metadata.startLine }))
+      : node.metadata.defaultValues || [];
+    return { typeName: node.name, typeId: node.id, source, values };
+  }
+
+  /**
+   * Trait methods implemented with the other asyncness: a plain `fn` (returning a future by hand) for
+   * an `async fn` in the trait, or the reverse. Both can compile, but they differ in `Send` bounds and
+   * lifetime capture, and often mean one side was refactored without the other.
+   */
+  asyncMismatches(): AsyncMismatch[] {
+    const mismatches: AsyncMismatch[] = [];
+    for (const edge of this.storage.findEdges(candidate => candidate.type === 'implements' && candidate.metadata?.methods !== undefined)) {
+      const trait = this.storage.getNode(edge.target);
+      if (!trait || trait.metadata?.rustKind !== 'trait') continue;
+      const typeName = this.storage.getNode(edge.source)?.name || edge.source;
+      const traitAsync = new Set<string>(trait.metadata.asyncMethods || []);
+      const implAsync = new Set<string>(edge.metadata!.asyncMethods || []);
+      const span = edge.evidence?.find(evidence => evidence.kind === 'impl');
+
+      for (const method of (edge.metadata!.methods as string[]).filter(name => (trait.metadata.methods || []).includes(name))) {
+        if (traitAsync.has(method) === implAsync.has(method)) continue;
+        mismatches.push({
+          typeName,
+          traitName: trait.name,
+          method,
+          traitAsync: traitAsync.has(method),
+          implAsync: implAsync.has(method),
+          filePath: span?.filePath,
+          line: span?.startLine ?? edge.metadata!.startLine,
+          message: traitAsync.has(method)
+            ? `${trait.name}::${method} is async but ${typeName} implements it with a plain fn`
+            : `${trait.name}::${method} is not async but ${typeName} implements it with an async fn`
+        });
+      }
+    }
+    return mismatches.sort((a, b) => (a.filePath || '').localeCompare(b.filePath || '') || (a.line || 0) - (b.line || 0) || a.method.localeCompare(b.method));
+  }
+
+  /**
+   * Blocking lock guards (`std::sync::Mutex`, `RwLock`) bound in an async function and still alive at a
+   * later `.await`. The task can be suspended holding the lock, blocking every other task that needs it
+   * and deadlocking when one of them runs on the same thread.
+   */
+  lockHeldAcrossAwait(): LockAcrossAwait[] {
+    const findings: LockAcrossAwait[] = [];
+    for (const node of this.storage.findNodes(candidate => candidate.type === 'function' && candidate.metadata?.language === 'rust' && candidate.metadata.isAsync && !candidate.metadata.inactive)) {
+      for (const site of (node.metadata.locksAcrossAwait || []) as RustLockAcrossAwait[]) {
+        findings.push({
+          nodeId: node.id,
+          name: node.name,
+          binding: site.binding,
+          acquire: site.acquire,
+          filePath: node.path,
+          line: site.line,
+          awaitLine: site.awaitLine,
+          message: `${node.name} holds the guard from ${site.acquire} across the .await on line ${site.awaitLine}`
+        });
+      }
+    }
+    return findings.sort((a, b) => (a.filePath || '').localeCompare(b.filePath || '') || a.line - b.line);
+  }
+
+  /**
+   * `match` expressions over a local enum whose arms leave variants uncovered with no `_` or binding
+   * arm to catch them. Arms with guards or refutable payloads (`A(0)`) do not count as covering their
+   * variant. Matches over enums outside the graph, such as `Option` and `Result`, are not checked.
+   */
+  nonExhaustiveMatches(): NonExhaustiveMatch[] {
+    const enums = this.storage.findNodes(node => node.type === 'class' && node.metadata?.language === 'rust' && node.metadata.rustKind === 'enum');
+    const findings: NonExhaustiveMatch[] = [];
+    for (const node of this.storage.findNodes(candidate => candidate.type === 'function' && candidate.metadata?.language === 'rust' && !candidate.metadata.inactive)) {
+      for (const site of (node.metadata.matchSites || []) as RustMatchSite[]) {
+        if (site.wildcard || !site.enumName) continue;
+        // `Self::A` inside an impl names the impl's type; a same-file enum wins over one elsewhere
+        const enumName = site.enumName === 'Self' && node.metadata.implTarget
+          ? this.lastSegment(String(node.metadata.implTarget).replace(/<[\s\S]*$/, ''))
+          : site.enumName;
+        const candidates = enums.filter(candidate => candidate.name === enumName);
+        const enumNode = candidates.find(candidate => candidate.path === node.path) || (candidates.length === 1 ? candidates[0] : undefined);
+        if (!enumNode) continue;
+        const covered = new Set(site.variants);
+        const missing = (enumNode.metadata.variants || []).filter((variant: string) => !covered.has(variant));
+        if (missing.length === 0) continue;
+        findings.push({
+          nodeId: node.id,
+          name: node.name,
+          enumName: enumNode.name,
+          enumId: enumNode.id,
+          scrutinee: site.scrutinee,
+          missing,
+          filePath: node.path,
+          line: site.line,
+          message: `match on ${site.scrutinee} in ${node.name} has no arm for ${missing.map((variant: string) => `${enumNode.name}::${variant}`).join(', ')}`
+        });
+      }
+    }
+    return findings.sort((a, b) => (a.filePath || '').localeCompare(b.filePath || '') || a.line - b.line);
+  }
+
+  /**
+   * Inherent methods sharing a name with a method of a trait the type implements but taking other
+   * parameters, a different receiver or returning another type. `value.method()` then picks the
+   * inherent one, while generic code over the trait calls the other.
+   */
+  methodShadowing(): MethodShadowing[] {
+    const shape = (method: RustMethodShape) =>
+      JSON.stringify([method.receiver, ...method.parameterTypes, method.returnType || '()'].map(part => part.replace(/\s+/g, '')));
+    const findings: MethodShadowing[] = [];
+    for (const type of this.storage.findNodes(node => node.type === 'class' && node.metadata?.language === 'rust' && node.metadata.inherentMethods?.length > 0)) {
+      for (const edge of this.storage.findEdges(candidate => candidate.type === 'implements' && candidate.source === type.id)) {
+        const trait = this.storage.getNode(edge.target);
+        if (!trait || trait.metadata?.rustKind !== 'trait') continue;
+        // The trait's declarations, or the impl's own signatures when the trait declares none
+        const traitShapes: RustMethodShape[] = trait.metadata.methodShapes?.length ? trait.metadata.methodShapes : edge.metadata?.methodShapes || [];
+        for (const inherent of type.metadata.inherentMethods as RustMethodShape[]) {
+          const declared = traitShapes.find(method => method.name === inherent.name);
+          if (!declared || shape(declared) === shape(inherent)) continue;
+          findings.push({
+            typeName: type.name,
+            typeId: type.id,
+            traitName: trait.name,
+            traitId: trait.id,
+            method: inherent.name,
+            inherentSignature: inherent.signature,
+            traitSignature: declared.signature,
+            filePath: type.path,
+            line: inherent.line,
+            message: `${type.name}::${inherent.name} shadows ${trait.name}::${inherent.name} with a different signature; ` +
+              `method calls on ${type.name} pick the inherent one`
+          });
+        }
+      }
+    }
+    return findings.sort((a, b) => (a.filePath || '').localeCompare(b.filePath || '') || a.line - b.line || a.traitName.localeCompare(b.traitName));
+  }
+
+  /**
+   * Flag statements that call a local `Result`/`Option`-returning function and drop the value
+   */
+  ignoredResults(): IgnoredResult[] {
+    const issues: IgnoredResult[] = [];
+    const functions = this.storage.findNodes(node => node.type === 'function' && node.metadata?.language === 'rust');
+
+    for (const caller of functions) {
+      for (const { call, line } of caller.metadata.discardedCalls || []) {
+        const calleeName = this.lastSegment(call.replace(/^\./, ''));
+        // Prefer a callee in the same file, as call edges do
+        const candidates = functions.filter(node => node.name === calleeName &&
+          (!call.startsWith('.') || (node.metadata.parameters || []).some((param: string) => param.endsWith('self'))));
+        const callee = candidates.find(node => node.path === caller.path) || (candidates.length === 1 ? candidates[0] : undefined);
+        const returnType: string | undefined = callee?.metadata.returnType;
+        if (!callee || !returnType) continue;
+
+        const wrapper = returnType.replace(/<[\s\S]*$/, '').trim();
+        if (!['Result', 'Option'].includes(this.lastSegment(wrapper))) continue;
+
+        issues.push({
+          nodeId: caller.id,
+          functionName: caller.name,
+          filePath: caller.path,
+          line,
+          call,
+          calleeId: callee.id,
+          returnType,
+          message: `${caller.name} discards the ${this.lastSegment(wrapper)} returned by ${calleeName}; handle it with \`?\`, match on it, or bind it to \`let _\``
+        });
+      }
+    }
+
+    return issues.sort((a, b) => (a.filePath || '').localeCompare(b.filePath || '') || a.line - b.line);
+  }
+
+  /**
+   * `let` bindings that shadow an earlier binding of the same name still in scope in their function
+   */
+  shadowingSites(): ShadowingSite[] {
+    const sites: ShadowingSite[] = [];
+    for (const func of this.storage.findNodes(node => node.type === 'function' && node.metadata?.language === 'rust')) {
+      for (const { name, line, previousLine } of func.metadata.shadowedBindings || []) {
+        sites.push({
+          nodeId: func.id,
+          functionName: func.name,
+          filePath: func.path,
+          name,
+          line,
+          previousLine,
+          message: `\`let ${name}\` in ${func.name} shadows the binding from line ${previousLine}`
+        });
+      }
+    }
+
+    return sites.sort((a, b) => (a.filePath || '').localeCompare(b.filePath || '') || a.line - b.line || a.name.localeCompare(b.name));
+  }
+
+  /**
+   * `pub` enums without `#[non_exhaustive]`: downstream crates may match them exhaustively, so adding
+   * a variant later is a breaking change
+   */
+  exhaustivePublicEnums(): ExhaustivePublicEnum[] {
+    return this.storage.findNodes(node => node.metadata?.rustKind === 'enum' && node.metadata.visibility === 'pub' && !node.metadata.inactive)
+      .filter(node => !(node.metadata.attributes || []).some((attribute: { name: string }) => attribute.name === 'non_exhaustive'))
+      .map(node => ({
+        nodeId: node.id,
+        name: node.name,
+        variants: node.metadata.variants || [],
+        filePath: node.path,
+        line: node.metadata.startLine,
+        message: `pub enum ${node.name} is exhaustive; mark it #[non_exhaustive] if variants may be added without a major release`
+      }))
+      .sort((a, b) => (a.filePath || '').localeCompare(b.filePath || '') || (a.line || 0) - (b.line || 0));
+  }
+
+  /**
+   * Rust items carrying an attribute that matches the pattern: `tokio::test`, `derive(Serialize)`,
+   * `cfg(feature = "extra")`, or an attribute macro like `instrument`. See `matchesAttribute`.
+   */
+  nodesWithAttribute(pattern: string): MindMapNode[] {
+    return this.storage.findNodes(node => node.metadata?.language === 'rust' &&
+      (node.metadata.attributes || []).some((attribute: RustAttribute) => matchesAttribute(attribute, pattern)))
+      .sort((a, b) => (a.path || '').localeCompare(b.path || '') || (a.metadata.startLine || 0) - (b.metadata.startLine || 0));
+  }
+
+  /**
+   * Deepest block nesting (branches, loops, match arms, closures) in a Rust function's body, by node ID
+   * or name; undefined for unknown functions and those without a parsed body
+   */
+  maxNestingDepth(functionRef: string): number | undefined {
+    const node = this.storage.getNode(functionRef) || this.storage.findNodes(candidate =>
+      candidate.type === 'function' && candidate.metadata?.language === 'rust' && candidate.name === functionRef
+    ).sort((a, b) => a.id.localeCompare(b.id))[0];
+    return node?.type === 'function' ? node.metadata?.nestingDepth : undefined;
+  }
+
+  /**
+   * Places that can panic: `.unwrap()`, `.expect(..)`, `panic!`, `unreachable!`, `todo!` and
+   * `unimplemented!`, each linked to its enclosing function
+   */
+  panicSites(options: PanicSiteOptions = {}): PanicSite[] {
+    const sites: PanicSite[] = [];
+    for (const node of this.storage.findNodes(candidate => candidate.type === 'function' && candidate.metadata?.language === 'rust' && !candidate.metadata.inactive)) {
+      const inTest = this.isTestOnly(node);
+      if (options.excludeTests && inTest) continue;
+      for (const site of (node.metadata.panicSites || []) as Array<{ kind: RustPanicKind; line: number }>) {
+        if (options.kinds && !options.kinds.includes(site.kind)) continue;
+        sites.push({ kind: site.kind, nodeId: node.id, name: node.name, filePath: node.path, line: site.line, inTest });
+      }
+    }
+    return sites.sort((a, b) => (a.filePath || '').localeCompare(b.filePath || '') || a.line - b.line);
+  }
+
+  /**
+   * `.clone()` calls that look unnecessary: the clone is only borrowed (`&x.clone()`) or bound with
+   * `let` and then never mutated or moved. Heuristic and low-confidence; `minConfidence` (default 0.5)
+   * drops the weaker candidates.
+   */
+  redundantCloneCandidates(minConfidence = 0.5): RedundantClone[] {
+    const candidates: RedundantClone[] = [];
+    for (const node of this.storage.findNodes(candidate => candidate.type === 'function' && candidate.metadata?.language === 'rust' && !candidate.metadata.inactive)) {
+      for (const site of (node.metadata.cloneSites || []) as RustCloneSite[]) {
+        if (site.confidence < minConfidence) continue;
+        candidates.push({
+          ...site,
+          nodeId: node.id,
+          name: node.name,
+          filePath: node.path,
+          message: site.reason === 'borrowed_clone'
+            ? `${site.expression} is only borrowed; borrowing the original avoids the copy`
+            : `${site.binding} holds ${site.expression} but is only read; a reference may do`
+        });
+      }
+    }
+    return candidates.sort((a, b) => (a.filePath || '').localeCompare(b.filePath || '') || a.line - b.line);
+  }
+
+  /**
+   * Functions that could be marked `const fn`: every operation in the body is allowed in const context
+   * (no allocation, I/O, loops over iterators, closures or non-const method calls) and every function
+   * called is const or a candidate itself. Trait methods, async, foreign and generic functions are left out.
+   */
+  constFnCandidates(): ConstFnCandidate[] {
+    const heapTypes = /(?<![\p{XID_Continue}])(?:String|Vec|Box|Rc|Arc|HashMap|HashSet|BTreeMap|BTreeSet|VecDeque)(?![\p{XID_Continue}])|(?<![\p{XID_Continue}])(?:dyn|impl)\s/u;
+    const eligible = new Map<string, MindMapNode>();
+    for (const node of this.storage.findNodes(candidate => candidate.type === 'function' && candidate.metadata?.language === 'rust')) {
+      const meta = node.metadata;
+      const eligibility = meta.constEligibility as RustConstEligibility | undefined;
+      if (!eligibility || eligibility.blockers.length > 0 || meta.isConst || meta.isAsync || meta.isForeign || meta.abi !== undefined) continue;
+      if (meta.traitName || meta.implTrait || meta.inactive || meta.external || (meta.typeParameters || []).length > 0) continue;
+      if (node.name === 'main' || this.isTestFunction(node)) continue;
+      // Values with destructors cannot be dropped in const context
+      if ([...(meta.parameterTypes || []), meta.returnType || ''].some((type: string) => heapTypes.test(type))) continue;
+      eligible.set(node.id, node);
+    }
+
+    // Callees are looked up among the resolved calls; a call to anything else is assumed non-const
+    const dependencies = new Map<string, string[]>();
+    for (const [id, node] of eligible) {
+      const callees = this.storage.findEdges(edge => edge.source === id && edge.type === 'calls')
+        .map(edge => this.storage.getNode(edge.target))
+        .filter((callee): callee is MindMapNode => callee !== undefined);
+      const pending: string[] = [];
+      let resolved = true;
+      for (const call of (node.metadata.constEligibility as RustConstEligibility).calls) {
+        if (CONST_STD_CALLS.test(call)) continue;
+        const callee = callees.find(candidate => candidate.name === this.lastSegment(call));
+        if (!callee || !(callee.metadata?.isConst || eligible.has(callee.id))) {
+          resolved = false;
+          break;
+        }
+        if (!callee.metadata.isConst && callee.id !== id) pending.push(callee.id);
+      }
+      if (resolved) dependencies.set(id, pending);
+    }
+
+    // Drop candidates whose callees were dropped, until nothing changes
+    let changed = true;
+    while (changed) {
+      changed = false;
+      for (const [id, pending] of dependencies) {
+        if (pending.every(dependency => dependencies.has(dependency))) continue;
+        dependencies.delete(id);
+        changed = true;
+      }
+    }
+
+    return Array.from(dependencies, ([id, pending]) => {
+      const node = eligible.get(id)!;
+      return {
+        nodeId: id,
+        name: node.name,
+        filePath: node.path,
+        line: node.metadata.startLine,
+        dependsOn: pending.sort(),
+        confidence: pending.length > 0 ? 0.3 : 0.4
+      };
+    }).sort((a, b) => (a.filePath || '').localeCompare(b.filePath || '') || a.line - b.line);
+  }
+
+  /**
+   * Parameters taken by value that the body never moves, mutates or consumes, only borrowing or
+   * reading them, so a reference would do. Trait methods, whose signatures the trait fixes, `Copy`
+   * types, shared pointers and path-qualified types (usually framework extractors) are left out.
+   */
+  unnecessaryOwnership(): UnnecessaryOwnership[] {
+    const findings: UnnecessaryOwnership[] = [];
+    for (const node of this.storage.findNodes(candidate => candidate.type === 'function' && candidate.metadata?.language === 'rust')) {
+      const meta = node.metadata;
+      if (!meta.readOnlyParameters?.length || meta.traitName || meta.implTrait || meta.inactive || meta.isForeign) continue;
+      for (const parameter of meta.readOnlyParameters as string[]) {
+        const type: string = meta.parameterTypes?.[(meta.parameters || []).indexOf(parameter)] || '';
+        const base = type.replace(/<[\s\S]*$/, '').trim();
+        if (!base || base.includes('::') || ['Arc', 'Rc', 'Cow'].includes(base) || (meta.typeParameters || []).includes(base)) continue;
+        if (this.storage.findNodes(candidate => candidate.type === 'class' && candidate.name === base && candidate.metadata?.language === 'rust')
+          .some(candidate => this.getSatisfiedTraits(candidate.id).has('Copy'))) continue;
+        const suggestion = this.borrowedForm(type);
+        findings.push({
+          nodeId: node.id,
+          name: node.name,
+          filePath: node.path,
+          line: meta.startLine,
+          parameter,
+          type,
+          suggestion,
+          message: `${node.name} takes ${parameter}: ${type} by value but only reads it; take ${suggestion} instead`
+        });
+      }
+    }
+    return findings.sort((a, b) => (a.filePath || '').localeCompare(b.filePath || '') || a.line - b.line || a.parameter.localeCompare(b.parameter));
+  }
+
+  private borrowedForm(type: string): string {
+    // The usual borrowed counterparts of owned std types
+    if (type === 'String') return '&str';
+    if (type === 'PathBuf') return '&Path';
+    if (type === 'OsString') return '&OsStr';
+    const element = type.match(/^(?:Vec|Box)\s*<([\s\S]+)>$/);
+    if (element) return type.startsWith('Vec') ? `&[${element[1].trim()}]` : `&${element[1].trim()}`;
+    return `&${type}`;
+  }
+
+  /**
+   * Literal values written in function bodies, optionally of one kind: hard-coded hosts, ports, paths
+   * and magic numbers, each linked to the function it appears in
+   */
+  literals(kind?: RustLiteralKind): LiteralUse[] {
+    const uses: LiteralUse[] = [];
+    for (const node of this.storage.findNodes(candidate => candidate.type === 'function' && candidate.metadata?.language === 'rust')) {
+      const ownerType = node.metadata.implTarget || node.metadata.traitName;
+      for (const literal of (node.metadata.literals || []) as RustLiteral[]) {
+        if (kind && literal.kind !== kind) continue;
+        uses.push({ ...literal, nodeId: node.id, owner: ownerType ? `${this.lastSegment(ownerType.replace(/<[\s\S]*$/, ''))}::${node.name}` : node.name, filePath: node.path });
+      }
+    }
+    return uses.sort((a, b) => (a.filePath || '').localeCompare(b.filePath || '') || a.line - b.line);
+  }
+
+  /**
+   * Where Rust meets other languages: foreign function declarations, functions exported with a C ABI or
+   * an unmangled symbol, and inline assembly. These are the places to audit first for memory safety.
+   */
+  ffiSurface(): FfiEntryPoint[] {
+    const kind = (node: MindMapNode): FfiEntryKind | undefined => {
+      if (node.metadata.patternType === 'inline_asm') return 'inline_asm';
+      if (node.type !== 'function' || !node.metadata.ffiBoundary) return undefined;
+      if (node.metadata.isForeign) return 'foreign_function';
+      return node.metadata.abi !== undefined ? 'extern_function' : 'exported_symbol';
+    };
+    return this.storage.findNodes(node => node.metadata?.language === 'rust' && !node.metadata.inactive && kind(node) !== undefined)
+      .map(node => ({
+        nodeId: node.id,
+        name: node.name,
+        kind: kind(node)!,
+        abi: node.metadata.abi,
+        filePath: node.path,
+        line: node.metadata.startLine
+      }))
+      .sort((a, b) => (a.filePath || '').localeCompare(b.filePath || '') || (a.line || 0) - (b.line || 0));
+  }
+
+  /**
+   * Environment variables the code reads by name, with every read site, as a map of the configuration
+   * a program takes from its environment. Reads through a computed name (`env::var(key)`) have a node
+   * marked `dynamic` but no name to list here.
+   */
+  envVars(): EnvVarUsage[] {
+    const usages = new Map<string, EnvVarUsage>();
+    for (const node of this.storage.findNodes(node => node.metadata?.patternType === 'env_var' && node.metadata.language === 'rust')) {
+      const name: string | undefined = node.metadata.variable;
+      if (name === undefined || node.metadata.inactive) continue;
+      if (!usages.has(name)) usages.set(name, { name, reads: [] });
+      for (const read of node.metadata.reads || []) {
+        const functionId = read.function ? `${node.path}#function:${read.function}` : undefined;
+        usages.get(name)!.reads.push({
+          nodeId: node.id,
+          access: read.access,
+          compileTime: read.compileTime,
+          function: read.function,
+          functionId: functionId && this.storage.getNode(functionId) ? functionId : undefined,
+          filePath: node.path,
+          line: read.line
+        });
+      }
+    }
+    for (const usage of usages.values()) {
+      usage.reads.sort((a, b) => (a.filePath || '').localeCompare(b.filePath || '') || a.line - b.line);
+    }
+    return Array.from(usages.values()).sort((a, b) => a.name.localeCompare(b.name));
+  }
+
+  /**
+   * Dependency edges between Rust items that go against the allowed directions between layers. The
+   * allowed directions must form a partial order: rules in which two layers may depend on each other,
+   * directly or through others, are rejected.
+   */
+  checkLayering(rules: LayeringRules): LayeringViolation[] {
+    const names = new Set(rules.layers.map(layer => layer.name));
+    for (const [from, targets] of Object.entries(rules.allow)) {
+      for (const name of [from, ...targets]) {
+        if (!names.has(name)) throw new Error(`Unknown layer in layering rules: ${name}`);
+      }
+    }
+
+    // Transitive closure of the allowed directions; a layer reaching itself through another is a cycle
+    const reachable = new Map<string, Set<string>>();
+    for (const name of names) {
+      const seen = new Set<string>();
+      const stack = [...(rules.allow[name] || [])];
+      while (stack.length > 0) {
+        const next = stack.pop()!;
+        if (next === name) throw new Error(`Layering rules are cyclic: ${name} may depend on itself through other layers`);
+        if (seen.has(next)) continue;
+        seen.add(next);
+        stack.push(...(rules.allow[next] || []));
+      }
+      reachable.set(name, seen);
+    }
+
+    const matchers = rules.layers.map(layer => ({ name: layer.name, patterns: layer.modules.map(pattern => this.modulePattern(pattern)) }));
+    const layerOf = (module: string) => matchers.find(layer => layer.patterns.some(pattern => pattern.test(module)))?.name;
+    const items = new Map(this.getRustItems().map(node => [node.id, node]));
+
+    const violations: LayeringViolation[] = [];
+    for (const edge of this.storage.findEdges(edge => DEPENDENCY_EDGE_TYPES.has(edge.type) && edge.source !== edge.target)) {
+      const source = items.get(edge.source);
+      const target = items.get(edge.target);
+      if (!source || !target) continue;
+      const sourceLayer = layerOf(source.metadata.module);
+      const targetLayer = layerOf(target.metadata.module);
+      if (!sourceLayer || !targetLayer || sourceLayer === targetLayer || reachable.get(sourceLayer)!.has(targetLayer)) continue;
+
+      const evidence = edge.evidence?.[0];
+      violations.push({
+        edgeId: edge.id,
+        edgeType: edge.type,
+        source: edge.source,
+        target: edge.target,
+        sourceLayer,
+        targetLayer,
+        sourceModule: source.metadata.module,
+        targetModule: target.metadata.module,
+        filePath: evidence?.filePath ?? source.path,
+        line: evidence?.startLine ?? edge.metadata?.line ?? source.metadata.startLine,
+        message: `${source.name} (${sourceLayer}) ${edge.type} ${target.name} (${targetLayer}), but ${sourceLayer} may not depend on ${targetLayer}`
+      });
+    }
+    return violations.sort((a, b) => (a.filePath || '').localeCompare(b.filePath || '') || (a.line || 0) - (b.line || 0) || a.edgeId.localeCompare(b.edgeId));
+  }
+
+  /**
+   * Coupling and cohesion of a module, computed over every dependency edge in the mind map
+   */
+  moduleMetrics(module: string): ModuleMetrics {
+    const name = this.moduleKey(module);
+    return this.computeModuleMetrics(name, this.moduleMembers(name));
+  }
+
+  /**
+   * Metrics for every Rust module, weakest structure (high coupling, low cohesion) first
+   */
+  allModuleMetrics(): ModuleMetrics[] {
+    const modules = new Map<string, Set<string>>();
+    for (const node of this.getRustItems()) {
+      // Count each item towards its own module and every enclosing one
+      const segments: string[] = node.metadata.module === 'crate' ? [] : node.metadata.module.split('::');
+      for (let depth = 1; depth <= segments.length; depth++) {
+        const name = segments.slice(0, depth).join('::');
+        modules.set(name, (modules.get(name) || new Set()).add(node.id));
+      }
+    }
+
+    return Array.from(modules, ([name, members]) => this.computeModuleMetrics(name, members))
+      .sort((a, b) => b.coupling - a.coupling || a.cohesion - b.cohesion || a.module.localeCompare(b.module));
+  }
+
+  /**
+   * A proposed split of a module into groups of items that depend on each other, found by label
+   * propagation over the dependencies between its items (processed in ID order, so the result is
+   * deterministic). Items depending on nothing else in the module stay together in one group.
+   */
+  suggestModuleSplit(module: string): ModuleSplit {
+    const name = this.moduleKey(module);
+    const members = Array.from(this.moduleMembers(name)).sort();
+    const memberSet = new Set(members);
+    const neighbors = new Map<string, Map<string, number>>(members.map(id => [id, new Map()]));
+    const internal = this.storage.findEdges(edge =>
+      (DEPENDENCY_EDGE_TYPES.has(edge.type) || edge.type === 'contains') && edge.source !== edge.target &&
+      memberSet.has(edge.source) && memberSet.has(edge.target)
+    );
+    for (const edge of internal) {
+      const link = (from: string, to: string) => neighbors.get(from)!.set(to, (neighbors.get(from)!.get(to) || 0) + 1);
+      link(edge.source, edge.target);
+      link(edge.target, edge.source);
+    }
+
+    // Each item takes the label most of its neighbours carry, keeping its own on a tie with it and
+    // otherwise taking the smallest, until nothing changes
+    const labels = new Map(members.map(id => [id, id]));
+    for (let round = 0, changed = true; changed && round < 50; round++) {
+      changed = false;
+      for (const id of members) {
+        const weights = new Map<string, number>();
+        for (const [neighbor, count] of neighbors.get(id)!) {
+          const label = labels.get(neighbor)!;
+          weights.set(label, (weights.get(label) || 0) + count);
+        }
+        if (weights.size === 0) continue;
+        const best = Math.max(...weights.values());
+        const current = labels.get(id)!;
+        const next = weights.get(current) === best ? current : Array.from(weights.keys()).filter(label => weights.get(label) === best).sort()[0];
+        if (next !== current) {
+          labels.set(id, next);
+          changed = true;
+        }
+      }
+    }
+
+    const groups = new Map<string, string[]>();
+    for (const id of members) {
+      const label = neighbors.get(id)!.size === 0 ? '' : labels.get(id)!;
+      groups.set(label, [...(groups.get(label) || []), id]);
+    }
+    const clusterOf = new Map<string, number>();
+    const clusters: ModuleCluster[] = Array.from(groups.values())
+      .sort((a, b) => b.length - a.length || a[0].localeCompare(b[0]))
+      .map((ids, index) => {
+        ids.forEach(id => clusterOf.set(id, index));
+        return { members: ids, names: ids.map(id => this.storage.getNode(id)?.name || id), internalEdges: 0 };
+      });
+    let crossEdges = 0;
+    for (const edge of internal) {
+      const cluster = clusterOf.get(edge.source)!;
+      if (cluster === clusterOf.get(edge.target)) clusters[cluster].internalEdges++;
+      else crossEdges++;
+    }
+
+    return { module: name, clusters, crossEdges };
+  }
+
+  /**
+   * Items most of whose dependencies, incoming and outgoing, are with the items of one other module,
+   * such as a helper only the other module calls: candidates for moving there. Methods move with their
+   * type and are not judged on their own.
+   */
+  misplacedItems(options: MisplacedItemOptions = {}): MisplacedItem[] {
+    const threshold = options.threshold ?? 0.6;
+    const minEdges = options.minEdges ?? 2;
+    const items = new Map(this.getRustItems()
+      .filter(node => !node.metadata.external && !node.metadata.implTarget && !node.metadata.traitName && !this.isTestFunction(node))
+      .map(node => [node.id, node]));
+    const moduleOf = (id: string) => {
+      const node = this.storage.getNode(id);
+      return typeof node?.metadata?.module === 'string' && !node.metadata.external ? node.metadata.module as string : undefined;
+    };
+
+    const counts = new Map<string, Map<string, number>>(); // Item -> module of the other endpoint -> dependencies
+    for (const edge of this.storage.findEdges(candidate => DEPENDENCY_EDGE_TYPES.has(candidate.type) && candidate.source !== candidate.target)) {
+      for (const [id, other] of [[edge.source, edge.target], [edge.target, edge.source]]) {
+        const module = items.has(id) ? moduleOf(other) : undefined;
+        if (module === undefined) continue;
+        const perModule = counts.get(id) || new Map<string, number>();
+        counts.set(id, perModule.set(module, (perModule.get(module) || 0) + 1));
+      }
+    }
+
+    const misplaced: MisplacedItem[] = [];
+    for (const [id, perModule] of counts) {
+      const node = items.get(id)!;
+      const totalEdges = Array.from(perModule.values()).reduce((sum, count) => sum + count, 0);
+      if (totalEdges < minEdges) continue;
+      const [suggestedModule, crossingEdges] = Array.from(perModule)
+        .filter(([module]) => module !== node.metadata.module)
+        .sort((a, b) => b[1] - a[1] || a[0].localeCompare(b[0]))[0] || [];
+      if (suggestedModule === undefined || crossingEdges / totalEdges < threshold || crossingEdges <= (perModule.get(node.metadata.module) || 0)) continue;
+      misplaced.push({
+        nodeId: id,
+        name: node.name,
+        module: node.metadata.module,
+        suggestedModule,
+        crossingEdges,
+        totalEdges,
+        share: crossingEdges / totalEdges,
+        filePath: node.path,
+        line: node.metadata.startLine,
+        message: `${node.name} is in ${node.metadata.module} but ${crossingEdges} of its ${totalEdges} dependencies are with ${suggestedModule}; consider moving it there`
+      });
+    }
+    return misplaced.sort((a, b) => b.share - a.share || a.nodeId.localeCompare(b.nodeId));
+  }
+
+  /**
+   * Async functions whose future no caller awaits or spawns and that are not registered as route handlers.
+   * Entry points (`#[tokio::main]`, `#[tokio::test]`, ...) are driven by their runtime and never flagged.
+   */
+  unusedAsync(): UnusedAsyncFunction[] {
+    const functions = this.storage.findNodes(node => node.type === 'function' && node.metadata?.language === 'rust');
+    const handlerIds = new Set(this.storage.findEdges(edge => edge.metadata?.relationship === 'route_handler').map(edge => edge.target));
+
+    // Awaited call names, with whether any awaits the name as a method
+    const awaitedNames = new Map<string, Array<{ callerId: string; isMethod: boolean }>>();
+    for (const caller of functions) {
+      for (const call of caller.metadata.awaitedCalls || []) {
+        const name = this.lastSegment(call.replace(/^\./, ''));
+        awaitedNames.set(name, [...(awaitedNames.get(name) || []), { callerId: caller.id, isMethod: call.startsWith('.') }]);
+      }
+    }
+
+    const unused: UnusedAsyncFunction[] = [];
+    for (const node of functions) {
+      if (!node.metadata.isAsync || handlerIds.has(node.id)) continue;
+
+      const attributes: Array<{ name: string }> = node.metadata.attributes || [];
+      if (attributes.some(attribute => /(?:^|::)(?:main|test)$/.test(attribute.name))) continue;
+
+      const hasReceiver = (node.metadata.parameters || []).some((param: string) => param.endsWith('self'));
+      const awaited = (awaitedNames.get(node.name) || [])
+        .some(use => use.callerId !== node.id && (!use.isMethod || hasReceiver));
+      if (awaited) continue;
+
+      unused.push({
+        nodeId: node.id,
+        name: node.name,
+        filePath: node.path,
+        line: node.metadata.startLine,
+        isPublic: node.metadata.isExported || false,
+        message: `async fn ${node.name} is never awaited, spawned or registered as a handler`
+      });
+    }
+
+    return unused.sort((a, b) => (a.filePath || '').localeCompare(b.filePath || '') || (a.line || 0) - (b.line || 0));
+  }
+
+  /**
+   * Check item and field names against per-kind case rules, merged over the Rust defaults
+   */
+  namingViolations(rules: NamingRules = {}): NamingViolation[] {
+    const effective: NamingRules = { ...DEFAULT_NAMING_RULES, ...rules };
+    const violations: NamingViolation[] = [];
+
+    const check = (node: MindMapNode, kind: NamingKind, name: string) => {
+      const expected = effective[kind];
+      // Raw identifiers (`r#type`) follow the same convention as the bare name
+      const bare = name.replace(/^r#/, '');
+      if (!expected || NAMING_PATTERNS[expected].test(bare)) return;
+      violations.push({
+        nodeId: node.id,
+        name,
+        kind,
+        expected,
+        suggestion: this.toCase(bare, expected),
+        filePath: node.path,
+        line: node.metadata.startLine
+      });
+    };
+
+    for (const node of this.storage.findNodes(node => node.metadata?.language === 'rust')) {
+      if (node.type === 'function') {
+        check(node, 'function', node.name);
+      } else if (node.type === 'class' && (node.metadata.rustKind === 'struct' || node.metadata.rustKind === 'trait')) {
+        check(node, node.metadata.rustKind, node.name);
+        for (const field of node.metadata.rustKind === 'struct' ? node.metadata.properties || [] : []) {
+          // Tuple struct fields are positional
+          if (!/^\d+$/.test(field)) check(node, 'field', field);
+        }
+      } else if (node.type === 'variable' && (node.metadata.variableType === 'const' || node.metadata.variableType === 'static')) {
+        check(node, node.metadata.variableType, node.name);
+      }
+    }
+
+    return violations.sort((a, b) =>
+      (a.filePath || '').localeCompare(b.filePath || '') || (a.line || 0) - (b.line || 0) || a.name.localeCompare(b.name)
+    );
+  }
+
+  /**
+   * How often each type is used by struct fields, parameters, return types and consts, including
+   * types nested in generics and trait objects (`Arc<dyn Repo>` uses both Arc and Repo)
+   */
+  typeHeatmap(options: TypeHeatmapOptions = {}): TypeUsage[] {
+    const dedupe = options.dedupe || 'node';
+    const usages = new Map<string, TypeUsage>();
+    const localTypes = new Map(this.storage.findNodes(node =>
+      node.type === 'class' && node.metadata?.language === 'rust'
+    ).map(node => [node.name, node.id]));
+
+    const record = (counted: Set<string>, typeText: string | undefined, role: TypeUsageRole) => {
+      for (const typeName of this.typeNamesIn(typeText || '')) {
+        if (dedupe === 'node' && counted.has(typeName)) continue;
+        counted.add(typeName);
+
+        let usage = usages.get(typeName);
+        if (!usage) {
+          usage = { typeName, nodeId: localTypes.get(typeName), count: 0, byRole: { field: 0, parameter: 0, return: 0, constant: 0 }, heat: 0 };
+          usages.set(typeName, usage);
+        }
+        usage.count++;
+        usage.byRole[role]++;
+      }
+    };
+
+    for (const node of this.storage.findNodes(node => node.metadata?.language === 'rust')) {
+      const counted = new Set<string>();
+      if (node.type === 'class' && node.metadata.rustKind === 'struct') {
+        for (const fieldType of node.metadata.propertyTypes || []) record(counted, fieldType, 'field');
+      } else if (node.type === 'function') {
+        for (const parameterType of node.metadata.parameterTypes || []) record(counted, parameterType, 'parameter');
+        record(counted, node.metadata.returnType, 'return');
+      } else if (node.type === 'variable') {
+        record(counted, node.metadata.dataType, 'constant');
+      }
+    }
+
+    const result = Array.from(usages.values());
+    const max = Math.max(1, ...result.map(usage => usage.count));
+    for (const usage of result) {
+      usage.heat = usage.count / max;
+    }
+
+    return result.sort((a, b) => b.count - a.count || a.typeName.localeCompare(b.typeName));
+  }
+
+  /**
+   * Public functions, structs and traits without a doc comment. With `minVisibility: 'pub'`
+   * restricted visibilities such as `pub(crate)` are left out.
+   */
+  undocumentedPublicApi(minVisibility: 'pub' | 'crate' = 'crate'): UndocumentedItem[] {
+    return this.storage.findNodes(node =>
+      node.metadata?.language === 'rust' &&
+      (node.type === 'function' || (node.type === 'class' && ['struct', 'trait'].includes(node.metadata.rustKind)))
+    )
+      .filter(node => {
+        const visibility: string = node.metadata.visibility || 'private';
+        if (visibility === 'private' || (minVisibility === 'pub' && visibility !== 'pub')) return false;
+        return !node.metadata.docComment;
+      })
+      .map(node => ({
+        nodeId: node.id,
+        name: node.name,
+        kind: node.type === 'function' ? 'function' : node.metadata.rustKind,
+        visibility: node.metadata.visibility,
+        filePath: node.path,
+        line: node.metadata.startLine
+      }))
+      .sort((a, b) => (a.filePath || '').localeCompare(b.filePath || '') || (a.line || 0) - (b.line || 0));
+  }
+
+  /**
+   * Public functions and methods no test exercises: nothing has a `tests` edge to them from a
+   * `#[test]` function, an integration test or a doc-test. Methods declared in or implementing
+   * a `pub` trait of the project count as public.
+   */
+  untestedPublicApi(): UntestedApiItem[] {
+    const publicTraits = new Set(this.storage.findNodes(node =>
+      node.metadata?.rustKind === 'trait' && node.metadata.visibility === 'pub'
+    ).map(node => node.name));
+    const tested = new Set(this.storage.findEdges(edge => edge.type === 'tests').map(edge => edge.target));
+
+    return this.storage.findNodes(node => node.type === 'function' && node.metadata?.language === 'rust')
+      .filter(node => !node.metadata.inactive && !this.isTestOnly(node) && !tested.has(node.id))
+      .map(node => ({ node, traitName: [node.metadata.traitName, node.metadata.implTrait].find(name => name && publicTraits.has(name)) }))
+      .filter(({ node, traitName }) => node.metadata.visibility === 'pub' || traitName)
+      .map(({ node, traitName }) => ({
+        nodeId: node.id,
+        name: node.name,
+        visibility: node.metadata.visibility || 'private',
+        traitName,
+        filePath: node.path,
+        line: node.metadata.startLine
+      }))
+      .sort((a, b) => (a.filePath || '').localeCompare(b.filePath || '') || (a.line || 0) - (b.line || 0));
+  }
+
+  /**
+   * Methods of a type (node ID or name) from its inherent and trait impls; for a trait, the methods it
+   * declares. Associated functions without a receiver are left out unless asked for.
+   */
+  typeMethods(typeNode: string, filter: TypeMethodFilter = {}): TypeMethod[] {
+    const types = this.storage.findNodes(node =>
+      node.type === 'class' && node.metadata?.language === 'rust' && (node.id === typeNode || node.name === typeNode)
+    );
+    const typeNames = new Set(types.filter(node => node.metadata.rustKind !== 'trait').map(node => node.name));
+    const traitNames = new Set(types.filter(node => node.metadata.rustKind === 'trait').map(node => node.name));
+
+    return this.storage.findNodes(node => node.type === 'function' && node.metadata?.language === 'rust')
+      .filter(node => {
+        const implTarget: string | undefined = node.metadata.implTarget;
+        return (implTarget && typeNames.has(this.lastSegment(implTarget.replace(/<[\s\S]*$/, '')))) ||
+          (!implTarget && traitNames.has(node.metadata.traitName));
+      })
+      .map(node => ({
+        nodeId: node.id,
+        name: node.name,
+        receiver: (node.metadata.receiver || 'none') as RustReceiver,
+        receiverMutable: node.metadata.receiverMutable || false,
+        traitName: node.metadata.implTrait || node.metadata.traitName,
+        filePath: node.path,
+        line: node.metadata.startLine
+      }))
+      .filter(method => filter.includeAssociated || method.receiver !== 'none')
+      .filter(method => filter.receiverMutable === undefined || method.receiverMutable === filter.receiverMutable)
+      .sort((a, b) => (a.filePath || '').localeCompare(b.filePath || '') || (a.line || 0) - (b.line || 0));
+  }
+
+  /**
+   * Functions in the impls of a type that take no `self`: constructors like `new` and other associated functions
+   */
+  associatedFunctions(typeNode: string): TypeMethod[] {
+    return this.typeMethods(typeNode, { includeAssociated: true }).filter(method => method.receiver === 'none');
+  }
+
+  /**
+   * Which private and restricted (`pub(crate)`, `pub(super)`) items can be reached through calls,
+   * containment and type references from the public API or an entry point. Methods of trait impls
+   * are live whenever their type is and are never reported.
+   */
+  internalReachableFromApi(): ApiReachability {
+    const items = this.getRustItems().filter(node => node.type === 'function' || node.type === 'class' || node.type === 'variable');
+    const handlerIds = new Set(this.storage.findEdges(edge => edge.metadata?.relationship === 'route_handler').map(edge => edge.target));
+    const roots = items.filter(node =>
+      node.metadata.visibility === 'pub' ||
+      (node.type === 'function' && (node.name === 'main' || handlerIds.has(node.id) || this.isTestFunction(node)))
+    ).map(node => node.id).sort();
+
+    const successors = new Map<string, string[]>();
+    for (const edge of this.storage.findEdges(edge => REACHABILITY_EDGE_TYPES.has(edge.type))) {
+      if (!successors.has(edge.source)) successors.set(edge.source, []);
+      successors.get(edge.source)!.push(edge.target);
+    }
+    const reachable = new Set(roots);
+    const queue = [...roots];
+    while (queue.length > 0) {
+      for (const next of successors.get(queue.shift()!) || []) {
+        if (!reachable.has(next)) {
+          reachable.add(next);
+          queue.push(next);
+        }
+      }
+    }
+
+    const unreachable = items
+      .filter(node => !reachable.has(node.id) && !node.metadata.implTrait && !node.metadata.inactive)
+      .map(node => ({
+        nodeId: node.id,
+        name: node.name,
+        kind: node.type === 'function' ? 'function' : node.metadata.rustKind || node.metadata.variableType,
+        visibility: node.metadata.visibility || 'private',
+        filePath: node.path,
+        line: node.metadata.startLine
+      }))
+      .sort((a, b) => (a.filePath || '').localeCompare(b.filePath || '') || (a.line || 0) - (b.line || 0));
+
+    return { roots, reachable: Array.from(reachable).sort(), unreachable };
+  }
+
+  /**
+   * Functions taking more than `threshold` parameters, widest first
+   */
+  longParameterLists(threshold = 5, options: LongParameterListOptions = {}): LongParameterList[] {
+    return this.storage.findNodes(node => node.type === 'function' && node.metadata?.language === 'rust')
+      .map(node => {
+        const parameters: string[] = node.metadata.parameters || [];
+        const counted = options.excludeSelf ? parameters.filter(param => !/^(?:&\s*)?(?:mut\s+)?self$/.test(param)) : parameters;
+        return {
+          nodeId: node.id,
+          name: node.name,
+          filePath: node.path,
+          line: node.metadata.startLine,
+          parameterCount: counted.length
+        };
+      })
+      .filter(item => item.parameterCount > threshold)
+      .sort((a, b) => b.parameterCount - a.parameterCount || a.nodeId.localeCompare(b.nodeId));
+  }
+
+  /**
+   * Private and restricted struct fields that no function reads, writes, initializes or
+   * destructures. Matching is by field name across all analyzed code, so a field is only
+   * reported when the name appears nowhere; structs deriving a trait that touches every field
+   * (`Debug`, `Serialize`, ...) and `_`-prefixed fields are skipped.
+   */
+  unusedFields(): UnusedField[] {
+    const mentioned = new Set<string>();
+    for (const func of this.storage.findNodes(node => node.type === 'function' && node.metadata?.language === 'rust')) {
+      for (const field of func.metadata.fieldMentions || []) mentioned.add(field);
+    }
+
+    const unused: UnusedField[] = [];
+    for (const struct of this.getRustTypes()) {
+      if (struct.metadata.inactive) continue;
+      const derives: string[] = (struct.metadata.derives || []).map((derive: string) => this.lastSegment(derive));
+      if (derives.some(derive => FIELD_TOUCHING_DERIVES.has(derive))) continue;
+
+      const { properties = [], propertyVisibility = [] } = struct.metadata;
+      properties.forEach((field: string, index: number) => {
+        const visibility: string = propertyVisibility[index] || 'private';
+        if (visibility === 'pub' || field.startsWith('_') || mentioned.has(field)) return;
+        unused.push({
+          nodeId: struct.id,
+          struct: struct.name,
+          field,
+          visibility,
+          filePath: struct.path,
+          line: struct.metadata.startLine
+        });
+      });
+    }
+    return unused.sort((a, b) => (a.filePath || '').localeCompare(b.filePath || '') || (a.line || 0) - (b.line || 0));
+  }
+
+  /**
+   * For functions (by ID or name) returning `Result<T, E>` with a local enum `E`, the variants the
+   * body returns as `Err(E::Variant)` next to the ones it never produces. Errors propagated with
+   * `?` or built by helpers are not seen, so `produced` is a lower bound.
+   */
+  errorVariants(functionNode: string): ErrorVariantUsage[] {
+    const usages: ErrorVariantUsage[] = [];
+    const functions = this.storage.findNodes(node =>
+      node.type === 'function' && node.metadata?.language === 'rust' && (node.id === functionNode || node.name === functionNode)
+    );
+    for (const func of functions.sort((a, b) => a.id.localeCompare(b.id))) {
+      const produced = new Set(this.storage.findEdges(edge =>
+        edge.source === func.id && edge.type === 'references' && edge.metadata?.relationship === 'error_variant'
+      ).map(edge => edge.target));
+      const errorType: string | undefined = func.metadata.errorType;
+      const enums = this.storage.findEdges(edge =>
+        edge.source === func.id && edge.type === 'references' && edge.metadata?.relationship === 'return_type'
+      ).map(edge => this.storage.getNode(edge.target))
+        .filter((node): node is MindMapNode =>
+          node?.metadata?.rustKind === 'enum' && errorType !== undefined && this.lastSegment(errorType.replace(/<[\s\S]*$/, '')) === node.name
+        );
+
+      for (const errorEnum of enums) {
+        const variants = this.storage.findNodes(node => node.metadata?.enumId === errorEnum.id)
+          .sort((a, b) => (a.metadata.startLine || 0) - (b.metadata.startLine || 0));
+        usages.push({
+          nodeId: func.id,
+          function: func.name,
+          errorType: errorEnum.name,
+          produced: variants.filter(variant => produced.has(variant.id)).map(variant => variant.name),
+          unproduced: variants.filter(variant => !produced.has(variant.id)).map(variant => variant.name)
+        });
+      }
+    }
+    return usages;
+  }
+
+  /**
+   * Generic bounds that can be dropped without changing a signature: a trait listed twice for the
+   * same parameter (inline and in `where`, or within one list), or one implied by a supertrait of
+   * another bound (`T: Ord + PartialOrd`). Supertraits come from the project's trait declarations
+   * and the standard library's comparison, closure, iterator and I/O traits.
+   */
+  redundantBounds(): RedundantBound[] {
+    const implied = this.supertraitClosure();
+
+    const redundant: RedundantBound[] = [];
+    for (const func of this.storage.findNodes(node => node.type === 'function' && node.metadata?.language === 'rust')) {
+      if (func.metadata.inactive) continue;
+      const byParameter = new Map<string, Array<{ bound: string; source: 'inline' | 'where' }>>();
+      for (const clause of func.metadata.genericBounds || []) {
+        const bounds = byParameter.get(clause.parameter) || [];
+        for (const bound of clause.bounds) bounds.push({ bound: this.lastSegment(bound), source: clause.source });
+        byParameter.set(clause.parameter, bounds);
+      }
+
+      for (const [parameter, bounds] of byParameter) {
+        const report = (bound: string, source: 'inline' | 'where', reason: 'implied' | 'duplicate', impliedBy?: string) => redundant.push({
+          nodeId: func.id,
+          function: func.name,
+          parameter,
+          bound,
+          reason,
+          impliedBy,
+          source,
+          filePath: func.path,
+          line: func.metadata.startLine
+        });
+
+        const seen = new Set<string>();
+        const distinct: Array<{ bound: string; source: 'inline' | 'where' }> = [];
+        for (const entry of bounds) {
+          if (seen.has(entry.bound)) report(entry.bound, entry.source, 'duplicate');
+          else distinct.push(entry);
+          seen.add(entry.bound);
+        }
+        for (const entry of distinct) {
+          const implier = distinct.find(other => other.bound !== entry.bound && implied(other.bound).has(entry.bound));
+          if (implier) report(entry.bound, entry.source, 'implied', implier.bound);
+        }
+      }
+    }
+    return redundant.sort((a, b) =>
+      (a.filePath || '').localeCompare(b.filePath || '') || (a.line || 0) - (b.line || 0) || a.bound.localeCompare(b.bound)
+    );
+  }
+
+  /**
+   * Every trait a type must implement to be used for a bounded parameter of a generic function: the
+   * written bounds and, transitively, their supertraits. `parameter` names a type parameter (`T`) or a
+   * value parameter typed `impl Trait` or `T`; by default the first bounded type parameter is used.
+   * Undefined when either node is missing or the parameter has no bounds.
+   */
+  requiredTraitsFor(typeNodeId: string, functionNodeId: string, parameter?: string): TraitRequirements | undefined {
+    const type = this.storage.getNode(typeNodeId);
+    const func = this.storage.getNode(functionNodeId);
+    if (!type || func?.type !== 'function') return undefined;
+
+    const clauses: Array<{ parameter: string; bounds: string[] }> = func.metadata.genericBounds || [];
+    const parameters: string[] = func.metadata.parameters || [];
+    const valueIndex = parameter === undefined ? -1 : parameters.indexOf(parameter);
+    const bounded = parameter ?? clauses[0]?.parameter;
+    const written = valueIndex >= 0
+      ? (func.metadata.parameterBounds?.[valueIndex] || []) as string[]
+      : clauses.filter(clause => clause.parameter === bounded).flatMap(clause => clause.bounds.map(bound => this.lastSegment(bound)));
+    if (bounded === undefined || written.length === 0) return undefined;
+
+    const implied = this.supertraitClosure();
+    const requiredBy = new Map<string, string>();
+    for (const bound of written) {
+      requiredBy.set(bound, bound);
+      for (const supertrait of implied(bound)) {
+        if (!requiredBy.has(supertrait)) requiredBy.set(supertrait, bound);
+      }
+    }
+
+    const implemented = new Set<string>([...(type.metadata?.implementedTraits || []), ...(type.metadata?.derives || [])]
+      .map((name: string) => this.lastSegment(name)));
+    const required = Array.from(requiredBy, ([trait, by]) => ({ trait, requiredBy: by, implemented: implemented.has(trait) }))
+      .sort((a, b) => a.trait.localeCompare(b.trait));
+    return {
+      typeId: type.id,
+      typeName: type.name,
+      functionId: func.id,
+      function: func.name,
+      parameter: bounded,
+      required,
+      missing: required.filter(entry => !entry.implemented).map(entry => entry.trait)
+    };
+  }
+
+  /**
+   * Functions declaring one lifetime that elision would infer: it annotates the only reference among the
+   * parameters and, if anywhere else, only references in the return type. Receivers, other input
+   * references, bounds and `where` clauses mentioning the lifetime all rule a function out.
+   */
+  redundantLifetimes(): RedundantLifetime[] {
+    const redundant: RedundantLifetime[] = [];
+    for (const func of this.storage.findNodes(node => node.type === 'function' && node.metadata?.language === 'rust')) {
+      if (func.metadata.inactive || !['none', 'value'].includes(func.metadata.receiver)) continue;
+      const params: Array<{ name: string; kind: string }> = func.metadata.genericParams || [];
+      const lifetimes = params.filter(param => param.kind === 'lifetime');
+      if (lifetimes.length !== 1 || lifetimes[0].name === 'static') continue;
+      const name = lifetimes[0].name;
+      const signature = String(func.metadata.signature || '');
+      const parameterTypes: string[] = (func.metadata.parameterTypes || []).filter((type: string) => type);
+      const returnType = String(func.metadata.returnType || '');
+      const uses = (text: string) => (text.match(new RegExp(`'${name}(?![\\p{XID_Continue}])`, 'gu')) || []).length;
+      const references = (text: string) => (text.match(new RegExp(`&\\s*'${name}(?![\\p{XID_Continue}])`, 'gu')) || []).length;
+
+      // One input reference, written `&'a T`, and no other reference or lifetime among the inputs
+      const inputs = parameterTypes.join(', ');
+      if (uses(inputs) !== 1 || references(inputs) !== 1 || (inputs.match(/&/g) || []).length !== 1 || /'(?!static\b)/.test(inputs.replace(new RegExp(`'${name}`, 'g'), ''))) continue;
+      // The output may only borrow from it, and nothing else may mention it
+      if (uses(returnType) !== references(returnType) || /'/.test(returnType.replace(new RegExp(`'${name}`, 'g'), ''))) continue;
+      if (uses(signature) !== 1 + uses(inputs) + uses(returnType)) continue;
+
+      const elided = signature
+        .replace(new RegExp(`<\\s*'${name}\\s*>`), '')
+        .replace(new RegExp(`'${name}\\s*,\\s*`), '')
+        .replace(new RegExp(`&\\s*'${name}\\s+`, 'g'), '&');
+      redundant.push({
+        nodeId: func.id,
+        function: func.name,
+        lifetime: `'${name}`,
+        elided,
+        filePath: func.path,
+        line: func.metadata.startLine,
+        message: `${func.name} declares '${name} where lifetime elision would infer it: ${elided}`
+      });
+    }
+    return redundant.sort((a, b) => (a.filePath || '').localeCompare(b.filePath || '') || (a.line || 0) - (b.line || 0));
+  }
+
+  /**
+   * Public API that other crates cannot use as written: `pub` structs whose fields are all
+   * private and that have no public constructor, function returning them or constructing trait
+   * impl, and `pub` functions returning a type with narrower visibility such as `pub(crate)`
+   */
+  visibilitySmells(): VisibilitySmell[] {
+    const smells: VisibilitySmell[] = [];
+    const returnEdges = this.storage.findEdges(edge => edge.type === 'references' && edge.metadata?.relationship === 'return_type');
+
+    for (const struct of this.getRustTypes()) {
+      const { visibility, properties = [], propertyVisibility = [], constructors = [] } = struct.metadata;
+      if (visibility !== 'pub' || properties.length === 0) continue;
+      if (propertyVisibility.some((fieldVisibility: string) => fieldVisibility === 'pub')) continue;
+      if (constructors.length > 0) continue;
+      if (Array.from(this.getSatisfiedTraits(struct.id)).some(trait => CONSTRUCTING_TRAITS.has(trait))) continue;
+      if (returnEdges.some(edge => edge.target === struct.id && this.storage.getNode(edge.source)?.metadata.visibility === 'pub')) continue;
+
+      smells.push({
+        kind: 'unconstructable_struct',
+        nodeId: struct.id,
+        name: struct.name,
+        filePath: struct.path,
+        line: struct.metadata.startLine,
+        message: `pub struct ${struct.name} has only private fields and no public constructor`
+      });
+    }
+
+    for (const edge of returnEdges) {
+      const func = this.storage.getNode(edge.source);
+      const type = this.storage.getNode(edge.target);
+      if (!func || !type || func.metadata.visibility !== 'pub' || func.metadata.traitName) continue;
+      const typeVisibility: string = type.metadata.visibility || 'private';
+      if (typeVisibility === 'pub') continue;
+
+      smells.push({
+        kind: 'private_return_type',
+        nodeId: func.id,
+        name: func.name,
+        relatedId: type.id,
+        filePath: func.path,
+        line: func.metadata.startLine,
+        message: `pub fn ${func.name} returns ${type.name}, which is only ${typeVisibility === 'private' ? 'visible in its module' : typeVisibility}`
+      });
+    }
+
+    return smells.sort((a, b) => (a.filePath || '').localeCompare(b.filePath || '') || (a.line || 0) - (b.line || 0));
+  }
+
+  /**
+   * Generic functions ranked by a monomorphization cost estimate: the number of distinct type
+   * argument combinations at their call sites (from turbofish or inferred argument types) times
+   * their size. Calls whose type arguments cannot be inferred are not counted.
+   */
+  genericBloatCandidates(): GenericBloatCandidate[] {
+    const functions = this.storage.findNodes(node => node.type === 'function' && node.metadata?.language === 'rust');
+    const generics = new Map<string, MindMapNode[]>();
+    for (const node of functions) {
+      if ((node.metadata.typeParameters || []).length > 0) {
+        generics.set(node.name, [...(generics.get(node.name) || []), node]);
+      }
+    }
+
+    const instantiations = new Map<string, Set<string>>();
+    for (const caller of functions) {
+      for (const site of caller.metadata.argumentTypes || []) {
+        const candidates = generics.get(this.lastSegment(site.call)) || [];
+        const callee = candidates.find(candidate => candidate.path === caller.path) || (candidates.length === 1 ? candidates[0] : undefined);
+        if (!callee || callee.id === caller.id) continue;
+
+        const key = this.instantiationKey(callee, site);
+        if (!key) continue;
+        if (!instantiations.has(callee.id)) instantiations.set(callee.id, new Set());
+        instantiations.get(callee.id)!.add(key);
+      }
+    }
+
+    return Array.from(instantiations.entries())
+      .map(([nodeId, keys]) => {
+        const node = this.storage.getNode(nodeId)!;
+        const { startLine, endLine } = node.metadata;
+        const sizeWeight = typeof startLine === 'number' && typeof endLine === 'number' ? endLine - startLine + 1 : 1;
+        return {
+          nodeId,
+          name: node.name,
+          filePath: node.path,
+          line: startLine,
+          typeParameters: node.metadata.typeParameters,
+          instantiations: keys.size,
+          instantiationTypes: Array.from(keys).sort(),
+          sizeWeight,
+          score: keys.size * sizeWeight
+        };
+      })
+      .sort((a, b) => b.score - a.score || a.nodeId.localeCompare(b.nodeId));
+  }
+
+  /**
+   * The item at a crate-relative path such as `crate::config::load_config` (`crate::` is optional)
+   */
+  nodeByPath(path: string): MindMapNode | undefined {
+    const query = this.normalizeItemPath(path);
+    return this.getRustItems()
+      .filter(node => this.itemPath(node) === query)
+      .sort((a, b) => a.id.localeCompare(b.id))[0];
+  }
+
+  /**
+   * Resolve an item path, suggesting the closest known paths by edit distance when nothing matches
+   * exactly. An exact match always wins and skips the search.
+   */
+  resolvePathFuzzy(path: string, options: FuzzyPathOptions = {}): PathResolution {
+    const exact = this.nodeByPath(path);
+    if (exact) {
+      return { query: path, nodeId: exact.id, exact: true, suggestions: [] };
+    }
+
+    const query = this.normalizeItemPath(path);
+    const maxDistance = options.maxDistance ?? 3;
+    const best = new Map<string, PathSuggestion>();
+    for (const node of this.getRustItems()) {
+      const candidate = this.itemPath(node);
+      // Lengths differing by more than the threshold cannot be within it
+      if (Math.abs(candidate.length - query.length) > maxDistance) continue;
+
+      const distance = this.editDistance(query, candidate);
+      const existing = best.get(candidate);
+      if (distance <= maxDistance && (!existing || node.id < existing.nodeId)) {
+        best.set(candidate, { path: candidate, nodeId: node.id, distance });
+      }
+    }
+
+    const suggestions = Array.from(best.values())
+      .sort((a, b) => a.distance - b.distance || a.path.localeCompare(b.path))
+      .slice(0, options.limit ?? 3);
+    return {
+      query: path,
+      exact: false,
+      suggestions,
+      message: suggestions.length > 0 ? `did you mean \`${suggestions[0].path}\`?` : undefined
+    };
+  }
+
+  /**
+   * The path an item is defined at, such as `crate::config::ServerConfig`, given its node ID or any
+   * path it can be named by, including one a `pub use` re-exports it under
+   */
+  canonicalPath(nodeRef: string): string | undefined {
+    const node = this.itemByRef(nodeRef);
+    return node ? this.itemPath(node) : undefined;
+  }
+
+  /**
+   * Every path a `pub` item can be named by from outside its crate: the path it is defined at and
+   * each path a chain of `pub use` declarations re-exports it under, renames applied. Shortest first.
+   * Module privacy is not tracked, so the definition path is listed even inside a private module.
+   */
+  publicPaths(nodeRef: string): string[] {
+    const node = this.itemByRef(nodeRef);
+    if (!node || node.metadata.visibility !== 'pub') return [];
+
+    const reexports = this.getReexports().filter(reexport => reexport.metadata.visibility === 'pub');
+    const paths = new Set([this.itemPath(node)]);
+    let frontier = Array.from(paths);
+    for (let depth = 0; depth < MAX_REEXPORT_DEPTH && frontier.length > 0; depth++) {
+      frontier = frontier
+        .flatMap(path => reexports.map(reexport => this.reexportedAs(reexport, path)))
+        .filter((path): path is string => path !== undefined && !paths.has(path));
+      frontier.forEach(path => paths.add(path));
+    }
+    return Array.from(paths).sort((a, b) => a.split('::').length - b.split('::').length || a.localeCompare(b));
+  }
+
+  /**
+   * Public API that exposes a type-erased error: `pub` functions returning or taking
+   * `Box<dyn Error>` / `anyhow::Error` (directly or through a crate `Result<T>` alias) and trait
+   * impls binding an associated type such as `type Error = Box<dyn Error>`
+   */
+  boxedErrorUsage(options: BoxedErrorOptions = {}): BoxedErrorSite[] {
+    const allow = options.allow || [];
+    const allowed = (node: MindMapNode) => allow.some(entry =>
+      node.path === entry || (entry.endsWith('/') && (node.path || '').startsWith(entry)) ||
+      node.metadata.module === entry || (node.metadata.module || '').startsWith(`${entry}::`)
+    );
+    const sites: BoxedErrorSite[] = [];
+
+    for (const node of this.getRustItems()) {
+      if (allowed(node)) continue;
+
+      if (node.type === 'function' && (node.metadata.visibility || 'private') !== 'private') {
+        const site = (kind: BoxedErrorSite['kind'], errorType: string, typeText: string) => sites.push({
+          nodeId: node.id,
+          name: node.name,
+          kind,
+          errorType,
+          typeText,
+          filePath: node.path,
+          line: node.metadata.startLine,
+          message: `${node.name} exposes ${errorType} in its ${kind === 'parameter' ? 'parameters' : 'return type'}`
+        });
+
+        const returnType: string = node.metadata.returnType || '';
+        const wideReturn = returnType.match(WIDE_ERROR_PATTERN);
+        if (wideReturn) {
+          site('return', wideReturn[0], returnType);
+        } else if (WIDE_RESULT_ALIASES[node.metadata.resultAlias]) {
+          site('result_alias', WIDE_RESULT_ALIASES[node.metadata.resultAlias], returnType);
+        }
+        for (const type of node.metadata.parameterTypes || []) {
+          const wideParam = type.match(WIDE_ERROR_PATTERN);
+          if (wideParam) site('parameter', wideParam[0], type);
+        }
+      }
+
+      for (const binding of node.metadata.associatedTypeBindings || []) {
+        const wide = binding.type.match(WIDE_ERROR_PATTERN);
+        if (!wide) continue;
+        sites.push({
+          nodeId: node.id,
+          name: `<${node.name} as ${binding.trait}>::${binding.name}`,
+          kind: 'associated_type',
+          errorType: wide[0],
+          typeText: binding.type,
+          filePath: node.path,
+          line: binding.line,
+          message: `${node.name} binds ${binding.trait}::${binding.name} to ${wide[0]}`
+        });
+      }
+    }
+
+    return sites.sort((a, b) => (a.filePath || '').localeCompare(b.filePath || '') || (a.line || 0) - (b.line || 0));
+  }
+
+  /**
+   * Types implementing a trait, by impl block or derive, with the bounds an impl is conditional on.
+   * Derives on generic types are conditional too: `#[derive(Clone)]` on `Wrapper<T>` requires `T: Clone`
+   * (the bounds recorded as `requires_bound` edges into the generated impl).
+   */
+  findImplementors(trait: string): TraitImplementor[] {
+    const traitName = this.lastSegment(trait);
+    const implementors = new Map<string, TraitImplementor>();
+    const add = (node: MindMapNode, via: TraitImplementor['via'], conditions: string[], line?: number) => {
+      if (implementors.has(node.id)) return;
+      implementors.set(node.id, {
+        nodeId: node.id,
+        name: node.name,
+        trait: traitName,
+        via,
+        conditional: conditions.length > 0,
+        conditions,
+        filePath: node.path,
+        line: line ?? node.metadata.startLine
+      });
+    };
+
+    for (const node of this.getRustItems().filter(node => node.type === 'class' && !node.metadata.inactive)) {
+      const conditional = (node.metadata.conditionalImpls || []).find((impl: { trait: string }) => impl.trait === traitName);
+      if (conditional) {
+        add(node, 'impl', conditional.conditions, conditional.line);
+      } else if (this.getImplementedTraits(node.id).includes(traitName)) {
+        add(node, 'impl', []);
+      } else if ((node.metadata.derives || []).some((derive: string) => this.lastSegment(derive) === traitName)) {
+        add(node, 'derive', this.deriveConditions(node, traitName));
+      }
+    }
+    // Impls in other files than the type, recorded only on the implements edge
+    for (const edge of this.storage.findEdgesByType('implements')) {
+      const target = this.storage.getNode(edge.target);
+      const source = this.storage.getNode(edge.source);
+      if (target?.name !== traitName || target.metadata?.rustKind !== 'trait' || !source || source.metadata?.inactive) continue;
+      if (edge.metadata?.generatedBy) add(source, 'derive', this.deriveConditions(source, traitName));
+      else add(source, 'impl', edge.metadata?.conditions || [], edge.metadata?.startLine);
+    }
+
+    return Array.from(implementors.values()).sort((a, b) => a.name.localeCompare(b.name) || a.nodeId.localeCompare(b.nodeId));
+  }
+
+  /**
+   * Implementors of a trait grouped by the module defining each type, to show where in the architecture
+   * a trait is implemented. Groups are sorted by crate and module path, the crate root first.
+   */
+  implementorsByModule(trait: string): ModuleImplementors[] {
+    const groups = new Map<string, ModuleImplementors>();
+    for (const implementor of this.findImplementors(trait)) {
+      const node = this.storage.getNode(implementor.nodeId)!;
+      const moduleName: string = node.metadata.module || 'crate';
+      const module = moduleName === 'crate' ? 'crate' : `crate::${moduleName}`;
+      const crate: string | undefined = node.metadata.crate;
+      const key = `${crate || ''}\0${module}`;
+      if (!groups.has(key)) groups.set(key, { module, ...(crate ? { crate } : {}), implementors: [] });
+      groups.get(key)!.implementors.push(implementor);
+    }
+    return Array.from(groups.values()).sort((a, b) => (a.crate || '').localeCompare(b.crate || '') || a.module.localeCompare(b.module));
+  }
+
+  /**
+   * `dyn Trait` in struct fields and function signatures, with the types implementing the trait. A
+   * trait object with one implementor in the analyzed code pays for dynamic dispatch it may not need.
+   */
+  dynDispatchSites(): DynDispatchSite[] {
+    const implementorCache = new Map<string, { traitId?: string; implementors: string[] }>();
+    const implementorsOf = (trait: string) => {
+      const name = this.lastSegment(trait);
+      if (!implementorCache.has(name)) implementorCache.set(name, this.traitImplementors(name));
+      return implementorCache.get(name)!;
+    };
+    const sites: DynDispatchSite[] = [];
+
+    const scan = (node: MindMapNode, site: string, role: DynDispatchSite['role'], typeText: string | undefined, line?: number) => {
+      if (!typeText || !/\bdyn\b/.test(typeText)) return;
+      for (const trait of this.traitObjectsIn(parseRustType(typeText))) {
+        const { traitId, implementors } = implementorsOf(trait);
+        sites.push({
+          nodeId: node.id,
+          site,
+          role,
+          trait,
+          traitId,
+          typeText,
+          implementors,
+          devirtualizable: implementors.length === 1,
+          filePath: node.path,
+          line
+        });
+      }
+    };
+
+    for (const node of this.getRustItems()) {
+      if (node.metadata.inactive) continue;
+      if (node.type === 'class' && node.metadata.rustKind === 'struct') {
+        (node.metadata.properties || []).forEach((field: string, index: number) =>
+          scan(node, `${node.name}.${field}`, 'field', node.metadata.propertyTypes?.[index], node.metadata.startLine));
+      } else if (node.type === 'function') {
+        (node.metadata.parameters || []).forEach((parameter: string, index: number) =>
+          scan(node, `${node.name}(${parameter})`, 'parameter', node.metadata.parameterTypes?.[index], node.metadata.startLine));
+        scan(node, `${node.name} -> return`, 'return', node.metadata.returnType, node.metadata.startLine);
+      }
+    }
+
+    return sites.sort((a, b) =>
+      (a.filePath || '').localeCompare(b.filePath || '') || (a.line || 0) - (b.line || 0) || a.site.localeCompare(b.site) || a.trait.localeCompare(b.trait)
+    );
+  }
+
+  /**
+   * How each trait is used: through trait objects (`dyn Trait` in fields and signatures) or as a bound
+   * on the type parameters and `impl Trait` parameters of functions. A trait only ever used statically
+   * need not stay object safe; one used only dynamically may not need to be generic-friendly. Each site
+   * is counted once per trait, however many bounds or `dyn` types in it name the trait.
+   */
+  dispatchProfile(trait?: string): DispatchProfile[] {
+    const profiles = new Map<string, DispatchProfile>();
+    const record = (traitPath: string, nodeId: string, site: string, kind: DispatchSite['kind']) => {
+      const name = this.lastSegment(traitPath.replace(/[<(][\s\S]*$/, '').trim());
+      if (!name || MARKER_TRAITS.has(name) || (trait !== undefined && name !== this.lastSegment(trait))) return;
+      if (!profiles.has(name)) profiles.set(name, { trait: name, dynamicSites: 0, genericSites: 0, dynamicShare: 0, sites: [] });
+      const profile = profiles.get(name)!;
+      if (profile.sites.some(existing => existing.nodeId === nodeId && existing.site === site)) return;
+      profile.sites.push({ nodeId, site, kind });
+      if (kind === 'dynamic') profile.dynamicSites++;
+      else profile.genericSites++;
+    };
+
+    for (const site of this.dynDispatchSites()) {
+      record(site.trait, site.nodeId, site.site, 'dynamic');
+    }
+    for (const node of this.getRustItems()) {
+      if (node.type !== 'function' || node.metadata.inactive) continue;
+      for (const clause of (node.metadata.genericBounds || []) as Array<{ parameter: string; bounds: string[] }>) {
+        for (const bound of clause.bounds) {
+          if (!bound.startsWith('?') && !bound.startsWith("'")) record(bound, node.id, `${node.name}<${clause.parameter}>`, 'generic');
+        }
+      }
+      // `impl Trait` parameters are anonymous type parameters; named ones were counted with their clause
+      const parameterTypes: string[] = node.metadata.parameterTypes || [];
+      (node.metadata.parameters || []).forEach((parameter: string, index: number) => {
+        if (!/^(?:&\s*(?:'[A-Za-z_][A-Za-z0-9_]*\s+)?(?:mut\s+)?)?impl\b/.test(parameterTypes[index] || '')) return;
+        for (const bound of (node.metadata.parameterBounds?.[index] || []) as string[]) {
+          record(bound, node.id, `${node.name}(${parameter})`, 'generic');
+        }
+      });
+    }
+
+    const traitIds = new Map<string, string>();
+    for (const node of this.storage.findNodes(node =>
+      node.type === 'class' && node.metadata?.rustKind === 'trait' && node.metadata.language === 'rust' && !node.metadata.external
+    ).sort((a, b) => a.id.localeCompare(b.id))) {
+      if (!traitIds.has(node.name)) traitIds.set(node.name, node.id);
+    }
+    return Array.from(profiles.values())
+      .map(profile => ({
+        ...profile,
+        traitId: traitIds.get(profile.trait),
+        dynamicShare: profile.dynamicSites / (profile.dynamicSites + profile.genericSites),
+        sites: profile.sites.sort((a, b) => a.kind.localeCompare(b.kind) || a.nodeId.localeCompare(b.nodeId) || a.site.localeCompare(b.site))
+      }))
+      .sort((a, b) => (b.dynamicSites + b.genericSites) - (a.dynamicSites + a.genericSites) || a.trait.localeCompare(b.trait));
+  }
+
+  /**
+   * Struct fields and function parameters typed with a concrete project type that implements a trait
+   * declared in the project, where depending on the trait (`dyn Trait`, a generic bound or `impl Trait`)
+   * would invert the dependency. Constructing the type, as `main` does, is not a dependency on it; the
+   * type's own methods, trait impl methods (whose signatures the trait fixes) and test code are skipped.
+   */
+  dipCandidates(): DipCandidate[] {
+    const abstractions = new Map<string, { id: string; traits: Set<string> }>(); // Concrete type name -> its project traits
+    for (const edge of this.storage.findEdgesByType('implements')) {
+      const trait = this.storage.getNode(edge.target);
+      const type = this.storage.getNode(edge.source);
+      if (trait?.metadata?.rustKind !== 'trait' || trait.metadata.external || edge.metadata?.generatedBy) continue;
+      if (type?.type !== 'class' || type.metadata?.rustKind === 'trait' || type.metadata?.inactive) continue;
+      if (!abstractions.has(type.name)) abstractions.set(type.name, { id: type.id, traits: new Set() });
+      abstractions.get(type.name)!.traits.add(trait.name);
+    }
+    if (abstractions.size === 0) return [];
+
+    const candidates: DipCandidate[] = [];
+    const scan = (node: MindMapNode, site: string, role: DipCandidate['role'], typeText: string | undefined, owner?: string) => {
+      if (!typeText) return;
+      for (const name of new Set(this.typeNamesIn(typeText))) {
+        const concrete = abstractions.get(name);
+        if (!concrete || name === owner) continue;
+        const traits = Array.from(concrete.traits).sort();
+        candidates.push({
+          nodeId: node.id,
+          site,
+          role,
+          concreteType: name,
+          concreteTypeId: concrete.id,
+          traits,
+          typeText,
+          filePath: node.path,
+          line: node.metadata.startLine,
+          message: `${site} depends on the concrete type ${name}; depending on ${traits.join(' or ')} instead would decouple it from the implementation`
+        });
+      }
+    };
+
+    for (const node of this.getRustItems()) {
+      if (node.metadata.inactive || node.metadata.external || this.isTestOnly(node)) continue;
+      if (node.type === 'class' && node.metadata.rustKind === 'struct') {
+        (node.metadata.properties || []).forEach((field: string, index: number) =>
+          scan(node, `${node.name}.${field}`, 'field', node.metadata.propertyTypes?.[index], node.name));
+      } else if (node.type === 'function' && !node.metadata.traitName) {
+        const owner = node.metadata.implTarget ? this.lastSegment(node.metadata.implTarget.replace(/<.*$/, '').trim()) : undefined;
+        (node.metadata.parameters || []).forEach((parameter: string, index: number) =>
+          scan(node, `${node.name}(${parameter})`, 'parameter', node.metadata.parameterTypes?.[index], owner));
+      }
+    }
+
+    return candidates.sort((a, b) =>
+      (a.filePath || '').localeCompare(b.filePath || '') || (a.line || 0) - (b.line || 0) || a.site.localeCompare(b.site)
+    );
+  }
+
+  /**
+   * Structs shared across threads, held in an `Arc` or moved into a spawned thread or task, whose fields
+   * make them lack `Send` or `Sync`. An `Arc` needs both; a spawned closure needs `Send`. Auto traits are
+   * worked out from field types, trusting a manual `unsafe impl Send` or `Sync`.
+   */
+  threadSafetyReport(): ThreadSafetyIssue[] {
+    const structs = this.getRustTypes().filter(node => !node.metadata.inactive);
+    const byName = new Map<string, MindMapNode>();
+    for (const node of structs.sort((a, b) => a.id.localeCompare(b.id))) {
+      if (!byName.has(node.name)) byName.set(node.name, node);
+    }
+
+    const shared = new Map<string, Set<'arc' | 'spawn'>>();
+    const share = (node: MindMapNode | undefined, via: 'arc' | 'spawn') => {
+      if (node) shared.set(node.id, (shared.get(node.id) || new Set()).add(via));
+    };
+    const visitShared = (type: RustType) => {
+      if (type.kind === 'path' && SHARING_WRAPPERS.has(this.lastSegment(type.name || '')) && type.args?.[0]?.kind === 'path') {
+        share(byName.get(this.lastSegment(type.args[0].name || '')), 'arc');
+      }
+      (type.args || []).forEach(visitShared);
+    };
+    for (const node of structs) {
+      (node.metadata.propertyTypes || []).forEach((type: string) => visitShared(parseRustType(type)));
+    }
+    for (const node of this.storage.findNodes(candidate => candidate.type === 'function' && candidate.metadata?.language === 'rust' && !candidate.metadata.inactive)) {
+      const types: string[] = [...(node.metadata.parameterTypes || []), node.metadata.returnType].filter(Boolean);
+      types.forEach(type => visitShared(parseRustType(type)));
+      if (!node.metadata.spawns) continue;
+      // What a spawning function owns may be moved into the closure it spawns
+      for (const name of [...types.flatMap(type => this.typeNamesIn(parseRustType(type))), ...(node.metadata.typeMentions || [])]) {
+        share(byName.get(this.lastSegment(name)), 'spawn');
+      }
+    }
+
+    const issues: ThreadSafetyIssue[] = [];
+    const gapCache = new Map<string, Array<{ field: string; gaps: Map<AutoTrait, string> }>>();
+    for (const [typeId, vias] of shared) {
+      const node = this.storage.getNode(typeId)!;
+      const required = new Set<AutoTrait>(vias.has('arc') ? ['Send', 'Sync'] : ['Send']);
+      for (const { field, gaps } of this.structAutoTraitGaps(node, byName, gapCache, new Set())) {
+        const missing = (['Send', 'Sync'] as AutoTrait[]).filter(trait => required.has(trait) && gaps.has(trait));
+        if (missing.length === 0) continue;
+        const culprit = gaps.get(missing[0])!;
+        const sharedVia = Array.from(vias).sort();
+        issues.push({
+          typeName: node.name,
+          typeId,
+          sharedVia,
+          missing,
+          field,
+          culprit,
+          filePath: node.path,
+          line: node.metadata.startLine,
+          message: `${node.name} is ${sharedVia.map(via => via === 'arc' ? 'held in an Arc' : 'moved into a spawned task').join(' and ')}, but field ${field} holds ${culprit}, which is not ${missing.join(' or ')}`
+        });
+      }
+    }
+    return issues.sort((a, b) => (a.filePath || '').localeCompare(b.filePath || '') || (a.line || 0) - (b.line || 0) || a.field.localeCompare(b.field));
+  }
+
+  /**
+   * Error handling by module: each function returns a `Result` with a plain, type-erased
+   * (`Box<dyn Error>`, `anyhow::Error`) or project enum error, panics outside tests, or drops the results
+   * of calls. A module is mixed when its `Result`s carry more than one kind of error, or when it also
+   * panics or drops results. Without a module, every module with a styled function is reported.
+   */
+  errorStyleReport(module?: string): ModuleErrorStyle[] {
+    const wanted = module === undefined ? undefined : module.replace(/^crate::/, '') || 'crate';
+    const enums = new Set(this.storage.findNodes(node => node.metadata?.language === 'rust' && node.metadata.rustKind === 'enum').map(node => node.name));
+    // `Self::Error` in a method resolves through the impl's binding, as reported by the boxed-error check
+    const wideBindings = new Set(this.boxedErrorUsage()
+      .filter(site => site.kind === 'associated_type')
+      .map(site => site.name.replace(/^<(.+) as .+>::(\w+)$/, '$1::$2')));
+    const panicking = new Set(this.panicSites({ excludeTests: true }).map(site => site.nodeId));
+    const ignoring = new Set(this.ignoredResults().map(issue => issue.nodeId));
+
+    const groups = new Map<string, ModuleErrorStyle>();
+    for (const node of this.storage.findNodes(candidate => candidate.type === 'function' && candidate.metadata?.language === 'rust' && !candidate.metadata.inactive)) {
+      const moduleName: string = node.metadata.module || 'crate';
+      if ((wanted && moduleName !== wanted) || this.isTestOnly(node)) continue;
+
+      const styles: ErrorHandlingStyle[] = [];
+      let errorType: string | undefined = node.metadata.errorType || WIDE_RESULT_ALIASES[node.metadata.resultAlias];
+      const returnType: string = node.metadata.returnType || '';
+      if (this.lastSegment(returnType.replace(/<[\s\S]*$/, '').trim()) === 'Result') {
+        const associated = errorType?.match(/^Self::(\w+)$/);
+        if (associated && node.metadata.implTarget && wideBindings.has(`${node.metadata.implTarget}::${associated[1]}`)) {
+          styles.push('boxed_error');
+        } else if (errorType && WIDE_ERROR_PATTERN.test(errorType)) {
+          styles.push('boxed_error');
+        } else if (errorType && enums.has(this.lastSegment(errorType.replace(/<[\s\S]*$/, '')))) {
+          styles.push('custom_error');
+        } else {
+          styles.push('result');
+        }
+      } else {
+        errorType = undefined;
+      }
+      if (panicking.has(node.id)) styles.push('panics');
+      if (ignoring.has(node.id)) styles.push('ignores');
+      if (styles.length === 0) continue;
+
+      const crate: string | undefined = node.metadata.crate;
+      const label = moduleName === 'crate' ? 'crate' : `crate::${moduleName}`;
+      const key = `${crate || ''}\0${label}`;
+      if (!groups.has(key)) groups.set(key, { module: label, ...(crate ? { crate } : {}), styles: [], functions: [], mixed: false, message: '' });
+      groups.get(key)!.functions.push({ nodeId: node.id, name: node.name, styles, ...(errorType ? { errorType } : {}), filePath: node.path, line: node.metadata.startLine });
+    }
+
+    for (const group of groups.values()) {
+      const used = new Set(group.functions.flatMap(func => func.styles));
+      group.styles = ERROR_HANDLING_STYLES.filter(style => used.has(style));
+      const resultStyles = group.styles.filter(style => RESULT_STYLES.has(style));
+      group.mixed = resultStyles.length > 1 || (resultStyles.length > 0 && group.styles.length > resultStyles.length);
+      group.functions.sort((a, b) => (a.filePath || '').localeCompare(b.filePath || '') || (a.line || 0) - (b.line || 0) || a.name.localeCompare(b.name));
+      const users = (style: ErrorHandlingStyle) => {
+        const names = group.functions.filter(func => func.styles.includes(style)).map(func => func.name);
+        return `${style} (${names.join(', ')})`;
+      };
+      group.message = group.mixed
+        ? `${group.module} mixes error handling styles: ${group.styles.map(users).join('; ')}`
+        : `${group.module} handles errors consistently: ${group.styles.map(users).join('; ')}`;
+    }
+    return Array.from(groups.values()).sort((a, b) => (a.crate || '').localeCompare(b.crate || '') || a.module.localeCompare(b.module));
+  }
+
+  /**
+   * Call sites that pass a project type to a parameter whose generic bound (`T: Serialize`,
+   * `where T: Clone`, `impl Display`) the type neither implements nor derives. Argument types are
+   * inferred from struct literals, `Type::ctor(..)` calls and variables bound to them.
+   */
+  missingImpls(): MissingImpl[] {
+    const functions = this.storage.findNodes(node => node.type === 'function' && node.metadata?.language === 'rust');
+    const bounded = new Map<string, MindMapNode[]>();
+    for (const node of functions) {
+      const parameters: string[] = node.metadata.parameters || [];
+      const hasReceiver = parameters.some(param => param.endsWith('self'));
+      if (!hasReceiver && (node.metadata.parameterBounds || []).some((bounds: string[]) => bounds.length > 0)) {
+        bounded.set(node.name, [...(bounded.get(node.name) || []), node]);
+      }
+    }
+
+    const types = new Map<string, MindMapNode[]>();
+    for (const node of this.getRustTypes()) {
+      types.set(node.name, [...(types.get(node.name) || []), node]);
+    }
+    const inSameFile = (candidates: MindMapNode[], path?: string) =>
+      candidates.find(candidate => candidate.path === path) || (candidates.length === 1 ? candidates[0] : undefined);
+
+    const missing: MissingImpl[] = [];
+    for (const caller of functions) {
+      for (const site of caller.metadata.argumentTypes || []) {
+        const callee = inSameFile(bounded.get(this.lastSegment(site.call)) || [], caller.path);
+        if (!callee) continue;
+
+        site.types.forEach((typeName: string | null, index: number) => {
+          const type = typeName ? inSameFile(types.get(typeName) || [], caller.path) : undefined;
+          if (!type) return;
+
+          const satisfied = this.getSatisfiedTraits(type.id);
+          for (const traitName of callee.metadata.parameterBounds[index] || []) {
+            if (BLANKET_TRAITS.has(traitName) || satisfied.has(traitName)) continue;
+            missing.push({
+              callerId: caller.id,
+              calleeId: callee.id,
+              typeName: type.name,
+              typeId: type.id,
+              traitName,
+              argumentIndex: index,
+              filePath: caller.path,
+              line: site.line,
+              message: `${type.name} is passed to ${callee.name} but does not implement ${traitName}`
+            });
+          }
+        });
+      }
+    }
+
+    return missing.sort((a, b) => (a.filePath || '').localeCompare(b.filePath || '') || a.line - b.line);
+  }
+
+  /**
+   * The slice of the graph the tests exercise: test-only items (`#[test]` functions and anything
+   * under `#[cfg(test)]`) plus the production items they call, reference or name in their bodies
+   */
+  testGraph(): TestGraph {
+    const items = this.storage.findNodes(node => node.metadata?.language === 'rust' && node.type !== 'file');
+    const testNodes = items.filter(node => this.isTestOnly(node));
+    const testIds = new Set(testNodes.map(node => node.id));
+
+    const typesByName = new Map<string, MindMapNode[]>();
+    for (const node of items) {
+      if (node.type === 'class' && !testIds.has(node.id)) {
+        typesByName.set(node.name, [...(typesByName.get(node.name) || []), node]);
+      }
+    }
+
+    const edges = this.storage.findEdges(edge => testIds.has(edge.source) && DEPENDENCY_EDGE_TYPES.has(edge.type));
+    const productionIds = new Set(edges.map(edge => edge.target).filter(id => !testIds.has(id)));
+
+    // Type names used in test bodies (`Repo::new()`, struct literals) that no edge records
+    for (const node of testNodes) {
+      for (const name of node.metadata.typeMentions || []) {
+        const candidates = typesByName.get(name) || [];
+        const match = candidates.find(candidate => candidate.path === node.path) || (candidates.length === 1 ? candidates[0] : undefined);
+        if (match) productionIds.add(match.id);
+      }
+    }
+
+    const nodes = [...testNodes, ...Array.from(productionIds).map(id => this.storage.getNode(id)!).filter(node => node)];
+    return {
+      nodes,
+      edges,
+      testNodeIds: Array.from(testIds).sort(),
+      productionNodeIds: Array.from(productionIds).filter(id => this.storage.getNode(id)).sort()
+    };
+  }
+
+  /**
+   * Parsed arguments of a node's attributes with the given name, e.g. `serde` or `derive`
+   */
+  attributeArgs(nodeId: string, name: string): RustAttributeArg[] {
+    return attributeArgs(this.storage.getNode(nodeId)?.metadata.attributes, name);
+  }
+
+  /**
+   * What parsing a file skipped or guessed, ordered by line; empty for files never scanned.
+   * `minSeverity` leaves out the less severe ones.
+   */
+  diagnosticsFor(filePath: string, minSeverity: RustDiagnosticSeverity = 'info'): RustDiagnostic[] {
+    const levels: RustDiagnosticSeverity[] = ['info', 'warning', 'error'];
+    const diagnostics: RustDiagnostic[] = this.storage.getNode(filePath)?.metadata?.diagnostics || [];
+    return diagnostics.filter(diagnostic => levels.indexOf(diagnostic.severity) >= levels.indexOf(minSeverity));
+  }
+
+  private supertraitClosure(): (trait: string) => Set<string> {
+    // Supertraits of the project's traits and the standard library's, followed transitively
+    const supertraits = new Map<string, string[]>(Object.entries(STD_SUPERTRAITS));
+    for (const trait of this.storage.findNodes(node => node.metadata?.rustKind === 'trait')) {
+      supertraits.set(trait.name, (trait.metadata.supertraits || []).map((name: string) => this.lastSegment(name)));
+    }
+    return (trait: string): Set<string> => {
+      const seen = new Set<string>();
+      const pending = [...(supertraits.get(trait) || [])];
+      while (pending.length > 0) {
+        const next = pending.pop()!;
+        if (seen.has(next)) continue;
+        seen.add(next);
+        pending.push(...(supertraits.get(next) || []));
+      }
+      return seen;
+    };
+  }
+
+  private moduleKey(module: string): string {
+    return module === 'crate' ? module : module.replace(/^crate::/, '');
+  }
+
+  private moduleMembers(name: string): Set<string> {
+    // Items of the module and its submodules
+    return new Set(this.getRustItems()
+      .filter(node => name === 'crate' || node.metadata.module === name || node.metadata.module.startsWith(`${name}::`))
+      .map(node => node.id));
+  }
+
+  private computeModuleMetrics(module: string, members: Set<string>): ModuleMetrics {
+    let internal = 0;
+    let incoming = 0;
+    let outgoing = 0;
+
+    for (const edge of this.storage.findEdges(edge => DEPENDENCY_EDGE_TYPES.has(edge.type) && edge.source !== edge.target)) {
+      const fromInside = members.has(edge.source);
+      const toInside = members.has(edge.target);
+      if (fromInside && toInside) internal++;
+      else if (fromInside) outgoing++;
+      else if (toInside) incoming++;
+    }
+
+    const itemCount = members.size;
+    const pairs = itemCount * (itemCount - 1) / 2;
+    const external = incoming + outgoing;
+    const cohesion = pairs > 0 ? Math.min(1, internal / pairs) : 0;
+    const coupling = internal + external > 0 ? external / (internal + external) : 0;
+
+    return {
+      module,
+      itemCount,
+      internalEdges: internal,
+      externalEdges: external,
+      incomingEdges: incoming,
+      outgoingEdges: outgoing,
+      cohesion,
+      coupling,
+      refactorCandidate: itemCount > 1 && coupling > 0.5 && cohesion < 0.5
+    };
+  }
+
+  private modulePattern(glob: string): RegExp {
+    // `api::*` -> one segment under `api`; `**` -> any depth. A leading `crate::` is optional.
+    const body = glob.replace(/^crate::/, '').split('::')
+      .map(segment => segment === '**' ? '.*' : segment === '*' ? '[^:]+' : segment.replace(/[.*+?^${}()|[\]\\]/g, '\\$&'))
+      .join('::');
+    return new RegExp(`^${body}(?:::.*)?$`);
+  }
+
+  private isTestFunction(node: MindMapNode): boolean {
+    const attributes: Array<{ name: string }> = node.metadata.attributes || [];
+    return attributes.some(attribute => /(?:^|::)test$/.test(attribute.name));
+  }
+
+  private isTestOnly(node: MindMapNode): boolean {
+    if (this.isTestFunction(node) || node.metadata.isIntegrationTest || node.metadata.isDocTest) return true;
+
+    // A cfg predicate that fails without `test` but holds with it, e.g. `test` or `all(test, unix)`
+    return (node.metadata.cfg || []).some((cfg: string) => parseAttributeArgs(cfg).some(predicate =>
+      evaluateCfg(predicate, { features: new Set(), test: false }) === false &&
+      evaluateCfg(predicate, { features: new Set(), test: true }) !== false
+    ));
+  }
+
+  private deriveConditions(typeNode: MindMapNode, traitName: string): string[] {
+    const implIds = new Set(this.storage.findNodes(node =>
+      node.metadata?.patternType === 'trait_impl' && node.metadata.targetId === typeNode.id && this.lastSegment(node.metadata.trait) === traitName
+    ).map(node => node.id));
+    return this.storage.findEdgesByType('requires_bound')
+      .filter(edge => implIds.has(edge.target) && typeof edge.metadata?.bound === 'string')
+      .map(edge => edge.metadata!.bound as string)
+      .sort();
+  }
+
+  private structAutoTraitGaps(
+    node: MindMapNode,
+    byName: Map<string, MindMapNode>,
+    cache: Map<string, Array<{ field: string; gaps: Map<AutoTrait, string> }>>,
+    visiting: Set<string>
+  ): Array<{ field: string; gaps: Map<AutoTrait, string> }> {
+    const cached = cache.get(node.id);
+    if (cached) return cached;
+    // A struct reached again through its own fields adds nothing its other fields do not
+    if (visiting.has(node.id)) return [];
+    visiting.add(node.id);
+
+    const implemented = new Set<string>(node.metadata.implementedTraits || []);
+    const fields: Array<{ field: string; gaps: Map<AutoTrait, string> }> = [];
+    (node.metadata.properties || []).forEach((field: string, index: number) => {
+      const type = node.metadata.propertyTypes?.[index];
+      if (!type) return;
+      const gaps = this.autoTraitGaps(parseRustType(type), byName, cache, visiting);
+      for (const trait of implemented) gaps.delete(trait as AutoTrait);
+      if (gaps.size > 0) fields.push({ field, gaps });
+    });
+
+    visiting.delete(node.id);
+    cache.set(node.id, fields);
+    return fields;
+  }
+
+  private autoTraitGaps(
+    type: RustType,
+    byName: Map<string, MindMapNode>,
+    cache: Map<string, Array<{ field: string; gaps: Map<AutoTrait, string> }>>,
+    visiting: Set<string>
+  ): Map<AutoTrait, string> {
+    // Each auto trait the type lacks, with the innermost type responsible
+    const inner = () => {
+      const gaps = new Map<AutoTrait, string>();
+      for (const arg of type.args || []) {
+        for (const [trait, culprit] of this.autoTraitGaps(arg, byName, cache, visiting)) {
+          if (!gaps.has(trait)) gaps.set(trait, culprit);
+        }
+      }
+      return gaps;
+    };
+    const both = (culprit: string) => new Map<AutoTrait, string>([['Send', culprit], ['Sync', culprit]]);
+
+    switch (type.kind) {
+      case 'pointer':
+        return both(type.text);
+      case 'trait_object': {
+        const bounds = new Set((type.args || []).map(bound => this.lastSegment(bound.name || '')));
+        return new Map((['Send', 'Sync'] as AutoTrait[]).filter(trait => !bounds.has(trait)).map(trait => [trait, type.text]));
+      }
+      case 'reference': {
+        // `&T` is `Send` only when `T` is `Sync`
+        const gaps = inner();
+        return gaps.has('Sync') && !type.mutable ? both(gaps.get('Sync')!) : gaps;
+      }
+      case 'path': {
+        const name = this.lastSegment(type.name || '');
+        if (NOT_SEND_OR_SYNC.has(name) || /\brc::Weak$/.test(type.name || '')) return both(type.text);
+        if (NOT_SYNC.has(name)) return new Map([...inner(), ['Sync', type.text]]);
+        if (NOT_SEND.has(name)) return new Map([...inner(), ['Send', type.text]]);
+        if (name === 'Arc') {
+          const gaps = inner();
+          return gaps.size > 0 ? both(Array.from(gaps.values())[0]) : gaps;
+        }
+        if (name === 'Mutex' || name === 'RwLock') {
+          // A lock makes contents that are only `Send` shareable; `RwLock` hands out `&T` to several readers
+          const gaps = inner();
+          if (gaps.has('Send')) return both(gaps.get('Send')!);
+          return name === 'RwLock' && gaps.has('Sync') ? new Map([['Sync', gaps.get('Sync')!]]) : new Map();
+        }
+        const gaps = inner();
+        const local = byName.get(name);
+        for (const field of local ? this.structAutoTraitGaps(local, byName, cache, visiting) : []) {
+          for (const [trait, culprit] of field.gaps) {
+            if (!gaps.has(trait)) gaps.set(trait, culprit);
+          }
+        }
+        return gaps;
+      }
+      case 'slice':
+      case 'array':
+      case 'tuple':
+        return inner();
+      default:
+        return new Map();
+    }
+  }
+
+  private typeNamesIn(type: RustType): string[] {
+    // Every path named in the type, e.g. `Arc` and `Shared` in `Arc<Shared>`
+    return [...(type.kind === 'path' && type.name ? [type.name] : []), ...(type.args || []).flatMap(arg => this.typeNamesIn(arg))];
+  }
+
+    private traitObjectsIn(type: RustType): string[] {
+    // Principal trait of every `dyn` in the type, including those nested in generic arguments and bindings
+    const traits: string[] = [];
+    const visit = (current: RustType) => {
+      if (current.kind === 'trait_object') {
+        const principal = (current.args || []).find(bound => bound.kind === 'path' && !MARKER_TRAITS.has(this.lastSegment(bound.name || '')));
+        if (principal?.name) traits.push(principal.name);
+      }
+      (current.args || []).forEach(visit);
+      (current.bindings || []).forEach(binding => visit(binding.type));
+      if (current.output) visit(current.output);
+    };
+    visit(type);
+    return traits;
+  }
+
+  private traitImplementors(traitName: string): { traitId?: string; implementors: string[] } {
+    // Local traits through their implements edges; others through the traits types record implementing
+    const trait = this.storage.findNodes(node =>
+      node.type === 'class' && node.metadata?.rustKind === 'trait' && node.metadata.language === 'rust' && node.name === traitName
+    ).sort((a, b) => a.id.localeCompare(b.id))[0];
+    const implementors = new Set<string>();
+    if (trait) {
+      for (const edge of this.storage.findEdges(edge => edge.type === 'implements' && edge.target === trait.id)) {
+        const implementor = this.storage.getNode(edge.source);
+        if (implementor && !implementor.metadata?.inactive) implementors.add(implementor.name);
+      }
+    } else {
+      for (const type of this.getRustItems().filter(node => node.type === 'class' && !node.metadata.inactive)) {
+        if (this.getImplementedTraits(type.id).includes(traitName)) implementors.add(type.name);
+      }
+    }
+    return { traitId: trait?.id, implementors: Array.from(implementors).sort() };
+  }
+
+  private getRustItems(): MindMapNode[] {
+    // Nodes recorded with the module they are declared in; re-exports and glob imports name items declared elsewhere
+    return this.storage.findNodes(node =>
+      node.metadata?.language === 'rust' && typeof node.metadata.module === 'string' &&
+      node.metadata.patternType !== 'reexport' && node.metadata.patternType !== 'glob_import'
+    );
+  }
+
+  private getRustTypes(): MindMapNode[] {
+    return this.storage.findNodes(node =>
+      node.type === 'class' && node.metadata?.language === 'rust' && node.metadata.rustKind === 'struct'
+    );
+  }
+
+  private getImplementedTraits(typeId: string): string[] {
+    // Manual impls recorded on the type, including traits defined outside the project
+    const node = this.storage.getNode(typeId);
+    return (node?.metadata.implementedTraits || []).map((trait: string) => this.lastSegment(trait));
+  }
+
+  private getSatisfiedTraits(typeId: string): Set<string> {
+    // Derives, manual impls (local or external) and synthetic derive impl nodes
+    const node = this.storage.getNode(typeId);
+    const traits = new Set<string>([
+      ...(node?.metadata.derives || []).map((derive: string) => this.lastSegment(derive)),
+      ...this.getImplementedTraits(typeId)
+    ]);
+    for (const edge of this.storage.findEdges(edge => edge.type === 'implements' && edge.source === typeId)) {
+      const trait = this.storage.getNode(edge.target);
+      if (trait) traits.add(trait.name);
+    }
+    for (const impl of this.storage.findNodes(impl => impl.metadata?.patternType === 'trait_impl' && impl.metadata.targetId === typeId)) {
+      traits.add(this.lastSegment(impl.metadata.trait));
+    }
+    return traits;
+  }
+
+  private typeNamesIn(typeText: string): string[] {
+    // Last path segment of every type path; lifetimes and associated types (`Self::Error`) are skipped
+    const names: string[] = [];
+    const text = typeText
+      .replace(/'[A-Za-z_][A-Za-z0-9_]*/g, '')
+      .replace(/[A-Za-z_][A-Za-z0-9_]*\s*=(?!=)/g, ''); // Associated type bindings: `Error = ...`
+    for (const path of text.match(/[A-Za-z_][A-Za-z0-9_]*(?:\s*::\s*[A-Za-z_][A-Za-z0-9_]*)*/g) || []) {
+      const segments = path.split('::').map(segment => segment.trim());
+      if (segments[0] === 'Self' && segments.length > 1) continue;
+      const name = segments[segments.length - 1];
+      if (!TYPE_TEXT_KEYWORDS.has(name)) names.push(name);
+    }
+    return names;
+  }
+
+  private instantiationKey(
+    callee: MindMapNode,
+    site: { types: Array<string | null>; turbofish?: string[] }
+  ): string | undefined {
+    // `T=Meters, U=u8`: turbofish arguments first, else the first argument passed for `T` or `&T`
+    const typeParameters: string[] = callee.metadata.typeParameters;
+    const parameterTypes: string[] = callee.metadata.parameterTypes || [];
+    const bindings = typeParameters.map((param, index) => {
+      const explicit = site.turbofish?.[index];
+      if (explicit && explicit !== '_') return `${param}=${explicit.replace(/\s+/g, '')}`;
+
+      const position = parameterTypes.findIndex(type =>
+        type.replace(/^&\s*(?:'[A-Za-z_][A-Za-z0-9_]*\s+)?(?:mut\s+)?/, '').trim() === param
+      );
+      const inferred = position >= 0 ? site.types[position] : null;
+      return inferred ? `${param}=${inferred}` : undefined;
+    });
+
+    return bindings.every(binding => binding) ? bindings.join(', ') : undefined;
+  }
+
+  private itemByRef(nodeRef: string): MindMapNode | undefined {
+    const node = this.storage.getNode(nodeRef) || this.nodeByPath(nodeRef);
+    if (node && node.metadata?.patternType !== 'reexport') return node;
+
+    // A re-exported path, or a re-export's node: follow the `use` declarations back to a definition
+    let frontier = [node ? node.metadata.resolvedPath as string : this.normalizeItemPath(nodeRef)];
+    const seen = new Set(frontier);
+    const reexports = this.getReexports();
+    for (let depth = 0; depth <= MAX_REEXPORT_DEPTH && frontier.length > 0; depth++) {
+      for (const path of frontier) {
+        const item = this.nodeByPath(path);
+        if (item) return item;
+      }
+      frontier = frontier
+        .flatMap(path => reexports.map(reexport => this.reexportSource(reexport, path)))
+        .filter((path): path is string => path !== undefined && !seen.has(path));
+      frontier.forEach(path => seen.add(path));
+    }
+    return undefined;
+  }
+
+  private getReexports(): MindMapNode[] {
+    return this.storage.findNodes(node => node.type === 'pattern' && node.metadata?.patternType === 'reexport');
+  }
+
+  private reexportedAs(reexport: MindMapNode, path: string): string | undefined {
+    // `pub use a::B as C` in module m makes `crate::a::B` (and anything under it) nameable as `crate::m::C`
+    const source: string = reexport.metadata.resolvedPath;
+    const module = reexport.metadata.module === 'crate' ? 'crate' : `crate::${reexport.metadata.module}`;
+    if (reexport.metadata.isGlob) {
+      const separator = path.lastIndexOf('::');
+      return path.substring(0, separator) === source ? `${module}${path.substring(separator)}` : undefined;
+    }
+    if (path === source || path.startsWith(`${source}::`)) return `${module}::${reexport.name}${path.substring(source.length)}`;
+    return undefined;
+  }
+
+  private reexportSource(reexport: MindMapNode, path: string): string | undefined {
+    // The inverse of `reexportedAs`
+    const source: string = reexport.metadata.resolvedPath;
+    const module = reexport.metadata.module === 'crate' ? 'crate' : `crate::${reexport.metadata.module}`;
+    if (reexport.metadata.isGlob) {
+      const separator = path.lastIndexOf('::');
+      return path.substring(0, separator) === module ? `${source}${path.substring(separator)}` : undefined;
+    }
+    const exported = `${module}::${reexport.name}`;
+    if (path === exported || path.startsWith(`${exported}::`)) return `${source}${path.substring(exported.length)}`;
+    return undefined;
+  }
+
+  private itemPath(node: MindMapNode): string {
+    const module: string = node.metadata.module;
+    return module === 'crate' ? `crate::${node.name}` : `crate::${module}::${node.name}`;
+  }
+
+  private normalizeItemPath(path: string): string {
+    const trimmed = path.trim().replace(/\s*::\s*/g, '::');
+    return trimmed === 'crate' || trimmed.startsWith('crate::') ? trimmed : `crate::${trimmed}`;
+  }
+
+  private editDistance(a: string, b: string): number {
+    // Levenshtein distance with a single rolling row
+    const row = Array.from({ length: b.length + 1 }, (_, j) => j);
+    for (let i = 1; i <= a.length; i++) {
+      let diagonal = row[0];
+      row[0] = i;
+      for (let j = 1; j <= b.length; j++) {
+        const above = row[j];
+        row[j] = Math.min(row[j] + 1, row[j - 1] + 1, diagonal + (a[i - 1] === b[j - 1] ? 0 : 1));
+        diagonal = above;
+      }
+    }
+    return row[b.length];
+  }
+
+  private toCase(name: string, target: NamingCase): string {
+    const words = name
+      .replace(/([a-z0-9])([A-Z])/g, '$1_$2')
+      .replace(/([A-Z]+)([A-Z][a-z])/g, '$1_$2')
+      .split('_')
+      .filter(word => word)
+      .map(word => word.toLowerCase());
+
+    switch (target) {
+      case 'PascalCase':
+        return words.map(word => word[0].toUpperCase() + word.slice(1)).join('');
+      case 'snake_case':
+        return words.join('_');
+      case 'SCREAMING_SNAKE_CASE':
+        return words.join('_').toUpperCase();
+    }
+  }
+
+  private lastSegment(path: string): string {
+    const segments = path.trim().split('::');
+    return segments[segments.length - 1];
+  }
+}
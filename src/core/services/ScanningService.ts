@@ -4,7 +4,14 @@ import { CodeAnalyzer } from '../CodeAnalyzer.js';
 import { CallPatternAnalyzer } from '../CallPatternAnalyzer.js';
 import { ParallelFileProcessor } from '../ParallelFileProcessor.js';
 import { ScalabilityManager } from '../ScalabilityManager.js';
-import { ProjectScale, ProcessingProgress } from '../../types/index.js';
+import { RustGraphBuilder } from '../RustGraphBuilder.js';
+import { resolveCrossFileCalls } from '../RustCallResolver.js';
+import { CargoWorkspace, discoverCargoWorkspace, tagCrates } from '../CargoWorkspace.js';
+import { ExternalCrateRegistry, ExternalCrateSummary, ExternalPathEquivalence, ExternalPolicy, resolveExternalReferences, removeExternalNodes, mergeEquivalentExternalNodes } from '../ExternalCrates.js';
+import { RelationshipExtractor, RelationshipExtractionContext } from '../RelationshipExtractor.js';
+import { ProjectScale, ProcessingProgress, MindMapEdge } from '../../types/index.js';
+import { join, relative, resolve, basename } from 'path';
+import { access, readFile } from 'fs/promises';
 
 export interface ScanResult {
   summary: string;
@@ -15,7 +22,35 @@ export interface ScanResult {
   projectScale: ProjectScale;
 }
 
+// Reported while a scan indexes files; counts never go down and the last report has every file processed
+export interface ScanProgress {
+  filesProcessed: number;
+  totalFiles: number;
+  currentFile?: string; // The file just indexed; unset in the final report
+  nodeCount: number; // Nodes in the graph so far
+  edgeCount: number;
+}
+
+export interface ScanOptions {
+  progress?: (progress: ScanProgress) => void;
+}
+
+export interface FileRescanResult {
+  filePath: string;
+  added: string[];
+  removed: string[];
+  changed: string[];
+  topologyChanged: string[]; // Endpoints of edges that were added or removed
+}
+
+type ProgressReport = (filesProcessed: number, totalFiles: number, currentFile?: string) => void;
+
 export class ScanningService {
+  private rustGraphBuilder = new RustGraphBuilder();
+  private relationshipExtractors: RelationshipExtractor[] = [];
+  private externalCrates = new ExternalCrateRegistry();
+  private cargoWorkspace?: CargoWorkspace;
+
   constructor(
     private storage: MindMapStorage,
     private scanner: FileScanner,
@@ -26,7 +61,7 @@ export class ScanningService {
     private projectRoot: string
   ) {}
 
-  async scanProject(forceRescan = false, useParallelProcessing = true): Promise<ScanResult> {
+  async scanProject(forceRescan = false, useParallelProcessing = true, options: ScanOptions = {}): Promise<ScanResult> {
     console.log('🔍 Starting scalable project scan...');
 
     // First, analyze project scale for adaptive configuration
@@ -54,18 +89,46 @@ export class ScanningService {
     }
 
     const startTime = Date.now();
+    const report = this.progressReporter(options.progress);
 
     if (useParallelProcessing && projectScale.fileCount > 50) {
-      return await this.scanWithParallelProcessing(projectScale, startTime);
+      return await this.scanWithParallelProcessing(projectScale, startTime, report);
     } else {
-      return await this.scanLegacy(forceRescan, useParallelProcessing, projectScale, startTime);
+      return await this.scanLegacy(forceRescan, useParallelProcessing, projectScale, startTime, report);
     }
   }
 
-  private async scanWithParallelProcessing(projectScale: ProjectScale, startTime: number): Promise<ScanResult> {
+  /**
+   * Funnel progress through one function, called only from the sequential indexing loops, so parallel
+   * file reads never invoke the callback. Reports come about every 1% of the files and once more after
+   * cross-file resolution, with the final counts. A restarted scan (the parallel path falling back)
+   * reports nothing until it passes the earlier count.
+   */
+  private progressReporter(callback?: (progress: ScanProgress) => void): ProgressReport {
+    if (!callback) return () => {};
+    let reported = -1;
+    return (filesProcessed, totalFiles, currentFile) => {
+      const done = filesProcessed === totalFiles;
+      const step = Math.max(1, Math.floor(totalFiles / 100));
+      if (filesProcessed <= reported || (done ? currentFile !== undefined : filesProcessed % step !== 0)) return;
+      reported = filesProcessed;
+      const graph = this.storage.getGraph();
+      callback({
+        filesProcessed,
+        totalFiles,
+        currentFile,
+        nodeCount: graph.nodes.size,
+        edgeCount: graph.edges.size
+      });
+    };
+  }
+
+  private async scanWithParallelProcessing(projectScale: ProjectScale, startTime: number, report: ProgressReport): Promise<ScanResult> {
     try {
       console.log('🚀 Using parallel processing for enhanced performance');
 
+      this.cargoWorkspace = await discoverCargoWorkspace(this.projectRoot);
+
       // Get file list and process files
       const files = await this.scanner.scanProject();
       const result = await this.parallelProcessor.processFiles(files.map(f => f.path));
@@ -78,123 +141,17 @@ export class ScanningService {
       console.log(`📊 Processed ${scannedFiles} files out of ${totalFiles} total files`);
 
       // Process the results and add to storage
-      for (const fileInfo of result) {
+      for (const [index, fileInfo] of result.entries()) {
         try {
-          // Determine if we should use enhanced analysis for TypeScript/JavaScript files
-          const isJsTsFile = fileInfo.path.match(/\.(ts|tsx|js|jsx)$/i);
-
-          let analysis: any = null;
-          let callPatternResult: any = null;
-
-          if (isJsTsFile) {
-            // Use CallPatternAnalyzer for enhanced variable and call analysis
-            try {
-              console.log(`🔍 Running CallPatternAnalyzer on ${fileInfo.path}...`);
-              const startTime = Date.now();
-
-              callPatternResult = await this.callPatternAnalyzer.analyzeFile(fileInfo.path);
-
-              const duration = Date.now() - startTime;
-              console.log(`✅ CallPatternAnalyzer completed for ${fileInfo.path} in ${duration}ms`);
-              console.log(`   Variables: ${callPatternResult.variableAnalysis?.declarations.length || 0}`);
-              console.log(`   Nodes: ${callPatternResult.nodes.length}, Edges: ${callPatternResult.edges.length}`);
-
-              analysis = this.extractBasicStructureFromCallPattern(callPatternResult);
-            } catch (error) {
-              console.warn(`❌ Failed CallPatternAnalyzer for ${fileInfo.path}, falling back to CodeAnalyzer:`, error);
-              analysis = await this.codeAnalyzer.analyzeFile(fileInfo.path);
-            }
-          } else {
-            // Use CodeAnalyzer for other file types
-            analysis = await this.codeAnalyzer.analyzeFile(fileInfo.path);
-          }
-
-          // Add the file node
-          const fileNode = {
-            id: fileInfo.path,
-            name: fileInfo.name,
-            type: (fileInfo.type === 'directory' ? 'directory' : 'file') as 'directory' | 'file',
-            path: fileInfo.path,
-            metadata: analysis || {},
-            confidence: 0.8,
-            lastUpdated: new Date()
-          };
-          this.storage.addNode(fileNode);
-
-          // If we have code analysis, add separate nodes for classes and functions
-          if (analysis && fileInfo.type === 'file') {
-            // Add class nodes
-            if (analysis.classes) {
-              for (const cls of analysis.classes) {
-                const classId = `${fileInfo.path}#class:${cls.name}`;
-                this.storage.addNode({
-                  id: classId,
-                  name: cls.name,
-                  type: 'class',
-                  path: fileInfo.path,
-                  metadata: {
-                    startLine: cls.startLine,
-                    endLine: cls.endLine,
-                    methods: cls.methods || [],
-                    properties: cls.properties || []
-                  },
-                  confidence: 0.9,
-                  lastUpdated: new Date()
-                });
-
-                // Add relationship: file contains class
-                this.storage.addEdge({
-                  id: `${fileInfo.path}->contains->${classId}`,
-                  source: fileInfo.path,
-                  target: classId,
-                  type: 'contains',
-                  confidence: 0.9,
-                  lastUpdated: new Date()
-                });
-              }
-            }
-
-            // Add function nodes
-            if (analysis.functions) {
-              for (const func of analysis.functions) {
-                const functionId = `${fileInfo.path}#function:${func.name}`;
-                this.storage.addNode({
-                  id: functionId,
-                  name: func.name,
-                  type: 'function',
-                  path: fileInfo.path,
-                  metadata: {
-                    startLine: func.startLine,
-                    endLine: func.endLine,
-                    parameters: func.parameters || [],
-                    returnType: func.returnType
-                  },
-                  confidence: 0.9,
-                  lastUpdated: new Date()
-                });
-
-                // Add relationship: file contains function
-                this.storage.addEdge({
-                  id: `${fileInfo.path}->contains->${functionId}`,
-                  source: fileInfo.path,
-                  target: functionId,
-                  type: 'contains',
-                  confidence: 0.9,
-                  lastUpdated: new Date()
-                });
-              }
-            }
-          }
-
-          // If we used CallPatternAnalyzer, add the enhanced nodes and edges
-          if (callPatternResult) {
-            this.addCallPatternNodesToStorage(callPatternResult, fileInfo.path);
-          }
+          await this.indexFile(fileInfo);
         } catch (error) {
           console.warn(`⚠️ Failed to analyze ${fileInfo.path}:`, error);
         }
+        report(index + 1, scannedFiles, fileInfo.path);
       }
 
+      this.finalizeScan();
+      report(scannedFiles, scannedFiles);
       await this.storage.save();
 
       return {
@@ -208,132 +165,25 @@ export class ScanningService {
 
     } catch (error) {
       console.warn('⚠️ Parallel processing failed, falling back to legacy scan:', error);
-      return await this.scanLegacy(true, false, projectScale, startTime);
+      return await this.scanLegacy(true, false, projectScale, startTime, report);
     }
   }
 
-  private async scanLegacy(forceRescan: boolean, useParallelProcessing: boolean, projectScale: ProjectScale, startTime: number): Promise<ScanResult> {
+  private async scanLegacy(forceRescan: boolean, useParallelProcessing: boolean, projectScale: ProjectScale, startTime: number, report: ProgressReport = () => {}): Promise<ScanResult> {
     if (forceRescan) {
       this.storage.clear();
     }
 
+    this.cargoWorkspace = await discoverCargoWorkspace(this.projectRoot);
     const files = await this.scanner.scanProject();
     console.log(`📁 Found ${files.length} files to analyze`);
 
     let processedFiles = 0;
     const totalFiles = files.length;
 
-    for (const file of files) {
+    for (const [index, file] of files.entries()) {
       try {
-        // Determine if we should use enhanced analysis for TypeScript/JavaScript files
-        const isJsTsFile = file.path.match(/\.(ts|tsx|js|jsx)$/i);
-
-        let analysis: any = null;
-        let callPatternResult: any = null;
-
-        if (isJsTsFile) {
-          // Use CallPatternAnalyzer for enhanced variable and call analysis
-          try {
-            console.log(`🔍 [Legacy] Running CallPatternAnalyzer on ${file.path}...`);
-            const startTime = Date.now();
-
-            callPatternResult = await this.callPatternAnalyzer.analyzeFile(file.path);
-
-            const duration = Date.now() - startTime;
-            console.log(`✅ [Legacy] CallPatternAnalyzer completed for ${file.path} in ${duration}ms`);
-            console.log(`   Variables: ${callPatternResult.variableAnalysis?.declarations.length || 0}`);
-            console.log(`   Nodes: ${callPatternResult.nodes.length}, Edges: ${callPatternResult.edges.length}`);
-
-            analysis = this.extractBasicStructureFromCallPattern(callPatternResult);
-          } catch (error) {
-            console.warn(`❌ [Legacy] Failed CallPatternAnalyzer for ${file.path}, falling back to CodeAnalyzer:`, error);
-            analysis = await this.codeAnalyzer.analyzeFile(file.path);
-          }
-        } else {
-          // Use CodeAnalyzer for other file types
-          analysis = await this.codeAnalyzer.analyzeFile(file.path);
-        }
-
-        // Add the file node
-        this.storage.addNode({
-          id: file.path,
-          name: file.name,
-          type: (file.type === 'directory' ? 'directory' : 'file') as 'directory' | 'file',
-          path: file.path,
-          metadata: analysis || {},
-          confidence: 0.8,
-          lastUpdated: new Date()
-        });
-
-        // If we have code analysis, add separate nodes for classes and functions
-        if (analysis && file.type === 'file') {
-          // Add class nodes
-          if (analysis.classes) {
-            for (const cls of analysis.classes) {
-              const classId = `${file.path}#class:${cls.name}`;
-              this.storage.addNode({
-                id: classId,
-                name: cls.name,
-                type: 'class',
-                path: file.path,
-                metadata: {
-                  startLine: cls.startLine,
-                  endLine: cls.endLine,
-                  methods: cls.methods || [],
-                  properties: cls.properties || []
-                },
-                confidence: 0.9,
-                lastUpdated: new Date()
-              });
-
-              // Add relationship: file contains class
-              this.storage.addEdge({
-                id: `${file.path}->contains->${classId}`,
-                source: file.path,
-                target: classId,
-                type: 'contains',
-                confidence: 0.9,
-                lastUpdated: new Date()
-              });
-            }
-          }
-
-          // Add function nodes
-          if (analysis.functions) {
-            for (const func of analysis.functions) {
-              const functionId = `${file.path}#function:${func.name}`;
-              this.storage.addNode({
-                id: functionId,
-                name: func.name,
-                type: 'function',
-                path: file.path,
-                metadata: {
-                  startLine: func.startLine,
-                  endLine: func.endLine,
-                  parameters: func.parameters || [],
-                  returnType: func.returnType
-                },
-                confidence: 0.9,
-                lastUpdated: new Date()
-              });
-
-              // Add relationship: file contains function
-              this.storage.addEdge({
-                id: `${file.path}->contains->${functionId}`,
-                source: file.path,
-                target: functionId,
-                type: 'contains',
-                confidence: 0.9,
-                lastUpdated: new Date()
-              });
-            }
-          }
-        }
-
-        // If we used CallPatternAnalyzer, add the enhanced nodes and edges
-        if (callPatternResult) {
-          this.addCallPatternNodesToStorage(callPatternResult, file.path);
-        }
+        await this.indexFile(file, '[Legacy] ');
 
         processedFiles++;
 
@@ -343,8 +193,11 @@ export class ScanningService {
       } catch (error) {
         console.warn(`⚠️ Failed to analyze ${file.path}:`, error);
       }
+      report(index + 1, totalFiles, file.path);
     }
 
+    this.finalizeScan();
+    report(totalFiles, totalFiles);
     const graph = this.storage.getGraph();
     graph.lastScan = new Date();
     await this.storage.save();
@@ -783,4 +636,368 @@ export class ScanningService {
       console.warn(`⚠️ Failed to add CallPatternAnalyzer results for ${filePath}:`, error);
     }
   }
-}
\ No newline at end of file
+
+  /**
+   * Register an extractor that runs on every node of each file indexed from now on.
+   * Registering an extractor with an existing name replaces it.
+   */
+  registerRelationshipExtractor(extractor: RelationshipExtractor): void {
+    this.relationshipExtractors = this.relationshipExtractors.filter(existing => existing.name !== extractor.name);
+    this.relationshipExtractors.push(extractor);
+  }
+
+  getRelationshipExtractors(): RelationshipExtractor[] {
+    return [...this.relationshipExtractors];
+  }
+
+  unregisterRelationshipExtractor(name: string): boolean {
+    const count = this.relationshipExtractors.length;
+    this.relationshipExtractors = this.relationshipExtractors.filter(existing => existing.name !== name);
+    return this.relationshipExtractors.length !== count;
+  }
+
+  /**
+   * Register the public items of an external crate and link the current graph to the ones it uses;
+   * later scans link to them too. Registering a crate again replaces its summary.
+   */
+  registerExternalCrate(crateName: string, summary: ExternalCrateSummary): number {
+    removeExternalNodes(this.storage, crateName);
+    this.externalCrates.register(crateName, summary);
+    return resolveExternalReferences(this.storage, this.externalCrates);
+  }
+
+  unregisterExternalCrate(crateName: string): boolean {
+    removeExternalNodes(this.storage, crateName);
+    return this.externalCrates.unregister(crateName);
+  }
+
+  getExternalCrates(): Array<[string, ExternalCrateSummary]> {
+    return this.externalCrates.entries();
+  }
+
+  /**
+   * Resolve external paths under equivalent prefixes to one node, merging the nodes already added for
+   * them. Returns the number of nodes merged away; an empty table keeps later spellings apart again.
+   */
+  setExternalEquivalences(equivalences: readonly ExternalPathEquivalence[]): number {
+    this.externalCrates.setEquivalences(equivalences);
+    const merged = mergeEquivalentExternalNodes(this.storage, this.externalCrates);
+    resolveExternalReferences(this.storage, this.externalCrates);
+    return merged;
+  }
+
+  getExternalEquivalences(): ExternalPathEquivalence[] {
+    return this.externalCrates.getEquivalences();
+  }
+
+  /**
+   * Choose how external items are represented, replacing the external nodes already in the graph
+   */
+  setExternalPolicy(policy: ExternalPolicy): void {
+    this.externalCrates.setPolicy(policy);
+    for (const node of this.storage.findNodes(node => node.metadata?.external === true)) {
+      this.storage.removeNode(node.id);
+    }
+    resolveExternalReferences(this.storage, this.externalCrates);
+  }
+
+  getExternalPolicy(): ExternalPolicy {
+    return this.externalCrates.getPolicy();
+  }
+
+  /**
+   * Re-index a single file after an edit, replacing the nodes previously derived from it.
+   * Edges from other files into the replaced nodes are kept when their endpoints still exist.
+   */
+  async rescanFile(filePath: string, content?: string): Promise<FileRescanResult> {
+    const relativePath = relative(this.projectRoot, resolve(this.projectRoot, filePath));
+
+    // Snapshot what this file contributed before the edit
+    const previous = new Map<string, string>();
+    const previousEdits = new Map<string, { source: string; editedAt: unknown }>();
+    for (const node of this.storage.findNodes(n => n.id === relativePath || (n.path === relativePath && n.type !== 'directory'))) {
+      const { lastEditedAt, ...metadata } = node.metadata || {};
+      previous.set(node.id, JSON.stringify(metadata));
+      previousEdits.set(node.id, { source: this.sourceKey(metadata), editedAt: lastEditedAt });
+    }
+    const previousEdges = new Map(this.storage.findEdges(edge =>
+      previous.has(edge.source) || previous.has(edge.target)
+    ).map(edge => [edge.id, edge]));
+    const externalEdges = Array.from(previousEdges.values()).filter(edge =>
+      previous.has(edge.source) !== previous.has(edge.target)
+    );
+
+    for (const nodeId of previous.keys()) {
+      this.storage.removeNode(nodeId);
+    }
+
+    const exists = content !== undefined || await access(join(this.projectRoot, relativePath)).then(() => true, () => false);
+    if (exists) {
+      await this.indexFile({ path: relativePath, name: basename(relativePath), type: 'file' }, '[Rescan] ', content);
+    }
+
+    for (const edge of externalEdges) {
+      if (this.storage.getNode(edge.source) && this.storage.getNode(edge.target)) {
+        this.storage.addEdge(edge);
+      }
+    }
+    this.finalizeScan();
+
+    const result: FileRescanResult = { filePath: relativePath, added: [], removed: [], changed: [], topologyChanged: [] };
+    const current = this.storage.findNodes(n => n.id === relativePath || (n.path === relativePath && n.type !== 'directory'));
+    const editedAt = new Date();
+    for (const node of current) {
+      if (!previous.has(node.id)) {
+        result.added.push(node.id);
+      } else if (previous.get(node.id) !== JSON.stringify(node.metadata)) {
+        result.changed.push(node.id);
+      }
+
+      // Nodes only moved by lines inserted above them keep their last edit time
+      const before = previousEdits.get(node.id);
+      const edited = !before || before.source !== this.sourceKey(node.metadata || {});
+      const lastEditedAt = edited ? editedAt : before.editedAt;
+      if (lastEditedAt !== undefined) node.metadata = { ...node.metadata, lastEditedAt };
+    }
+    const currentIds = new Set(current.map(node => node.id));
+    result.removed = Array.from(previous.keys()).filter(id => !currentIds.has(id));
+
+    const currentEdges = new Map(this.storage.findEdges(edge =>
+      currentIds.has(edge.source) || currentIds.has(edge.target)
+    ).map(edge => [edge.id, edge]));
+    const topologyChanged = new Set<string>();
+    for (const [edgeId, edge] of previousEdges) {
+      if (!currentEdges.has(edgeId)) {
+        topologyChanged.add(edge.source);
+        topologyChanged.add(edge.target);
+      }
+    }
+    for (const [edgeId, edge] of currentEdges) {
+      if (!previousEdges.has(edgeId)) {
+        topologyChanged.add(edge.source);
+        topologyChanged.add(edge.target);
+      }
+    }
+    result.topologyChanged = Array.from(topologyChanged);
+
+    return result;
+  }
+
+  private finalizeScan(): void {
+    // Put the graph in ID order before and after cross-file and external resolution so neither the resolved
+    // edges nor anything numbered on save depend on the order files finished parsing
+    if (this.cargoWorkspace) tagCrates(this.storage, this.cargoWorkspace);
+    this.storage.canonicalizeOrder();
+    resolveCrossFileCalls(this.storage);
+    resolveExternalReferences(this.storage, this.externalCrates);
+    this.storage.canonicalizeOrder();
+  }
+
+  /**
+   * The crates found by the last scan, when the project root has a Cargo.toml
+   */
+  getCargoWorkspace(): CargoWorkspace | undefined {
+    return this.cargoWorkspace;
+  }
+
+  private sourceKey(metadata: Record<string, any>): string {
+    // Metadata that changes only when the node's own source does
+    const { startLine, endLine, lineNumber, lastEditedAt, ...rest } = metadata;
+    return JSON.stringify(rest);
+  }
+
+  /**
+   * Analyze a single file and add its file, class, function and language-specific nodes to storage.
+   * When content is given it is analyzed instead of the file on disk.
+   */
+  private async indexFile(file: { path: string; name: string; type: string }, logPrefix: string = '', content?: string): Promise<void> {
+    // Determine if we should use enhanced analysis for TypeScript/JavaScript files
+    const isJsTsFile = file.path.match(/\.(ts|tsx|js|jsx)$/i);
+
+    let analysis: any = null;
+    let callPatternResult: any = null;
+
+    if (isJsTsFile) {
+      // Use CallPatternAnalyzer for enhanced variable and call analysis
+      try {
+        console.log(`🔍 ${logPrefix}Running CallPatternAnalyzer on ${file.path}...`);
+        const startTime = Date.now();
+
+        callPatternResult = await this.callPatternAnalyzer.analyzeFile(file.path, content);
+
+        const duration = Date.now() - startTime;
+        console.log(`✅ ${logPrefix}CallPatternAnalyzer completed for ${file.path} in ${duration}ms`);
+        console.log(`   Variables: ${callPatternResult.variableAnalysis?.declarations.length || 0}`);
+        console.log(`   Nodes: ${callPatternResult.nodes.length}, Edges: ${callPatternResult.edges.length}`);
+
+        analysis = this.extractBasicStructureFromCallPattern(callPatternResult);
+      } catch (error) {
+        console.warn(`❌ ${logPrefix}Failed CallPatternAnalyzer for ${file.path}, falling back to CodeAnalyzer:`, error);
+        analysis = await this.codeAnalyzer.analyzeFile(join(this.projectRoot, file.path), content);
+      }
+    } else {
+      // Use CodeAnalyzer for other file types
+      analysis = await this.codeAnalyzer.analyzeFile(join(this.projectRoot, file.path), content);
+    }
+
+    // Add the file node
+    this.storage.addNode({
+      id: file.path,
+      name: file.name,
+      type: (file.type === 'directory' ? 'directory' : 'file') as 'directory' | 'file',
+      path: file.path,
+      metadata: analysis || {},
+      confidence: 0.8,
+      lastUpdated: new Date()
+    });
+
+    // If we have code analysis, add separate nodes for classes and functions
+    if (analysis && file.type === 'file') {
+      // Add class nodes
+      if (analysis.classes) {
+        for (const cls of analysis.classes) {
+          const classId = `${file.path}#class:${cls.name}`;
+          this.storage.addNode({
+            id: classId,
+            name: cls.name,
+            type: 'class',
+            path: file.path,
+            metadata: {
+              startLine: cls.startLine,
+              endLine: cls.endLine,
+              methods: cls.methods || [],
+              properties: cls.properties || []
+            },
+            confidence: 0.9,
+            lastUpdated: new Date()
+          });
+
+          // Add relationship: file contains class
+          this.storage.addEdge({
+            id: `${file.path}->contains->${classId}`,
+            source: file.path,
+            target: classId,
+            type: 'contains',
+            confidence: 0.9,
+            lastUpdated: new Date()
+          });
+        }
+      }
+
+      // Add function nodes
+      if (analysis.functions) {
+        for (const func of analysis.functions) {
+          const functionId = `${file.path}#function:${func.name}`;
+          this.storage.addNode({
+            id: functionId,
+            name: func.name,
+            type: 'function',
+            path: file.path,
+            metadata: {
+              startLine: func.startLine,
+              endLine: func.endLine,
+              parameters: func.parameters || [],
+              returnType: func.returnType
+            },
+            confidence: 0.9,
+            lastUpdated: new Date()
+          });
+
+          // Add relationship: file contains function
+          this.storage.addEdge({
+            id: `${file.path}->contains->${functionId}`,
+            source: file.path,
+            target: functionId,
+            type: 'contains',
+            confidence: 0.9,
+            lastUpdated: new Date()
+          });
+        }
+      }
+    }
+
+    // If we used CallPatternAnalyzer, add the enhanced nodes and edges
+    if (callPatternResult) {
+      this.addCallPatternNodesToStorage(callPatternResult, file.path);
+    }
+
+    // Add Rust item nodes (structs, consts, statics) and their relationships
+    if (analysis?.language === 'rust' && file.type === 'file') {
+      this.addRustNodesToStorage(analysis, file.path);
+    }
+
+    if (this.relationshipExtractors.length > 0 && file.type === 'file') {
+      await this.runRelationshipExtractors(file.path, content);
+    }
+  }
+
+  // Helper method to apply registered extractors to every node indexed from a file
+  private async runRelationshipExtractors(filePath: string, content?: string): Promise<void> {
+    let fileContent = content;
+    if (fileContent === undefined) {
+      try {
+        fileContent = await readFile(join(this.projectRoot, filePath), 'utf-8');
+      } catch {
+        return;
+      }
+    }
+
+    const lines = fileContent.split('\n');
+    const fileNodes = this.storage.findNodes(node => node.id === filePath || (node.path === filePath && node.type !== 'directory'));
+    const context: RelationshipExtractionContext = {
+      filePath,
+      fileContent,
+      fileNodes,
+      getNode: id => this.storage.getNode(id),
+      addNode: node => this.storage.addNode(node)
+    };
+
+    const edges: MindMapEdge[] = [];
+    for (const extractor of this.relationshipExtractors) {
+      for (const node of fileNodes) {
+        const { startLine, endLine } = node.metadata || {};
+        const source = node.id === filePath || typeof startLine !== 'number'
+          ? fileContent
+          : lines.slice(startLine - 1, typeof endLine === 'number' ? endLine : startLine).join('\n');
+
+        try {
+          edges.push(...extractor.extract(node, source, context));
+        } catch (error) {
+          console.warn(`⚠️ Relationship extractor ${extractor.name} failed on ${node.id}:`, error);
+        }
+      }
+    }
+
+    // Endpoints may be nodes the extractor added, so check them only after every extractor ran
+    for (const edge of edges) {
+      if (this.storage.getNode(edge.source) && this.storage.getNode(edge.target)) {
+        this.storage.addEdge(edge);
+      } else {
+        console.warn(`⚠️ Dropping edge ${edge.id} from a relationship extractor: unknown endpoint`);
+      }
+    }
+  }
+
+  // Helper method to add RustAnalyzer item nodes and edges to storage
+  private addRustNodesToStorage(analysis: any, filePath: string): void {
+    try {
+      const { nodes, edges, diagnostics } = this.rustGraphBuilder.createMindMapElements(analysis, filePath);
+
+      // The file node carries the parse diagnostics, completed with those found while resolving calls
+      const fileNode = this.storage.getNode(filePath);
+      if (fileNode) {
+        this.storage.addNode({ ...fileNode, metadata: { ...fileNode.metadata, diagnostics } });
+      }
+
+      for (const node of nodes) {
+        this.storage.addNode(node);
+      }
+
+      for (const edge of edges) {
+        this.storage.addEdge(edge);
+      }
+    } catch (error) {
+      console.warn(`⚠️ Failed to add Rust item nodes for ${filePath}:`, error);
+    }
+  }
+}
@@ -0,0 +1,131 @@
+import { join } from 'path';
+import { pathToFileURL } from 'url';
+import { MindMapStorage } from '../MindMapStorage.js';
+import { MindMapNode } from '../../types/index.js';
+import { SymbolKind, SymbolKindMap, SymbolNodeKind, symbolNodeKind } from '../SymbolKinds.js';
+
+export interface SymbolPosition {
+  line: number; // 0-based, as in LSP
+  character: number;
+}
+
+// Shaped like LSP `SymbolInformation`, so results can be returned to clients as they are
+export interface SymbolInformation {
+  name: string;
+  kind: SymbolKind;
+  location: { uri: string; range: { start: SymbolPosition; end: SymbolPosition } };
+  containerName?: string;
+  nodeId: string;
+  crate?: string; // Crate of a Cargo workspace member the symbol was parsed from
+  qualifiedName: string; // Prefixed with the crate when there is one, e.g. `api::handlers::User`
+}
+
+export interface NodeSpan {
+  nodeId: string;
+  name: string;
+  kind: SymbolNodeKind;
+  startLine: number; // 1-based, inclusive
+  endLine: number;
+}
+
+export type NodePredicate = (node: MindMapNode) => boolean;
+
+// Narrows the nodes a predicate is tested against, as the edge queries narrow by endpoint
+export interface NodeSpanFilter {
+  kind?: SymbolNodeKind | SymbolNodeKind[];
+  name?: string | RegExp; // Exact name or pattern
+}
+
+/**
+ * Document and workspace symbol queries for editor integrations, with symbol kinds taken from a
+ * configurable mapping
+ */
+export class SymbolService {
+  readonly kindMap = new SymbolKindMap();
+
+  constructor(
+    private storage: MindMapStorage,
+    private projectRoot: string
+  ) {}
+
+  /**
+   * Symbols declared in a file, in source order
+   */
+  documentSymbols(filePath: string): SymbolInformation[] {
+    return this.symbolsOf(this.storage.findNodes(node => node.path === filePath && node.type !== 'file'))
+      .sort((a, b) => a.location.range.start.line - b.location.range.start.line || a.name.localeCompare(b.name));
+  }
+
+  /**
+   * Symbols whose names contain the query, ignoring case; exact and prefix matches come first. An
+   * empty query matches every symbol. In a Cargo workspace this covers every member crate.
+   */
+  workspaceSymbols(query: string, limit = 100): SymbolInformation[] {
+    const needle = query.toLowerCase();
+    const rank = (name: string) => name.toLowerCase() === needle ? 0 : name.toLowerCase().startsWith(needle) ? 1 : 2;
+    const matches = this.storage.findNodes(node =>
+      node.type !== 'file' && typeof node.path === 'string' && node.name.toLowerCase().includes(needle)
+    );
+    return this.symbolsOf(matches)
+      .sort((a, b) => rank(a.name) - rank(b.name) || a.name.localeCompare(b.name) || a.nodeId.localeCompare(b.nodeId))
+      .slice(0, limit);
+  }
+
+  /**
+   * Spans of the nodes in a file that match the predicate and filter, ordered by start position so
+   * editors can highlight them in one pass
+   */
+  spansWhere(filePath: string, predicate: NodePredicate = () => true, filter: NodeSpanFilter = {}): NodeSpan[] {
+    const kinds = filter.kind === undefined ? undefined : new Set(Array.isArray(filter.kind) ? filter.kind : [filter.kind]);
+    const spans: NodeSpan[] = [];
+    for (const node of this.storage.findNodes(node => node.path === filePath && node.type !== 'file')) {
+      const kind = symbolNodeKind(node);
+      const startLine = node.metadata?.startLine;
+      if (!kind || typeof startLine !== 'number' || node.metadata?.external) continue;
+      if (kinds && !kinds.has(kind)) continue;
+      if (filter.name !== undefined && !(typeof filter.name === 'string' ? node.name === filter.name : filter.name.test(node.name))) continue;
+      if (!predicate(node)) continue;
+      spans.push({ nodeId: node.id, name: node.name, kind, startLine, endLine: Math.max(startLine, node.metadata?.endLine ?? startLine) });
+    }
+    // Enclosing spans come before the spans nested in them
+    return spans.sort((a, b) => a.startLine - b.startLine || b.endLine - a.endLine || a.nodeId.localeCompare(b.nodeId));
+  }
+
+  private symbolsOf(nodes: MindMapNode[]): SymbolInformation[] {
+    const symbols: SymbolInformation[] = [];
+    for (const node of nodes) {
+      const kind = this.kindMap.kindOf(node);
+      if (kind === undefined || node.metadata?.external) continue;
+
+      // Spans cover whole lines: from the start of the first to the start of the line after the last
+      const startLine = Math.max(1, node.metadata?.startLine ?? 1);
+      const endLine = Math.max(startLine, node.metadata?.endLine ?? startLine);
+      symbols.push({
+        name: node.name,
+        kind,
+        location: {
+          uri: pathToFileURL(join(this.projectRoot, node.path!)).href,
+          range: { start: { line: startLine - 1, character: 0 }, end: { line: endLine, character: 0 } }
+        },
+        containerName: this.containerOf(node),
+        nodeId: node.id,
+        crate: node.metadata?.crate,
+        qualifiedName: this.qualifiedNameOf(node)
+      });
+    }
+    return symbols;
+  }
+
+  private qualifiedNameOf(node: MindMapNode): string {
+    // Top-level items are in module `crate`, nested ones in `config` or `config::loader`
+    const meta = node.metadata || {};
+    const module = meta.module && meta.module !== 'crate' ? meta.module : undefined;
+    const owner = meta.implTarget || meta.traitName || meta.enum;
+    return [meta.crate, module, owner, node.name].filter(Boolean).join('::');
+  }
+
+  private containerOf(node: MindMapNode): string | undefined {
+    const meta = node.metadata || {};
+    return meta.implTarget || meta.traitName || meta.enum || meta.owner || meta.target || meta.module;
+  }
+}
@@ -0,0 +1,266 @@
+import { readFile } from 'fs/promises';
+import { join } from 'path';
+import { MindMapStorage } from '../MindMapStorage.js';
+import { CodeAnalyzer } from '../CodeAnalyzer.js';
+import { CodeStructure, MindMapNode } from '../../types/index.js';
+import { IdentifierNormalizer, languageOfPath } from '../IdentifierNormalizer.js';
+
+export interface TypeParityMismatch {
+  field: string; // Serialized name
+  rustType: string;
+  expectedType: string; // Rust type mapped to its serialized TypeScript shape
+  otherType: string;
+}
+
+export interface TypeParityReport {
+  rustType: string;
+  rustNodeId: string;
+  otherType: string;
+  otherPath: string;
+  missingInOther: string[]; // Serialized Rust fields the other type lacks
+  missingInRust: string[]; // Fields of the other type with no serialized Rust counterpart
+  typeMismatches: TypeParityMismatch[];
+  optionalityMismatches: string[]; // Fields nullable on one side only
+  matches: boolean;
+}
+
+export interface CrossLanguageSymbol {
+  nodeId: string;
+  name: string;
+  path?: string;
+}
+
+export interface CrossLanguageMatch {
+  key: string; // Normalized identifier both names map to
+  kind: 'function' | 'type';
+  source: CrossLanguageSymbol;
+  target: CrossLanguageSymbol;
+}
+
+type InterfaceInfo = NonNullable<CodeStructure['interfaces']>[number];
+
+const NUMBER_TYPES = new Set(['i8', 'i16', 'i32', 'i64', 'i128', 'isize', 'u8', 'u16', 'u32', 'u64', 'u128', 'usize', 'f32', 'f64']);
+const STRING_TYPES = new Set(['String', 'str', 'char', 'Uuid', 'DateTime', 'NaiveDate', 'NaiveDateTime', 'NaiveTime', 'PathBuf', 'Url']);
+const WRAPPER_TYPES = new Set(['Box', 'Arc', 'Rc', 'Cow']);
+const SEQUENCE_TYPES = new Set(['Vec', 'VecDeque', 'HashSet', 'BTreeSet', 'IndexSet']);
+const MAP_TYPES = new Set(['HashMap', 'BTreeMap', 'IndexMap']);
+
+/**
+ * Compares serde-serialized Rust structs against TypeScript interfaces describing the same payload
+ */
+export class TypeParityService {
+  readonly normalizer = new IdentifierNormalizer();
+
+  constructor(
+    private storage: MindMapStorage,
+    private codeAnalyzer: CodeAnalyzer,
+    private projectRoot: string
+  ) {}
+
+  /**
+   * Functions and types of one language that name the same logical symbol as one in another language
+   * once each name is normalized under its language's rules, e.g. Rust `create_user` and TypeScript
+   * `createUser`. A symbol matching several candidates is reported once per candidate.
+   */
+  crossLanguageMatches(sourceLanguage: string, targetLanguage: string): CrossLanguageMatch[] {
+    const symbols = (language: string) => {
+      const byKey = new Map<string, Map<string, CrossLanguageSymbol>>();
+      for (const node of this.storage.findNodes(candidate => this.languageOf(candidate) === language && this.symbolKind(candidate) !== undefined)) {
+        if (node.metadata?.external || node.metadata?.inactive) continue;
+        const key = `${this.symbolKind(node)}:${this.normalizer.normalize(node.name, language)}`;
+        // Scanners may record the same declaration under more than one node ID
+        const entries = byKey.get(key) || new Map<string, CrossLanguageSymbol>();
+        if (!entries.has(`${node.path}:${node.name}`)) entries.set(`${node.path}:${node.name}`, { nodeId: node.id, name: node.name, path: node.path });
+        byKey.set(key, entries);
+      }
+      return byKey;
+    };
+
+    const targets = symbols(targetLanguage);
+    const matches: CrossLanguageMatch[] = [];
+    for (const [key, sources] of symbols(sourceLanguage)) {
+      const [kind, normalized] = key.split(':') as ['function' | 'type', string];
+      for (const source of sources.values()) {
+        for (const target of (targets.get(key) || new Map()).values()) {
+          matches.push({ key: normalized, kind, source, target });
+        }
+      }
+    }
+    return matches.sort((a, b) => a.key.localeCompare(b.key) || a.source.nodeId.localeCompare(b.source.nodeId) || a.target.nodeId.localeCompare(b.target.nodeId));
+  }
+
+  async typeParity(rustStruct: string, otherType: string, otherFile?: string): Promise<TypeParityReport> {
+    const rustNode = this.storage.findNodes(node =>
+      node.type === 'class' && node.metadata?.language === 'rust' && node.metadata.rustKind === 'struct' &&
+      (node.id === rustStruct || node.name === rustStruct)
+    ).sort((a, b) => a.id.localeCompare(b.id))[0];
+    if (!rustNode) {
+      throw new Error(`Rust struct not found: ${rustStruct}`);
+    }
+
+    const other = await this.findInterface(otherType, otherFile);
+    if (!other) {
+      throw new Error(`TypeScript type not found: ${otherType}${otherFile ? ` in ${otherFile}` : ''}`);
+    }
+
+    const fields: string[] = rustNode.metadata.properties || [];
+    const fieldTypes: string[] = rustNode.metadata.propertyTypes || [];
+    const serdeNames: Array<string | null> = rustNode.metadata.serdeNames || fields;
+    const otherProperties = new Map(other.info.properties.map(property => [property.name, property]));
+
+    const report: TypeParityReport = {
+      rustType: rustNode.name,
+      rustNodeId: rustNode.id,
+      otherType: other.info.name,
+      otherPath: other.path,
+      missingInOther: [],
+      missingInRust: [],
+      typeMismatches: [],
+      optionalityMismatches: [],
+      matches: true
+    };
+
+    const serialized = new Set<string>();
+    serdeNames.forEach((name, index) => {
+      if (name === null) return;
+      serialized.add(name);
+
+      const property = otherProperties.get(name);
+      if (!property) {
+        report.missingInOther.push(name);
+        return;
+      }
+
+      const rustType = fieldTypes[index] || '';
+      const expected = this.toTypeScript(rustType);
+      const actual = this.normalizeTypeScript(property.type || 'any');
+
+      if (!this.isCompatible(expected.type, actual.type)) {
+        report.typeMismatches.push({ field: name, rustType, expectedType: expected.type, otherType: property.type || 'any' });
+      }
+      if (expected.nullable !== (actual.nullable || property.optional)) {
+        report.optionalityMismatches.push(name);
+      }
+    });
+
+    report.missingInRust = other.info.properties
+      .map(property => property.name)
+      .filter(name => !serialized.has(name));
+    report.matches = report.missingInOther.length === 0 && report.missingInRust.length === 0 &&
+      report.typeMismatches.length === 0 && report.optionalityMismatches.length === 0;
+
+    return report;
+  }
+
+  private languageOf(node: MindMapNode): string | undefined {
+    return node.metadata?.language || (node.path ? languageOfPath(node.path) : undefined);
+  }
+
+  private symbolKind(node: MindMapNode): CrossLanguageMatch['kind'] | undefined {
+    if (node.type === 'function') return 'function';
+    return node.type === 'class' ? 'type' : undefined;
+  }
+
+  private async findInterface(name: string, otherFile?: string): Promise<{ path: string; info: InterfaceInfo } | undefined> {
+    const candidates = otherFile
+      ? [otherFile]
+      : this.storage.findNodes(node => node.type === 'file' && /\.tsx?$/.test(node.path || ''))
+        .map(node => node.path!)
+        .sort();
+    const declaration = new RegExp(`\\b(?:interface|type)\\s+${name}\\b`);
+
+    for (const path of candidates) {
+      const fullPath = join(this.projectRoot, path);
+      let content: string;
+      try {
+        content = await readFile(fullPath, 'utf-8');
+      } catch {
+        continue;
+      }
+      if (!declaration.test(content)) continue;
+
+      const structure = await this.codeAnalyzer.analyzeFile(fullPath, content);
+      const info = structure?.interfaces?.find(candidate => candidate.name === name);
+      if (info) {
+        return { path, info };
+      }
+    }
+
+    return undefined;
+  }
+
+  /**
+   * Serialized TypeScript shape of a Rust type under serde's default representation
+   */
+  private toTypeScript(rustType: string): { type: string; nullable: boolean } {
+    const type = rustType.trim().replace(/^&(?:'[a-z_]+\s+)?(?:mut\s+)?/, '');
+
+    const slice = type.match(/^\[\s*(.+?)\s*(?:;\s*[^\]]+)?\]$/);
+    if (slice) {
+      return { type: `${this.toTypeScript(slice[1]).type}[]`, nullable: false };
+    }
+
+    const generic = type.match(/^([A-Za-z_][A-Za-z0-9_:]*)\s*<([\s\S]*)>$/);
+    const base = (generic ? generic[1] : type).split('::').pop()!;
+    const args = generic ? this.splitTypeArgs(generic[2]).filter(arg => !arg.startsWith("'")) : [];
+
+    if (base === 'Option' && args[0]) {
+      return { type: this.toTypeScript(args[0]).type, nullable: true };
+    }
+    if (WRAPPER_TYPES.has(base) && args.length > 0) {
+      return this.toTypeScript(args[args.length - 1]);
+    }
+    if (SEQUENCE_TYPES.has(base) && args[0]) {
+      return { type: `${this.toTypeScript(args[0]).type}[]`, nullable: false };
+    }
+    if (MAP_TYPES.has(base) && args[1]) {
+      return { type: `Record<string,${this.toTypeScript(args[1]).type}>`, nullable: false };
+    }
+    if (STRING_TYPES.has(base)) return { type: 'string', nullable: false };
+    if (NUMBER_TYPES.has(base)) return { type: 'number', nullable: false };
+    if (base === 'bool') return { type: 'boolean', nullable: false };
+    if (base === 'Value') return { type: 'any', nullable: false };
+
+    return { type: base, nullable: false };
+  }
+
+  private normalizeTypeScript(tsType: string): { type: string; nullable: boolean } {
+    const members = tsType.split('|').map(member => member.trim()).filter(member => member);
+    const nullable = members.some(member => member === 'null' || member === 'undefined');
+    const type = members
+      .filter(member => member !== 'null' && member !== 'undefined')
+      .join('|')
+      .replace(/\s+/g, '')
+      .replace(/^Array<(.+)>$/, '$1[]')
+      .replace(/^\{\[[A-Za-z_]+:string\]:(.+?);?\}$/, 'Record<string,$1>');
+    return { type, nullable };
+  }
+
+  private isCompatible(expected: string, actual: string): boolean {
+    if (actual === 'any' || actual === 'unknown' || expected === 'any') return true;
+    // Dates serialize as strings but are often declared as Date after parsing
+    if (expected === 'string' && actual === 'Date') return true;
+    return expected === actual;
+  }
+
+  private splitTypeArgs(text: string): string[] {
+    const args: string[] = [];
+    let depth = 0;
+    let current = '';
+
+    for (const char of text) {
+      if (char === '<' || char === '(' || char === '[') depth++;
+      else if (char === '>' || char === ')' || char === ']') depth--;
+
+      if (char === ',' && depth === 0) {
+        args.push(current.trim());
+        current = '';
+      } else {
+        current += char;
+      }
+    }
+    if (current.trim()) args.push(current.trim());
+
+    return args;
+  }
+}
@@ -313,4 +313,33 @@ export class QueryHandlers {
 
     return ResponseFormatter.formatSuccessResponse(text);
   }
-}
\ No newline at end of file
+
+  async handleGetContextPack(args: {
+    seeds: string[];
+    budget_tokens?: number;
+  }) {
+    const { seeds, budget_tokens = 1000 } = args;
+
+    // Validate inputs
+    if (!Array.isArray(seeds) || seeds.length === 0 || seeds.some(seed => typeof seed !== 'string' || !seed)) {
+      throw new Error('seeds must be a non-empty array of strings');
+    }
+    ValidationMiddleware.validateNumericRange(budget_tokens, 50, 100000, 'budget_tokens');
+
+    const pack = this.mindMap.contextPack(seeds, budget_tokens);
+
+    if (pack.seeds.length === 0) {
+      return ResponseFormatter.formatSuccessResponse(`No nodes found matching seeds: ${seeds.join(', ')}`);
+    }
+
+    let text = `${pack.text}
+
+`;
+    text += `~${pack.tokensUsed}/${pack.budgetTokens} tokens, ${pack.items.length} items`;
+    if (pack.omitted > 0) {
+      text += `, ${pack.omitted} less relevant items omitted`;
+    }
+
+    return ResponseFormatter.formatSuccessResponse(text);
+  }
+}
@@ -17,6 +17,10 @@ import { ToolingHandlers } from './handlers/ToolingHandlers.js';
 import { FrameworkHandlers } from './handlers/FrameworkHandlers.js';
 import { DocumentHandlers } from './handlers/DocumentHandlers.js';
 import { ResponseFormatter } from './middleware/ResponseFormatter.js';
+import { ResponseBudget } from './middleware/ResponseBudget.js';
+
+// Tool responses larger than this are summarized; MINDMAP_RESPONSE_BUDGET_BYTES overrides, 0 disables
+const DEFAULT_RESPONSE_BUDGET_BYTES = 100_000;
 
 class MindMapMCPServer {
   private server: Server;
@@ -30,6 +34,7 @@ class MindMapMCPServer {
   private toolingHandlers: ToolingHandlers;
   private frameworkHandlers: FrameworkHandlers;
   private documentHandlers: DocumentHandlers;
+  private responseBudget: ResponseBudget;
 
   constructor() {
     this.projectRoot = process.cwd();
@@ -59,6 +64,12 @@ class MindMapMCPServer {
     this.frameworkHandlers = new FrameworkHandlers(this.mindMap);
     this.documentHandlers = new DocumentHandlers(this.mindMap);
 
+    const budgetBytes = Number(process.env.MINDMAP_RESPONSE_BUDGET_BYTES ?? DEFAULT_RESPONSE_BUDGET_BYTES);
+    this.responseBudget = new ResponseBudget(
+      Number.isFinite(budgetBytes) ? budgetBytes : DEFAULT_RESPONSE_BUDGET_BYTES,
+      () => this.mindMap.createRelevanceScorer()
+    );
+
     this.setupHandlers();
 
     // Initialize the mind map (load existing data)
@@ -106,149 +117,158 @@ class MindMapMCPServer {
       tools: ALL_TOOLS
     }));
 
-    // Handle tool calls - delegate to appropriate handlers
+    // Handle tool calls - delegate to appropriate handlers, keeping responses within the byte budget
     this.server.setRequestHandler(CallToolRequestSchema, async (request) => {
       const { name, arguments: args } = request.params;
+      const response = await this.callTool(name, args);
+      return name === 'fetch_more_results' ? response : this.responseBudget.apply(name, response);
+    });
+  }
 
-      try {
-        switch (name) {
-          // Query handlers
-          case 'query_mindmap':
-            return await this.queryHandlers.handleQueryMindMap(args as any);
-          case 'update_mindmap':
-            return await this.queryHandlers.handleUpdateMindMap(args as any);
-          case 'advanced_query':
-            return await this.queryHandlers.handleAdvancedQuery(args as any);
-          case 'temporal_query':
-            return await this.queryHandlers.handleTemporalQuery(args as any);
-          case 'aggregate_query':
-            return await this.queryHandlers.handleAggregateQuery(args as any);
-          case 'save_query':
-            return await this.queryHandlers.handleSaveQuery(args as any);
-          case 'execute_saved_query':
-            return await this.queryHandlers.handleExecuteSavedQuery(args as any);
-
-          // Analysis handlers
-          case 'predict_errors':
-            return await this.analysisHandlers.handlePredictErrors(args as any);
-          case 'suggest_fixes':
-            return await this.analysisHandlers.handleSuggestFixes(args as any);
-          case 'analyze_architecture':
-            return await this.analysisHandlers.handleAnalyzeArchitecture(args as any);
-          case 'get_insights':
-            return await this.analysisHandlers.handleGetInsights(args as any);
-          case 'detect_cross_language_apis':
-            return await this.analysisHandlers.handleDetectCrossLanguageAPIs(args as any);
-          case 'analyze_test_coverage':
-            return await this.analysisHandlers.handleAnalyzeTestCoverage(args as any);
-          case 'analyze_configuration_relationships':
-            return await this.analysisHandlers.handleAnalyzeConfigurationRelationships(args as any);
-          case 'analyze_error_propagation':
-            return await this.analysisHandlers.handleAnalyzeErrorPropagation(args as any);
-
-          // System handlers
-          case 'get_context':
-            return await this.systemHandlers.handleGetContext(args as any);
-          case 'suggest_exploration':
-            return await this.systemHandlers.handleSuggestExploration(args as any);
-          case 'scan_project':
-            return await this.systemHandlers.handleScanProject(args as any);
-          case 'get_stats':
-            return await this.systemHandlers.handleGetStats();
-          case 'get_performance':
-            return await this.systemHandlers.handleGetPerformance(args as any);
-          case 'get_cache_stats':
-            return await this.systemHandlers.handleGetCacheStats(args as any);
-          case 'clear_cache':
-            return await this.systemHandlers.handleClearCache(args as any);
-          case 'update_ignore_patterns':
-            return await this.systemHandlers.handleUpdateIgnorePatterns(args as any);
-          case 'test_ignore_patterns':
-            return await this.systemHandlers.handleTestIgnorePatterns(args as any);
-          case 'get_ignore_stats':
-            return await this.systemHandlers.handleGetIgnoreStats(args as any);
-
-          // Tooling handlers
-          case 'detect_project_tooling':
-            return await this.toolingHandlers.handleDetectProjectTooling(args as any);
-          case 'run_language_tool':
-            return await this.toolingHandlers.handleRunLanguageTool(args as any);
-          case 'get_tooling_recommendations':
-            return await this.toolingHandlers.handleGetToolingRecommendations(args as any);
-          case 'run_tool_suite':
-            return await this.toolingHandlers.handleRunToolSuite(args as any);
-          case 'detect_cross_language_deps':
-            return await this.toolingHandlers.handleDetectCrossLanguageDeps(args as any);
-          case 'analyze_polyglot_project':
-            return await this.toolingHandlers.handleAnalyzePolyglotProject(args as any);
-          case 'generate_multi_language_refactorings':
-            return await this.toolingHandlers.handleGenerateMultiLanguageRefactorings(args as any);
-
-          // Framework handlers
-          case 'detect_enhanced_frameworks':
-            return await this.frameworkHandlers.handleDetectEnhancedFrameworks(args as any);
-          case 'get_framework_recommendations':
-            return await this.frameworkHandlers.handleGetFrameworkRecommendations(args as any);
-
-          // Additional handlers that need to be implemented in handlers
-          case 'get_inhibitory_stats':
-            return await this.handleGetInhibitoryStats(args as any);
-          case 'get_hebbian_stats':
-            return await this.handleGetHebbianStats(args as any);
-          case 'get_multi_modal_fusion_stats':
-            return await this.handleGetMultiModalFusionStats(args as any);
-          case 'get_hierarchical_context_stats':
-            return await this.handleGetHierarchicalContextStats(args as any);
-          case 'get_context_summary':
-            return await this.handleGetContextSummary(args as any);
-          case 'get_attention_stats':
-            return await this.handleGetAttentionStats(args as any);
-          case 'allocate_attention':
-            return await this.handleAllocateAttention(args as any);
-          case 'update_attention':
-            return await this.handleUpdateAttention(args as any);
-          case 'get_bi_temporal_stats':
-            return await this.handleGetBiTemporalStats(args as any);
-          case 'create_context_window':
-            return await this.handleCreateContextWindow(args as any);
-          case 'query_bi_temporal':
-            return await this.handleQueryBiTemporal(args as any);
-          case 'create_temporal_snapshot':
-            return await this.handleCreateTemporalSnapshot(args as any);
-          case 'invalidate_relationship':
-            return await this.handleInvalidateRelationship(args as any);
-          case 'get_prediction_engine_stats':
-            return await this.handleGetPredictionEngineStats(args as any);
-          case 'get_pattern_predictions':
-            return await this.handleGetPatternPredictions(args as any);
-          case 'get_emerging_patterns':
-            return await this.handleGetEmergingPatterns(args as any);
-          case 'predict_pattern_emergence':
-            return await this.handlePredictPatternEmergence(args as any);
-          case 'analyze_and_predict':
-            return await this.handleAnalyzeAndPredict(args as any);
-          case 'init_claude_code':
-            return await this.handleInitClaudeCode(args as any);
-
-          // Document Intelligence handlers (Phase 7.5)
-          case 'analyze_project_documentation':
-            return await this.documentHandlers.handleAnalyzeProjectDocumentation(args as any);
-          case 'analyze_document':
-            return await this.documentHandlers.handleAnalyzeDocument(args as any);
-          case 'get_documentation_statistics':
-            return await this.documentHandlers.handleGetDocumentationStatistics(args as any);
-          case 'get_documentation_insights':
-            return await this.documentHandlers.handleGetDocumentationInsights(args as any);
-          case 'get_document_relationships':
-            return await this.documentHandlers.handleGetDocumentRelationships(args as any);
-
-          default:
-            throw new Error(`Unknown tool: ${name}`);
-        }
-      } catch (error) {
-        return ResponseFormatter.formatErrorResponse(name, error);
+  private async callTool(name: string, args: unknown): Promise<any> {
+    try {
+      switch (name) {
+        // Query handlers
+        case 'query_mindmap':
+          return await this.queryHandlers.handleQueryMindMap(args as any);
+        case 'update_mindmap':
+          return await this.queryHandlers.handleUpdateMindMap(args as any);
+        case 'advanced_query':
+          return await this.queryHandlers.handleAdvancedQuery(args as any);
+        case 'temporal_query':
+          return await this.queryHandlers.handleTemporalQuery(args as any);
+        case 'aggregate_query':
+          return await this.queryHandlers.handleAggregateQuery(args as any);
+        case 'save_query':
+          return await this.queryHandlers.handleSaveQuery(args as any);
+        case 'execute_saved_query':
+          return await this.queryHandlers.handleExecuteSavedQuery(args as any);
+        case 'get_context_pack':
+          return await this.queryHandlers.handleGetContextPack(args as any);
+
+        // Analysis handlers
+        case 'predict_errors':
+          return await this.analysisHandlers.handlePredictErrors(args as any);
+        case 'suggest_fixes':
+          return await this.analysisHandlers.handleSuggestFixes(args as any);
+        case 'analyze_architecture':
+          return await this.analysisHandlers.handleAnalyzeArchitecture(args as any);
+        case 'get_insights':
+          return await this.analysisHandlers.handleGetInsights(args as any);
+        case 'detect_cross_language_apis':
+          return await this.analysisHandlers.handleDetectCrossLanguageAPIs(args as any);
+        case 'analyze_test_coverage':
+          return await this.analysisHandlers.handleAnalyzeTestCoverage(args as any);
+        case 'analyze_configuration_relationships':
+          return await this.analysisHandlers.handleAnalyzeConfigurationRelationships(args as any);
+        case 'analyze_error_propagation':
+          return await this.analysisHandlers.handleAnalyzeErrorPropagation(args as any);
+
+        // System handlers
+        case 'get_context':
+          return await this.systemHandlers.handleGetContext(args as any);
+        case 'suggest_exploration':
+          return await this.systemHandlers.handleSuggestExploration(args as any);
+        case 'scan_project':
+          return await this.systemHandlers.handleScanProject(args as any);
+        case 'get_stats':
+          return await this.systemHandlers.handleGetStats();
+        case 'get_performance':
+          return await this.systemHandlers.handleGetPerformance(args as any);
+        case 'get_cache_stats':
+          return await this.systemHandlers.handleGetCacheStats(args as any);
+        case 'clear_cache':
+          return await this.systemHandlers.handleClearCache(args as any);
+        case 'update_ignore_patterns':
+          return await this.systemHandlers.handleUpdateIgnorePatterns(args as any);
+        case 'test_ignore_patterns':
+          return await this.systemHandlers.handleTestIgnorePatterns(args as any);
+        case 'get_ignore_stats':
+          return await this.systemHandlers.handleGetIgnoreStats(args as any);
+
+        // Tooling handlers
+        case 'detect_project_tooling':
+          return await this.toolingHandlers.handleDetectProjectTooling(args as any);
+        case 'run_language_tool':
+          return await this.toolingHandlers.handleRunLanguageTool(args as any);
+        case 'get_tooling_recommendations':
+          return await this.toolingHandlers.handleGetToolingRecommendations(args as any);
+        case 'run_tool_suite':
+          return await this.toolingHandlers.handleRunToolSuite(args as any);
+        case 'detect_cross_language_deps':
+          return await this.toolingHandlers.handleDetectCrossLanguageDeps(args as any);
+        case 'analyze_polyglot_project':
+          return await this.toolingHandlers.handleAnalyzePolyglotProject(args as any);
+        case 'generate_multi_language_refactorings':
+          return await this.toolingHandlers.handleGenerateMultiLanguageRefactorings(args as any);
+
+        // Framework handlers
+        case 'detect_enhanced_frameworks':
+          return await this.frameworkHandlers.handleDetectEnhancedFrameworks(args as any);
+        case 'get_framework_recommendations':
+          return await this.frameworkHandlers.handleGetFrameworkRecommendations(args as any);
+
+        // Additional handlers that need to be implemented in handlers
+        case 'get_inhibitory_stats':
+          return await this.handleGetInhibitoryStats(args as any);
+        case 'get_hebbian_stats':
+          return await this.handleGetHebbianStats(args as any);
+        case 'get_multi_modal_fusion_stats':
+          return await this.handleGetMultiModalFusionStats(args as any);
+        case 'get_hierarchical_context_stats':
+          return await this.handleGetHierarchicalContextStats(args as any);
+        case 'get_context_summary':
+          return await this.handleGetContextSummary(args as any);
+        case 'get_attention_stats':
+          return await this.handleGetAttentionStats(args as any);
+        case 'allocate_attention':
+          return await this.handleAllocateAttention(args as any);
+        case 'update_attention':
+          return await this.handleUpdateAttention(args as any);
+        case 'get_bi_temporal_stats':
+          return await this.handleGetBiTemporalStats(args as any);
+        case 'create_context_window':
+          return await this.handleCreateContextWindow(args as any);
+        case 'query_bi_temporal':
+          return await this.handleQueryBiTemporal(args as any);
+        case 'create_temporal_snapshot':
+          return await this.handleCreateTemporalSnapshot(args as any);
+        case 'invalidate_relationship':
+          return await this.handleInvalidateRelationship(args as any);
+        case 'get_prediction_engine_stats':
+          return await this.handleGetPredictionEngineStats(args as any);
+        case 'get_pattern_predictions':
+          return await this.handleGetPatternPredictions(args as any);
+        case 'get_emerging_patterns':
+          return await this.handleGetEmergingPatterns(args as any);
+        case 'predict_pattern_emergence':
+          return await this.handlePredictPatternEmergence(args as any);
+        case 'analyze_and_predict':
+          return await this.handleAnalyzeAndPredict(args as any);
+        case 'init_claude_code':
+          return await this.handleInitClaudeCode(args as any);
+
+        // Document Intelligence handlers (Phase 7.5)
+        case 'analyze_project_documentation':
+          return await this.documentHandlers.handleAnalyzeProjectDocumentation(args as any);
+        case 'analyze_document':
+          return await this.documentHandlers.handleAnalyzeDocument(args as any);
+        case 'get_documentation_statistics':
+          return await this.documentHandlers.handleGetDocumentationStatistics(args as any);
+        case 'get_documentation_insights':
+          return await this.documentHandlers.handleGetDocumentationInsights(args as any);
+        case 'get_document_relationships':
+          return await this.documentHandlers.handleGetDocumentRelationships(args as any);
+
+        case 'fetch_more_results':
+          return this.responseBudget.fetchMore((args as any).continuation_token);
+
+        default:
+          throw new Error(`Unknown tool: ${name}`);
       }
-    });
+    } catch (error) {
+      return ResponseFormatter.formatErrorResponse(name, error);
+    }
   }
 
   // Temporary handlers for brain-inspired systems - these should be moved to separate handler classes
@@ -0,0 +1,133 @@
+import { randomUUID } from 'crypto';
+
+// Scores one result item (a JSON value or a paragraph of text); higher is shown first
+export type ResponseRelevance = (item: unknown) => number;
+
+export interface SummarizedResponse {
+  summarized: true;
+  tool: string;
+  originalBytes: number;
+  budgetBytes: number;
+  counts: { items: number; shown: number; remaining: number }; // `items` counts the whole response; `remaining` what no page has shown yet
+  top: unknown[];
+  continuationToken?: string; // Pass to `fetch_more_results`; absent when nothing is left
+}
+
+interface Continuation {
+  tool: string;
+  items: unknown[]; // Ranked, not yet returned
+  total: number; // Items in the original response
+  originalBytes: number;
+}
+
+const MAX_CONTINUATIONS = 50;
+
+/**
+ * Keeps tool responses under a byte budget. A response whose text is too large is replaced by
+ * a JSON summary (item counts plus the most relevant items that fit) instead of being cut off
+ * mid-structure; the rest can be paged in with the continuation token.
+ */
+export class ResponseBudget {
+  private continuations = new Map<string, Continuation>();
+
+  constructor(
+    private budgetBytes: number,
+    private createRelevance: () => ResponseRelevance = () => () => 0 // Called once per oversized response
+  ) {}
+
+  apply(tool: string, response: any): any {
+    const text = this.responseText(response);
+    if (text === undefined || this.budgetBytes <= 0 || Buffer.byteLength(text, 'utf-8') <= this.budgetBytes) {
+      return response;
+    }
+
+    const relevance = this.createRelevance();
+    const items = this.splitItems(text)
+      .map((item, index) => ({ item, index, score: relevance(item) }))
+      .sort((a, b) => b.score - a.score || a.index - b.index)
+      .map(({ item }) => item);
+
+    return this.summarize(tool, items, items.length, Buffer.byteLength(text, 'utf-8'));
+  }
+
+  fetchMore(token: string): any {
+    const continuation = this.continuations.get(token);
+    if (!continuation) {
+      throw new Error(`Unknown or expired continuation token: ${token}`);
+    }
+    this.continuations.delete(token);
+    return this.summarize(continuation.tool, continuation.items, continuation.total, continuation.originalBytes);
+  }
+
+  private summarize(tool: string, ranked: unknown[], total: number, originalBytes: number): any {
+    // Largest prefix of the ranking whose summary fits; at least one item so paging always advances
+    const render = (shown: number, token?: string) => {
+      const summary: SummarizedResponse = {
+        summarized: true,
+        tool,
+        originalBytes,
+        budgetBytes: this.budgetBytes,
+        counts: { items: total, shown, remaining: ranked.length - shown },
+        top: ranked.slice(0, shown),
+        continuationToken: token
+      };
+      return JSON.stringify(summary, null, 2);
+    };
+
+    const placeholder = '00000000-0000-0000-0000-000000000000';
+    const fits = (shown: number) =>
+      Buffer.byteLength(render(shown, shown < ranked.length ? placeholder : undefined), 'utf-8') <= this.budgetBytes;
+    // Summaries only grow with `shown` once a token is needed, so binary search the partial pages
+    let shown = ranked.length;
+    if (shown > 1 && !fits(shown)) {
+      let low = 1;
+      let high = ranked.length - 1;
+      while (low < high) {
+        const mid = Math.ceil((low + high) / 2);
+        if (fits(mid)) low = mid;
+        else high = mid - 1;
+      }
+      shown = low;
+    }
+
+    let token: string | undefined;
+    if (shown < ranked.length) {
+      token = randomUUID();
+      this.continuations.set(token, { tool, items: ranked.slice(shown), total, originalBytes });
+      if (this.continuations.size > MAX_CONTINUATIONS) {
+        this.continuations.delete(this.continuations.keys().next().value!);
+      }
+    }
+
+    return {
+      content: [
+        {
+          type: 'text',
+          text: render(shown, token)
+        }
+      ]
+    };
+  }
+
+  private responseText(response: any): string | undefined {
+    const content = response?.content;
+    if (!Array.isArray(content) || response.isError) return undefined;
+    const texts = content.filter((part: any) => part?.type === 'text' && typeof part.text === 'string');
+    return texts.length === content.length ? texts.map((part: any) => part.text).join('\n') : undefined;
+  }
+
+  private splitItems(text: string): unknown[] {
+    // JSON results page through their largest array; text results through blank-line separated blocks
+    try {
+      const parsed = JSON.parse(text);
+      if (Array.isArray(parsed)) return parsed;
+      if (parsed && typeof parsed === 'object') {
+        const arrays = Object.values(parsed).filter(Array.isArray) as unknown[][];
+        if (arrays.length > 0) return arrays.sort((a, b) => b.length - a.length)[0];
+      }
+    } catch {
+      // Not JSON
+    }
+    return text.split(/\n\s*\n/).map(block => block.trim()).filter(block => block.length > 0);
+  }
+}
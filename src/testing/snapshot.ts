@@ -0,0 +1,153 @@
+import { mkdir, readFile, writeFile } from 'fs/promises';
+import { dirname } from 'path';
+import { MindMapEdge, MindMapNode } from '../types/index.js';
+
+/**
+ * Golden snapshots of parse results, so a change to a parser that alters the graph it builds for a
+ * fixture shows up as a reviewable diff. Nodes are keyed by what they are (type, kind, qualified name and
+ * file) rather than by ID, and references to other nodes in metadata are rewritten to those keys, so
+ * renaming the ID scheme leaves a snapshot intact while an added, lost or re-linked item changes it.
+ * Set `UPDATE_SNAPSHOTS=1` to record or rewrite golden files instead of comparing against them.
+ */
+
+export const UPDATE_SNAPSHOTS_ENV = 'UPDATE_SNAPSHOTS';
+
+export interface SnapshotOptions {
+  root?: string; // Absolute path replaced by `<root>` wherever it appears
+  omitMetadata?: string[]; // Metadata keys left out, on top of timestamps
+}
+
+export type SnapshotStatus = 'matched' | 'recorded' | 'updated' | 'missing' | 'mismatch';
+
+export interface SnapshotResult {
+  status: SnapshotStatus;
+  goldenPath: string;
+  diff?: string; // For a mismatch: golden lines missing from the actual snapshot (-) and new ones (+)
+}
+
+// Vary between runs of the same parse
+const VOLATILE_KEYS = new Set(['lastUpdated', 'lastEditedAt', 'createdAt', 'created', 'lastReinforcedAt', 'lastDecayedAt']);
+// Shown in the span column rather than with the rest of the metadata
+const SPAN_KEYS = new Set(['startLine', 'endLine']);
+const MAX_DIFF_LINES = 40;
+
+/**
+ * Serialize nodes and edges to one line each, sorted, with spans as `L<start>-<end>` and metadata as
+ * JSON with sorted keys. The output ends with a newline and is identical for identical graphs.
+ */
+export function serializeSnapshot(
+  graph: { nodes: Iterable<MindMapNode>; edges: Iterable<MindMapEdge> },
+  options: SnapshotOptions = {}
+): string {
+  const nodes = Array.from(graph.nodes);
+  const edges = Array.from(graph.edges);
+  const omitted = new Set([...VOLATILE_KEYS, ...(options.omitMetadata || [])]);
+  const normalizePath = (text: string) => {
+    const withSlashes = text.replace(/\\/g, '/');
+    const root = options.root?.replace(/\\/g, '/').replace(/\/$/, '');
+    return root ? withSlashes.split(root).join('<root>') : withSlashes;
+  };
+
+  // Items sharing a key (overloads, repeated placeholders) are numbered by position, then in input order
+  const keys = new Map<string, string>();
+  const byKey = new Map<string, MindMapNode[]>();
+  for (const node of nodes) {
+    const qualified = node.metadata?.qualifiedName || node.name;
+    const kind = node.customKind || node.metadata?.rustKind || node.metadata?.patternType;
+    const key = `${node.type}${kind ? `/${kind}` : ''} ${qualified}${node.path ? ` @${normalizePath(node.path)}` : ''}`;
+    byKey.set(key, [...(byKey.get(key) || []), node]);
+  }
+  for (const [key, group] of byKey) {
+    if (group.length === 1) {
+      keys.set(group[0].id, key);
+      continue;
+    }
+    const line = (node: MindMapNode, field: string) => typeof node.metadata?.[field] === 'number' ? node.metadata[field] as number : 0;
+    [...group]
+      .sort((a, b) => line(a, 'startLine') - line(b, 'startLine') || line(a, 'endLine') - line(b, 'endLine'))
+      .forEach((node, index) => keys.set(node.id, `${key} #${index + 1}`));
+  }
+
+  const rewrite = (value: any): any => {
+    if (typeof value === 'string') return keys.has(value) ? `<${keys.get(value)}>` : normalizePath(value);
+    if (Array.isArray(value)) return value.map(rewrite);
+    if (value instanceof Date) return undefined;
+    if (value && typeof value === 'object') {
+      return Object.fromEntries(Object.entries(value).filter(([key]) => !omitted.has(key)).map(([key, inner]) => [key, rewrite(inner)]));
+    }
+    return value;
+  };
+  const span = (metadata: Record<string, any> | undefined) => {
+    const start = metadata?.startLine;
+    if (typeof start !== 'number') return '-';
+    const end = typeof metadata?.endLine === 'number' ? metadata.endLine : start;
+    return `L${start}-${end}`;
+  };
+  const details = (metadata: Record<string, any> | undefined) => {
+    const kept = Object.fromEntries(Object.entries(metadata || {}).filter(([key]) => !SPAN_KEYS.has(key)));
+    return stableJson(rewrite(kept), omitted);
+  };
+
+  const nodeLines = nodes.map(node =>
+    `node ${keys.get(node.id)} ${span(node.metadata)} c=${node.confidence.toFixed(2)} ${details(node.metadata)}`
+  );
+  const edgeLines = edges.map(edge => {
+    const kind = edge.type === 'custom' && edge.customKind ? `custom/${edge.customKind}` : edge.type;
+    const endpoint = (id: string) => keys.has(id) ? `<${keys.get(id)}>` : `<missing ${normalizePath(id)}>`;
+    const evidence = (edge.evidence || [])
+      .map(item => `${normalizePath(item.filePath)}:L${item.startLine}-${item.endLine}${item.kind ? `/${item.kind}` : ''}`)
+      .sort();
+    const extras = evidence.length > 0 ? ` evidence=${evidence.join(',')}` : '';
+    return `edge ${endpoint(edge.source)} -${kind}-> ${endpoint(edge.target)} c=${edge.confidence.toFixed(2)}${extras} ${details(edge.metadata)}`;
+  });
+
+  return [...nodeLines.sort(), ...edgeLines.sort()].join('\n') + '\n';
+}
+
+/**
+ * Compare a serialized snapshot with its golden file. Golden files are only written in update mode
+ * (`update`, defaulting to the `UPDATE_SNAPSHOTS` variable); otherwise a missing one is a failure, so a
+ * deleted or never-committed golden cannot pass silently.
+ */
+export async function matchSnapshot(
+  actual: string,
+  goldenPath: string,
+  update: boolean = process.env[UPDATE_SNAPSHOTS_ENV] === '1'
+): Promise<SnapshotResult> {
+  const golden = await readFile(goldenPath, 'utf-8').catch(() => undefined);
+  if (golden === actual) return { status: 'matched', goldenPath };
+  if (!update) {
+    return golden === undefined
+      ? { status: 'missing', goldenPath }
+      : { status: 'mismatch', goldenPath, diff: snapshotDiff(golden, actual) };
+  }
+  await mkdir(dirname(goldenPath), { recursive: true });
+  await writeFile(goldenPath, actual);
+  return { status: golden === undefined ? 'recorded' : 'updated', goldenPath };
+}
+
+function snapshotDiff(expected: string, actual: string): string {
+  // Lines are sorted and mostly unique, so set differences read as a diff
+  const expectedLines = expected.split('\n').filter(line => line.length > 0);
+  const actualLines = actual.split('\n').filter(line => line.length > 0);
+  const expectedSet = new Set(expectedLines);
+  const actualSet = new Set(actualLines);
+  const changes = [
+    ...expectedLines.filter(line => !actualSet.has(line)).map(line => `- ${line}`),
+    ...actualLines.filter(line => !expectedSet.has(line)).map(line => `+ ${line}`)
+  ];
+  if (changes.length === 0) return 'Only the order or number of repeated lines differs';
+  const shown = changes.slice(0, MAX_DIFF_LINES);
+  return [...shown, ...(changes.length > shown.length ? [`... ${changes.length - shown.length} more`] : [])].join('\n');
+}
+
+function stableJson(value: any, omitted: Set<string>): string {
+  // JSON with object keys sorted, undefined values and omitted keys dropped
+  return JSON.stringify(value, (key, inner) => {
+    if (omitted.has(key)) return undefined;
+    if (inner && typeof inner === 'object' && !Array.isArray(inner)) {
+      return Object.fromEntries(Object.keys(inner).sort().map(name => [name, inner[name]]));
+    }
+    return inner;
+  }) ?? 'null';
+}
@@ -1415,6 +1415,42 @@ export const ANALYZE_ERROR_PROPAGATION_TOOL: Tool = {
   }
 };
 
+export const GET_CONTEXT_PACK_TOOL: Tool = {
+  name: 'get_context_pack',
+  description: 'Build a compact, relevance-ranked summary of the code around one or more seed symbols, sized to fit an approximate token budget for use in LLM prompts',
+  inputSchema: {
+    type: 'object',
+    properties: {
+      seeds: {
+        type: 'array',
+        items: { type: 'string' },
+        description: 'Node IDs or symbol names to center the context on (e.g. ["create_user_handler"])'
+      },
+      budget_tokens: {
+        type: 'number',
+        description: 'Approximate token budget for the summary, estimated as characters / 4 (default: 1000)',
+        default: 1000
+      }
+    },
+    required: ['seeds']
+  }
+};
+
+export const FETCH_MORE_RESULTS_TOOL: Tool = {
+  name: 'fetch_more_results',
+  description: 'Fetch the next page of a tool response that was summarized for exceeding the response byte budget (responses with "summarized": true)',
+  inputSchema: {
+    type: 'object',
+    properties: {
+      continuation_token: {
+        type: 'string',
+        description: 'The continuationToken from the summarized response'
+      }
+    },
+    required: ['continuation_token']
+  }
+};
+
 export const ALL_TOOLS: Tool[] = [
   QUERY_MINDMAP_TOOL,
   UPDATE_MINDMAP_TOOL,
@@ -1474,5 +1510,7 @@ export const ALL_TOOLS: Tool[] = [
   DETECT_CROSS_LANGUAGE_APIS_TOOL,
   ANALYZE_TEST_COVERAGE_TOOL,
   ANALYZE_CONFIGURATION_RELATIONSHIPS_TOOL,
-  ANALYZE_ERROR_PROPAGATION_TOOL
+  ANALYZE_ERROR_PROPAGATION_TOOL,
+  GET_CONTEXT_PACK_TOOL,
+  FETCH_MORE_RESULTS_TOOL
 ];
\ No newline at end of file
@@ -1,6 +1,7 @@
 export interface MindMapNode {
   id: string;
-  type: 'file' | 'directory' | 'function' | 'class' | 'error' | 'pattern' | 'episodic_memory' | 'call_pattern' | 'document' | 'link' | 'section' | 'variable' | 'type_parameter';
+  type: 'file' | 'directory' | 'function' | 'class' | 'error' | 'pattern' | 'episodic_memory' | 'call_pattern' | 'document' | 'link' | 'section' | 'variable' | 'type_parameter' | 'custom';
+  customKind?: string; // User-defined kind: set for 'custom' nodes and for nodes an extractor tagged
   name: string;
   path?: string;
   metadata: Record<string, any>;
@@ -15,18 +16,29 @@ export interface MindMapEdge {
   id: string;
   source: string;
   target: string;
-  type: 'contains' | 'imports' | 'calls' | 'fixes' | 'relates_to' | 'depends_on' | 'detects' | 'co_activates' | 'documents' | 'links_to' | 'references' | 'implements' | 'describes' | 'used_by' | 'instantiated_as' | 'violates_constraint';
+  type: 'contains' | 'imports' | 'calls' | 'fixes' | 'relates_to' | 'depends_on' | 'detects' | 'co_activates' | 'documents' | 'links_to' | 'references' | 'implements' | 'describes' | 'used_by' | 'instantiated_as' | 'violates_constraint' | 'tests' | 'requires_bound' | 'wraps' | 'alias_of' | 'custom';
+  customKind?: string; // Set for 'custom' edges produced by relationship extractors
+  evidence?: EvidenceSpan[]; // Source locations supporting the edge, merged across extraction passes
   weight?: number;
   confidence: number;
   metadata?: Record<string, any>;
   created?: Date;
   lastUpdated?: Date;
-  createdAt?: Date;
+  createdAt?: Date; // When the edge first entered the graph; kept when a rescan re-adds it
+  lastReinforcedAt?: Date; // Last traversal that strengthened the edge
+  lastDecayedAt?: Date; // Last time decay was applied to the weight
   // Compatibility properties for Hebbian system
   sourceId?: string;
   targetId?: string;
 }
 
+export interface EvidenceSpan {
+  filePath: string;
+  startLine: number;
+  endLine: number;
+  kind?: string; // What the span shows, e.g. 'impl' or 'derive'
+}
+
 export interface MindMapGraph {
   nodes: Map<string, MindMapNode>;
   edges: Map<string, MindMapEdge>;
@@ -257,6 +269,16 @@ export interface CodeStructure {
     methods: string[];
     properties: string[];
   }>;
+  interfaces?: Array<{
+    name: string;
+    startLine: number;
+    endLine: number;
+    properties: Array<{
+      name: string;
+      type?: string; // Declared type as written
+      optional: boolean;
+    }>;
+  }>;
   imports: Array<{
     module: string;
     path?: string;
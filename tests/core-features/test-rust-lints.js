@@ -0,0 +1,2466 @@
+#!/usr/bin/env node
+
+/**
+ * Rust Lint Test Suite
+ *
+ * Runs the Rust consistency checks exposed by MindMapEngine against the
+ * Rust example fixture (expected clean) and small synthetic projects.
+ */
+
+import { MindMapEngine } from '../../dist/core/MindMapEngine.js';
+import { join } from 'path';
+import { existsSync, mkdirSync, rmSync, readFileSync, writeFileSync } from 'fs';
+
+const FIXTURE_PATH = join(process.cwd(), 'tests', 'example-files', 'test-rust-example.rs');
+
+class RustLintTestSuite {
+  constructor() {
+    this.testDir = join(process.cwd(), 'test-temp-rust-lints');
+    this.engine = null;
+    this.results = {
+      passed: 0,
+      failed: 0,
+      tests: []
+    };
+  }
+
+  async setupTestEnvironment() {
+    if (existsSync(this.testDir)) {
+      rmSync(this.testDir, { recursive: true, force: true });
+    }
+    mkdirSync(this.testDir, { recursive: true });
+
+    this.engine = await this.scanProject('fixture', {
+      'src/main.rs': readFileSync(FIXTURE_PATH, 'utf-8')
+    });
+
+    console.log(`✅ Test environment created: ${this.testDir}`);
+  }
+
+  async scanProject(name, files) {
+    const projectDir = join(this.testDir, name);
+    mkdirSync(projectDir, { recursive: true });
+    writeFileSync(join(projectDir, 'Cargo.toml'), `[package]\nname = "${name}"\nversion = "0.1.0"\n`);
+
+    for (const [filePath, content] of Object.entries(files)) {
+      const fullPath = join(projectDir, filePath);
+      mkdirSync(join(fullPath, '..'), { recursive: true });
+      writeFileSync(fullPath, content);
+    }
+
+    const engine = new MindMapEngine(projectDir);
+    await engine.initialize();
+    await engine.scanProject(true);
+    return engine;
+  }
+
+  async cleanupTestEnvironment() {
+    if (existsSync(this.testDir)) {
+      rmSync(this.testDir, { recursive: true, force: true });
+      console.log(`🗑️  Cleaned up test directory`);
+    }
+  }
+
+  async runTest(name, testFn) {
+    try {
+      console.log(`🧪 Running: ${name}`);
+      const startTime = Date.now();
+
+      await testFn();
+
+      const duration = Date.now() - startTime;
+      console.log(`✅ PASSED: ${name} (${duration}ms)`);
+      this.results.passed++;
+      this.results.tests.push({ name, status: 'PASSED', duration });
+
+    } catch (error) {
+      console.error(`❌ FAILED: ${name}`);
+      console.error(`   Error: ${error.message}`);
+      this.results.failed++;
+      this.results.tests.push({ name, status: 'FAILED', error: error.message });
+    }
+  }
+
+  async testDeriveConsistencyClean() {
+    const issues = this.engine.deriveConsistency();
+    if (issues.length !== 0) {
+      throw new Error(`Fixture derives should be consistent, got: ${issues.map(i => i.message).join('; ')}`);
+    }
+  }
+
+  async testDeriveConsistencyFlagged() {
+    const engine = await this.scanProject('derive-mismatch', {
+      'src/lib.rs': `
+#[derive(Debug, Eq)]
+pub struct UserId(u64);
+
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+pub struct Priority {
+    level: u8,
+}
+
+#[derive(Debug, Eq, Hash)]
+pub struct Token {
+    value: String,
+}
+
+impl PartialEq for Token {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+`
+    });
+
+    const issues = engine.deriveConsistency();
+    if (issues.length !== 1) {
+      throw new Error(`Expected exactly 1 issue, got: ${issues.map(i => i.message).join('; ')}`);
+    }
+
+    const [issue] = issues;
+    if (issue.typeName !== 'UserId' || issue.derive !== 'Eq' || issue.missing !== 'PartialEq') {
+      throw new Error(`Unexpected issue: ${JSON.stringify(issue)}`);
+    }
+    if (!issue.message.includes('PartialEq')) {
+      throw new Error('Issue message should name the missing derive');
+    }
+  }
+
+  async testTraitCoverageFixture() {
+    const [coverage] = this.engine.traitCoverage('UserRepository for InMemoryUserRepository');
+    if (!coverage) {
+      throw new Error('No coverage reported for InMemoryUserRepository');
+    }
+    if (coverage.implemented.length !== 3 || coverage.defaulted.length !== 0 || coverage.missing.length !== 0) {
+      throw new Error(`Unexpected coverage: ${JSON.stringify(coverage)}`);
+    }
+    if (!coverage.complete) {
+      throw new Error('Fixture impl should be complete');
+    }
+  }
+
+  async testTraitCoverageIncomplete() {
+    const engine = await this.scanProject('trait-coverage', {
+      'src/lib.rs': `
+pub trait Store {
+    fn get(&self, key: &str) -> Option<String>;
+    fn put(&mut self, key: &str, value: String);
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+pub struct PartialStore {
+    items: Vec<String>,
+}
+
+impl Store for PartialStore {
+    fn get(&self, key: &str) -> Option<String> {
+        None
+    }
+
+    fn len(&self) -> usize {
+        self.items.len()
+    }
+}
+`
+    });
+
+    const [coverage] = engine.traitCoverage('PartialStore', 'Store');
+    if (!coverage) {
+      throw new Error('No coverage reported for PartialStore');
+    }
+    if (coverage.missing.join(',') !== 'put') {
+      throw new Error(`Expected put to be missing, got: ${JSON.stringify(coverage.missing)}`);
+    }
+    if (coverage.defaulted.join(',') !== 'is_empty') {
+      throw new Error(`Expected is_empty to be defaulted, got: ${JSON.stringify(coverage.defaulted)}`);
+    }
+    if (coverage.implemented.join(',') !== 'get,len' || coverage.complete) {
+      throw new Error(`Unexpected coverage: ${JSON.stringify(coverage)}`);
+    }
+  }
+
+  async testDefaultMethodUsage() {
+    if (this.engine.defaultMethodUsage('UserRepository').length !== 0) {
+      throw new Error('UserRepository has no default methods');
+    }
+
+    const engine = await this.scanProject('default-methods', {
+      'src/lib.rs': `
+pub trait Greeter {
+    fn name(&self) -> String;
+
+    fn greet(&self) -> String {
+        format!("Hello, {}", self.name())
+    }
+}
+
+pub struct English;
+pub struct Pirate;
+
+impl Greeter for English {
+    fn name(&self) -> String {
+        "friend".to_string()
+    }
+}
+
+impl Greeter for Pirate {
+    fn name(&self) -> String {
+        "matey".to_string()
+    }
+
+    fn greet(&self) -> String {
+        "Ahoy!".to_string()
+    }
+}
+`
+    });
+
+    const usage = engine.defaultMethodUsage('Greeter');
+    if (usage.length !== 1 || usage[0].method !== 'greet') {
+      throw new Error(`Only greet has a default body: ${JSON.stringify(usage)}`);
+    }
+    if (usage[0].overriddenBy.join(',') !== 'Pirate' || usage[0].usesDefault.join(',') !== 'English') {
+      throw new Error(`Unexpected override split: ${JSON.stringify(usage[0])}`);
+    }
+  }
+
+  async testOverridesOf() {
+    const required = this.engine.overridesOf('UserRepository::create_user');
+    if (required.map(o => o.typeName).join(',') !== 'InMemoryUserRepository' || required[0].overridesDefault) {
+      throw new Error(`Every implementor provides a required method: ${JSON.stringify(required)}`);
+    }
+
+    const engine = await this.scanProject('overrides', {
+      'src/lib.rs': `
+pub trait Shape {
+    fn area(&self) -> f64;
+
+    fn describe(&self) -> String {
+        format!("area {}", self.area())
+    }
+}
+
+pub struct Circle;
+pub struct Square;
+pub struct Triangle;
+
+impl Shape for Circle {
+    fn area(&self) -> f64 { 3.14 }
+
+    fn describe(&self) -> String {
+        "round".to_string()
+    }
+}
+
+impl Shape for Square {
+    fn area(&self) -> f64 { 1.0 }
+}
+
+impl Shape for Triangle {
+    fn area(&self) -> f64 { 0.5 }
+
+    fn describe(&self) -> String {
+        "pointy".to_string()
+    }
+}
+
+pub trait Named {
+    fn describe(&self) -> String;
+}
+
+impl Named for Square {
+    fn describe(&self) -> String {
+        "square".to_string()
+    }
+}
+`
+    });
+
+    const overrides = engine.overridesOf('Shape::describe');
+    if (overrides.map(o => o.typeName).join(',') !== 'Circle,Triangle' || !overrides.every(o => o.overridesDefault)) {
+      throw new Error(`Only Circle and Triangle override the default: ${JSON.stringify(overrides)}`);
+    }
+    if (overrides[0].line !== 14 || overrides[0].filePath !== 'src/lib.rs') {
+      throw new Error(`Overrides should point at their impl block: ${JSON.stringify(overrides[0])}`);
+    }
+    const byName = engine.overridesOf('describe').map(o => `${o.traitName}:${o.typeName}`);
+    if (byName.join(',') !== 'Named:Square,Shape:Circle,Shape:Triangle') {
+      throw new Error(`A bare method name should search every trait: ${byName.join(', ')}`);
+    }
+  }
+
+  async testDefaultValues() {
+    const defaults = this.engine.defaultValues('ServerConfig');
+    const found = defaults?.values.map(v => `${v.field}=${v.value ?? v.expression}:${v.kind}`);
+    if (defaults?.source !== 'manual' || found.join(',') !== 'host="127.0.0.1":literal,port=8080:literal,workers=num_cpus::get():call') {
+      throw new Error(`ServerConfig's Default impl should give three field values: ${JSON.stringify(defaults)}`);
+    }
+    const portLine = readFileSync(FIXTURE_PATH, 'utf-8').split('\n').findIndex(line => line.includes('port: 8080,')) + 1;
+    if (defaults.values[1].line !== portLine || this.engine.defaultValues('AppState') !== undefined) {
+      throw new Error(`Values should point at their lines, and AppState has no Default: ${JSON.stringify(defaults.values[1])}`);
+    }
+
+    const engine = await this.scanProject('derived-default', {
+      'src/lib.rs': `
+#[derive(Debug, Default)]
+pub struct Limits {
+    pub max_connections: u32,
+    pub timeout: Option<u64>,
+}
+`
+    });
+    const derived = engine.defaultValues('crate::Limits');
+    if (derived?.source !== 'derived' || derived.values.map(v => `${v.field}:${v.kind}`).join(',') !== 'max_connections:derived,timeout:derived') {
+      throw new Error(`Derived defaults should be noted as derived: ${JSON.stringify(derived)}`);
+    }
+  }
+
+  async testIgnoredResultsClean() {
+    const issues = this.engine.ignoredResults();
+    if (issues.length !== 0) {
+      throw new Error(`Fixture handles every Result, got: ${issues.map(i => i.message).join('; ')}`);
+    }
+  }
+
+  async testIgnoredResultsFlagged() {
+    const engine = await this.scanProject('ignored-results', {
+      'src/lib.rs': `
+pub struct Store;
+
+impl Store {
+    pub fn save(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+pub fn do_thing() -> Result<u32, String> {
+    Ok(1)
+}
+
+pub fn log_line(message: &str) {
+    println!("{}", message);
+}
+
+pub fn run(store: &Store) -> Result<(), String> {
+    do_thing();
+    log_line("done");
+    let _ = do_thing();
+    do_thing()?;
+    store.save().unwrap();
+    store.save();
+    Ok(())
+}
+`
+    });
+
+    const issues = engine.ignoredResults();
+    const calls = issues.map(issue => `${issue.call}@${issue.line}`).join(',');
+    if (calls !== 'do_thing@19,.save@24') {
+      throw new Error(`Expected the two discarded results to be flagged, got: ${calls}`);
+    }
+    if (issues.some(issue => issue.functionName !== 'run') || !issues[0].returnType.startsWith('Result')) {
+      throw new Error(`Unexpected issue details: ${JSON.stringify(issues)}`);
+    }
+  }
+
+  async testShadowingClean() {
+    const sites = this.engine.shadowingSites();
+    if (sites.length !== 0) {
+      throw new Error(`Fixture binds every name once, got: ${sites.map(site => site.message).join('; ')}`);
+    }
+  }
+
+  async testShadowingFlagged() {
+    const engine = await this.scanProject('shadowing', {
+      'src/lib.rs': `
+pub fn clean(values: &[u32]) -> u32 {
+    let total = values.iter().sum::<u32>();
+    if let Some(first) = values.first() {
+        let doubled = first * 2;
+        return total + doubled;
+    }
+    let first = 0;
+    total + first
+}
+
+pub fn shadowing(input: &str) -> usize {
+    let x = input.trim();
+    let x = x.len();
+    {
+        let x = x * 2;
+        println!("{}", x);
+    }
+    x
+}
+`
+    });
+
+    const sites = engine.shadowingSites();
+    const found = sites.map(site => `${site.functionName}:${site.name}@${site.line}<${site.previousLine}`).join(',');
+    if (found !== 'shadowing:x@14<13,shadowing:x@16<14') {
+      throw new Error(`Expected the re-lets of x to be flagged and clean() to pass, got: ${found}`);
+    }
+  }
+
+
+  async testParseDiagnostics() {
+    const engine = await this.scanProject('diagnostics', {
+      'src/lib.rs': `use crate::util::helper;
+
+pub fn run<F>(callback: F) -> u32
+where
+    F: Fn(u32) -> u32,
+{
+    let a = helper(1);
+    let b = callback(a);
+    drop(a);
+    mystery(b)
+}
+
+fn local() -> u32 {
+    run(|x| x) + String::new().len() as u32
+}
+`,
+      'src/util.rs': `pub fn helper(x: u32) -> u32 {
+    x
+}
+`
+    });
+
+    const diagnostics = engine.diagnosticsFor('src/lib.rs');
+    const unresolved = diagnostics.filter(diagnostic => diagnostic.code === 'unresolved-call');
+    if (unresolved.length !== 1 || !unresolved[0].message.startsWith('mystery()') || unresolved[0].severity !== 'info') {
+      throw new Error(`Only mystery() should be unresolved, got: ${JSON.stringify(diagnostics)}`);
+    }
+    if (unresolved[0].span.startLine !== 10 || unresolved[0].span.endLine !== 10 || unresolved[0].item !== 'run') {
+      throw new Error(`The diagnostic should point at the call in run: ${JSON.stringify(unresolved[0].span)}`);
+    }
+    if (engine.diagnosticsFor('src/lib.rs', 'warning').length !== 0 || engine.diagnosticsFor('src/util.rs').length !== 0) {
+      throw new Error('Filtering by severity should drop info diagnostics, and util.rs has none');
+    }
+    if (engine.diagnosticsFor(join(this.testDir, 'diagnostics', 'src', 'lib.rs')).length !== diagnostics.length) {
+      throw new Error('Absolute paths should find the same file');
+    }
+  }
+
+  async testExhaustivePublicEnums() {
+    if (this.engine.exhaustivePublicEnums().length !== 0) {
+      throw new Error('The fixture declares no enums');
+    }
+
+    const engine = await this.scanProject('exhaustive-enums', {
+      'src/lib.rs': `
+pub enum Status {
+    A,
+    B,
+}
+
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum Event {
+    Started,
+    Stopped,
+}
+
+pub(crate) enum Internal {
+    One,
+}
+
+enum Private {
+    Two,
+}
+`
+    });
+
+    const flagged = engine.exhaustivePublicEnums();
+    if (flagged.map(item => `${item.name}:${item.variants.join('|')}`).join(',') !== 'Status:A|B') {
+      throw new Error(`Only the exhaustive pub enum should be flagged, got: ${flagged.map(item => item.message).join('; ')}`);
+    }
+  }
+  async testFfiSurface() {
+    if (this.engine.ffiSurface().length !== 0) {
+      throw new Error('The fixture has no FFI boundaries');
+    }
+
+    const engine = await this.scanProject('ffi-surface', {
+      'src/lib.rs': `
+use std::os::raw::{c_char, c_int};
+
+extern "C" {
+    fn puts(s: *const c_char) -> c_int;
+}
+
+#[no_mangle]
+pub fn exported_symbol() {}
+
+pub extern "C" fn ffi_entry(x: c_int) -> c_int {
+    x + 1
+}
+
+pub unsafe fn read_counter() -> u64 {
+    let value: u64;
+    std::arch::asm!("rdtsc", out("rax") value);
+    value
+}
+
+pub fn plain() {}
+`
+    });
+
+    const surface = engine.ffiSurface().map(entry => `${entry.kind}:${entry.name}:${entry.abi || '-'}`);
+    const expected = ['foreign_function:puts:C', 'exported_symbol:exported_symbol:-', 'extern_function:ffi_entry:C', 'inline_asm:asm!:-'];
+    if (surface.join(',') !== expected.join(',')) {
+      throw new Error(`Expected ${expected.join(', ')}, got: ${surface.join(', ')}`);
+    }
+
+    const asm = engine.ffiSurface().find(entry => entry.kind === 'inline_asm');
+    const owner = engine.storage.getEdge(`src/lib.rs#function:read_counter->contains->${asm.nodeId}`);
+    if (!owner) {
+      throw new Error('Inline assembly should hang off its enclosing function');
+    }
+  }
+  async testLockAcrossAwaitClean() {
+    // The fixture's repository state is never locked
+    const flagged = this.engine.lockHeldAcrossAwait();
+    if (flagged.length !== 0) {
+      throw new Error(`The fixture holds no locks across awaits: ${flagged.map(finding => finding.message).join('; ')}`);
+    }
+  }
+
+  async testLockAcrossAwaitFlagged() {
+    const engine = await this.scanProject('lock-across-await', {
+      'src/lib.rs': `
+use std::sync::Mutex;
+
+async fn something() {}
+
+pub async fn held(m: &Mutex<u32>) {
+    let g = m.lock().unwrap();
+    something().await;
+    println!("{}", *g);
+}
+
+pub async fn dropped(m: &Mutex<u32>) {
+    let g = m.lock().unwrap();
+    let value = *g;
+    drop(g);
+    something().await;
+    println!("{}", value);
+}
+
+pub async fn scoped(m: &Mutex<u32>) {
+    {
+        let mut g = m.lock().unwrap();
+        *g += 1;
+    }
+    something().await;
+}
+
+pub async fn async_lock(m: &tokio::sync::Mutex<u32>) {
+    let g = m.lock().await;
+    something().await;
+    println!("{}", *g);
+}
+`
+    });
+
+    const flagged = engine.lockHeldAcrossAwait();
+    if (flagged.map(finding => `${finding.name}:${finding.binding}`).join(', ') !== 'held:g') {
+      throw new Error(`Only held keeps its guard across the await: ${flagged.map(finding => finding.message).join('; ')}`);
+    }
+    if (flagged[0].acquire !== 'm.lock()' || flagged[0].awaitLine !== flagged[0].line + 1) {
+      throw new Error(`The guard and the await should be located: ${JSON.stringify(flagged[0])}`);
+    }
+  }
+
+  async testNonExhaustiveMatchesClean() {
+    // The fixture only matches over Option and Result, which are not in the graph
+    const flagged = this.engine.nonExhaustiveMatches();
+    if (flagged.length !== 0) {
+      throw new Error(`The fixture has no incomplete matches: ${flagged.map(finding => finding.message).join('; ')}`);
+    }
+  }
+
+  async testNonExhaustiveMatchesFlagged() {
+    const engine = await this.scanProject('non-exhaustive-matches', {
+      'src/lib.rs': `
+pub enum Status {
+    A,
+    B(u32),
+}
+
+pub fn partial(status: Status) -> u32 {
+    match status {
+        Status::A => 1,
+    }
+}
+
+pub fn complete(status: Status) -> u32 {
+    match status {
+        Status::A => 1,
+        Status::B(n) => n,
+    }
+}
+
+pub fn defaulted(status: Status) -> u32 {
+    match status {
+        Status::A => 1,
+        _ => 0,
+    }
+}
+`
+    });
+
+    const flagged = engine.nonExhaustiveMatches();
+    if (flagged.map(finding => `${finding.name}:${finding.missing.join('|')}`).join(', ') !== 'partial:B') {
+      throw new Error(`Only partial misses a variant: ${flagged.map(finding => finding.message).join('; ')}`);
+    }
+    if (flagged[0].enumId !== 'src/lib.rs#enum:Status') {
+      throw new Error(`The finding should link to the enum node: ${flagged[0].enumId}`);
+    }
+    if (flagged[0].scrutinee !== 'status') {
+      throw new Error(`The scrutinee should be reported: ${flagged[0].scrutinee}`);
+    }
+  }
+
+  async testMethodShadowingClean() {
+    // InMemoryUserRepository's inherent new and initialize are not UserRepository methods
+    const flagged = this.engine.methodShadowing();
+    if (flagged.length !== 0) {
+      throw new Error(`The fixture has no shadowing methods: ${flagged.map(finding => finding.message).join('; ')}`);
+    }
+  }
+
+  async testMethodShadowingFlagged() {
+    const engine = await this.scanProject('method-shadowing', {
+      'src/lib.rs': `
+pub struct CreateUserRequest {
+    pub name: String,
+}
+
+pub trait UserRepository {
+    fn create_user(&self, request: CreateUserRequest) -> u32;
+    fn count(&self) -> usize;
+}
+
+pub struct Repo;
+
+impl Repo {
+    pub fn create_user(&self, x: u32) -> u32 {
+        x
+    }
+
+    pub fn count(&self) -> usize {
+        0
+    }
+}
+
+impl UserRepository for Repo {
+    fn create_user(&self, r: CreateUserRequest) -> u32 {
+        r.name.len() as u32
+    }
+
+    fn count(&self) -> usize {
+        1
+    }
+}
+`
+    });
+
+    const flagged = engine.methodShadowing();
+    if (flagged.map(finding => `${finding.typeName}::${finding.method}:${finding.traitName}`).join(', ') !== 'Repo::create_user:UserRepository') {
+      throw new Error(`Only create_user differs from the trait: ${flagged.map(finding => finding.message).join('; ')}`);
+    }
+    if (!flagged[0].inherentSignature.includes('x: u32') || !flagged[0].traitSignature.includes('CreateUserRequest')) {
+      throw new Error(`Both signatures should be reported: ${JSON.stringify(flagged[0])}`);
+    }
+  }
+
+  async testAsyncMismatches() {
+    if (this.engine.asyncMismatches().length !== 0) {
+      throw new Error(`The fixture implements its async trait with async methods: ${this.engine.asyncMismatches().map(item => item.message).join('; ')}`);
+    }
+
+    const engine = await this.scanProject('async-mismatch', {
+      'src/lib.rs': `
+use std::future::Future;
+
+pub trait Store {
+    async fn create_user(&self, name: String) -> u64;
+    async fn count(&self) -> usize;
+    fn name(&self) -> &str;
+}
+
+pub struct Memory;
+
+impl Store for Memory {
+    fn create_user(&self, name: String) -> impl Future<Output = u64> {
+        async move { name.len() as u64 }
+    }
+
+    async fn count(&self) -> usize {
+        0
+    }
+
+    fn name(&self) -> &str {
+        "memory"
+    }
+}
+`
+    });
+
+    const flagged = engine.asyncMismatches();
+    if (flagged.map(item => `${item.typeName}:${item.method}:${item.traitAsync}/${item.implAsync}`).join(',') !== 'Memory:create_user:true/false') {
+      throw new Error(`Only the sync create_user should be flagged, got: ${flagged.map(item => item.message).join('; ')}`);
+    }
+  }
+
+  async testLayering() {
+    const fixtureRules = { layers: [{ name: 'app', modules: ['crate'] }, { name: 'config', modules: ['config'] }], allow: { app: ['config'] } };
+    if (this.engine.checkLayering(fixtureRules).length !== 0) {
+      throw new Error(`The fixture only depends on config from the crate root: ${this.engine.checkLayering(fixtureRules).map(item => item.message).join('; ')}`);
+    }
+
+    const engine = await this.scanProject('layering', {
+      'src/lib.rs': `
+pub mod repo {
+    pub fn find(id: u64) -> Option<u64> {
+        Some(id)
+    }
+}
+
+pub mod handlers {
+    pub fn show(id: u64) -> Option<u64> {
+        crate::repo::find(id)
+    }
+}
+
+pub mod config {
+    pub fn warm_up() {
+        crate::handlers::show(1);
+    }
+}
+`
+    });
+
+    const rules = {
+      layers: [
+        { name: 'handlers', modules: ['handlers'] },
+        { name: 'repo', modules: ['repo'] },
+        { name: 'config', modules: ['config'] }
+      ],
+      allow: { handlers: ['repo'], repo: ['config'] }
+    };
+    const violations = engine.checkLayering(rules);
+    if (violations.map(item => `${item.sourceLayer}->${item.targetLayer}:${item.edgeType}`).join(',') !== 'config->handlers:calls') {
+      throw new Error(`Only config calling a handler should be flagged, got: ${violations.map(item => item.message).join('; ')}`);
+    }
+
+    rules.allow.config = ['handlers'];
+    try {
+      engine.checkLayering(rules);
+      throw new Error('Rules allowing a cycle should be rejected');
+    } catch (error) {
+      if (!/cyclic/.test(error.message)) throw error;
+    }
+  }
+
+  async testPanicSites() {
+    const sites = this.engine.panicSites().filter(site => site.filePath === 'src/main.rs');
+    const found = sites.map(site => `${site.kind}:${site.name}:${site.inTest}`);
+    if (found.join(',') !== 'unwrap:test_user_creation:true,unwrap:test_user_repository_trait:true') {
+      throw new Error(`The fixture's tests unwrap twice, got: ${found.join(', ')}`);
+    }
+    if (this.engine.panicSites({ excludeTests: true }).some(site => site.filePath === 'src/main.rs')) {
+      throw new Error('Test-only unwraps should be excluded with excludeTests');
+    }
+
+    const engine = await this.scanProject('panic-sites', {
+      'src/lib.rs': `
+pub fn parse(text: &str) -> u32 {
+    let value = text.parse::<u32>().expect("a number");
+    let fallback = text.len().checked_sub(1).unwrap_or(0);
+    if value == 0 {
+        unreachable!("zero is rejected earlier");
+    }
+    // todo!() in a comment does not count
+    value + fallback as u32
+}
+
+pub fn later() {
+    todo!()
+}
+`
+    });
+
+    const production = engine.panicSites({ excludeTests: true }).map(site => `${site.kind}:${site.name}`);
+    if (production.join(',') !== 'expect:parse,unreachable:parse,todo:later') {
+      throw new Error(`Unexpected panic sites: ${production.join(', ')}`);
+    }
+  }
+
+  async testSarifReport() {
+    const engine = await this.scanProject('sarif-report', {
+      'src/lib.rs': `
+pub struct my_config {
+    pub retries: u32,
+}
+
+pub fn load(text: &str) -> u32 {
+    text.parse::<u32>().unwrap()
+}
+`
+    });
+
+    const findings = [
+      ...engine.panicSites().map(site => ({ rule: 'panic_site', message: `\`${site.kind}\` in ${site.name}`, nodeId: site.nodeId, filePath: site.filePath, line: site.line })),
+      ...engine.namingViolations().map(v => ({ rule: 'naming_violation', message: `${v.name} should be ${v.expected}: ${v.suggestion}`, level: 'note', nodeId: v.nodeId }))
+    ];
+    const sarif = engine.toSarif(findings, { rules: { panic_site: { description: 'Code that can panic', level: 'error' } } });
+    const run = sarif.runs?.[0];
+    if (sarif.version !== '2.1.0' || !sarif.$schema || !run || run.tool?.driver?.name !== 'mind-map' || !Array.isArray(run.results)) {
+      throw new Error('The log should carry the SARIF version, a schema and one run with a named tool');
+    }
+
+    const rules = run.tool.driver.rules;
+    if (rules.map(rule => rule.id).join(',') !== 'naming_violation,panic_site' || rules.some(rule => !rule.shortDescription?.text)) {
+      throw new Error(`Each category should be a described rule: ${JSON.stringify(rules)}`);
+    }
+    if (rules[1].defaultConfiguration.level !== 'error' || rules[0].defaultConfiguration.level !== 'warning') {
+      throw new Error('Rule levels should come from the descriptions, defaulting to warning');
+    }
+
+    if (run.results.length !== findings.length || findings.length < 2) {
+      throw new Error(`Expected a result per finding, got ${run.results.length} of ${findings.length}`);
+    }
+    for (const result of run.results) {
+      const location = result.locations?.[0]?.physicalLocation;
+      if (!result.message?.text || rules[result.ruleIndex]?.id !== result.ruleId || !['error', 'warning', 'note'].includes(result.level)) {
+        throw new Error(`Malformed result: ${JSON.stringify(result)}`);
+      }
+      if (location?.artifactLocation?.uri !== 'src/lib.rs' || !(location.region?.startLine >= 1)) {
+        throw new Error(`Results should be located in the file: ${JSON.stringify(result.locations)}`);
+      }
+    }
+
+    const panic = run.results.find(result => result.ruleId === 'panic_site');
+    const naming = run.results.find(result => result.ruleId === 'naming_violation');
+    if (panic.level !== 'error' || panic.locations[0].physicalLocation.region.startLine !== 7 || panic.locations[0].physicalLocation.region.endLine !== undefined) {
+      throw new Error(`The unwrap is on line 7: ${JSON.stringify(panic)}`);
+    }
+    if (naming.level !== 'note' || naming.locations[0].physicalLocation.region.startLine !== 2) {
+      throw new Error(`The struct name is declared on line 2: ${JSON.stringify(naming)}`);
+    }
+  }
+
+  async testConstFnCandidates() {
+    const engine = await this.scanProject('const-fn', {
+      'src/lib.rs': `
+pub fn area(width: u32, height: u32) -> u32 {
+    width * height
+}
+
+pub fn padded_area(width: u32, height: u32) -> u32 {
+    area(width + 2, height + 2)
+}
+
+pub const fn double(x: u32) -> u32 {
+    x * 2
+}
+
+pub fn quadruple(x: u32) -> u32 {
+    double(double(x))
+}
+
+pub fn workers() -> usize {
+    num_cpus::get() * 2
+}
+
+pub fn total(values: &[u32]) -> u32 {
+    let mut sum = 0;
+    for value in values {
+        sum += value;
+    }
+    sum
+}
+
+pub fn label(id: u32) -> String {
+    format!("user-{}", id)
+}
+
+pub fn scaled(x: u32) -> usize {
+    workers() * x as usize
+}
+`
+    });
+
+    const found = engine.constFnCandidates().map(candidate => candidate.name);
+    if (found.join(',') !== 'area,padded_area,quadruple') {
+      throw new Error(`Only pure arithmetic over const callees should be flagged, got: ${found.join(', ')}`);
+    }
+    const padded = engine.constFnCandidates().find(candidate => candidate.name === 'padded_area');
+    if (JSON.stringify(padded.dependsOn) !== JSON.stringify(['src/lib.rs#function:area']) || padded.confidence >= 0.5) {
+      throw new Error(`padded_area relies on area becoming const: ${JSON.stringify(padded)}`);
+    }
+    if (engine.constFnCandidates().find(candidate => candidate.name === 'quadruple').dependsOn.length !== 0) {
+      throw new Error('double is already const');
+    }
+
+    // ServerConfig::default calls num_cpus::get() and implements a trait
+    if (this.engine.constFnCandidates().some(candidate => candidate.name === 'default')) {
+      throw new Error('The fixture default() calls a non-const function');
+    }
+  }
+
+  async testRedundantClones() {
+    const engine = await this.scanProject('redundant-clones', {
+      'src/lib.rs': `
+pub struct User { pub name: String, pub tags: Vec<String> }
+
+pub fn greet(user: &User) -> String {
+    let copy = user.clone();
+    println!("{}", copy.name);
+    format!("hello {}", copy.name.len())
+}
+
+pub fn tag(user: &User) -> User {
+    let mut copy = user.clone();
+    copy.tags.push("seen".to_string());
+    copy
+}
+
+pub fn count(user: &User) -> usize {
+    measure(&user.clone())
+}
+
+pub fn keep(user: &User) -> User {
+    let owned = user.clone();
+    owned
+}
+
+fn measure(user: &User) -> usize {
+    user.tags.len()
+}
+`
+    });
+
+    const found = engine.redundantCloneCandidates().map(site => `${site.name}:${site.reason}:${site.expression}:${site.line}`);
+    if (found.join(',') !== 'greet:read_only_binding:user.clone():5,count:borrowed_clone:user.clone():17') {
+      throw new Error(`Only the read-only and borrowed clones should be flagged, got: ${found.join(', ')}`);
+    }
+    const greet = engine.redundantCloneCandidates().find(site => site.name === 'greet');
+    if (greet.binding !== 'copy' || greet.confidence >= 0.8 || greet.nodeId !== 'src/lib.rs#function:greet') {
+      throw new Error(`The read-only binding is a weaker signal than a borrowed clone: ${JSON.stringify(greet)}`);
+    }
+    const confident = engine.redundantCloneCandidates(0.7).map(site => site.name);
+    if (confident.join(',') !== 'count') {
+      throw new Error(`A higher threshold should keep only the borrowed clone: ${confident.join(', ')}`);
+    }
+  }
+
+  async testUnusedAsyncClean() {
+    const unused = this.engine.unusedAsync();
+    if (unused.length !== 0) {
+      throw new Error(`Fixture async functions are all awaited or route-registered, got: ${unused.map(u => u.name).join(', ')}`);
+    }
+  }
+
+  async testUnusedAsyncFlagged() {
+    const engine = await this.scanProject('unused-async', {
+      'src/main.rs': `
+pub async fn fetch() -> u32 {
+    1
+}
+
+async fn orphan() -> u32 {
+    2
+}
+
+async fn spawned_job() {}
+
+async fn dropped_future() {}
+
+pub async fn run() {
+    let value = fetch().await;
+    tokio::spawn(spawned_job());
+    dropped_future();
+}
+
+#[tokio::main]
+async fn main() {
+    run().await;
+}
+`
+    });
+
+    const names = engine.unusedAsync().map(item => item.name).join(',');
+    if (names !== 'orphan,dropped_future') {
+      throw new Error(`Expected the orphan and the never-awaited future, got: ${names}`);
+    }
+  }
+
+  async testConditionalImpls() {
+    const engine = await this.scanProject('conditional-impls', {
+      'src/lib.rs': `
+use std::fmt::Debug;
+
+pub trait Describe {
+    fn describe(&self) -> String;
+}
+
+pub struct Wrapper<T> {
+    inner: T,
+}
+
+impl<T: Clone> Clone for Wrapper<T> {
+    fn clone(&self) -> Self {
+        Wrapper { inner: self.inner.clone() }
+    }
+}
+
+impl<T> Describe for Wrapper<T>
+where
+    T: Debug,
+    Self: Sized,
+{
+    fn describe(&self) -> String {
+        format!("{:?}", self.inner)
+    }
+}
+
+#[derive(Clone)]
+pub struct Pair<A> {
+    first: A,
+    second: A,
+}
+
+pub struct Plain;
+
+impl Clone for Plain {
+    fn clone(&self) -> Self {
+        Plain
+    }
+}
+`
+    });
+
+    const edge = engine.storage.getEdge('src/lib.rs#struct:Wrapper->implements->src/lib.rs#trait:Describe');
+    if (!edge?.metadata?.conditional || edge.metadata.conditions.join(',') !== 'T: Debug') {
+      throw new Error(`The where clause should gate the impl, without Self bounds: ${JSON.stringify(edge?.metadata)}`);
+    }
+
+    const clone = engine.findImplementors('Clone').map(impl => `${impl.name}:${impl.via}:${impl.conditions.join('|')}`);
+    if (clone.join(',') !== 'Pair:derive:A: Clone,Plain:impl:,Wrapper:impl:T: Clone') {
+      throw new Error(`Unexpected Clone implementors: ${clone.join(', ')}`);
+    }
+    const wrapper = engine.storage.getNode('src/lib.rs#struct:Wrapper');
+    const recorded = (wrapper?.metadata.conditionalImpls || []).map(impl => `${impl.trait}<${impl.genericParams.join(',')}>:${impl.conditions.join('|')}`);
+    if (recorded.join(',') !== 'Clone<T>:T: Clone,Describe<T>:T: Debug') {
+      throw new Error(`The impls' own parameters and bounds should be kept apart from the type's: ${recorded.join(', ')}`);
+    }
+  }
+
+  async testImplementorsByModule() {
+    const fixture = this.engine.implementorsByModule('UserRepository');
+    if (fixture.length !== 1 || fixture[0].module !== 'crate' || fixture[0].implementors.map(impl => impl.name).join(',') !== 'InMemoryUserRepository') {
+      throw new Error(`InMemoryUserRepository should be grouped under the crate root: ${JSON.stringify(fixture)}`);
+    }
+
+    const engine = await this.scanProject('implementors-by-module', {
+      'src/lib.rs': `
+pub trait Store {
+    fn get(&self, key: &str) -> Option<String>;
+}
+
+pub struct Local;
+
+impl Store for Local {
+    fn get(&self, key: &str) -> Option<String> { None }
+}
+
+pub mod storage {
+    use super::Store;
+
+    pub struct Disk;
+
+    impl Store for Disk {
+        fn get(&self, key: &str) -> Option<String> { None }
+    }
+}
+
+pub mod cache {
+    use super::Store;
+
+    pub struct Memory;
+    pub struct Lru;
+
+    impl Store for Memory {
+        fn get(&self, key: &str) -> Option<String> { None }
+    }
+
+    impl Store for Lru {
+        fn get(&self, key: &str) -> Option<String> { None }
+    }
+}
+`
+    });
+    const groups = engine.implementorsByModule('Store').map(group => `${group.module}[${group.implementors.map(impl => impl.name).join(',')}]`);
+    if (groups.join(' ') !== 'crate[Local] crate::cache[Lru,Memory] crate::storage[Disk]') {
+      throw new Error(`Implementors should be grouped by module, sorted: ${groups.join(' ')}`);
+    }
+  }
+
+  async testDynDispatchSites() {
+    const sites = this.engine.dynDispatchSites().filter(site => site.filePath === 'src/main.rs');
+    const repo = sites.find(site => site.site === 'AppState.user_repo' && site.trait === 'UserRepository');
+    if (!repo || repo.role !== 'field' || repo.implementors.join(',') !== 'InMemoryUserRepository' || !repo.devirtualizable || !repo.traitId) {
+      throw new Error(`AppState.user_repo has a single implementor behind dyn: ${JSON.stringify(repo)}`);
+    }
+    const error = sites.find(site => site.site === 'AppState.user_repo' && site.trait === 'std::error::Error');
+    if (!error || error.devirtualizable) {
+      throw new Error(`The nested Box<dyn Error> should be reported without local implementors: ${JSON.stringify(error)}`);
+    }
+
+    const engine = await this.scanProject('dyn-dispatch', {
+      'src/lib.rs': `
+pub trait Shape {
+    fn area(&self) -> f64;
+}
+
+pub struct Square(f64);
+pub struct Circle(f64);
+
+impl Shape for Square {
+    fn area(&self) -> f64 { self.0 * self.0 }
+}
+
+impl Shape for Circle {
+    fn area(&self) -> f64 { 3.14 * self.0 * self.0 }
+}
+
+pub fn total(shapes: &[Box<dyn Shape + Send>]) -> f64 {
+    shapes.iter().map(|shape| shape.area()).sum()
+}
+
+pub fn generic<S: Shape>(shape: &S) -> f64 {
+    shape.area()
+}
+`
+    });
+
+    const found = engine.dynDispatchSites().map(site => `${site.site}:${site.trait}:${site.implementors.join('|')}:${site.devirtualizable}`);
+    if (found.join(',') !== 'total(shapes):Shape:Circle|Square:false') {
+      throw new Error(`Only the boxed trait object should be reported, with both implementors: ${found.join(', ')}`);
+    }
+  }
+
+  async testDispatchProfile() {
+    // AppState holds the fixture's only UserRepository, as a trait object
+    const [repo] = this.engine.dispatchProfile('UserRepository');
+    if (!repo || repo.dynamicSites !== 1 || repo.genericSites !== 0 || repo.dynamicShare !== 1 || !repo.traitId) {
+      throw new Error(`UserRepository is used once, dynamically: ${JSON.stringify(repo)}`);
+    }
+    if (repo.sites.map(site => `${site.site}:${site.kind}`).join(',') !== 'AppState.user_repo:dynamic') {
+      throw new Error(`The dynamic use is AppState.user_repo: ${JSON.stringify(repo.sites)}`);
+    }
+
+    const engine = await this.scanProject('dispatch-profile', {
+      'src/lib.rs': `
+pub trait Shape {
+    fn area(&self) -> f64;
+}
+
+pub struct Canvas {
+    pub shapes: Vec<Box<dyn Shape>>,
+}
+
+pub fn largest(shapes: &[&dyn Shape]) -> f64 {
+    shapes.iter().map(|shape| shape.area()).fold(0.0, f64::max)
+}
+
+pub fn area_of<S: Shape>(shape: &S) -> f64 {
+    shape.area()
+}
+
+pub fn scaled<S>(shape: &S, factor: f64) -> f64 where S: Shape + Clone, S: Shape {
+    shape.area() * factor
+}
+
+pub fn describe(shape: &impl Shape) -> String {
+    format!("{}", shape.area())
+}
+`
+    });
+
+    const shape = engine.dispatchProfile().find(profile => profile.trait === 'Shape');
+    if (shape?.dynamicSites !== 2 || shape.genericSites !== 3 || Math.abs(shape.dynamicShare - 0.4) > 1e-9) {
+      throw new Error(`Shape is used dynamically twice and generically three times: ${JSON.stringify(shape)}`);
+    }
+    const sites = shape.sites.map(site => `${site.site}:${site.kind}`).sort().join(',');
+    if (sites !== 'Canvas.shapes:dynamic,area_of<S>:generic,describe(shape):generic,largest(shapes):dynamic,scaled<S>:generic') {
+      throw new Error(`Each site should be counted once: ${sites}`);
+    }
+    const clone = engine.dispatchProfile('Clone')[0];
+    if (clone?.genericSites !== 1 || clone.dynamicSites !== 0 || clone.dynamicShare !== 0 || clone.traitId) {
+      throw new Error(`Clone bounds scaled's parameter only: ${JSON.stringify(clone)}`);
+    }
+  }
+
+  async testThreadSafetyClean() {
+    // AppState is shared through web::Data and holds an `Arc<dyn UserRepository + Send + Sync>`
+    const issues = this.engine.threadSafetyReport().filter(issue => issue.filePath === 'src/main.rs');
+    if (issues.length !== 0) {
+      throw new Error(`Fixture state is Send + Sync, got: ${issues.map(issue => issue.message).join('; ')}`);
+    }
+  }
+
+  async testThreadSafetyFlagged() {
+    const engine = await this.scanProject('thread-safety', {
+      'src/lib.rs': `
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+pub struct Cache {
+    entries: Rc<RefCell<Vec<String>>>,
+}
+
+pub struct Counter {
+    hits: RefCell<u64>,
+}
+
+pub struct Shared {
+    cache: Cache,
+    counter: Mutex<Counter>,
+}
+
+pub struct Job {
+    shared: Arc<Shared>,
+}
+
+pub struct Handle {
+    raw: *mut u8,
+}
+
+unsafe impl Send for Handle {}
+unsafe impl Sync for Handle {}
+
+pub struct Local {
+    names: Rc<Vec<String>>,
+}
+
+pub fn share(shared: Arc<Shared>, handle: Arc<Handle>) -> usize {
+    0
+}
+
+pub fn run(job: Job) {
+    std::thread::spawn(move || {
+        let _ = job;
+    });
+}
+
+pub fn local_only(local: Local) -> usize {
+    local.names.len()
+}
+`
+    });
+
+    const issues = engine.threadSafetyReport();
+    const found = issues.map(issue => `${issue.typeName}.${issue.field}:${issue.missing.join('+')}:${issue.sharedVia.join('+')}`);
+    if (found.join(',') !== 'Shared.cache:Send+Sync:arc,Job.shared:Send:spawn') {
+      throw new Error(`The Rc behind Shared should be flagged where it is shared: ${found.join(', ')}`);
+    }
+    if (issues[0].culprit !== 'Rc<RefCell<Vec<String>>>' || !issues[0].message.includes('held in an Arc')) {
+      throw new Error(`The culprit should be the Rc itself: ${JSON.stringify(issues[0])}`);
+    }
+  }
+
+  async testErrorStyleReport() {
+    // Handlers return actix's `Result<HttpResponse>`; the repository and main return `Box<dyn Error>`
+    const [report] = this.engine.errorStyleReport('crate').filter(group => group.functions.some(func => func.filePath === 'src/main.rs'));
+    if (!report || !report.styles.includes('result') || !report.styles.includes('boxed_error')) {
+      throw new Error(`Crate root should list Result and Box<dyn Error> styles, got: ${report ? report.styles.join(', ') : 'no report'}`);
+    }
+    const styleOf = name => report.functions.find(func => func.name === name)?.styles || [];
+    if (!styleOf('get_user_handler').includes('result') || !styleOf('initialize').includes('boxed_error')) {
+      throw new Error(`Unexpected function styles: ${report.message}`);
+    }
+    if (!report.mixed) {
+      throw new Error('Mixing Result<HttpResponse> with Box<dyn Error> should be flagged');
+    }
+    if (report.functions.some(func => func.name.startsWith('test_'))) {
+      throw new Error('Test functions should not be classified');
+    }
+  }
+
+  async testUnnecessaryOwnershipClean() {
+    // create_user moves request.name and request.email into the User it builds
+    const flagged = this.engine.unnecessaryOwnership();
+    if (flagged.some(finding => finding.name === 'create_user')) {
+      throw new Error(`create_user consumes its request: ${flagged.map(finding => finding.message).join('; ')}`);
+    }
+  }
+
+  async testUnnecessaryOwnershipFlagged() {
+    const engine = await this.scanProject('ownership', {
+      'src/lib.rs': `
+pub struct Request {
+    pub name: String,
+}
+
+pub struct Record {
+    pub name: String,
+}
+
+#[derive(Clone, Copy)]
+pub struct Point {
+    pub x: i32,
+}
+
+pub fn store(request: Request) -> Record {
+    Record { name: request.name }
+}
+
+pub fn name_length(name: String) -> usize {
+    name.len()
+}
+
+pub fn total(values: Vec<u32>, point: Point) -> u32 {
+    println!("{}", point.x);
+    values.iter().sum()
+}
+
+pub fn consume(values: Vec<u32>) -> Vec<u32> {
+    values.into_iter().rev().collect()
+}
+`
+    });
+
+    const flagged = engine.unnecessaryOwnership();
+    const summary = flagged.map(finding => `${finding.name}:${finding.parameter}:${finding.suggestion}`).join(', ');
+    if (summary !== 'name_length:name:&str, total:values:&[u32]') {
+      throw new Error(`Expected the read-only String and Vec parameters, got: ${summary}`);
+    }
+  }
+
+  async testBoxedErrorUsage() {
+    const sites = this.engine.boxedErrorUsage();
+    const binding = sites.find(site => site.kind === 'associated_type');
+    if (!binding || !binding.name.includes('InMemoryUserRepository') || binding.typeText !== 'Box<dyn std::error::Error>') {
+      throw new Error(`InMemoryUserRepository's Error binding should be flagged: ${JSON.stringify(sites.map(site => site.name))}`);
+    }
+    const bindingLine = readFileSync(FIXTURE_PATH, 'utf-8').split('\n')
+      .findIndex(line => line.includes('type Error = Box<dyn std::error::Error>;')) + 1;
+    if (binding.line !== bindingLine) {
+      throw new Error(`Binding should point at its own line, got ${binding.line}`);
+    }
+
+    const byName = Object.fromEntries(sites.map(site => [site.name, site.kind]));
+    if (byName.initialize !== 'return' || byName.create_user_handler !== 'result_alias') {
+      throw new Error(`Public functions returning wide errors should be flagged: ${JSON.stringify(byName)}`);
+    }
+    if ('main' in byName) {
+      throw new Error('Private functions are not public API');
+    }
+
+    const allowed = this.engine.boxedErrorUsage({ allow: ['src/main.rs'] });
+    if (allowed.length !== 0) {
+      throw new Error(`Allowlisted files should be skipped, got: ${allowed.map(site => site.name).join(', ')}`);
+    }
+  }
+
+  async testApiReachabilityFixture() {
+    const { reachable, unreachable } = this.engine.internalReachableFromApi();
+    const initialize = this.engine.storage.findNodes(n => n.name === 'initialize' && n.type === 'function')[0];
+    if (!initialize || !reachable.includes(initialize.id)) {
+      throw new Error('initialize is reachable from main');
+    }
+    if (unreachable.length !== 0) {
+      throw new Error(`Every internal fixture item is reachable, got: ${unreachable.map(item => item.name).join(', ')}`);
+    }
+  }
+
+  async testApiReachabilityFlagged() {
+    const engine = await this.scanProject('api-reachability', {
+      'src/lib.rs': `
+pub struct Parser {
+    input: String,
+}
+
+impl Parser {
+    pub fn parse(&self) -> usize {
+        self.tokenize().len()
+    }
+
+    fn tokenize(&self) -> Vec<String> {
+        self.input.split(' ').map(|s| s.to_string()).collect()
+    }
+
+    fn legacy_tokenize(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+fn unused_helper() -> u32 {
+    42
+}
+`
+    });
+
+    const { reachable, unreachable } = engine.internalReachableFromApi();
+    const names = unreachable.map(item => item.name);
+    if (!reachable.some(id => id.endsWith('#function:tokenize')) || names.includes('tokenize')) {
+      throw new Error('tokenize is called from the public parse method');
+    }
+    if (JSON.stringify(names) !== JSON.stringify(['legacy_tokenize', 'unused_helper'])) {
+      throw new Error(`Expected legacy_tokenize and unused_helper, got: ${names.join(', ')}`);
+    }
+  }
+
+  async testLongParameterListsClean() {
+    const flagged = this.engine.longParameterLists();
+    if (flagged.length !== 0) {
+      throw new Error(`The fixture's functions are small, got: ${flagged.map(item => item.name).join(', ')}`);
+    }
+  }
+
+  async testLongParameterListsFlagged() {
+    const engine = await this.scanProject('long-parameters', {
+      'src/lib.rs': `
+pub struct Canvas {
+    pub width: u32,
+}
+
+impl Canvas {
+    pub fn draw(&mut self, x: u32, y: u32, w: u32, h: u32, color: u32) {
+        self.width = x + y + w + h + color;
+    }
+}
+
+pub fn render(a: u8, b: u8, c: u8, d: u8, e: u8, f: u8, g: u8) -> u8 {
+    a + b + c + d + e + f + g
+}
+`
+    });
+
+    const counts = list => list.map(item => `${item.name}:${item.parameterCount}`).join(', ');
+    const all = engine.longParameterLists();
+    if (counts(all) !== 'render:7, draw:6') {
+      throw new Error(`Expected render and draw (counting self), got: ${counts(all)}`);
+    }
+    const withoutSelf = engine.longParameterLists(5, { excludeSelf: true });
+    if (counts(withoutSelf) !== 'render:7') {
+      throw new Error(`draw has five parameters besides self, got: ${counts(withoutSelf)}`);
+    }
+  }
+
+  async testVisibilitySmellsClean() {
+    // User's fields are public and InMemoryUserRepository has `pub fn new`
+    const smells = this.engine.visibilitySmells();
+    if (smells.length !== 0) {
+      throw new Error(`The fixture has no visibility smells, got: ${smells.map(smell => smell.message).join('; ')}`);
+    }
+  }
+
+  async testVisibilitySmellsFlagged() {
+    const engine = await this.scanProject('visibility-smells', {
+      'src/lib.rs': `
+pub struct Token {
+    secret: String,
+}
+
+impl Token {
+    pub fn secret(&self) -> &str {
+        &self.secret
+    }
+}
+
+pub struct Handle {
+    id: u32,
+}
+
+impl Handle {
+    pub fn new(id: u32) -> Self {
+        Handle { id }
+    }
+}
+
+pub(crate) struct Internal {
+    pub value: u32,
+}
+
+pub fn internal() -> Internal {
+    Internal { value: 1 }
+}
+`
+    });
+
+    const smells = engine.visibilitySmells().map(smell => `${smell.kind}:${smell.name}`);
+    const expected = ['unconstructable_struct:Token', 'private_return_type:internal'];
+    if (JSON.stringify(smells) !== JSON.stringify(expected)) {
+      throw new Error(`Expected ${expected.join(', ')}, got: ${smells.join(', ')}`);
+    }
+  }
+
+  async testUnusedFieldsClean() {
+    // User and CreateUserRequest derive Debug/Serialize; the repository reads both of its fields
+    const unused = this.engine.unusedFields();
+    if (unused.length !== 0) {
+      throw new Error(`The fixture has no unused fields, got: ${unused.map(item => `${item.struct}.${item.field}`).join(', ')}`);
+    }
+  }
+
+  async testUnusedFieldsFlagged() {
+    const engine = await this.scanProject('unused-fields', {
+      'src/lib.rs': `
+pub struct Cache {
+    entries: Vec<u32>,
+    hits: u64,
+    stale_marker: bool,
+    _reserved: u8,
+    pub label: String,
+}
+
+impl Cache {
+    pub fn new(label: String) -> Self {
+        Cache { entries: Vec::new(), hits: 0, stale_marker: false, _reserved: 0, label }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+fn norm(point: &Point) -> i32 {
+    let Point { x, .. } = point;
+    x.abs()
+}
+
+#[derive(Debug)]
+struct Logged {
+    never_read: u32,
+}
+`
+    });
+
+    const unused = engine.unusedFields().map(item => `${item.struct}.${item.field}`);
+    const expected = ['Point.y'];
+    if (JSON.stringify(unused) !== JSON.stringify(expected)) {
+      throw new Error(`Expected ${expected.join(', ')}, got: ${unused.join(', ')}`);
+    }
+  }
+
+  async testRequiredTraits() {
+    const engine = await this.scanProject('required-traits', {
+      'src/lib.rs': `
+use serde::Serialize;
+
+pub trait Entity: Clone + std::fmt::Debug {}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct User {
+    pub id: u64,
+}
+
+#[derive(Clone)]
+pub struct Draft;
+
+pub fn f<T: Serialize + Clone>() {}
+
+pub fn store<E>(entity: E) where E: Entity + Ord {}
+`
+    });
+
+    const user = 'src/lib.rs#struct:User';
+    const direct = engine.requiredTraitsFor(user, 'src/lib.rs#function:f');
+    if (direct?.parameter !== 'T' || direct.required.map(entry => entry.trait).join(',') !== 'Clone,Serialize' || direct.missing.length !== 0) {
+      throw new Error(`f needs Serialize and Clone, both of which User has: ${JSON.stringify(direct)}`);
+    }
+    const draft = engine.requiredTraitsFor('src/lib.rs#struct:Draft', 'src/lib.rs#function:f');
+    if (draft?.missing.join(',') !== 'Serialize') {
+      throw new Error(`Draft lacks Serialize: ${JSON.stringify(draft)}`);
+    }
+
+    // Supertraits of the project's traits and of the standard library's are required too
+    const transitive = engine.requiredTraitsFor(user, 'src/lib.rs#function:store', 'entity');
+    const required = transitive?.required.map(entry => `${entry.trait}<${entry.requiredBy}`).join(',');
+    if (required !== 'Clone<Entity,Debug<Entity,Entity<Entity,Eq<Ord,Ord<Ord,PartialEq<Ord,PartialOrd<Ord') {
+      throw new Error(`Expected the bounds with their supertraits: ${required}`);
+    }
+    if (transitive.missing.join(',') !== 'Entity,Eq,Ord,PartialEq,PartialOrd') {
+      throw new Error(`User implements only Clone and Debug of these: ${transitive.missing.join(',')}`);
+    }
+  }
+
+  async testDipCandidatesClean() {
+    // AppState holds the repository as `dyn UserRepository`; main only constructs the concrete type
+    const candidates = this.engine.dipCandidates().filter(candidate => candidate.filePath === 'src/main.rs');
+    if (candidates.length !== 0) {
+      throw new Error(`The fixture depends on UserRepository, not its implementation: ${candidates.map(candidate => candidate.message).join('; ')}`);
+    }
+  }
+
+  async testDipCandidatesFlagged() {
+    const engine = await this.scanProject('dip-candidates', {
+      'src/lib.rs': `
+pub trait UserRepository {
+    fn find(&self, id: u64) -> Option<String>;
+}
+
+pub struct InMemoryUserRepository {
+    users: Vec<String>,
+}
+
+impl InMemoryUserRepository {
+    pub fn new() -> Self {
+        Self { users: Vec::new() }
+    }
+
+    pub fn merge(&mut self, other: &InMemoryUserRepository) {}
+}
+
+impl UserRepository for InMemoryUserRepository {
+    fn find(&self, id: u64) -> Option<String> {
+        self.users.get(id as usize).cloned()
+    }
+}
+
+pub struct Handlers {
+    pub repo: std::sync::Arc<InMemoryUserRepository>,
+}
+
+pub struct Abstracted {
+    pub repo: Box<dyn UserRepository>,
+}
+
+pub fn get_user_handler(repo: &InMemoryUserRepository, id: u64) -> Option<String> {
+    repo.find(id)
+}
+
+pub fn get_user_generic<R: UserRepository>(repo: &R, id: u64) -> Option<String> {
+    repo.find(id)
+}
+
+pub fn get_user_dyn(repo: &dyn UserRepository, id: u64) -> Option<String> {
+    repo.find(id)
+}
+
+pub fn build() -> Abstracted {
+    Abstracted { repo: Box::new(InMemoryUserRepository::new()) }
+}
+`
+    });
+
+    const found = engine.dipCandidates().map(candidate => `${candidate.site}:${candidate.role}:${candidate.concreteType}:${candidate.traits.join('|')}`).sort();
+    const expected = ['Handlers.repo:field:InMemoryUserRepository:UserRepository', 'get_user_handler(repo):parameter:InMemoryUserRepository:UserRepository'];
+    if (JSON.stringify(found) !== JSON.stringify(expected)) {
+      throw new Error(`Only the concrete field and parameter should be flagged, got: ${found.join(', ')}`);
+    }
+    const handler = engine.dipCandidates().find(candidate => candidate.nodeId === 'src/lib.rs#function:get_user_handler');
+    if (handler?.concreteTypeId !== 'src/lib.rs#struct:InMemoryUserRepository' || !handler.message.includes('UserRepository instead')) {
+      throw new Error(`The candidate should point at the type and suggest the trait: ${JSON.stringify(handler)}`);
+    }
+  }
+
+  async testRedundantLifetimesClean() {
+    const redundant = this.engine.redundantLifetimes();
+    if (redundant.length !== 0) {
+      throw new Error(`The fixture declares no lifetimes, got: ${redundant.map(item => item.message).join('; ')}`);
+    }
+  }
+
+  async testRedundantLifetimesFlagged() {
+    const engine = await this.scanProject('redundant-lifetimes', {
+      'src/lib.rs': `
+pub fn f<'a>(x: &'a str) -> &'a str {
+    x
+}
+
+pub fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {
+    if x.len() > y.len() { x } else { y }
+}
+
+pub fn first<'a>(x: &'a str, y: &str) -> &'a str {
+    let _ = y;
+    x
+}
+
+pub struct Holder;
+
+impl Holder {
+    pub fn get<'a>(&self, x: &'a str) -> &'a str {
+        x
+    }
+}
+`
+    });
+
+    const redundant = engine.redundantLifetimes();
+    if (redundant.map(item => `${item.function}:${item.lifetime}`).join(', ') !== "f:'a") {
+      throw new Error(`Only f has removable lifetimes: ${redundant.map(item => item.message).join('; ')}`);
+    }
+    if (!redundant[0].elided.includes('fn f(x: &str) -> &str')) {
+      throw new Error(`The elided signature should drop every 'a: ${redundant[0].elided}`);
+    }
+  }
+
+  async testRedundantBoundsClean() {
+    const redundant = this.engine.redundantBounds();
+    if (redundant.length !== 0) {
+      throw new Error(`The fixture has no generic bounds, got: ${redundant.map(item => `${item.function}: ${item.bound}`).join(', ')}`);
+    }
+  }
+
+  async testRedundantBoundsFlagged() {
+    const engine = await this.scanProject('redundant-bounds', {
+      'src/lib.rs': `
+pub trait Shape: Clone {
+    fn area(&self) -> f64;
+}
+
+pub trait Solid: Shape {}
+
+pub fn f<T: Ord + PartialOrd>(items: &[T]) {}
+
+pub fn twice<T: Clone>(value: T) where T: Clone {}
+
+pub fn solid<S: Solid + Shape + Clone>(shape: S) {}
+
+pub fn unrelated<T: Ord + std::fmt::Debug, U>(value: T, other: U) where U: Copy {}
+`
+    });
+
+    const redundant = engine.redundantBounds().map(item => `${item.function}:${item.bound}:${item.reason}:${item.impliedBy || item.source}`);
+    const expected = [
+      'f:PartialOrd:implied:Ord',
+      'twice:Clone:duplicate:where',
+      'solid:Clone:implied:Solid',
+      'solid:Shape:implied:Solid'
+    ];
+    if (JSON.stringify(redundant) !== JSON.stringify(expected)) {
+      throw new Error(`Expected ${expected.join(', ')}, got: ${redundant.join(', ')}`);
+    }
+  }
+
+  async testErrorVariantsFixture() {
+    // The fixture's fallible functions return `Box<dyn Error>`, which has no variants to link
+    const usages = this.engine.errorVariants('main');
+    if (usages.length !== 0) {
+      throw new Error(`Boxed errors have no variants, got: ${JSON.stringify(usages)}`);
+    }
+  }
+
+  async testErrorVariantsProduced() {
+    const engine = await this.scanProject('error-variants', {
+      'src/lib.rs': `
+pub enum MyError {
+    A,
+    B(String),
+    C { code: u32 },
+}
+
+pub fn parse(input: &str) -> Result<u32, MyError> {
+    if input.is_empty() {
+        return Err(MyError::A);
+    }
+    input.parse().map_err(|_| ()).or(Err(MyError::B(input.to_string())))
+}
+
+pub fn infallible() -> Result<u32, MyError> {
+    Ok(1)
+}
+`
+    });
+
+    const [usage] = engine.errorVariants('parse');
+    if (!usage || usage.errorType !== 'MyError') {
+      throw new Error(`parse should report its MyError variants, got: ${JSON.stringify(usage)}`);
+    }
+    if (usage.produced.join(',') !== 'A,B' || usage.unproduced.join(',') !== 'C') {
+      throw new Error(`Expected A,B produced and C unproduced, got: ${usage.produced.join(',')} / ${usage.unproduced.join(',')}`);
+    }
+
+    const linked = engine.storage.findEdges(edge => edge.source === usage.nodeId && edge.metadata?.relationship === 'error_variant')
+      .map(edge => engine.storage.getNode(edge.target).name)
+      .sort();
+    if (linked.join(',') !== 'A,B') {
+      throw new Error(`parse should link to exactly A and B, got: ${linked.join(',')}`);
+    }
+
+    const [none] = engine.errorVariants('infallible');
+    if (!none || none.produced.length !== 0 || none.unproduced.join(',') !== 'A,B,C') {
+      throw new Error(`A function never returning Err produces no variants, got: ${JSON.stringify(none)}`);
+    }
+  }
+
+  async testUntestedPublicApi() {
+    const untested = this.engine.untestedPublicApi().map(item => item.name);
+    for (const handler of ['create_user_handler', 'get_user_handler', 'list_users_handler']) {
+      if (!untested.includes(handler)) {
+        throw new Error(`${handler} has no tests and should be reported, got: ${untested.join(', ')}`);
+      }
+    }
+    if (untested.includes('create_user')) {
+      throw new Error('create_user is exercised by test_user_creation');
+    }
+    if (untested.some(name => name.startsWith('test_'))) {
+      throw new Error(`Tests are not public API: ${untested.join(', ')}`);
+    }
+  }
+
+  async testUntestedPublicApiDocAndIntegrationTests() {
+    const engine = await this.scanProject('api-coverage', {
+      'src/lib.rs': `
+/// Adds one.
+///
+/// \`\`\`
+/// assert_eq!(increment(1), 2);
+/// \`\`\`
+pub fn increment(x: u32) -> u32 {
+    x + 1
+}
+
+pub fn decrement(x: u32) -> u32 {
+    x - 1
+}
+
+pub fn untested(x: u32) -> u32 {
+    x
+}
+
+fn private_helper() {}
+`,
+      'tests/api.rs': `
+#[test]
+fn decrements() {
+    assert_eq!(api_coverage::decrement(2), 1);
+}
+`
+    });
+
+    const untested = engine.untestedPublicApi().map(item => item.name);
+    if (JSON.stringify(untested) !== JSON.stringify(['untested'])) {
+      throw new Error(`Doc-tests and integration tests should count as coverage, got: ${untested.join(', ')}`);
+    }
+  }
+
+  async testGenericBloatCandidates() {
+    const fixture = this.engine.genericBloatCandidates();
+    if (fixture.length !== 0) {
+      throw new Error(`The fixture has no generic functions, got: ${fixture.map(c => c.name).join(', ')}`);
+    }
+
+    const engine = await this.scanProject('generic-bloat', {
+      'src/lib.rs': `
+pub struct Alpha {
+    pub v: u32,
+}
+
+pub struct Beta {
+    pub v: u32,
+}
+
+impl Beta {
+    pub fn new() -> Self {
+        Beta { v: 0 }
+    }
+}
+
+pub fn encode<T: Clone>(value: &T) -> usize {
+    let copy = value.clone();
+    let size = std::mem::size_of_val(&copy);
+    size * 2
+}
+
+pub fn identity<T>(value: T) -> T {
+    value
+}
+
+pub fn run() {
+    encode(&Alpha { v: 1 });
+    encode(&Beta::new());
+    let again = Alpha { v: 2 };
+    encode(&again);
+    encode::<Beta>(&Beta::new());
+    encode(&7u8);
+    identity(1.5);
+}
+`
+    });
+
+    const candidates = engine.genericBloatCandidates();
+    const encode = candidates[0];
+    if (encode?.name !== 'encode' || encode.instantiations !== 3) {
+      throw new Error(`encode should rank first with three instantiations: ${JSON.stringify(candidates)}`);
+    }
+    if (encode.instantiationTypes.join(',') !== 'T=Alpha,T=Beta,T=u8' || encode.score !== 3 * encode.sizeWeight) {
+      throw new Error(`Unexpected instantiations or score: ${JSON.stringify(encode)}`);
+    }
+    const identity = candidates.find(c => c.name === 'identity');
+    if (!identity || identity.instantiations !== 1 || identity.instantiationTypes[0] !== 'T=f64') {
+      throw new Error(`identity is instantiated once with f64: ${JSON.stringify(identity)}`);
+    }
+  }
+
+  async testResolvePathFuzzy() {
+    const exact = this.engine.resolvePathFuzzy('crate::config::load_config');
+    const loadConfig = this.engine.nodeByPath('config::load_config');
+    if (!exact.exact || !loadConfig || exact.nodeId !== loadConfig.id || exact.suggestions.length !== 0) {
+      throw new Error(`Exact paths should resolve directly: ${JSON.stringify(exact)}`);
+    }
+
+    const typo = this.engine.resolvePathFuzzy('crate::config::load_confg');
+    if (typo.exact || typo.suggestions[0]?.path !== 'crate::config::load_config' || typo.suggestions[0].distance !== 1) {
+      throw new Error(`A one-character typo should suggest load_config: ${JSON.stringify(typo)}`);
+    }
+    if (typo.message !== 'did you mean `crate::config::load_config`?') {
+      throw new Error(`Unexpected suggestion message: ${typo.message}`);
+    }
+
+    const strict = this.engine.resolvePathFuzzy('crate::config::load_conf', { maxDistance: 1 });
+    if (strict.suggestions.length !== 0 || strict.message !== undefined) {
+      throw new Error(`Suggestions beyond the threshold should be dropped: ${JSON.stringify(strict.suggestions)}`);
+    }
+  }
+
+  async testPublicPaths() {
+    const engine = await this.scanProject('reexports', {
+      'src/lib.rs': `
+pub mod config {
+    pub struct ServerConfig {
+        pub port: u16,
+    }
+
+    pub mod defaults {
+        pub fn port() -> u16 {
+            8080
+        }
+    }
+
+    fn validate() {}
+}
+
+pub mod prelude {
+    pub use crate::config::defaults::*;
+    pub use super::Config;
+}
+
+pub use config::ServerConfig as Config;
+pub(crate) use config::defaults;
+`
+    });
+
+    const paths = engine.publicPaths('crate::config::ServerConfig');
+    if (paths.join(',') !== 'crate::Config,crate::config::ServerConfig,crate::prelude::Config') {
+      throw new Error(`ServerConfig is re-exported as Config, then again by the prelude: ${paths.join(', ')}`);
+    }
+    const port = engine.publicPaths('config::defaults::port');
+    if (port.join(',') !== 'crate::config::defaults::port,crate::prelude::port') {
+      throw new Error(`A glob re-export names port in the prelude, pub(crate) adds nothing: ${port.join(', ')}`);
+    }
+    if (engine.publicPaths('crate::config::validate').length !== 0) {
+      throw new Error('A private function has no public paths');
+    }
+
+    for (const path of ['crate::Config', 'prelude::Config', 'crate::config::ServerConfig']) {
+      if (engine.canonicalPath(path) !== 'crate::config::ServerConfig') {
+        throw new Error(`${path} should lead back to the definition, got ${engine.canonicalPath(path)}`);
+      }
+    }
+    if (engine.canonicalPath('crate::prelude::port') !== 'crate::config::defaults::port' || engine.canonicalPath('crate::Missing') !== undefined) {
+      throw new Error(`Unexpected canonical paths: ${engine.canonicalPath('crate::prelude::port')}, ${engine.canonicalPath('crate::Missing')}`);
+    }
+  }
+
+  async testMissingImplsClean() {
+    const missing = this.engine.missingImpls();
+    if (missing.length !== 0) {
+      throw new Error(`Fixture bounds are all satisfied, got: ${missing.map(m => m.message).join('; ')}`);
+    }
+  }
+
+  async testMissingImplsFlagged() {
+    const engine = await this.scanProject('missing-impls', {
+      'src/main.rs': `
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Serialize, Clone)]
+pub struct Report {
+    pub title: String,
+}
+
+impl Report {
+    pub fn new() -> Self {
+        Report { title: String::new() }
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.title)
+    }
+}
+
+#[derive(Clone)]
+pub struct Draft {
+    pub body: String,
+}
+
+pub fn save<T: Serialize>(value: &T) {}
+
+pub fn publish<T>(item: T) where T: Serialize + Clone + Send {}
+
+pub fn show(value: impl fmt::Display) {}
+
+pub fn run() {
+    let report = Report::new();
+    save(&report);
+    show(Report::new());
+    publish(Report { title: String::new() });
+
+    let draft = Draft { body: String::new() };
+    publish(draft);
+}
+`
+    });
+
+    const missing = engine.missingImpls();
+    const found = missing.map(m => `${m.typeName}:${m.traitName}`).join(',');
+    if (found !== 'Draft:Serialize') {
+      throw new Error(`Expected only Draft to miss Serialize, got: ${found}`);
+    }
+    if (!missing[0].calleeId.endsWith('#function:publish') || missing[0].argumentIndex !== 0) {
+      throw new Error(`Violation should point at the publish call: ${JSON.stringify(missing[0])}`);
+    }
+  }
+
+  async testNamingConventionsClean() {
+    const violations = this.engine.namingViolations();
+    if (violations.length !== 0) {
+      throw new Error(`Fixture follows Rust naming conventions, got: ${violations.map(v => `${v.kind} ${v.name}`).join(', ')}`);
+    }
+  }
+
+  async testNamingConventionsFlagged() {
+    const engine = await this.scanProject('naming', {
+      'src/lib.rs': `
+pub struct user_thing {
+    UserName: String,
+}
+
+pub fn CreateUser() {}
+
+const max_size: usize = 10;
+`
+    });
+
+    const found = engine.namingViolations().map(v => `${v.kind}:${v.name}->${v.suggestion}`).sort().join(',');
+    const expected = ['const:max_size->MAX_SIZE', 'field:UserName->user_name', 'function:CreateUser->create_user', 'struct:user_thing->UserThing'].join(',');
+    if (found !== expected) {
+      throw new Error(`Unexpected violations: ${found}`);
+    }
+
+    // Rules are per kind and can be relaxed or changed
+    const relaxed = engine.namingViolations({ function: null, field: 'PascalCase' }).map(v => `${v.kind}:${v.name}`).sort().join(',');
+    if (relaxed !== 'const:max_size,struct:user_thing') {
+      throw new Error(`Overridden rules not applied: ${relaxed}`);
+    }
+  }
+
+  async testTypeHeatmap() {
+    const usage = (heatmap, name) => {
+      const entry = heatmap.find(u => u.typeName === name);
+      if (!entry) {
+        throw new Error(`${name} missing from heatmap`);
+      }
+      return entry;
+    };
+
+    // String is the type of fields in User (2), CreateUserRequest (2) and ServerConfig (1)
+    const heatmap = this.engine.typeHeatmap();
+    const string = usage(heatmap, 'String');
+    if (string.count !== 3 || string.byRole.field !== 3 || string.nodeId !== undefined) {
+      throw new Error(`String should be used by 3 structs, got: ${JSON.stringify(string)}`);
+    }
+    if (usage(this.engine.typeHeatmap({ dedupe: 'reference' }), 'String').count !== 5) {
+      throw new Error('Counting every reference should see all 5 String fields');
+    }
+
+    // CreateUserRequest is a parameter of create_user and create_user_handler
+    const request = usage(heatmap, 'CreateUserRequest');
+    if (request.count !== 2 || request.byRole.parameter !== 2 || request.nodeId !== 'src/main.rs#struct:CreateUserRequest') {
+      throw new Error(`Unexpected CreateUserRequest usage: ${JSON.stringify(request)}`);
+    }
+    if (heatmap[0].heat !== 1 || string.heat !== string.count / heatmap[0].count) {
+      throw new Error('Heat should be relative to the most used type');
+    }
+  }
+
+  async testModuleMetrics() {
+    // config holds ServerConfig, default and load_config. Inside: load_config -> ServerConfig (return type)
+    // and load_config -> default (ServerConfig::default); across the boundary: main -> load_config
+    const metrics = this.engine.moduleMetrics('config');
+    const counts = `${metrics.itemCount}/${metrics.internalEdges}/${metrics.externalEdges}`;
+    if (counts !== '3/2/1' || metrics.incomingEdges !== 1 || metrics.outgoingEdges !== 0) {
+      throw new Error(`Expected 3 items, 2 internal and 1 incoming edge, got: ${JSON.stringify(metrics)}`);
+    }
+    if (Math.abs(metrics.cohesion - 2 / 3) > 1e-9 || Math.abs(metrics.coupling - 1 / 3) > 1e-9) {
+      throw new Error(`Expected cohesion 2/3 and coupling 1/3, got ${metrics.cohesion} and ${metrics.coupling}`);
+    }
+    if (metrics.refactorCandidate) {
+      throw new Error('config is cohesive and should not be a refactor candidate');
+    }
+
+    const all = this.engine.allModuleMetrics();
+    if (!all.some(entry => entry.module === 'config' && entry.internalEdges === 2)) {
+      throw new Error(`config missing from module listing: ${all.map(entry => entry.module).join(', ')}`);
+    }
+  }
+
+  async testModuleSplit() {
+    const config = this.engine.suggestModuleSplit('config');
+    if (config.clusters.length !== 1 || config.clusters[0].members.length !== 3 || config.crossEdges !== 0) {
+      throw new Error(`config is one cohesive group and should be returned whole: ${JSON.stringify(config.clusters)}`);
+    }
+
+    const engine = await this.scanProject('module-split', {
+      'src/lib.rs': `
+pub mod big {
+    pub fn parse_header(text: &str) -> usize {
+        tokenize(text).len()
+    }
+
+    pub fn parse_body(text: &str) -> usize {
+        tokenize(text).len() + parse_header(text)
+    }
+
+    fn tokenize(text: &str) -> Vec<&str> {
+        text.split(' ').collect()
+    }
+
+    pub fn render_page() -> String {
+        render_footer() + &render_header()
+    }
+
+    fn render_header() -> String {
+        String::from("<h1>")
+    }
+
+    fn render_footer() -> String {
+        render_header()
+    }
+}
+`
+    });
+
+    const split = engine.suggestModuleSplit('crate::big');
+    const groups = split.clusters.map(cluster => cluster.names.join('|'));
+    if (split.module !== 'big' || groups.join(',') !== 'parse_body|parse_header|tokenize,render_footer|render_header|render_page') {
+      throw new Error(`Expected the parsing and rendering functions as two groups, got: ${groups.join(', ')}`);
+    }
+    if (split.crossEdges !== 0 || split.clusters.some(cluster => cluster.internalEdges !== 3)) {
+      throw new Error(`Each group has three internal calls and none cross: ${JSON.stringify(split)}`);
+    }
+    if (JSON.stringify(engine.suggestModuleSplit('big')) !== JSON.stringify(split)) {
+      throw new Error('The split should be deterministic');
+    }
+  }
+
+  async testMisplacedItems() {
+    const engine = await this.scanProject('misplaced-items', {
+      'src/lib.rs': `
+pub mod a {
+    pub fn helper(x: u32) -> u32 {
+        x + 1
+    }
+
+    pub fn own() -> u32 {
+        other()
+    }
+
+    fn other() -> u32 {
+        1
+    }
+}
+
+pub mod b {
+    use crate::a::helper;
+
+    pub fn first() -> u32 {
+        helper(1)
+    }
+
+    pub fn second() -> u32 {
+        helper(2)
+    }
+}
+`
+    });
+
+    const misplaced = engine.misplacedItems();
+    const helper = misplaced.find(item => item.name === 'helper');
+    if (!helper || helper.module !== 'a' || helper.suggestedModule !== 'b' || helper.share < 0.6) {
+      throw new Error(`helper is only used by b and should be suggested to move there: ${JSON.stringify(misplaced)}`);
+    }
+    if (misplaced.some(item => ['own', 'other', 'first', 'second'].includes(item.name))) {
+      throw new Error(`Items used within their own module should stay: ${misplaced.map(item => item.message).join('; ')}`);
+    }
+    if (engine.misplacedItems({ threshold: 1.01 }).length !== 0) {
+      throw new Error('No item can clear a threshold above 1');
+    }
+  }
+
+  async testUndocumentedPublicApi() {
+    const names = this.engine.undocumentedPublicApi().map(item => item.name);
+    for (const expected of ['User', 'UserRepository', 'load_config']) {
+      if (!names.includes(expected)) {
+        throw new Error(`${expected} has no docs and should be listed, got: ${names.join(', ')}`);
+      }
+    }
+
+    const documented = readFileSync(FIXTURE_PATH, 'utf-8')
+      .replace('#[derive(Debug, Clone, Serialize, Deserialize)]\npub struct User {', '/// A registered user\n#[derive(Debug, Clone, Serialize, Deserialize)]\npub struct User {')
+      .replace('pub trait UserRepository {', '/** Storage for users */\npub trait UserRepository {') +
+      '\npub(crate) fn internal_helper() {}\n';
+    const engine = await this.scanProject('documented', { 'src/main.rs': documented });
+
+    const items = engine.undocumentedPublicApi();
+    const remaining = items.map(item => item.name);
+    if (remaining.includes('User') || remaining.includes('UserRepository')) {
+      throw new Error(`Documented items should not be listed, got: ${remaining.join(', ')}`);
+    }
+    if (!remaining.includes('CreateUserRequest') || !remaining.includes('internal_helper')) {
+      throw new Error(`Undocumented items should still be listed, got: ${remaining.join(', ')}`);
+    }
+    if (engine.undocumentedPublicApi('pub').some(item => item.name === 'internal_helper')) {
+      throw new Error('pub(crate) items should be excluded at minimum visibility pub');
+    }
+
+    const user = engine.storage.findNodes(node => node.name === 'User' && node.type === 'class')[0];
+    if (user.metadata.docComment !== 'A registered user') {
+      throw new Error(`Unexpected doc comment: ${JSON.stringify(user.metadata.docComment)}`);
+    }
+  }
+
+  async testTypeParity() {
+    const engine = await this.scanProject('type-parity', {
+      'src/main.rs': readFileSync(FIXTURE_PATH, 'utf-8'),
+      'src/events.rs': `
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEvent {
+    pub user_id: u64,
+    pub action_name: String,
+    pub note: Option<String>,
+    #[serde(skip)]
+    pub internal_flag: bool,
+}
+`,
+      'web/types.ts': `
+export interface User {
+  id: number;
+  name: string;
+  email: string;
+}
+
+export interface CreateUserRequest {
+  name: string;
+}
+
+export interface AuditEvent {
+  userId: number;
+  actionName: string;
+  note?: string | null;
+}
+`
+    });
+
+    const request = await engine.typeParity('CreateUserRequest', 'CreateUserRequest');
+    if (request.missingInOther.join(',') !== 'email' || request.matches) {
+      throw new Error(`Missing email should be reported: ${JSON.stringify(request)}`);
+    }
+
+    const user = await engine.typeParity('User', 'User', 'web/types.ts');
+    if (user.missingInOther.join(',') !== 'created_at') {
+      throw new Error(`Missing created_at should be reported: ${JSON.stringify(user)}`);
+    }
+    if (user.typeMismatches.length !== 0) {
+      throw new Error(`u64/String should map onto number/string: ${JSON.stringify(user.typeMismatches)}`);
+    }
+
+    const audit = await engine.typeParity('AuditEvent', 'AuditEvent');
+    if (!audit.matches) {
+      throw new Error(`Serialized camelCase names should match the interface: ${JSON.stringify(audit)}`);
+    }
+  }
+
+  async testCrossLanguageIdentifiers() {
+    if (this.engine.normalizeIdentifier('create_user', 'rust') !== this.engine.normalizeIdentifier('createUser', 'typescript')) {
+      throw new Error('create_user and createUser should normalize to the same key');
+    }
+
+    const engine = await this.scanProject('cross-language-names', {
+      'src/lib.rs': `
+pub struct UserProfile {
+    pub id: u64,
+}
+
+pub fn create_user(name: &str) -> UserProfile {
+    UserProfile { id: name.len() as u64 }
+}
+
+pub fn delete_everything() {}
+`,
+      'web/api.ts': `
+export class UserProfile {
+  id = 0;
+}
+
+export function createUser(name: string): UserProfile {
+  return new UserProfile();
+}
+
+export function listUsers(): UserProfile[] {
+  return [];
+}
+`
+    });
+
+    const pairs = engine.crossLanguageMatches('rust', 'typescript').map(match => `${match.kind}:${match.source.name}=${match.target.name}`);
+    if (pairs.join(',') !== 'function:create_user=createUser,type:UserProfile=UserProfile') {
+      throw new Error(`Expected create_user and UserProfile to be linked, got: ${pairs.join(', ')}`);
+    }
+
+    // Rules are per language: keeping case in TypeScript stops `createUser` matching `createuser`
+    engine.setIdentifierNormalization('typescript', { caseSensitive: true, separators: '_$' });
+    if (engine.crossLanguageMatches('rust', 'typescript').some(match => match.kind === 'function')) {
+      throw new Error('A case-sensitive TypeScript rule should no longer match create_user');
+    }
+  }
+
+  async runAllTests() {
+    console.log('🦀 Starting Rust Lint Test Suite\n');
+
+    try {
+      await this.setupTestEnvironment();
+
+      await this.runTest('Derive Consistency (fixture)', () => this.testDeriveConsistencyClean());
+      await this.runTest('Derive Consistency (missing companion)', () => this.testDeriveConsistencyFlagged());
+      await this.runTest('Trait Coverage (fixture)', () => this.testTraitCoverageFixture());
+      await this.runTest('Trait Coverage (missing method)', () => this.testTraitCoverageIncomplete());
+      await this.runTest('Default Method Usage', () => this.testDefaultMethodUsage());
+      await this.runTest('Overrides Of', () => this.testOverridesOf());
+      await this.runTest('Default Values', () => this.testDefaultValues());
+      await this.runTest('Ignored Results (fixture)', () => this.testIgnoredResultsClean());
+      await this.runTest('Ignored Results (discarded Result)', () => this.testIgnoredResultsFlagged());
+      await this.runTest('Shadowing (fixture)', () => this.testShadowingClean());
+      await this.runTest('Shadowing (re-let)', () => this.testShadowingFlagged());
+      await this.runTest('Exhaustive public enums', () => this.testExhaustivePublicEnums());
+      await this.runTest('Parse diagnostics', () => this.testParseDiagnostics());
+      await this.runTest('FFI surface', () => this.testFfiSurface());
+      await this.runTest('Async mismatches', () => this.testAsyncMismatches());
+      await this.runTest('Method Shadowing (fixture)', () => this.testMethodShadowingClean());
+      await this.runTest('Method Shadowing (flagged)', () => this.testMethodShadowingFlagged());
+      await this.runTest('Non-exhaustive Matches (fixture)', () => this.testNonExhaustiveMatchesClean());
+      await this.runTest('Non-exhaustive Matches (flagged)', () => this.testNonExhaustiveMatchesFlagged());
+      await this.runTest('Lock Across Await (fixture)', () => this.testLockAcrossAwaitClean());
+      await this.runTest('Lock Across Await (flagged)', () => this.testLockAcrossAwaitFlagged());
+      await this.runTest('Layering', () => this.testLayering());
+      await this.runTest('Panic sites', () => this.testPanicSites());
+      await this.runTest('SARIF report', () => this.testSarifReport());
+      await this.runTest('Redundant clones', () => this.testRedundantClones());
+      await this.runTest('Const fn candidates', () => this.testConstFnCandidates());
+      await this.runTest('Unused Async (fixture)', () => this.testUnusedAsyncClean());
+      await this.runTest('Unused Async (orphan)', () => this.testUnusedAsyncFlagged());
+      await this.runTest('Boxed Error Usage', () => this.testBoxedErrorUsage());
+      await this.runTest('Implementors By Module', () => this.testImplementorsByModule());
+      await this.runTest('Dyn dispatch sites', () => this.testDynDispatchSites());
+      await this.runTest('Dispatch profile', () => this.testDispatchProfile());
+      await this.runTest('Thread Safety (fixture)', () => this.testThreadSafetyClean());
+      await this.runTest('Thread Safety (shared Rc)', () => this.testThreadSafetyFlagged());
+      await this.runTest('Error Style Report', () => this.testErrorStyleReport());
+      await this.runTest('Unnecessary Ownership (fixture)', () => this.testUnnecessaryOwnershipClean());
+      await this.runTest('Unnecessary Ownership (flagged)', () => this.testUnnecessaryOwnershipFlagged());
+      await this.runTest('Conditional impls', () => this.testConditionalImpls());
+      await this.runTest('API Reachability (fixture)', () => this.testApiReachabilityFixture());
+      await this.runTest('API Reachability (unreachable)', () => this.testApiReachabilityFlagged());
+      await this.runTest('Long Parameter Lists (fixture)', () => this.testLongParameterListsClean());
+      await this.runTest('Long Parameter Lists (flagged)', () => this.testLongParameterListsFlagged());
+      await this.runTest('Visibility Smells (fixture)', () => this.testVisibilitySmellsClean());
+      await this.runTest('Visibility Smells (flagged)', () => this.testVisibilitySmellsFlagged());
+      await this.runTest('Generic Bloat Candidates', () => this.testGenericBloatCandidates());
+      await this.runTest('Fuzzy Path Resolution', () => this.testResolvePathFuzzy());
+      await this.runTest('Public Paths', () => this.testPublicPaths());
+      await this.runTest('Missing Impls (fixture)', () => this.testMissingImplsClean());
+      await this.runTest('Missing Impls (unsatisfied bound)', () => this.testMissingImplsFlagged());
+      await this.runTest('Naming Conventions (fixture)', () => this.testNamingConventionsClean());
+      await this.runTest('Naming Conventions (violations)', () => this.testNamingConventionsFlagged());
+      await this.runTest('Unused Fields (fixture)', () => this.testUnusedFieldsClean());
+      await this.runTest('Unused Fields (flagged)', () => this.testUnusedFieldsFlagged());
+      await this.runTest('Redundant Bounds (fixture)', () => this.testRedundantBoundsClean());
+      await this.runTest('Redundant Bounds (flagged)', () => this.testRedundantBoundsFlagged());
+      await this.runTest('Redundant Lifetimes (fixture)', () => this.testRedundantLifetimesClean());
+      await this.runTest('Redundant Lifetimes (flagged)', () => this.testRedundantLifetimesFlagged());
+      await this.runTest('Required Traits', () => this.testRequiredTraits());
+      await this.runTest('DIP Candidates (fixture)', () => this.testDipCandidatesClean());
+      await this.runTest('DIP Candidates (flagged)', () => this.testDipCandidatesFlagged());
+      await this.runTest('Error Variants (fixture)', () => this.testErrorVariantsFixture());
+      await this.runTest('Error Variants (produced)', () => this.testErrorVariantsProduced());
+      await this.runTest('Untested Public API (fixture)', () => this.testUntestedPublicApi());
+      await this.runTest('Untested Public API (doc and integration tests)', () => this.testUntestedPublicApiDocAndIntegrationTests());
+      await this.runTest('Type Heatmap', () => this.testTypeHeatmap());
+      await this.runTest('Module Metrics (config)', () => this.testModuleMetrics());
+      await this.runTest('Module Split', () => this.testModuleSplit());
+      await this.runTest('Misplaced Items', () => this.testMisplacedItems());
+      await this.runTest('Undocumented Public API', () => this.testUndocumentedPublicApi());
+      await this.runTest('Type Parity (Rust vs TypeScript)', () => this.testTypeParity());
+      await this.runTest('Cross-Language Identifiers', () => this.testCrossLanguageIdentifiers());
+
+    } finally {
+      await this.cleanupTestEnvironment();
+    }
+
+    this.printResults();
+  }
+
+  printResults() {
+    console.log('\n📊 TEST RESULTS');
+    console.log('='.repeat(50));
+    console.log(`✅ Passed: ${this.results.passed}`);
+    console.log(`❌ Failed: ${this.results.failed}`);
+    console.log(`📈 Success Rate: ${((this.results.passed / (this.results.passed + this.results.failed)) * 100).toFixed(1)}%`);
+
+    if (this.results.failed > 0) {
+      console.log('\n❌ FAILED TESTS:');
+      this.results.tests
+        .filter(test => test.status === 'FAILED')
+        .forEach(test => {
+          console.log(`   • ${test.name}: ${test.error}`);
+        });
+    }
+
+    console.log('\n🦀 Rust Lint Test Complete');
+    process.exit(this.results.failed > 0 ? 1 : 0);
+  }
+}
+
+// Run tests if called directly
+if (import.meta.url === `file://${process.argv[1]}`) {
+  const testSuite = new RustLintTestSuite();
+  testSuite.runAllTests().catch(error => {
+    console.error('❌ Test suite failed:', error);
+    process.exit(1);
+  });
+}
+
+export { RustLintTestSuite };
@@ -0,0 +1,1306 @@
+#!/usr/bin/env node
+
+/**
+ * Rust Item Extraction Test Suite
+ *
+ * Tests RustAnalyzer item extraction and the graph elements built from it
+ * by RustGraphBuilder, using small synthetic Rust sources.
+ */
+
+import { mkdtemp, mkdir, writeFile, readFile, rm } from 'fs/promises';
+import { join } from 'path';
+import { tmpdir } from 'os';
+import { RustAnalyzer } from '../../dist/core/RustAnalyzer.js';
+import { RustGraphBuilder } from '../../dist/core/RustGraphBuilder.js';
+import { attributeArgs } from '../../dist/core/RustAttributes.js';
+import { MindMapStorage } from '../../dist/core/MindMapStorage.js';
+import { loadMmap } from '../../dist/core/FileReader.js';
+import { serializeSnapshot, matchSnapshot, UPDATE_SNAPSHOTS_ENV } from '../../dist/testing/snapshot.js';
+
+const FIXTURE_PATH = join(process.cwd(), 'tests', 'example-files', 'test-rust-example.rs');
+const FIXTURE_SNAPSHOT_PATH = join(process.cwd(), 'tests', 'example-files', 'snapshots', 'test-rust-example.rs.snap');
+
+class RustItemsTestSuite {
+  constructor() {
+    this.analyzer = new RustAnalyzer();
+    this.builder = new RustGraphBuilder();
+    this.testDir = null;
+    this.results = {
+      passed: 0,
+      failed: 0,
+      tests: []
+    };
+  }
+
+  async runTest(name, testFn) {
+    try {
+      console.log(`🧪 Running: ${name}`);
+      const startTime = Date.now();
+
+      await testFn();
+
+      const duration = Date.now() - startTime;
+      console.log(`✅ PASSED: ${name} (${duration}ms)`);
+      this.results.passed++;
+      this.results.tests.push({ name, status: 'PASSED', duration });
+
+    } catch (error) {
+      console.error(`❌ FAILED: ${name}`);
+      console.error(`   Error: ${error.message}`);
+      this.results.failed++;
+      this.results.tests.push({ name, status: 'FAILED', error: error.message });
+    }
+  }
+
+  async analyze(fileName, source) {
+    const filePath = join(this.testDir, fileName);
+    await writeFile(filePath, source);
+    const structure = await this.analyzer.analyzeFile(filePath);
+    if (!structure) {
+      throw new Error(`RustAnalyzer returned no structure for ${fileName}`);
+    }
+    return { filePath, structure };
+  }
+
+  async testConstAndStaticItems() {
+    const { structure } = await this.analyze('consts.rs', `
+pub const MAX_USERS: usize = 100;
+const GREETING: &str = "hello";
+pub(crate) static mut COUNTER: u32 = 0;
+static DEFAULT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+pub const fn not_a_const() -> u32 { 1 }
+`);
+
+    const consts = structure.constants;
+    if (consts.length !== 4) {
+      throw new Error(`Expected 4 const/static items, found ${consts.length}`);
+    }
+
+    const maxUsers = consts.find(c => c.name === 'MAX_USERS');
+    if (!maxUsers || maxUsers.kind !== 'const' || maxUsers.type !== 'usize' || maxUsers.value !== '100' || !maxUsers.isPublic) {
+      throw new Error(`MAX_USERS extracted incorrectly: ${JSON.stringify(maxUsers)}`);
+    }
+
+    const greeting = consts.find(c => c.name === 'GREETING');
+    if (!greeting || greeting.visibility !== 'private' || greeting.value !== '"hello"') {
+      throw new Error(`GREETING extracted incorrectly: ${JSON.stringify(greeting)}`);
+    }
+
+    const counter = consts.find(c => c.name === 'COUNTER');
+    if (!counter || counter.kind !== 'static' || !counter.isMutable || counter.visibility !== 'pub(crate)') {
+      throw new Error(`COUNTER extracted incorrectly: ${JSON.stringify(counter)}`);
+    }
+
+    const timeout = consts.find(c => c.name === 'DEFAULT_TIMEOUT');
+    if (!timeout || timeout.value !== undefined) {
+      throw new Error('Non-literal initializers should not be recorded as values');
+    }
+  }
+
+  async testNestedConstants() {
+    const { filePath, structure } = await this.analyze('nested.rs', `
+pub struct Limits {
+    pub max: usize,
+}
+
+impl Limits {
+    pub const DEFAULT_MAX: usize = 64;
+}
+
+pub mod config {
+    pub const PORT: u16 = 8080;
+
+    pub mod defaults {
+        pub static LIMITS: Limits = Limits { max: 10 };
+    }
+}
+`);
+
+    const associated = structure.constants.find(c => c.name === 'DEFAULT_MAX');
+    if (!associated || associated.implTarget !== 'Limits') {
+      throw new Error(`Associated const should record impl target: ${JSON.stringify(associated)}`);
+    }
+
+    const port = structure.constants.find(c => c.name === 'PORT');
+    if (!port || port.modulePath.join('::') !== 'config') {
+      throw new Error(`PORT should be nested in config: ${JSON.stringify(port)}`);
+    }
+
+    const limits = structure.constants.find(c => c.name === 'LIMITS');
+    if (!limits || limits.modulePath.join('::') !== 'config::defaults') {
+      throw new Error(`LIMITS should be nested in config::defaults: ${JSON.stringify(limits)}`);
+    }
+
+    const { nodes, edges } = this.builder.createMindMapElements(structure, filePath);
+
+    const structId = `${filePath}#struct:Limits`;
+    const associatedId = `${filePath}#const:Limits::DEFAULT_MAX`;
+    if (!edges.some(e => e.type === 'contains' && e.source === structId && e.target === associatedId)) {
+      throw new Error('Associated const should be contained by its struct');
+    }
+
+    const limitsNode = nodes.find(n => n.id === `${filePath}#static:config::defaults::LIMITS`);
+    if (!limitsNode || limitsNode.type !== 'variable' || limitsNode.metadata.variableType !== 'static') {
+      throw new Error('Static node not created with qualified id');
+    }
+
+    const fieldType = edges.find(e => e.source === limitsNode.id && e.type === 'references');
+    if (!fieldType || fieldType.target !== structId || fieldType.metadata.relationship !== 'field_type') {
+      throw new Error('Static should reference its declared struct type');
+    }
+  }
+
+  async testAttributeArguments() {
+    const { filePath, structure } = await this.analyze('attrs.rs', `
+#[derive(Debug, Clone)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub struct Event {
+    id: u64,
+}
+
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize)
+)]
+/// A documented struct with a multi-line attribute
+pub struct Settings {
+    name: String,
+}
+
+#[get("/users/{id}")]
+pub async fn get_user(id: u64) -> String {
+    format!("{}", id)
+}
+`);
+
+    const event = structure.structs.find(s => s.name === 'Event');
+    const serde = attributeArgs(event.attributes, 'serde');
+    const renameAll = serde.find(arg => arg.key === 'rename_all');
+    if (!renameAll || renameAll.value !== 'camelCase') {
+      throw new Error(`rename_all should parse as a key/value pair: ${JSON.stringify(serde)}`);
+    }
+    if (event.derives.join(',') !== 'Debug,Clone') {
+      throw new Error(`Derives should come from the derive attribute: ${event.derives.join(',')}`);
+    }
+
+    const settings = structure.structs.find(s => s.name === 'Settings');
+    const [featureArg, deriveArg] = attributeArgs(settings.attributes, 'cfg_attr');
+    if (featureArg?.key !== 'feature' || featureArg.value !== 'serde') {
+      throw new Error(`cfg_attr predicate not parsed: ${JSON.stringify(featureArg)}`);
+    }
+    if (deriveArg?.value !== 'derive' || deriveArg.args.map(arg => arg.value).join(',') !== 'Serialize,Deserialize') {
+      throw new Error(`Nested derive list not balanced: ${JSON.stringify(deriveArg)}`);
+    }
+
+    const route = structure.routes.find(r => r.handler === 'get_user');
+    if (!route || route.method !== 'GET' || route.path !== '/users/{id}') {
+      throw new Error(`Route attribute should be read from parsed arguments: ${JSON.stringify(structure.routes)}`);
+    }
+
+    const { nodes } = this.builder.createMindMapElements(structure, filePath);
+    const eventNode = nodes.find(n => n.id === `${filePath}#struct:Event`);
+    if (attributeArgs(eventNode.metadata.attributes, 'serde').length !== 2) {
+      throw new Error('Struct node should carry its parsed attributes');
+    }
+  }
+
+  async testFixtureAttributes() {
+    const source = await readFile(FIXTURE_PATH, 'utf-8');
+    const { structure } = await this.analyze('fixture.rs', source);
+
+    const user = structure.structs.find(s => s.name === 'User');
+    const derives = attributeArgs(user.attributes, 'derive').map(arg => arg.value);
+    if (derives.join(',') !== 'Debug,Clone,Serialize,Deserialize') {
+      throw new Error(`User derive list should be positional: ${derives.join(',')}`);
+    }
+
+    const tests = structure.modules.find(m => m.name === 'tests');
+    const predicate = attributeArgs(tests.attributes, 'cfg');
+    if (predicate.length !== 1 || predicate[0].value !== 'test' || predicate[0].key !== undefined) {
+      throw new Error(`cfg(test) predicate not exposed: ${JSON.stringify(predicate)}`);
+    }
+
+    const main = structure.functions.find(f => f.name === 'main');
+    if (!main.attributes.some(attr => attr.name === 'tokio::main')) {
+      throw new Error('Path attributes like #[tokio::main] should keep their full name');
+    }
+  }
+
+  async testImplementsEvidence() {
+    const { structure } = await this.analyze('evidence.rs', `pub trait Describe {
+    fn describe(&self) -> String {
+        String::new()
+    }
+}
+
+#[derive(Debug, Describe)]
+pub struct Widget {
+    id: u32,
+}
+
+impl Describe for Widget {}
+`);
+    const { edges } = this.builder.createMindMapElements(structure, 'src/evidence.rs');
+
+    const implementsEdges = edges.filter(e => e.type === 'implements');
+    if (implementsEdges.length !== 1) {
+      throw new Error(`Derive and impl should merge into one edge, got: ${implementsEdges.map(e => e.id).join(', ')}`);
+    }
+    const [edge] = implementsEdges;
+    const spans = edge.evidence.map(span => `${span.kind}@${span.startLine}`).sort().join(',');
+    if (spans !== 'derive@7,impl@12') {
+      throw new Error(`Expected derive and impl evidence, got: ${spans}`);
+    }
+
+    // A second pass producing the same edge accumulates evidence instead of duplicating
+    const storage = new MindMapStorage(this.testDir);
+    storage.addEdge(edge);
+    storage.addEdge({ ...edge, evidence: [{ filePath: 'src/bounds.rs', startLine: 3, endLine: 3, kind: 'where_bound' }] });
+    storage.addEdge({ ...edge, evidence: [edge.evidence[0]] });
+    const merged = storage.getEdge(edge.id);
+    if (merged.evidence.length !== 3 || storage.findEdges(e => e.type === 'implements').length !== 1) {
+      throw new Error(`Storage should merge evidence without duplicates: ${JSON.stringify(merged.evidence)}`);
+    }
+  }
+
+  async testDeriveProvenance() {
+    const source = await readFile(FIXTURE_PATH, 'utf-8');
+    const { structure } = await this.analyze('fixture.rs', source);
+    const { nodes, edges } = this.builder.createMindMapElements(structure, 'src/main.rs');
+
+    const impl = nodes.find(n => n.id === 'src/main.rs#impl:Serialize for User');
+    if (!impl) {
+      throw new Error(`Derive should produce an impl node: ${nodes.filter(n => n.metadata.patternType === 'trait_impl').map(n => n.id).join(', ')}`);
+    }
+    const { generatedBy } = impl.metadata;
+    if (!impl.metadata.generated || generatedBy?.kind !== 'derive' || generatedBy.macro !== 'Serialize') {
+      throw new Error(`Impl node should be marked generated by the Serialize derive: ${JSON.stringify(impl.metadata)}`);
+    }
+    const user = structure.structs.find(s => s.name === 'User');
+    if (impl.metadata.targetId !== 'src/main.rs#struct:User' || generatedBy.line !== user.attributes[0].line) {
+      throw new Error(`Impl node should point at User and its derive line: ${JSON.stringify(impl.metadata)}`);
+    }
+
+    // Hand-written impls get no synthetic node
+    if (nodes.some(n => n.id.includes('#impl:UserRepository'))) {
+      throw new Error('Only derives should produce generated impl nodes');
+    }
+    const handWritten = edges.find(e => e.type === 'implements');
+    if (handWritten?.metadata?.generatedBy) {
+      throw new Error('Implements edges from impl blocks are not generated');
+    }
+  }
+
+  async testDeriveRequiredBounds() {
+    const fixture = await this.analyze('fixture.rs', await readFile(FIXTURE_PATH, 'utf-8'));
+    const fixtureEdges = this.builder.createMindMapElements(fixture.structure, 'src/main.rs').edges;
+    if (fixtureEdges.some(e => e.type === 'requires_bound')) {
+      throw new Error('Non-generic fixture structs should not imply any bounds');
+    }
+
+    const { structure } = await this.analyze('bounds.rs', `#[derive(Clone)]
+struct W<T>(T);
+
+#[derive(Debug, Serialize)]
+pub struct Pair<'a, K, V: Default, const N: usize> {
+    key: &'a K,
+    marker: std::marker::PhantomData<u8>,
+}
+`);
+    const { nodes, edges } = this.builder.createMindMapElements(structure, 'src/bounds.rs');
+    const param = nodes.find(n => n.id === 'src/bounds.rs#type_param:W::T');
+    if (!param || param.type !== 'type_parameter' || !edges.some(e => e.type === 'contains' && e.source === 'src/bounds.rs#struct:W' && e.target === param.id)) {
+      throw new Error(`W should contain its type parameter T: ${nodes.filter(n => n.type === 'type_parameter').map(n => n.id).join(', ')}`);
+    }
+
+    const bounds = edges.filter(e => e.type === 'requires_bound');
+    const clone = bounds.find(e => e.source === param.id);
+    if (!clone || clone.target !== 'src/bounds.rs#impl:Clone for W' || clone.metadata.bound !== 'T: Clone' || clone.evidence[0].startLine !== 1) {
+      throw new Error(`derive(Clone) should imply T: Clone: ${JSON.stringify(clone)}`);
+    }
+
+    // Debug bounds every type parameter; Serialize only those used in fields. Lifetimes and const generics have none.
+    const implied = bounds.filter(e => e.source.includes('Pair')).map(e => e.metadata.bound).sort();
+    if (JSON.stringify(implied) !== JSON.stringify(['K: Debug', 'K: Serialize', 'V: Debug'])) {
+      throw new Error(`Unexpected implied bounds for Pair: ${implied.join(', ')}`);
+    }
+  }
+
+  async testConstGenerics() {
+    const { structure } = await this.analyze('buffer.rs', `pub struct Buffer<const N: usize> {
+    data: [u8; N],
+    len: usize,
+}
+
+pub fn take<T: Copy, const N: usize>(x: [T; N]) -> Buffer<N> {
+    todo!()
+}
+
+pub fn plain(x: [u8; 4]) {}
+`);
+    const buffer = structure.structs.find(s => s.name === 'Buffer');
+    if (JSON.stringify(buffer.genericParams) !== JSON.stringify([{ name: 'N', kind: 'const', type: 'usize' }])) {
+      throw new Error(`Buffer should declare const N: usize, got: ${JSON.stringify(buffer.genericParams)}`);
+    }
+    const take = structure.functions.find(f => f.name === 'take');
+    if (take.genericParams.map(p => `${p.kind}:${p.name}`).join(',') !== 'type:T,const:N' || take.typeParameters.join(',') !== 'T') {
+      throw new Error(`take should have a type and a const parameter: ${JSON.stringify(take.genericParams)}`);
+    }
+
+    const { nodes, edges } = this.builder.createMindMapElements(structure, 'src/buffer.rs');
+    const param = nodes.find(n => n.id === 'src/buffer.rs#type_param:Buffer::N');
+    if (!param || param.metadata.kind !== 'const' || param.metadata.constType !== 'usize') {
+      throw new Error(`Buffer's N should be a const parameter node: ${JSON.stringify(param?.metadata)}`);
+    }
+    const usage = edges.find(e => e.source === 'src/buffer.rs#struct:Buffer' && e.target === param.id && e.metadata?.relationship === 'const_generic');
+    if (!usage || JSON.stringify(usage.metadata.usages) !== JSON.stringify([{ field: 'data', role: 'array_length' }])) {
+      throw new Error(`The data field's array length should link to N: ${JSON.stringify(usage?.metadata)}`);
+    }
+
+    const takeUsage = edges.find(e => e.target === 'src/buffer.rs#type_param:take::N');
+    const roles = (takeUsage?.metadata.usages || []).map(u => `${u.parameter || 'return'}:${u.role}`).join(',');
+    if (roles !== 'x:array_length,return:type_argument') {
+      throw new Error(`take's N should size x and parameterize the return type, got: ${roles}`);
+    }
+    if (nodes.some(n => n.id.includes('#type_param:take::T') || n.id.includes('#type_param:plain'))) {
+      throw new Error('Only const parameters of functions get nodes');
+    }
+  }
+
+  async testReceivers() {
+    const { structure } = await this.analyze('receivers.rs', `
+pub struct Builder;
+
+impl Builder {
+    pub fn new() -> Self { Builder }
+    pub fn build(self) -> u32 { 0 }
+    pub fn port(mut self, port: u16) -> Self { self }
+    pub fn name<'a>(&'a self) -> &'a str { "" }
+    pub fn reset(&'a mut self) {}
+    pub fn boxed(self: Box<Self>) {}
+    pub fn pinned(self: Pin<&mut Self>) {}
+    pub fn shared(self: Rc<Self>) {}
+}
+
+pub fn free(value: u32) {}
+`);
+
+    const receivers = Object.fromEntries(structure.functions.map(f => [f.name, `${f.receiver}${f.receiverMutable ? ':mut' : ''}`]));
+    const expected = {
+      new: 'none', build: 'value', port: 'value:mut', name: 'ref', reset: 'ref_mut:mut',
+      boxed: 'boxed', pinned: 'typed:mut', shared: 'typed', free: 'none'
+    };
+    if (JSON.stringify(receivers) !== JSON.stringify(expected)) {
+      throw new Error(`Receivers extracted incorrectly: ${JSON.stringify(receivers)}`);
+    }
+    if (structure.functions.find(f => f.name === 'boxed').implTarget !== 'Builder' || structure.functions.find(f => f.name === 'free').implTarget) {
+      throw new Error('Methods should record their impl target; free functions have none');
+    }
+  }
+
+  async testReturnBorrows() {
+    const { filePath, structure } = await this.analyze('borrows.rs', `
+pub fn first<'a>(s: &'a [u8]) -> &'a u8 { &s[0] }
+pub fn longest<'a, 'b>(x: &'a str, y: &'a str, z: &'b str) -> &'a str { x }
+pub fn elided(values: &[u32], n: usize) -> Option<&u32> { values.get(n) }
+pub fn ambiguous(a: &str, b: &str) -> &'static str { "" }
+pub fn owned(s: &str) -> String { s.to_string() }
+
+impl Store {
+    pub fn get(&self, key: &str) -> Option<&String> { None }
+}
+`);
+
+    const borrows = Object.fromEntries(structure.functions.map(f => [f.name, f.borrowsFrom.join(',')]));
+    const expected = { first: 's', longest: 'x,y', elided: 'values', ambiguous: '', owned: '', get: 'self' };
+    if (JSON.stringify(borrows) !== JSON.stringify(expected)) {
+      throw new Error(`Returned references should borrow from the parameters they are tied to: ${JSON.stringify(borrows)}`);
+    }
+    const { nodes } = this.builder.createMindMapElements(structure, filePath);
+    if (nodes.find(n => n.name === 'first').metadata.borrowsFrom.join(',') !== 's') {
+      throw new Error('Function nodes should carry the borrowsFrom link');
+    }
+
+    const fixture = await this.analyze('fixture.rs', await readFile(FIXTURE_PATH, 'utf-8'));
+    if (fixture.structure.functions.some(f => f.borrowsFrom.length > 0)) {
+      throw new Error('The fixture only returns owned values');
+    }
+  }
+
+  async testAssociatedTypeBounds() {
+    const { structure } = await this.analyze('assoc.rs', `
+use std::fmt::Display;
+
+pub trait Validate {}
+
+pub trait R {
+    type E;
+    type Item: Clone + Send;
+    type Plain;
+    fn f(&self) where Self::E: Display;
+    fn g(&self) -> Result<(), Self::Plain> where <Self as R>::Item: Validate, Self: Sized;
+}
+`);
+
+    const bounds = structure.traits.find(t => t.name === 'R').associatedTypeBounds;
+    if (JSON.stringify(bounds) !== JSON.stringify({ Item: ['Clone', 'Send', 'Validate'], E: ['Display'] })) {
+      throw new Error(`Associated type bounds extracted incorrectly: ${JSON.stringify(bounds)}`);
+    }
+
+    const { nodes, edges } = this.builder.createMindMapElements(structure, 'src/assoc.rs');
+    const e = nodes.find(n => n.id === 'src/assoc.rs#assoc_type:R::E');
+    if (!e || e.metadata.bounds.join(',') !== 'Display' || !edges.some(edge => edge.type === 'contains' && edge.source === 'src/assoc.rs#trait:R' && edge.target === e.id)) {
+      throw new Error(`R should contain an associated type node E bounded by Display: ${JSON.stringify(e?.metadata)}`);
+    }
+    const required = edges.filter(edge => edge.type === 'requires_bound' && edge.source.includes('#assoc_type:'));
+    if (required.length !== 1 || required[0].target !== 'src/assoc.rs#trait:Validate' || required[0].metadata.bound !== 'Item: Validate') {
+      throw new Error(`Bounds on local traits should be linked: ${required.map(edge => edge.id).join(', ')}`);
+    }
+
+    const fixture = await this.analyze('fixture.rs', await readFile(FIXTURE_PATH, 'utf-8'));
+    if (JSON.stringify(fixture.structure.traits.find(t => t.name === 'UserRepository').associatedTypeBounds) !== '{}') {
+      throw new Error('UserRepository::Error has no bounds');
+    }
+  }
+
+  async testGenericAssociatedTypes() {
+    const { structure } = await this.analyze('gat.rs', `
+pub trait LendingIterator {
+    type Item<'a> where Self: 'a;
+    type Mapped<T: Clone, 'b>: Iterator<Item = T>;
+    type Plain;
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>>;
+}
+`);
+    const trait = structure.traits.find(t => t.name === 'LendingIterator');
+    if (!trait || trait.associatedTypes.join(',') !== 'Item,Mapped,Plain' || trait.methods.join(',') !== 'next') {
+      throw new Error(`Generic associated types should parse like plain ones: ${JSON.stringify(trait)}`);
+    }
+    const { nodes } = this.builder.createMindMapElements(structure, 'src/gat.rs');
+    const params = name => nodes.find(n => n.id === `src/gat.rs#assoc_type:LendingIterator::${name}`).metadata.genericParams;
+    if (JSON.stringify(params('Item')) !== JSON.stringify([{ name: 'a', kind: 'lifetime' }])) {
+      throw new Error(`Item should record its lifetime parameter: ${JSON.stringify(params('Item'))}`);
+    }
+    if (params('Mapped').map(p => `${p.kind}:${p.name}`).join(',') !== 'type:T,lifetime:b' || params('Plain').length !== 0) {
+      throw new Error(`Mapped takes T and 'b, Plain nothing: ${JSON.stringify(params('Mapped'))}`);
+    }
+
+    const fixture = await this.analyze('fixture.rs', await readFile(FIXTURE_PATH, 'utf-8'));
+    const repository = fixture.structure.traits.find(t => t.name === 'UserRepository');
+    if (repository.associatedTypes.join(',') !== 'Error' || JSON.stringify(repository.associatedTypeGenerics) !== '{}') {
+      throw new Error('UserRepository::Error is a plain associated type');
+    }
+  }
+
+  async testTraitAliases() {
+    const { structure } = await this.analyze('aliases.rs', `
+pub trait Handler {
+    fn handle(&self);
+}
+
+pub trait Named {}
+
+/// Anything that can serve requests
+pub trait Service = Handler + Named + Send + 'static;
+
+trait Convert<T> = Into<T> + Clone where T: Sized;
+
+pub trait Real: Handler {}
+`);
+    if (structure.traits.map(t => t.name).join(',') !== 'Handler,Named,Real') {
+      throw new Error(`Aliases are not traits with bodies: ${structure.traits.map(t => t.name).join(', ')}`);
+    }
+    const aliases = structure.traitAliases.map(a => `${a.name}=${a.traits.join('+')}@${a.startLine}`);
+    if (JSON.stringify(aliases) !== JSON.stringify(['Service=Handler+Named+Send@9', 'Convert=Into+Clone@11'])) {
+      throw new Error(`Trait aliases parsed incorrectly: ${JSON.stringify(aliases)}`);
+    }
+
+    const { nodes, edges } = this.builder.createMindMapElements(structure, 'src/aliases.rs');
+    const service = nodes.find(n => n.id === 'src/aliases.rs#trait_alias:Service');
+    if (service?.metadata.rustKind !== 'trait_alias' || service.metadata.docComment !== 'Anything that can serve requests') {
+      throw new Error(`Service should be a trait alias node: ${JSON.stringify(service?.metadata)}`);
+    }
+    const aliasOf = edges.filter(edge => edge.type === 'alias_of').map(edge => `${edge.source.split('#')[1]}->${edge.target.split('#')[1]}`);
+    if (JSON.stringify(aliasOf) !== JSON.stringify(['trait_alias:Service->trait:Handler', 'trait_alias:Service->trait:Named'])) {
+      throw new Error(`Aliases should link to the local traits they compose: ${JSON.stringify(aliasOf)}`);
+    }
+  }
+
+  async testNewtypes() {
+    const { filePath, structure } = await this.analyze('newtypes.rs', `
+use std::ops::Deref;
+
+pub struct UserId(u64);
+
+#[repr(transparent)]
+pub struct Email {
+    inner: String,
+}
+
+pub struct Tagged<T>(pub Account, std::marker::PhantomData<T>);
+
+pub struct Pair(u32, u32);
+
+pub struct Account {
+    pub id: UserId,
+}
+
+impl Deref for UserId {
+    type Target = u64;
+    fn deref(&self) -> &u64 { &self.0 }
+}
+
+impl From<u64> for UserId {
+    fn from(id: u64) -> Self { UserId(id) }
+}
+
+impl From<UserId> for u64 {
+    fn from(id: UserId) -> u64 { id.0 }
+}
+`);
+
+    const wrapped = Object.fromEntries(structure.structs.map(s => [s.name, s.wrappedType]));
+    if (wrapped.UserId !== 'u64' || wrapped.Email !== 'String' || wrapped.Tagged !== 'Account' || wrapped.Pair || wrapped.Account) {
+      throw new Error(`Newtypes detected incorrectly: ${JSON.stringify(wrapped)}`);
+    }
+    if (structure.structs.find(s => s.name === 'Pair').tupleFieldTypes.join(',') !== 'u32,u32') {
+      throw new Error('Tuple struct fields should be recorded positionally');
+    }
+
+    const { nodes, edges } = this.builder.createMindMapElements(structure, filePath);
+    const userId = nodes.find(n => n.name === 'UserId' && n.metadata.rustKind === 'struct');
+    if (!userId.metadata.isNewtype || userId.metadata.wrappedType !== 'u64' || userId.metadata.reprTransparent) {
+      throw new Error(`UserId should be flagged as a newtype wrapping u64: ${JSON.stringify(userId.metadata)}`);
+    }
+    if (userId.metadata.newtypeImpls.join(',') !== 'Deref,From<UserId> for u64,From<u64>') {
+      throw new Error(`Deref and From impls should be cross-referenced: ${userId.metadata.newtypeImpls.join(',')}`);
+    }
+    const email = nodes.find(n => n.name === 'Email');
+    if (!email.metadata.isNewtype || !email.metadata.reprTransparent) {
+      throw new Error('A repr(transparent) struct with one field is a newtype');
+    }
+
+    const wraps = edges.filter(e => e.type === 'wraps').map(e => `${nodes.find(n => n.id === e.source).name}->${nodes.find(n => n.id === e.target).name}`);
+    if (wraps.join(',') !== 'Tagged->Account') {
+      throw new Error(`Only newtypes over project types get wraps edges, got: ${wraps.join(',')}`);
+    }
+  }
+
+  async testTypeDepthFixture() {
+    const source = await readFile(FIXTURE_PATH, 'utf-8');
+    const { structure } = await this.analyze('fixture.rs', source);
+
+    const appState = structure.structs.find(s => s.name === 'AppState');
+    const repo = appState.fieldTypeTrees[0];
+    if (appState.truncatedType || repo.kind !== 'path' || repo.name !== 'std::sync::Arc') {
+      throw new Error(`AppState.user_repo should parse fully: ${JSON.stringify(repo)}`);
+    }
+    const object = repo.args[0];
+    const bounds = object.args.map(bound => bound.name).join(',');
+    if (object.kind !== 'trait_object' || bounds !== 'UserRepository,Send,Sync') {
+      throw new Error(`Arc should hold a trait object bounded by UserRepository + Send + Sync, got ${object.kind}: ${bounds}`);
+    }
+    const error = object.args[0].bindings?.find(binding => binding.name === 'Error')?.type;
+    if (error?.text !== 'Box<dyn std::error::Error>' || error.args[0].kind !== 'trait_object') {
+      throw new Error(`The Error binding should parse as Box<dyn Error>: ${JSON.stringify(error)}`);
+    }
+    if (structure.functions.some(f => f.truncatedType)) {
+      throw new Error('No fixture signature nests past the default depth');
+    }
+  }
+
+  async testTypeDepthTruncation() {
+    const deep = 'Vec<'.repeat(5000) + 'u8' + '>'.repeat(5000);
+    const { filePath, structure } = await this.analyze('deep.rs', `
+pub struct Deep {
+    pub value: ${deep},
+}
+
+pub fn deep(value: ${deep}) -> Option<u8> {
+    None
+}
+`);
+
+    const func = structure.functions.find(f => f.name === 'deep');
+    if (!func.truncatedType || structure.structs[0].truncatedType !== true) {
+      throw new Error('Types past the depth limit should be flagged as truncated');
+    }
+    let type = func.parameterTypeTrees[0];
+    let depth = 1;
+    while (type.kind === 'path') {
+      type = type.args[0];
+      depth++;
+    }
+    if (depth !== 33 || type.kind !== 'opaque' || !type.truncated || !type.text.startsWith('Vec<Vec<') || !type.text.endsWith('u8>>')) {
+      throw new Error(`Parsing should stop at the default depth of 32 and keep the rest as text, stopped at ${depth}: ${type.kind}`);
+    }
+    if (func.returnTypeTree.text !== 'Option<u8>' || func.returnTypeTree.args[0].name !== 'u8') {
+      throw new Error(`The return type is unaffected: ${JSON.stringify(func.returnTypeTree)}`);
+    }
+
+    const { nodes } = this.builder.createMindMapElements(structure, filePath);
+    if (nodes.find(n => n.name === 'deep').metadata.truncatedType !== true) {
+      throw new Error('Function nodes should carry the truncation flag');
+    }
+
+    this.analyzer.setParseOptions({ maxTypeDepth: 2 });
+    try {
+      const { structure: shallow } = await this.analyze('shallow.rs', 'pub fn f(x: Vec<Vec<Vec<u8>>>) {}\npub fn g(x: Vec<u8>) {}\n');
+      const [f, g] = ['f', 'g'].map(name => shallow.functions.find(fn => fn.name === name));
+      if (!f.truncatedType || f.parameterTypeTrees[0].args[0].args[0].text !== 'Vec<u8>' || g.truncatedType) {
+        throw new Error(`maxTypeDepth should bound the parse: ${JSON.stringify(f.parameterTypeTrees[0])}`);
+      }
+    } finally {
+      this.analyzer.setParseOptions({});
+    }
+  }
+
+  async testMacroBodyModes() {
+    const source = `
+macro_rules! make_fn {
+    ($name:ident) => { pub fn $name() { println!("x"); } };
+}
+
+my_macro! {
+    pub fn generated() {}
+    pub struct Gen;
+}
+
+#[doc = concat!("Real ", "docs")]
+pub fn real() {
+    inner_macro! { pub fn not_an_item() {} }
+}
+`;
+    const names = structure => [...structure.functions, ...structure.structs].map(item => item.name).sort().join(',');
+
+    const { structure: bestEffort } = await this.analyze('best_effort.rs', source);
+    if (!['generated', 'Gen', 'real'].every(name => names(bestEffort).split(',').includes(name)) || bestEffort.macroInvocations) {
+      throw new Error(`Best-effort mode should parse macro input as items: ${names(bestEffort)}`);
+    }
+
+    try {
+      this.analyzer.setParseOptions({ macroBodyMode: 'skip' });
+      const { structure: skipped } = await this.analyze('skip.rs', source);
+      if (names(skipped) !== 'real' || skipped.macroInvocations) {
+        throw new Error(`Skip mode should ignore items inside macro invocations: ${names(skipped)}`);
+      }
+      if (skipped.functions[0].startLine !== 12) {
+        throw new Error(`Line numbers should be unaffected: ${skipped.functions[0].startLine}`);
+      }
+
+      this.analyzer.setParseOptions({ macroBodyMode: 'opaque' });
+      const { filePath, structure: opaque } = await this.analyze('opaque.rs', source);
+      const invocations = opaque.macroInvocations.map(m => `${m.name}:${m.startLine}-${m.endLine}`);
+      if (names(opaque) !== 'real' || invocations.join(',') !== 'my_macro:6-9') {
+        throw new Error(`Opaque mode should record only item-level invocations: ${names(opaque)} ${invocations.join(',')}`);
+      }
+      const { nodes, edges } = this.builder.createMindMapElements(opaque, filePath);
+      const placeholder = nodes.find(n => n.metadata.patternType === 'macro_invocation');
+      if (placeholder.id !== `${filePath}#macro_invocation:my_macro:6` || !edges.some(e => e.type === 'contains' && e.target === placeholder.id)) {
+        throw new Error(`Each invocation should get a placeholder node contained by the file: ${placeholder.id}`);
+      }
+
+      const fixture = await readFile(FIXTURE_PATH, 'utf-8');
+      const { structure: fixtureOpaque } = await this.analyze('fixture.rs', fixture);
+      this.analyzer.setParseOptions({});
+      const { structure: fixtureBestEffort } = await this.analyze('fixture.rs', fixture);
+      if (names(fixtureOpaque) !== names(fixtureBestEffort) || fixtureOpaque.macroInvocations.length !== 0) {
+        throw new Error('The fixture only invokes macros inside function bodies');
+      }
+    } finally {
+      this.analyzer.setParseOptions({});
+    }
+  }
+
+  async testImportScopes() {
+    const fixture = await this.analyze('imports.rs', await readFile(FIXTURE_PATH, 'utf-8'));
+    const fixtureNodes = this.builder.createMindMapElements(fixture.structure, 'src/main.rs').nodes;
+    const handler = fixtureNodes.find(n => n.name === 'create_user_handler');
+    const origins = Object.fromEntries(handler.metadata.importedReferences.map(ref => [ref.name, ref.path]));
+    if (origins.web !== 'actix_web::web' || origins.HttpResponse !== 'actix_web::HttpResponse') {
+      throw new Error(`web and HttpResponse should resolve to actix_web: ${JSON.stringify(origins)}`);
+    }
+
+    const filePath = 'src/alias.rs';
+    const { structure } = await this.analyze('alias.rs', `
+use self::models::User as Person;
+use self::math::{self, compute as calc};
+use std::time::Duration as Wait;
+use crate::prelude::*;
+
+mod models {
+    pub struct User {
+        pub name: String,
+    }
+}
+
+mod math {
+    pub fn compute(x: u32) -> u32 {
+        x * 2
+    }
+}
+
+pub struct Team {
+    lead: Person,
+    timeout: Wait,
+}
+
+pub fn greet(person: &Person) -> String {
+    let n = calc(2);
+    helper(n);
+    person.name.clone()
+}
+`);
+    const { nodes, edges } = this.builder.createMindMapElements(structure, filePath);
+    const userId = `${filePath}#struct:models::User`;
+    const teamId = `${filePath}#struct:Team`;
+    const greetId = `${filePath}#function:greet`;
+    if (!edges.some(e => e.id === `${teamId}->references->${userId}`)) {
+      throw new Error('A field typed with the Person alias should reference models::User');
+    }
+    if (!edges.some(e => e.id === `${greetId}->references->${userId}` && e.metadata.relationship === 'parameter_type')) {
+      throw new Error('A parameter typed with the Person alias should reference models::User');
+    }
+    if (!edges.some(e => e.id === `${greetId}->calls->${filePath}#function:compute`)) {
+      throw new Error('calc() should resolve to the renamed compute');
+    }
+
+    const team = nodes.find(n => n.id === teamId);
+    const wait = team.metadata.importedReferences.find(ref => ref.name === 'Wait');
+    if (wait?.path !== 'std::time::Duration' || wait.ambiguous) {
+      throw new Error(`Wait should resolve to std::time::Duration: ${JSON.stringify(team.metadata.importedReferences)}`);
+    }
+    const greet = nodes.find(n => n.id === greetId);
+    const helper = greet.metadata.importedReferences.find(ref => ref.name === 'helper');
+    if (helper?.path !== 'crate::prelude::helper' || !helper.ambiguous) {
+      throw new Error(`helper should be attributed to the glob, ambiguously: ${JSON.stringify(greet.metadata.importedReferences)}`);
+    }
+
+    const strict = new RustGraphBuilder({ globs: false }).createMindMapElements(structure, filePath).nodes;
+    if (strict.find(n => n.id === greetId).metadata.importedReferences.some(ref => ref.name === 'helper')) {
+      throw new Error('Glob attribution can be turned off');
+    }
+  }
+
+  async testMethodChains() {
+    const source = await readFile(FIXTURE_PATH, 'utf-8');
+    const { structure } = await this.analyze('fixture.rs', source);
+    const main = structure.functions.find(f => f.name === 'main');
+    const chains = main.methodChains.map(chain => chain.calls.join(' -> '));
+    if (!chains.includes('HttpServer::new -> bind -> workers -> run')) {
+      throw new Error(`The server chain in main should be captured in order: ${JSON.stringify(chains)}`);
+    }
+    if (!chains.includes('App::new -> app_data -> route -> route -> route')) {
+      throw new Error(`The app chain inside the closure should be captured: ${JSON.stringify(chains)}`);
+    }
+
+    const { structure: builder } = await this.analyze('builder.rs', `pub struct Config {
+    port: u16,
+}
+
+impl Config {
+    pub fn with_port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+}
+`);
+    const { nodes } = this.builder.createMindMapElements(builder, 'src/lib.rs');
+    const flags = nodes.filter(n => n.type === 'function').map(n => `${n.name}:${n.metadata.isBuilderMethod}`);
+    if (JSON.stringify(flags) !== JSON.stringify(['with_port:true', 'port:false'])) {
+      throw new Error(`Only with_port is a builder method: ${flags.join(', ')}`);
+    }
+  }
+
+  async testCallSequences() {
+    const source = await readFile(FIXTURE_PATH, 'utf-8');
+    const { structure } = await this.analyze('fixture.rs', source);
+    const main = structure.functions.find(f => f.name === 'main');
+    const server = main.callSequences.find(sequence => sequence.receiver === 'HttpServer');
+    const lines = server?.calls.map(call => call.line);
+    if (server?.calls.map(call => call.method).join(' -> ') !== 'new -> bind -> workers -> run' ||
+        lines.some((line, i) => i > 0 && line <= lines[i - 1])) {
+      throw new Error(`The HttpServer calls should be recorded in source order: ${JSON.stringify(server)}`);
+    }
+    const app = main.callSequences.find(sequence => sequence.receiver === 'App');
+    if (app?.calls.map(call => call.method).join(' -> ') !== 'new -> app_data -> route -> route -> route') {
+      throw new Error(`The app built inside the closure is a receiver of its own: ${JSON.stringify(main.callSequences)}`);
+    }
+    const initialize = structure.functions.find(f => f.name === 'initialize');
+    if (JSON.stringify(initialize.callSequences.map(s => [s.receiver, s.calls.map(c => c.method)])) !== '[["self",["create_user"]]]') {
+      throw new Error(`initialize only calls create_user on self: ${JSON.stringify(initialize.callSequences)}`);
+    }
+
+    const { structure: protocol } = await this.analyze('protocol.rs', `pub fn run(conn: &mut Connection) -> Result<(), Error> {
+    conn.open()?;
+    let reply = conn.send(b"ping")?.trim().to_vec();
+    self::log(reply.len());
+    conn.close();
+    Ok(())
+}
+`);
+    const sequences = protocol.functions[0].callSequences.map(s => `${s.receiver}: ${s.calls.map(c => c.method).join(', ')}`);
+    if (JSON.stringify(sequences) !== JSON.stringify(['conn: open, send, trim, to_vec, close', 'reply: len'])) {
+      throw new Error(`Calls should group by receiver across statements: ${JSON.stringify(sequences)}`);
+    }
+  }
+
+  async testRawAndUnicodeIdentifiers() {
+    const { structure } = await this.analyze('identifiers.rs', `pub struct Café {
+    pub crème: u32,
+}
+
+impl Café {
+    pub fn r#type(&self) -> u32 {
+        self.crème
+    }
+}
+
+pub fn r#async() -> Café {
+    Café { crème: 1 }
+}
+`);
+    const type = structure.functions.find(f => f.name === 'type');
+    if (!type || !type.signature.includes('fn r#type(')) {
+      throw new Error(`r#type should be stored as type with its raw signature: ${JSON.stringify(structure.functions.map(f => [f.name, f.signature]))}`);
+    }
+    if (!structure.functions.some(f => f.name === 'async')) {
+      throw new Error('r#async should be stored as async');
+    }
+
+    const cafe = structure.structs.find(s => s.name === 'Café');
+    if (!cafe || JSON.stringify(cafe.fields) !== JSON.stringify(['crème'])) {
+      throw new Error(`The unicode struct should be captured: ${JSON.stringify(structure.structs)}`);
+    }
+    const impl = structure.impls.find(i => i.target === 'Café');
+    if (!impl || !impl.methods.includes('type')) {
+      throw new Error(`The impl block should target Café: ${JSON.stringify(structure.impls)}`);
+    }
+
+    const { edges } = this.builder.createMindMapElements(structure, 'src/lib.rs');
+    if (!edges.some(e => e.id === 'src/lib.rs#function:async->references->src/lib.rs#struct:Café')) {
+      throw new Error('The return type should resolve to the unicode struct');
+    }
+  }
+
+  async testTraitDefaultBodies() {
+    const { structure } = await this.analyze('defaults.rs', `pub trait Worker {
+    async fn inner(&self) -> u32;
+
+    fn label(&self) -> String;
+
+    async fn helper(&self) -> u32 {
+        let label = self.label();
+        self.inner().await + label.len() as u32
+    }
+}
+`);
+    const { nodes, edges } = this.builder.createMindMapElements(structure, 'src/lib.rs');
+
+    const helper = nodes.find(n => n.id === 'src/lib.rs#function:helper');
+    const inner = nodes.find(n => n.id === 'src/lib.rs#function:inner');
+    if (!helper?.metadata.isTraitDefault || helper.metadata.traitName !== 'Worker') {
+      throw new Error(`helper should be recorded as a Worker default method: ${JSON.stringify(helper?.metadata)}`);
+    }
+    if (!inner || inner.metadata.isTraitDefault || inner.metadata.traitName !== 'Worker') {
+      throw new Error(`inner is a required method without a body: ${JSON.stringify(inner?.metadata)}`);
+    }
+
+    const awaits = edges.find(e => e.id === 'src/lib.rs#function:helper->calls->src/lib.rs#function:inner');
+    if (!awaits || awaits.metadata?.awaited !== true) {
+      throw new Error(`Default body should await inner: ${JSON.stringify(edges.filter(e => e.type === 'calls'))}`);
+    }
+    const plain = edges.find(e => e.id === 'src/lib.rs#function:helper->calls->src/lib.rs#function:label');
+    if (!plain || plain.metadata?.awaited) {
+      throw new Error('The synchronous label call should be a plain call');
+    }
+  }
+
+  async testParameterNames() {
+    const { structure: fixture } = await this.analyze('fixture.rs', await readFile(FIXTURE_PATH, 'utf-8'));
+    const createUser = fixture.functions.filter(f => f.name === 'create_user');
+    if (createUser.length !== 2) {
+      throw new Error(`Expected the trait declaration and the impl of create_user: ${createUser.length}`);
+    }
+    for (const func of createUser) {
+      if (func.parameters.join(',') !== '&self,request' || !func.signature.includes('create_user(&self, request: CreateUserRequest)')) {
+        throw new Error(`Parameter names should be kept in the signature: ${func.signature} [${func.parameters}]`);
+      }
+    }
+
+    const { structure } = await this.analyze('params.rs', `
+pub trait Handler {
+    fn handle(
+        &self,
+        // Not needed by every handler
+        _: u32,
+        #[allow(unused)] context: &str, /* borrowed */
+    ) -> bool;
+}
+`);
+    const handle = structure.functions.find(f => f.name === 'handle');
+    if (handle?.parameters.join(',') !== '&self,_,context' || handle.parameterTypes.join(',') !== ',u32,&str') {
+      throw new Error(`Comments and attributes should not hide parameters: ${JSON.stringify(handle)}`);
+    }
+    if (handle.signature !== 'fn handle(&self, _: u32, context: &str) -> bool') {
+      throw new Error(`Unexpected signature: ${handle.signature}`);
+    }
+  }
+
+  async testEnabledAnalyses() {
+    const source = await readFile(FIXTURE_PATH, 'utf-8');
+    const callEdges = structure => this.builder.createMindMapElements(structure, 'src/main.rs').edges
+      .filter(e => e.type === 'calls');
+
+    try {
+      this.analyzer.resetParseStats();
+      const { structure: full } = await this.analyze('fixture.rs', source);
+      const fullStats = this.analyzer.getParseStats();
+      if (callEdges(full).length === 0 || fullStats.callGraphScans === 0) {
+        throw new Error(`The default analyses should build the call graph: ${JSON.stringify(fullStats)}`);
+      }
+
+      this.analyzer.setParseOptions({ enabledAnalyses: ['await_flow', 'duplicates', 'discarded_results'] });
+      this.analyzer.resetParseStats();
+      const { structure: noCalls } = await this.analyze('fixture.rs', source);
+      const stats = this.analyzer.getParseStats();
+      if (callEdges(noCalls).length !== 0 || stats.callGraphScans !== 0) {
+        throw new Error(`Disabling call_graph should skip call scanning: ${JSON.stringify(stats)}`);
+      }
+      if (!noCalls.functions.some(f => f.bodyHash)) {
+        throw new Error('Other body analyses should keep running');
+      }
+
+      // With no body analyses at all, bodies are never extracted
+      this.analyzer.setParseOptions({ enabledAnalyses: [] });
+      this.analyzer.resetParseStats();
+      const { structure: signatures } = await this.analyze('fixture.rs', source);
+      const fastStats = this.analyzer.getParseStats();
+      if (fastStats.bodiesExtracted !== 0 || fastStats.functions !== full.functions.length) {
+        throw new Error(`Signature-only parsing should skip every body: ${JSON.stringify(fastStats)}`);
+      }
+      if (signatures.functions.map(f => f.name).join(',') !== full.functions.map(f => f.name).join(',')) {
+        throw new Error('Skipping bodies must not change which functions are found');
+      }
+    } finally {
+      this.analyzer.setParseOptions({});
+    }
+  }
+
+  async testAnalysisProfiles() {
+    const source = await readFile(FIXTURE_PATH, 'utf-8');
+    const callEdges = structure => this.builder.createMindMapElements(structure, 'src/main.rs').edges
+      .filter(e => e.type === 'calls');
+
+    try {
+      this.analyzer.setParseOptions({ profile: 'fast' });
+      const { structure: fast } = await this.analyze('fixture.rs', source);
+      if (callEdges(fast).length !== 0 || fast.functions.some(f => f.bodyHash)) {
+        throw new Error('The fast profile should skip the call graph and duplicate detection');
+      }
+      if (this.analyzer.getEnabledLints().includes('panic_sites')) {
+        throw new Error('The fast profile should not select panic site lints');
+      }
+
+      this.analyzer.setParseOptions({ profile: 'security_audit' });
+      const lints = this.analyzer.getEnabledLints();
+      for (const lint of ['panic_sites', 'unsafe_surface', 'ffi_surface', 'boxed_errors']) {
+        if (!lints.includes(lint)) throw new Error(`security_audit should enable ${lint}: ${lints.join(', ')}`);
+      }
+
+      // Options given with a profile override its values
+      this.analyzer.setParseOptions({ profile: 'fast', enabledAnalyses: ['call_graph'], lints: ['panic_sites'] });
+      const { structure: overridden } = await this.analyze('fixture.rs', source);
+      if (callEdges(overridden).length === 0) {
+        throw new Error('An explicit enabledAnalyses should override the profile');
+      }
+      if (this.analyzer.getEnabledLints().join(',') !== 'panic_sites') {
+        throw new Error(`An explicit lint selection should override the profile: ${this.analyzer.getEnabledLints()}`);
+      }
+    } finally {
+      this.analyzer.setParseOptions({});
+    }
+  }
+
+  async testLargeFileReads() {
+    const fixture = await readFile(FIXTURE_PATH, 'utf-8');
+    // Enough items to pass the size from which the presized backend reads into one sized buffer
+    const items = Array.from({ length: 4000 }, (_, i) => `/// Item ${i}: naïve ünïcode keeps byte and char offsets apart
+pub struct Item${i} {
+    pub value: u64,
+}
+
+pub fn make_item_${i}(value: u64) -> Item${i} {
+    let doubled = value * 2;
+    Item${i} { value: doubled }
+}
+`);
+    const sources = { 'fixture.rs': fixture, 'large.rs': items.join('\n') };
+    // Without the optional native module the mmap backend reads buffered
+    const mmapAvailable = (await loadMmap()) !== null;
+
+    try {
+      for (const [fileName, source] of Object.entries(sources)) {
+        const filePath = join(this.testDir, fileName);
+        await writeFile(filePath, source);
+        const graphs = [];
+        for (const options of [{}, { presizedRead: true }, { mmap: true }]) {
+          this.analyzer.setParseOptions(options);
+          this.analyzer.resetParseStats();
+          const structure = await this.analyzer.analyzeFile(filePath);
+          const { nodes, edges } = this.builder.createMindMapElements(structure, `src/${fileName}`);
+          const strip = element => ({ ...element, lastUpdated: undefined, createdAt: undefined });
+          graphs.push(JSON.stringify({ nodes: nodes.map(strip), edges: edges.map(strip) }));
+          const { presizedReads, mappedReads } = this.analyzer.getParseStats();
+          const large = fileName === 'large.rs';
+          if (presizedReads !== (options.presizedRead && large ? 1 : 0) || mappedReads !== (options.mmap && mmapAvailable && large ? 1 : 0)) {
+            throw new Error(`${fileName} read with ${JSON.stringify(options)} counted ${presizedReads} presized and ${mappedReads} mapped reads`);
+          }
+        }
+        if (graphs.some(graph => graph !== graphs[0])) {
+          throw new Error(`Every backend should build the same graph for ${fileName}`);
+        }
+      }
+    } finally {
+      this.analyzer.setParseOptions({});
+    }
+  }
+
+  async testPublicOnly() {
+    const source = await readFile(FIXTURE_PATH, 'utf-8');
+    try {
+      this.analyzer.setParseOptions({ publicOnly: true });
+      this.analyzer.resetParseStats();
+      const { structure } = await this.analyze('fixture.rs', source);
+      const { nodes, edges } = this.builder.createMindMapElements(structure, 'src/main.rs');
+      if (this.analyzer.getParseStats().bodiesExtracted !== 0 || edges.some(e => e.type === 'calls')) {
+        throw new Error('A public-only parse should skip bodies and build no call edges');
+      }
+
+      const names = structure.functions.map(f => f.name);
+      for (const name of ['create_user_handler', 'get_user_handler', 'list_users_handler', 'load_config', 'new', 'create_user']) {
+        if (!names.includes(name)) throw new Error(`${name} is public API: ${names.join(', ')}`);
+      }
+      if (names.includes('main') || names.includes('test_user_creation')) {
+        throw new Error(`Private functions should be left out: ${names.join(', ')}`);
+      }
+      const handler = structure.functions.find(f => f.name === 'create_user_handler');
+      if (handler.returnType !== 'Result<HttpResponse>' || handler.parameterTypes[1] !== 'web::Json<CreateUserRequest>' || handler.panicSites?.length) {
+        throw new Error(`Handler signatures should be kept without body facts: ${JSON.stringify(handler)}`);
+      }
+
+      const user = structure.structs.find(s => s.name === 'User');
+      if (user?.derives.join(',') !== 'Debug,Clone,Serialize,Deserialize' || user.fieldTypes[1] !== 'String') {
+        throw new Error(`User should keep its derives and public field types: ${JSON.stringify(user)}`);
+      }
+      const repository = structure.structs.find(s => s.name === 'InMemoryUserRepository');
+      if (!repository || repository.fields.length !== 0) {
+        throw new Error(`Private fields should be dropped: ${JSON.stringify(repository?.fields)}`);
+      }
+      const config = structure.structs.find(s => s.name === 'ServerConfig');
+      if (config?.modulePath.join('::') !== 'config' || !structure.traits.some(t => t.name === 'UserRepository')) {
+        throw new Error('The config module API and the repository trait should be kept');
+      }
+      if (!edges.some(e => e.type === 'implements' && e.source.endsWith('#struct:InMemoryUserRepository') && e.target.endsWith('#trait:UserRepository'))) {
+        throw new Error('Trait impls should still link the implementor');
+      }
+      if (nodes.some(n => n.name === 'tests')) {
+        throw new Error('Private modules should be left out');
+      }
+    } finally {
+      this.analyzer.setParseOptions({});
+    }
+  }
+
+  async testConditionalCompilation() {
+    const source = `#[cfg(feature = "extra")]
+pub fn extra_api() -> u32 {
+    1
+}
+
+#[cfg(not(feature = "extra"))]
+pub fn fallback_api() -> u32 {
+    0
+}
+
+#[cfg(unix)]
+pub fn unix_only() {}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn it_works() {}
+}
+`;
+    const status = structure => Object.fromEntries(structure.functions.map(f => [f.name, f.inactive || false]));
+
+    const { structure: defaults } = await this.analyze('cfg.rs', source);
+    const extra = defaults.functions.find(f => f.name === 'extra_api');
+    if (extra.cfg?.join(',') !== 'feature = "extra"') {
+      throw new Error(`cfg predicate should be recorded: ${JSON.stringify(extra.cfg)}`);
+    }
+    let flags = status(defaults);
+    if (!flags.extra_api || flags.fallback_api || flags.unix_only || flags.it_works) {
+      throw new Error(`Without the feature only extra_api should be inactive: ${JSON.stringify(flags)}`);
+    }
+
+    this.analyzer.setParseOptions({ cfgFeatures: ['extra'], cfgTest: false });
+    try {
+      const { structure } = await this.analyze('cfg.rs', source);
+      flags = status(structure);
+      if (flags.extra_api || !flags.fallback_api || flags.unix_only) {
+        throw new Error(`Enabling the feature should flip the variants: ${JSON.stringify(flags)}`);
+      }
+      const test = structure.functions.find(f => f.name === 'it_works');
+      if (!test.inactive || test.cfg.join(',') !== 'test') {
+        throw new Error(`Items in a cfg(test) module inherit its predicate: ${JSON.stringify(test)}`);
+      }
+
+      const { nodes } = this.builder.createMindMapElements(structure, 'src/cfg.rs');
+      const node = nodes.find(n => n.name === 'fallback_api');
+      if (!node || node.metadata.inactive !== true) {
+        throw new Error('Inactive items should be kept as nodes and flagged');
+      }
+    } finally {
+      this.analyzer.setParseOptions({});
+    }
+  }
+
+  async testGoldenSnapshot() {
+    // A fresh analyzer, so parse options set by other tests cannot leak into the snapshot
+    const analyzer = new RustAnalyzer();
+    const filePath = join(this.testDir, 'golden', 'main.rs');
+    await mkdir(join(this.testDir, 'golden'), { recursive: true });
+    await writeFile(filePath, await readFile(FIXTURE_PATH, 'utf-8'));
+    const structure = await analyzer.analyzeFile(filePath);
+    const graph = this.builder.createMindMapElements(structure, 'src/main.rs');
+    const snapshot = serializeSnapshot(graph, { root: this.testDir });
+
+    if (serializeSnapshot(graph, { root: this.testDir }) !== snapshot || snapshot.includes(this.testDir)) {
+      throw new Error('Serializing the same graph twice should give the same snapshot, free of the temp directory');
+    }
+
+    // Renumbering every ID leaves the snapshot unchanged
+    const renumbered = new Map(graph.nodes.map((node, index) => [node.id, `n${index}`]));
+    const renamed = value => {
+      if (typeof value === 'string') return renumbered.get(value) ?? value;
+      if (Array.isArray(value)) return value.map(renamed);
+      if (value && typeof value === 'object' && !(value instanceof Date)) {
+        return Object.fromEntries(Object.entries(value).map(([key, inner]) => [key, renamed(inner)]));
+      }
+      return value;
+    };
+    const remapped = {
+      nodes: graph.nodes.map(node => ({ ...node, id: renamed(node.id), metadata: renamed(node.metadata) })),
+      edges: graph.edges.map(edge => ({ ...edge, id: `e:${edge.id}`, source: renamed(edge.source), target: renamed(edge.target), metadata: renamed(edge.metadata) }))
+    };
+    if (serializeSnapshot(remapped, { root: this.testDir }) !== snapshot) {
+      throw new Error('The snapshot should not depend on how IDs are assigned');
+    }
+
+    // A lost edge or a re-linked one does change it
+    const implementsEdge = graph.edges.find(edge => edge.type === 'implements');
+    const withoutEdge = { nodes: graph.nodes, edges: graph.edges.filter(edge => edge !== implementsEdge) };
+    const relinked = { nodes: graph.nodes, edges: graph.edges.map(edge => edge === implementsEdge ? { ...edge, target: edge.source, source: edge.target } : edge) };
+    if (serializeSnapshot(withoutEdge, { root: this.testDir }) === snapshot || serializeSnapshot(relinked, { root: this.testDir }) === snapshot) {
+      throw new Error('Structural changes should show in the snapshot');
+    }
+
+    // Outside update mode a missing golden fails rather than being written
+    const absentPath = join(this.testDir, 'golden', 'absent.snap');
+    const absent = await matchSnapshot(snapshot, absentPath, false);
+    const written = await readFile(absentPath, 'utf-8').then(() => true, () => false);
+    if (absent.status !== 'missing' || written) {
+      throw new Error(`A missing golden should report 'missing' without being recorded, got ${absent.status}`);
+    }
+
+    const result = await matchSnapshot(snapshot, FIXTURE_SNAPSHOT_PATH);
+    if (result.status === 'missing') {
+      throw new Error(`No golden snapshot at ${result.goldenPath} (run with ${UPDATE_SNAPSHOTS_ENV}=1 to record it)`);
+    }
+    if (result.status === 'mismatch') {
+      throw new Error(`The fixture no longer parses to its golden snapshot (rerun with ${UPDATE_SNAPSHOTS_ENV}=1 if the change is intended):\n${result.diff}`);
+    }
+    if (result.status !== 'matched') {
+      console.log(`   📸 Snapshot ${result.status}: ${result.goldenPath}`);
+    }
+  }
+
+  async runAllTests() {
+    console.log('🦀 Starting Rust Item Extraction Test Suite\n');
+
+    this.testDir = await mkdtemp(join(tmpdir(), 'mindmap-rust-items-'));
+
+    try {
+      await this.runTest('Const and static items', () => this.testConstAndStaticItems());
+      await this.runTest('Nested const and static items', () => this.testNestedConstants());
+      await this.runTest('Attribute arguments', () => this.testAttributeArguments());
+      await this.runTest('Fixture attributes', () => this.testFixtureAttributes());
+      await this.runTest('Implements evidence', () => this.testImplementsEvidence());
+      await this.runTest('Derive provenance', () => this.testDeriveProvenance());
+      await this.runTest('Import scopes', () => this.testImportScopes());
+      await this.runTest('Method chains', () => this.testMethodChains());
+      await this.runTest('Call sequences', () => this.testCallSequences());
+      await this.runTest('Raw and unicode identifiers', () => this.testRawAndUnicodeIdentifiers());
+      await this.runTest('Trait default bodies', () => this.testTraitDefaultBodies());
+      await this.runTest('Enabled analyses', () => this.testEnabledAnalyses());
+      await this.runTest('Analysis profiles', () => this.testAnalysisProfiles());
+      await this.runTest('Public-only parsing', () => this.testPublicOnly());
+      await this.runTest('Large file reads', () => this.testLargeFileReads());
+      await this.runTest('Parameter names', () => this.testParameterNames());
+      await this.runTest('Conditional compilation', () => this.testConditionalCompilation());
+      await this.runTest('Derive-required bounds', () => this.testDeriveRequiredBounds());
+      await this.runTest('Const generics', () => this.testConstGenerics());
+      await this.runTest('Receivers', () => this.testReceivers());
+      await this.runTest('Return borrows', () => this.testReturnBorrows());
+      await this.runTest('Associated type bounds', () => this.testAssociatedTypeBounds());
+      await this.runTest('Generic associated types', () => this.testGenericAssociatedTypes());
+      await this.runTest('Trait aliases', () => this.testTraitAliases());
+      await this.runTest('Newtypes', () => this.testNewtypes());
+      await this.runTest('Type depth (fixture)', () => this.testTypeDepthFixture());
+      await this.runTest('Type depth (truncation)', () => this.testTypeDepthTruncation());
+      await this.runTest('Macro body modes', () => this.testMacroBodyModes());
+      await this.runTest('Golden snapshot', () => this.testGoldenSnapshot());
+    } finally {
+      await rm(this.testDir, { recursive: true, force: true });
+    }
+
+    this.printResults();
+  }
+
+  printResults() {
+    console.log('\n📊 TEST RESULTS');
+    console.log('='.repeat(50));
+    console.log(`✅ Passed: ${this.results.passed}`);
+    console.log(`❌ Failed: ${this.results.failed}`);
+    console.log(`📈 Success Rate: ${((this.results.passed / (this.results.passed + this.results.failed)) * 100).toFixed(1)}%`);
+
+    if (this.results.failed > 0) {
+      console.log('\n❌ FAILED TESTS:');
+      this.results.tests
+        .filter(test => test.status === 'FAILED')
+        .forEach(test => {
+          console.log(`   • ${test.name}: ${test.error}`);
+        });
+    }
+
+    console.log('\n🦀 Rust Item Extraction Test Complete');
+    process.exit(this.results.failed > 0 ? 1 : 0);
+  }
+}
+
+// Run tests if called directly
+if (import.meta.url === `file://${process.argv[1]}`) {
+  const testSuite = new RustItemsTestSuite();
+  testSuite.runAllTests().catch(error => {
+    console.error('❌ Test suite failed:', error);
+    process.exit(1);
+  });
+}
+
+export { RustItemsTestSuite };